@@ -1,10 +1,30 @@
 // Copyright (c) 2024-2025 Zededa, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::ui::ipdialog::InterfaceState;
+use crate::{
+    diag::DiagRequest,
+    dpc::l2link::L2LinkEdit,
+    ipc::eve_types::{CellularAccessPoint, DevicePortConfig, WifiConfig, WwanProbe},
+    model::model::AppCommand,
+    ui::ipdialog::InterfaceState,
+};
+use uuid::Uuid;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum MonActions {
     NetworkInterfaceUpdated(InterfaceState, InterfaceState),
     ServerUpdated(String),
+    WifiConfigUpdated(String, Vec<WifiConfig>),
+    CellularConfigUpdated(String, Vec<CellularAccessPoint>, WwanProbe),
+    L2LinkUpdated(L2LinkEdit),
+    DpcReverted(Box<DevicePortConfig>),
+    DpcConfirmed(Box<DevicePortConfig>),
+    DpcEditAgain,
+    DpcRevertApplied,
+    CrashReportAcknowledged(String),
+    CrashReportAddToBundle(String),
+    RunDiagnostic(DiagRequest),
+    CancelDiagnostic,
+    CheckController(String),
+    AppCommandConfirmed(Uuid, AppCommand),
 }
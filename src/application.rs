@@ -2,24 +2,42 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::actions::MonActions;
+use crate::crash_report::{find_crash_reports, CrashReport};
+use crate::diag::controller::{check_controller, find_proxy, CheckMessage, CheckOutput};
+use crate::diag::{run_diagnostic, DiagMessage, DiagOutput, DiagRequest};
+use crate::dpc::builder::{build_dpc, build_revert_dpc};
+use crate::dpc::cellular::{build_cellular_dpc, cellular_config};
+use crate::dpc::diff::diff_dpc;
+use crate::dpc::l2link::{build_l2_link_dpc, port_label, L2LinkEdit};
+use crate::dpc::verification::DpcVerification;
+use crate::dpc::wifi::build_wifi_dpc;
 use crate::events::Event;
-use crate::ipc::eve_types::TuiEveConfig;
+use crate::ipc::eve_types::{
+    CellularAccessPoint, DevicePortConfig, L2LinkType, TuiEveConfig, WifiConfig, WirelessType,
+    WwanProbe,
+};
+use crate::kmsg::read_kmsg_file;
+use crate::model::device::network::NetworkType;
+use crate::model::device::traffic::SYSFS_ROOT;
+use crate::model::model::AppCommand;
 use crate::model::model::Model;
 use crate::model::model::MonitorModel;
 use crate::ui::ipdialog::InterfaceState;
+use crate::ui::l2linkdialog::{BondState, L2LinkForm, VlanState};
+use crate::ui::theme::{self, RenderProfile, RenderProfileMode};
 use crate::ui::ui::Ui;
 
 use std::cell::RefCell;
 use std::collections::HashMap;
-use std::net::IpAddr;
 use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::result::Result::Ok;
 use std::str::FromStr;
+use std::time::Instant;
 
 use anyhow::Result;
-use ipnet::IpNet;
+use chrono::{DateTime, Utc};
 use log::error;
 use log::LevelFilter;
 use log::{debug, info, trace, warn};
@@ -32,17 +50,65 @@ use futures::{FutureExt, SinkExt, StreamExt};
 
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
 
 use crate::ipc::ipc_client::IpcClient;
 use crate::ipc::message::{IpcMessage, Request};
-use crate::terminal::TerminalWrapper;
+use crate::terminal::{TerminalEventStream, TerminalWrapper};
 use crate::ui::action::{Action, UiActions};
 
+const EVE_MONITOR_SOCKET: &str = "/run/monitor.sock";
+// period of Event::Tick
+pub const TICK_PERIOD_MS: u64 = 500;
+const DEV_KMSG: &str = "/dev/kmsg";
+
+/// Settings of the development mode. The section is present in the config
+/// only when the monitor runs on a development host (see `is_dev_mode` in main.rs)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DevModeConfig {
+    // file with kmsg records or dmesg output to show on the Dmesg page.
+    // /dev/kmsg streams kernel messages of the host
+    pub kmsg: PathBuf,
+    // IPC socket to connect to, e.g. of the mock server
+    pub socket: PathBuf,
+    // root of the sysfs tree with interface statistics, e.g. a fixture tree
+    #[serde(default = "default_sysfs")]
+    pub sysfs: PathBuf,
+}
+
+fn default_sysfs() -> PathBuf {
+    PathBuf::from(SYSFS_ROOT)
+}
+
+impl Default for DevModeConfig {
+    fn default() -> Self {
+        Self {
+            kmsg: PathBuf::from(DEV_KMSG),
+            socket: PathBuf::from("./persist/monitor.sock"),
+            sysfs: default_sysfs(),
+        }
+    }
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct AppConfig {
     #[serde(skip)]
     config_path: PathBuf,
+    #[serde(skip)]
+    base_dir: PathBuf,
     pub log_level: String,
+    // ASCII/low color profile for serial consoles. Detected automatically by default
+    #[serde(default)]
+    pub render_profile: RenderProfileMode,
+    // crash reports from previous sessions the user has already seen
+    #[serde(default)]
+    pub acknowledged_crash_reports: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dev_mode: Option<DevModeConfig>,
+    // the settings above are used only when main.rs resolved the development
+    // mode, a copied config must not redirect the monitor on EVE
+    #[serde(skip)]
+    dev_mode_enabled: bool,
 }
 
 impl AppConfig {
@@ -52,7 +118,12 @@ impl AppConfig {
     {
         Self {
             config_path: path.as_ref().to_path_buf(),
+            base_dir: PathBuf::new(),
             log_level: "info".to_string(),
+            render_profile: RenderProfileMode::default(),
+            acknowledged_crash_reports: Vec::new(),
+            dev_mode: None,
+            dev_mode_enabled: false,
         }
     }
 
@@ -79,7 +150,7 @@ impl AppConfig {
 
         let config_path = config_dir.join("config.json");
 
-        AppConfig::load(&config_path).map_or_else(
+        let mut cfg = AppConfig::load(&config_path).map_or_else(
             |e| {
                 log::error!("Failed to load config: {}", e);
                 let cfg = AppConfig::new(config_path);
@@ -87,13 +158,41 @@ impl AppConfig {
                 cfg
             },
             |c| c,
-        )
+        );
+        cfg.base_dir = base_dir.to_path_buf();
+        cfg
+    }
+
+    /// Check whether the config under `base_dir` enables the development mode
+    pub fn has_dev_mode(base_dir: &Path) -> bool {
+        AppConfig::load(base_dir.join("config").join("config.json"))
+            .is_ok_and(|cfg| cfg.dev_mode.is_some())
+    }
+
+    pub fn enable_dev_mode(&mut self) {
+        self.dev_mode_enabled = true;
+    }
+
+    /// Settings of the development mode if it is enabled
+    pub fn dev_mode_settings(&self) -> Option<&DevModeConfig> {
+        self.dev_mode.as_ref().filter(|_| self.dev_mode_enabled)
+    }
+
+    pub fn log_dir(&self) -> PathBuf {
+        self.base_dir.join("log")
+    }
+
+    pub fn support_bundle_dir(&self) -> PathBuf {
+        self.base_dir.join("support")
     }
 }
 
+// called with the result of a request once EVE responds
+type ResponseHandler = Rc<dyn Fn(&mut Application, Result<String, String>)>;
+
 pub struct Application {
-    terminal_rx: UnboundedReceiver<Event>,
-    terminal_tx: UnboundedSender<Event>,
+    // taken by the terminal task when the application starts
+    event_stream: Option<TerminalEventStream>,
     action_rx: UnboundedReceiver<Action>,
     action_tx: UnboundedSender<Action>,
     ipc_tx: Option<UnboundedSender<IpcMessage>>,
@@ -101,24 +200,41 @@ pub struct Application {
     // this is our model :)
     model: Rc<Model>,
     // pending requests
-    pending_requests: HashMap<u64, Rc<dyn Fn(&mut Application)>>,
+    pending_requests: HashMap<u64, ResponseHandler>,
     config: AppConfig,
+    // crash reports from previous sessions waiting for acknowledgement
+    crash_reports: Vec<CrashReport>,
+    // output of the diagnostics tools
+    diag_rx: UnboundedReceiver<DiagMessage>,
+    diag_tx: UnboundedSender<DiagMessage>,
+    // cancels the running diagnostics tool
+    diag_cancel: Option<CancellationToken>,
+    // results of the controller reachability check
+    check_rx: UnboundedReceiver<CheckMessage>,
+    check_tx: UnboundedSender<CheckMessage>,
+    check_cancel: Option<CancellationToken>,
 }
 
 impl Application {
-    pub fn new(config: AppConfig) -> Result<Self> {
+    /// Create the application rendering to `terminal` and reading key events
+    /// from `event_stream`. Tests pass a test terminal and a scripted stream
+    pub fn new(
+        config: AppConfig,
+        terminal: TerminalWrapper,
+        event_stream: TerminalEventStream,
+    ) -> Result<Self> {
         let (action_tx, action_rx) = mpsc::unbounded_channel::<Action>();
-        let (terminal_tx, terminal_rx) = mpsc::unbounded_channel::<Event>();
-        let terminal = TerminalWrapper::open_terminal()?;
+        theme::set_profile(RenderProfile::from_mode(config.render_profile));
         let mut ui = Ui::new(action_tx.clone(), terminal)?;
         let model = Rc::new(RefCell::new(MonitorModel::default()));
         let pending_requests = HashMap::new();
+        let (diag_tx, diag_rx) = mpsc::unbounded_channel::<DiagMessage>();
+        let (check_tx, check_rx) = mpsc::unbounded_channel::<CheckMessage>();
 
         ui.init();
 
         Ok(Self {
-            terminal_rx,
-            terminal_tx,
+            event_stream: Some(event_stream),
             action_rx,
             action_tx,
             ui,
@@ -126,54 +242,221 @@ impl Application {
             model,
             pending_requests,
             config,
+            crash_reports: Vec::new(),
+            diag_rx,
+            diag_tx,
+            diag_cancel: None,
+            check_rx,
+            check_tx,
+            check_cancel: None,
         })
     }
-    pub fn send_ipc_message<F>(&mut self, msg: IpcMessage, handle_response: F)
+
+    /// Start a tool of the Diagnostics page. A tool which is still running is
+    /// cancelled, its late output is dropped by the session
+    fn run_diagnostic(&mut self, request: DiagRequest) {
+        self.cancel_diagnostic();
+        info!("Running diagnostics: {}", request.title());
+        let run = self.model.borrow_mut().diagnostics.start(request.title());
+        let cancel = CancellationToken::new();
+        let out = DiagOutput::new(run, self.diag_tx.clone());
+        tokio::spawn(run_diagnostic(request, out, cancel.clone()));
+        self.diag_cancel = Some(cancel);
+    }
+
+    fn cancel_diagnostic(&mut self) {
+        if let Some(cancel) = self.diag_cancel.take() {
+            cancel.cancel();
+        }
+    }
+
+    /// Sample the traffic counters of the network interfaces
+    fn update_traffic(&mut self) {
+        let sysfs = match self.config.dev_mode_settings() {
+            Some(dev) => dev.sysfs.clone(),
+            None => PathBuf::from(SYSFS_ROOT),
+        };
+        let mut model = self.model.borrow_mut();
+        let model = &mut *model;
+        let names = model.network.iter().map(|iface| iface.name.as_str());
+        model.traffic.update(&sysfs, names, Instant::now());
+    }
+
+    /// Check that the controller at `url` is reachable through the proxy of
+    /// the management ports and show the result
+    fn check_controller(&mut self, url: String) {
+        if let Some(cancel) = self.check_cancel.take() {
+            cancel.cancel();
+        }
+        let proxy = find_proxy(&self.model.borrow().network);
+        info!("Checking controller {} proxy {:?}", url, proxy);
+        let run = self
+            .model
+            .borrow_mut()
+            .controller_check
+            .start(url.clone(), proxy.clone());
+        let cancel = CancellationToken::new();
+        let out = CheckOutput::new(run, self.check_tx.clone());
+        tokio::spawn(check_controller(url, proxy, out, cancel.clone()));
+        self.check_cancel = Some(cancel);
+        self.ui.show_controller_check_dialog();
+    }
+
+    /// Send a lifecycle command to an app instance. The progress is tracked
+    /// in the model until the app reaches the expected state
+    fn send_app_command(&mut self, uuid: Uuid, command: AppCommand) {
+        info!("Sending {} to app {}", command, uuid);
+        let sent = self.send_ipc_request(
+            IpcMessage::new_request(command.request(uuid)),
+            move |app, result| match result {
+                Ok(_) => app.model.borrow_mut().accept_app_command(uuid),
+                Err(e) => app.reject_app_command(uuid, command, &e),
+            },
+        );
+        if sent {
+            self.model.borrow_mut().request_app_command(uuid, command);
+        } else {
+            self.ui.message_box(
+                "App instance command",
+                &format!(
+                    "The {} command could not be sent. EVE is not connected",
+                    command
+                ),
+            );
+        }
+    }
+
+    /// EVE refused the command sent to the app instance
+    fn reject_app_command(&mut self, uuid: Uuid, command: AppCommand, error: &str) {
+        warn!("EVE refused {} of app {}: {}", command, uuid, error);
+        self.model
+            .borrow_mut()
+            .fail_app_command(uuid, command, error);
+        let name = self
+            .model
+            .borrow()
+            .apps
+            .get(&uuid)
+            .map_or_else(|| uuid.to_string(), |app| app.name.clone());
+        self.ui.message_box(
+            "App instance command",
+            &format!("EVE did not accept the {} of {}: {}", command, name, error),
+        );
+    }
+
+    /// Look for panic reports left by previous sessions and show a dialog for
+    /// each one that was not acknowledged yet
+    pub fn show_crash_reports(&mut self) {
+        let log_dir = self.config.log_dir();
+        let current_session = std::env::var("EVE_MONITOR_LOG_DIR").ok().map(PathBuf::from);
+
+        // forget acknowledged reports whose sessions were already rotated away
+        let acknowledged_count = self.config.acknowledged_crash_reports.len();
+        self.config
+            .acknowledged_crash_reports
+            .retain(|id| log_dir.join(id).exists());
+        if acknowledged_count != self.config.acknowledged_crash_reports.len() {
+            self.save_config();
+        }
+
+        self.crash_reports = find_crash_reports(
+            &log_dir,
+            current_session.as_deref(),
+            &self.config.acknowledged_crash_reports,
+        );
+
+        // push the newest report first so the oldest one is on top
+        for report in self.crash_reports.iter().rev() {
+            warn!("Found crash report from previous session: {}", report.id());
+            self.ui.show_crash_report_dialog(report);
+        }
+    }
+
+    fn acknowledge_crash_report(&mut self, id: &str) {
+        info!("Crash report {} acknowledged", id);
+        self.crash_reports.retain(|r| r.id() != id);
+        if !self
+            .config
+            .acknowledged_crash_reports
+            .iter()
+            .any(|a| a == id)
+        {
+            self.config.acknowledged_crash_reports.push(id.to_string());
+            self.save_config();
+        }
+    }
+
+    fn save_config(&mut self) {
+        match self.config.save() {
+            Ok(_) => {
+                info!("Application Configuration saved");
+            }
+            Err(e) => {
+                error!("Failed to save configuration: {}", e);
+            }
+        }
+    }
+
+    /// Send `msg` to EVE. `handle_response` is called if EVE accepts the
+    /// request. Returns false if the message could not be sent
+    pub fn send_ipc_message<F>(&mut self, msg: IpcMessage, handle_response: F) -> bool
     where
         F: Fn(&mut Application) -> () + 'static,
+    {
+        self.send_ipc_request(msg, move |app, result| {
+            if result.is_ok() {
+                handle_response(app);
+            }
+        })
+    }
+
+    /// Same as `send_ipc_message` but `handle_result` is called with errors
+    /// returned by EVE too
+    pub fn send_ipc_request<F>(&mut self, msg: IpcMessage, handle_result: F) -> bool
+    where
+        F: Fn(&mut Application, Result<String, String>) + 'static,
     {
         if !self.model.borrow().ipc_connected {
             warn!(
                 "Attempted to send IPC message while disconnected: {:?}",
                 msg
             );
-            return;
+            return false;
         }
-        if let Some(ipc_tx) = &self.ipc_tx {
-            match &msg {
-                IpcMessage::Request { request, id } => {
-                    debug!("Pending response for: {:?}", request);
-                    self.pending_requests.insert(*id, Rc::new(handle_response));
-                }
-                _ => {}
+        let Some(ipc_tx) = &self.ipc_tx else {
+            return false;
+        };
+        let id = match &msg {
+            IpcMessage::Request { request, id } => {
+                debug!("Pending response for: {:?}", request);
+                self.pending_requests.insert(*id, Rc::new(handle_result));
+                Some(*id)
             }
+            _ => None,
+        };
 
-            match ipc_tx.send(msg) {
-                Ok(_) => {
-                    debug!("Sent IPC message");
-                }
-                Err(e) => {
-                    error!("Error sending IPC message: {:?}", e);
+        match ipc_tx.send(msg) {
+            Ok(_) => {
+                debug!("Sent IPC message");
+                true
+            }
+            Err(e) => {
+                error!("Error sending IPC message: {:?}", e);
+                if let Some(id) = id {
+                    self.pending_requests.remove(&id);
                 }
+                false
             }
         }
     }
 
-    fn get_socket_path() -> String {
-        // try to get XDG_RUNTIME_DIR first if we run a standalone app on development host
-        if let Ok(xdg_runtime_dir) = std::env::var("XDG_RUNTIME_DIR") {
-            return format!("{}/monitor.sock", xdg_runtime_dir);
-        } else {
-            // EVE path
-            return "/run/monitor.sock".to_string();
+    fn get_socket_path(&self) -> String {
+        match self.config.dev_mode_settings() {
+            Some(dev) => dev.socket.to_string_lossy().to_string(),
+            None => EVE_MONITOR_SOCKET.to_string(),
         }
     }
 
-    fn is_desktop() -> bool {
-        //std::env::var("XDG_RUNTIME_DIR").is_ok()
-        false
-    }
-
     pub fn handle_ipc_message(&mut self, msg: IpcMessage) {
         match msg {
             IpcMessage::Connecting => {
@@ -202,18 +485,12 @@ impl Application {
             }
             IpcMessage::Response { result, id } => {
                 debug!("Got response: {:?}", result);
-                match result {
-                    Ok(_) => {
-                        debug!("Response OK");
-                        if let Some(handle_response) = self.pending_requests.remove(&id) {
-                            handle_response(self);
-                        }
-                    }
-                    Err(e) => {
-                        error!("Response error: {:?}", e);
-                        // remove pending request
-                        self.pending_requests.remove(&id);
-                    }
+                match &result {
+                    Ok(_) => debug!("Response OK"),
+                    Err(e) => error!("Response error: {:?}", e),
+                }
+                if let Some(handle_result) = self.pending_requests.remove(&id) {
+                    handle_result(self, result);
                 }
             }
 
@@ -286,14 +563,7 @@ impl Application {
                 );
                 // update application config
                 self.config.log_level = cfg.log_level;
-                match self.config.save() {
-                    Ok(_) => {
-                        info!("Application Configuration saved");
-                    }
-                    Err(e) => {
-                        error!("Failed to save configuration: {}", e);
-                    }
-                }
+                self.save_config();
             }
 
             IpcMessage::TpmLogs(logs) => {
@@ -301,6 +571,11 @@ impl Application {
                 self.model.borrow_mut().update_tpm_logs(logs);
             }
 
+            IpcMessage::WwanMetrics(metrics) => {
+                debug!("Got WwanMetrics");
+                self.model.borrow_mut().update_wwan_metrics(metrics);
+            }
+
             #[allow(unreachable_patterns)]
             _ => {
                 warn!("Unhandled IPC message: {:?}", msg);
@@ -308,76 +583,197 @@ impl Application {
         }
     }
 
+    /// Build the DPC with the settings of the IP dialog and close the dialog
+    /// once they are valid
     pub fn send_dpc(&mut self, old: InterfaceState, new: InterfaceState) {
+        let current_dpc = self.model.borrow().get_current_dpc().cloned();
+        let Some(current_dpc) = current_dpc else {
+            warn!("send_dpc: no current DPC for iface {}", &new.iface_name);
+            self.ui.pop_layer();
+            return;
+        };
+        info!("send_dpc: Preparing DPC for iface {}", &new.iface_name);
+        match build_dpc(&current_dpc, &old, &new) {
+            Ok(new_dpc) => {
+                self.ui.pop_layer();
+                self.preview_dpc(&current_dpc, new_dpc);
+            }
+            Err(errors) => {
+                let message = errors
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                error!(
+                    "send_dpc: invalid settings for {}: {}",
+                    &new.iface_name, message
+                );
+                self.ui.message_box("ERROR", &message);
+            }
+        }
+    }
+
+    pub fn send_wifi_dpc(&mut self, iface: &str, networks: Vec<WifiConfig>) {
         let current_dpc = self.model.borrow().get_current_dpc().cloned();
         if let Some(current_dpc) = current_dpc {
-            info!("send_dpc: Sending DPC for iface {}", &new.iface_name);
-            let mut new_dpc = current_dpc.to_new_dpc_with_key("manual");
-
-            // Update IP configuration based on DHCP/static selection
-            match (old.is_dhcp(), new.is_dhcp()) {
-                (false, true) => {
-                    // case 2: Static -> DHCP
-                    new_dpc
-                        .get_port_by_name_mut(&new.iface_name)
-                        .unwrap()
-                        .to_dhcp();
-                }
-                (_, false) => {
-                    let ip: IpAddr = new.ipv4.parse().unwrap();
-                    let mask: IpAddr = new.mask.parse().unwrap();
-
-                    // parse DNS server string and convert to Option<Vec<IpAddr>>
-                    let dns_servers = new
-                        .dns
-                        .split(',')
-                        .map(|s| s.parse::<IpAddr>().ok())
-                        .flatten()
-                        .collect::<Vec<IpAddr>>();
-
-                    // same for NTP. NTP can now be either IP or FQDN
-                    let ntp_servers = new
-                        .ntp
-                        .split(',')
-                        .map(|s| s.to_string())
-                        .collect::<Vec<String>>();
-
-                    // case 1,3: DHCP -> Static or Static -> Static
-                    new_dpc
-                        .get_port_by_name_mut(&new.iface_name)
-                        .unwrap()
-                        .to_static(
-                            IpNet::with_netmask(ip, mask).unwrap(),
-                            new.gw.parse().unwrap(),
-                            new.domain.clone(),
-                            if ntp_servers.is_empty() {
-                                None
-                            } else {
-                                Some(ntp_servers)
-                            },
-                            if dns_servers.is_empty() {
-                                None
-                            } else {
-                                Some(dns_servers)
-                            },
-                        );
+            info!("send_wifi_dpc: Preparing DPC for iface {}", iface);
+            match build_wifi_dpc(&current_dpc, iface, networks) {
+                Ok(new_dpc) => {
+                    self.preview_dpc(&current_dpc, new_dpc);
+                }
+                Err(e) => {
+                    error!("send_wifi_dpc: {}", e);
+                    self.ui.message_box("ERROR", &e.to_string());
+                }
+            }
+        }
+    }
+
+    pub fn send_cellular_dpc(
+        &mut self,
+        iface: &str,
+        access_points: Vec<CellularAccessPoint>,
+        probe: WwanProbe,
+    ) {
+        let current_dpc = self.model.borrow().get_current_dpc().cloned();
+        if let Some(current_dpc) = current_dpc {
+            info!("send_cellular_dpc: Preparing DPC for iface {}", iface);
+            match build_cellular_dpc(&current_dpc, iface, access_points, probe) {
+                Ok(new_dpc) => {
+                    self.preview_dpc(&current_dpc, new_dpc);
+                }
+                Err(e) => {
+                    error!("send_cellular_dpc: {}", e);
+                    self.ui.message_box("ERROR", &e.to_string());
+                }
+            }
+        }
+    }
+
+    pub fn send_l2_link_dpc(&mut self, edit: L2LinkEdit) {
+        let current_dpc = self.model.borrow().get_current_dpc().cloned();
+        if let Some(current_dpc) = current_dpc {
+            info!("send_l2_link_dpc: Preparing DPC for {:?}", edit);
+            match build_l2_link_dpc(&current_dpc, edit) {
+                Ok(new_dpc) => {
+                    self.preview_dpc(&current_dpc, new_dpc);
+                }
+                Err(e) => {
+                    error!("send_l2_link_dpc: {}", e);
+                    self.ui.message_box("ERROR", &e.to_string());
                 }
-                (true, true) => {
-                    // DHCP -> DHCP: No IP configuration changes needed, but proxy settings may have changed
-                    info!(
-                        "send_dpc: DHCP -> DHCP transition for iface {}, checking for proxy changes",
-                        &new.iface_name
-                    );
-                } // IP config stays the same, proxy config will be handled below
             }
+        }
+    }
 
-            // Update proxy configuration regardless of DHCP/static IP settings
-            if let Some(port) = new_dpc.get_port_by_name_mut(&new.iface_name) {
-                let proxy_config = new.create_proxy_config();
-                port.set_proxy_config(proxy_config);
+    pub fn send_revert_dpc(&mut self, dpc: &DevicePortConfig) {
+        let in_use = self
+            .model
+            .borrow()
+            .get_dpc_list()
+            .is_some_and(|list| list.is_current(dpc));
+        if in_use {
+            self.ui
+                .message_box("Revert", "This port configuration is already in use");
+            return;
+        }
+        let current_dpc = self.model.borrow().get_current_dpc().cloned();
+        if let Some(current_dpc) = current_dpc {
+            info!("send_revert_dpc: Preparing DPC {}", dpc.key);
+            self.preview_dpc(&current_dpc, build_revert_dpc(dpc));
+        }
+    }
+
+    /// Show what changes compared to the DPC in use. `new_dpc` is sent once
+    /// the change is confirmed
+    fn preview_dpc(&mut self, current_dpc: &DevicePortConfig, new_dpc: DevicePortConfig) {
+        let diff = diff_dpc(current_dpc, &new_dpc);
+        if diff.is_empty() {
+            self.ui
+                .message_box("Port configuration", "There is nothing to change");
+        } else {
+            self.ui.show_dpc_diff_dialog(diff, new_dpc);
+        }
+    }
+
+    /// Send `dpc` to EVE and follow it through the verification
+    pub fn send_new_dpc(&mut self, dpc: DevicePortConfig) {
+        info!("send_new_dpc: Sending DPC {}", dpc.key);
+        let (key, time_priority) = (dpc.key.clone(), dpc.time_priority);
+        let sent = self.send_ipc_request(
+            IpcMessage::new_request(Request::SetDPC(dpc.clone())),
+            move |app, result| {
+                if let Err(e) = result {
+                    app.reject_dpc(&key, time_priority, &e);
+                }
+            },
+        );
+        if !sent {
+            self.ui.message_box(
+                "Port configuration",
+                "The port configuration could not be sent. EVE is not connected",
+            );
+            return;
+        }
+        let previous = self.model.borrow().get_current_dpc().cloned();
+        if let Some(previous) = previous {
+            self.model.borrow_mut().dpc_verification = Some(DpcVerification::new(dpc, previous));
+            self.ui.show_dpc_verify_page();
+        }
+    }
+
+    /// EVE refused the DPC with the given key and time priority
+    fn reject_dpc(&mut self, key: &str, time_priority: DateTime<Utc>, error: &str) {
+        warn!("reject_dpc: EVE refused DPC {}: {}", key, error);
+        let verifying = match self.model.borrow_mut().dpc_verification.as_mut() {
+            Some(v) if v.dpc.key == key && v.dpc.time_priority == time_priority => {
+                v.reject(error);
+                true
+            }
+            _ => false,
+        };
+        if !verifying {
+            self.ui.message_box(
+                "Port configuration",
+                &format!("EVE did not accept the port configuration: {}", error),
+            );
+        }
+    }
+
+    /// Open the editor for the first port changed by the DPC being verified
+    fn edit_verified_dpc(&mut self) {
+        let port = self.model.borrow().dpc_verification.as_ref().and_then(|v| {
+            let name = diff_dpc(&v.previous, &v.dpc).ports.first()?.name.clone();
+            v.dpc
+                .get_port_by_name(&name)
+                .or(v.previous.get_port_by_name(&name))
+                .cloned()
+        });
+        let Some(port) = port else {
+            self.ui.show_dpc_list_page();
+            return;
+        };
+        let action = if port.l2_link_config.l2_type != L2LinkType::L2LinkTypeNone {
+            UiActions::ShowL2Topology
+        } else {
+            match port.wireless_cfg.w_type {
+                WirelessType::Wifi => UiActions::EditWifiConfig(port.if_name),
+                WirelessType::Cellular => UiActions::EditCellularConfig(port.if_name),
+                _ => UiActions::EditIfaceConfig(port.if_name),
             }
+        };
+        self.handle_action(Action::new("app", action));
+    }
 
-            self.send_ipc_message(IpcMessage::new_request(Request::SetDPC(new_dpc)), |_| {});
+    /// Undo the DPC being verified no matter whether EVE already fell back
+    fn revert_verified_dpc(&mut self) {
+        let verification = self.model.borrow().dpc_verification.clone();
+        if let Some(v) = verification {
+            info!("revert_verified_dpc: Preparing DPC {}", v.previous.key);
+            // EVE may already use the previous DPC again
+            let current_dpc = self.model.borrow().get_current_dpc().cloned();
+            let current_dpc = current_dpc.unwrap_or(v.dpc);
+            self.preview_dpc(&current_dpc, build_revert_dpc(&v.previous));
         }
     }
 
@@ -391,55 +787,52 @@ impl Application {
         let cancel_token = CancellationToken::new();
         let cancel_token_child = cancel_token.clone();
         let (dmesg_tx, dmesg_rx) = mpsc::unbounded_channel::<rmesg::entry::Entry>();
-        let is_desktop = Application::is_desktop();
+        // in development mode kmsg is read either from a fixture file or from the host
+        // never clear the kernel log of the host
+        let (backend, clear, fixture) = match self.config.dev_mode_settings() {
+            Some(dev) if dev.kmsg != Path::new(DEV_KMSG) => {
+                (rmesg::Backend::DevKMsg, false, Some(dev.kmsg.clone()))
+            }
+            Some(_) => (rmesg::Backend::DevKMsg, false, None),
+            None => (rmesg::Backend::Default, true, None),
+        };
 
         let kmsg_task: JoinHandle<Result<()>> = tokio::spawn(async move {
-            if is_desktop {
-                let mut index = 0;
-                while !cancel_token_child.is_cancelled() {
-                    let dummy_entry = rmesg::entry::Entry {
-                        level: Some(rmesg::entry::LogLevel::Info),
-                        message: format!("[INFO] {} Desktop mode: no kmsg", index),
-                        facility: None,
-                        sequence_num: None,
-                        timestamp_from_system_start: None,
-                    };
-
-                    index += 1;
-
-                    tokio::select! {
-                        _ = cancel_token_child.cancelled() => {
-                            info!("Kmsg task was cancelled");
-                            return Ok(());
-                        }
-                        _ = tokio::time::timeout(tokio::time::Duration::from_millis(200), cancel_token_child.cancelled() ) => {
-                            dmesg_tx.send(dummy_entry.clone()).unwrap();
-                        }
-                    }
+            if let Some(fixture) = fixture {
+                info!("Reading kmsg from {:?}", &fixture);
+                match read_kmsg_file(&fixture) {
+                    Ok(entries) => entries
+                        .into_iter()
+                        .for_each(|entry| dmesg_tx.send(entry).unwrap()),
+                    Err(e) => warn!("Cannot read kmsg file {:?}: {}", &fixture, e),
                 }
-            } else {
-                //FIXME: this stream is buggy!!! it leaves a thread behind and tokio cannot gracefully shutdown
-                let mut st = rmesg::logs_stream(rmesg::Backend::Default, true, false).await?;
+                // keep the channel open, the application exits when it is closed
+                cancel_token_child.cancelled().await;
+                info!("Kmsg task was cancelled");
+                return Ok(());
+            }
 
-                while !cancel_token_child.is_cancelled() {
-                    tokio::select! {
-                        _ = cancel_token_child.cancelled() => {
-                            info!("Kmsg task was cancelled");
-                            return Ok(());
-                        }
-                        log = st.next() => {
-                            trace!("Got log entry");
-                            match log {
-                                Some(Ok(log)) => {
-                                    dmesg_tx.send(log).unwrap();
-                                }
-                                Some(Err(e)) => {
-                                    warn!("Error reading kmsg: {:?}", e);
-                                }
-                                None => {
-                                    warn!("Kmsg stream ended");
-                                    break;
-                                }
+            //FIXME: this stream is buggy!!! it leaves a thread behind and tokio cannot gracefully shutdown
+            let mut st = rmesg::logs_stream(backend, clear, false).await?;
+
+            while !cancel_token_child.is_cancelled() {
+                tokio::select! {
+                    _ = cancel_token_child.cancelled() => {
+                        info!("Kmsg task was cancelled");
+                        return Ok(());
+                    }
+                    log = st.next() => {
+                        trace!("Got log entry");
+                        match log {
+                            Some(Ok(log)) => {
+                                dmesg_tx.send(log).unwrap();
+                            }
+                            Some(Err(e)) => {
+                                warn!("Error reading kmsg: {:?}", e);
+                            }
+                            None => {
+                                warn!("Kmsg stream ended");
+                                break;
                             }
                         }
                     }
@@ -484,9 +877,9 @@ impl Application {
         let ipc_cancel_token = CancellationToken::new();
         let ipc_cancel_token_clone = ipc_cancel_token.clone();
         self.ipc_tx = Some(ipc_cmd_tx);
+        let socket_path = self.get_socket_path();
 
         let ipc_task = tokio::spawn(async move {
-            let socket_path = Application::get_socket_path();
             let mut has_connected = false;
 
             loop {
@@ -596,9 +989,14 @@ impl Application {
         (ipc_task, ipc_cancel_token, ipc_rx)
     }
 
-    fn create_terminal_task(&mut self) -> (JoinHandle<()>, CancellationToken) {
-        let mut terminal_event_stream = TerminalWrapper::get_stream();
-        let terminal_tx_clone = self.terminal_tx.clone();
+    fn create_terminal_task(
+        &mut self,
+    ) -> (JoinHandle<()>, CancellationToken, UnboundedReceiver<Event>) {
+        let mut terminal_event_stream = self
+            .event_stream
+            .take()
+            .unwrap_or_else(TerminalWrapper::get_stream);
+        let (terminal_tx_clone, terminal_rx) = mpsc::unbounded_channel::<Event>();
         let terminal_cancel_token = CancellationToken::new();
         let terminal_cancel_token_child = terminal_cancel_token.clone();
         let terminal_task = tokio::spawn(async move {
@@ -631,7 +1029,7 @@ impl Application {
                 }
             }
         });
-        (terminal_task, terminal_cancel_token)
+        (terminal_task, terminal_cancel_token, terminal_rx)
     }
 
     pub async fn run(&mut self) -> Result<()> {
@@ -639,10 +1037,11 @@ impl Application {
 
         // TODO: handle suspend/resume for the case when we give away /dev/tty
         // because we passed through the GPU to a guest VM
-        let (terminal_task, terminal_cancel_token) = self.create_terminal_task();
+        let (terminal_task, terminal_cancel_token, mut terminal_rx) = self.create_terminal_task();
 
         // spawn a timer to send tick events
-        let (timer_task, timer_cancellation_token, mut timer_rx) = self.create_timer_task(500);
+        let (timer_task, timer_cancellation_token, mut timer_rx) =
+            self.create_timer_task(TICK_PERIOD_MS);
 
         // start a task to fetch kernel messages using rmesg
         let (kmsg_task, kmsg_cancellation_token, mut dmesg_rx) = self.create_kmsg_task();
@@ -667,11 +1066,8 @@ impl Application {
                 }
                 tick = timer_rx.recv() => {
                     match tick {
-                        Some(event) => {
-                            let action = self.ui.handle_event(event);
-                            if let Some(action) = action {
-                                trace!("Event loop got action on tick: {:?}", action);
-                            }
+                        Some(_) => {
+                            self.handle_tick();
                         }
                         None => {
                             warn!("Timer stream ended");
@@ -679,26 +1075,16 @@ impl Application {
                         }
                     }
                 }
-                event = self.terminal_rx.recv() => {
+                event = terminal_rx.recv() => {
                     match event {
-                        Some(Event::Key(key)) => {
-                            let action = self.ui.handle_event(Event::Key(key));
-                            if let Some(action) = action {
-                                info!("Event loop got action: {:?}", action);
-
-                                self.handle_action(action);
-                            }
-                         }
-                        Some(Event::TerminalResize(w, h)) => {
-                            info!("Terminal resized: {}x{}", w, h);
+                        Some(event) => {
+                            self.handle_terminal_event(event);
                         }
                         None => {
                             warn!("Terminal event stream ended");
                             break;
                         }
-                        _ => {}
                     }
-
                 }
                 ipc_event = ipc_rx.recv() => {
                     match ipc_event {
@@ -718,12 +1104,8 @@ impl Application {
                 action = self.action_rx.recv() => {
                     match action {
                         Some(action) => {
-                            info!("Async Action: {:?}", action);
-                            match action.action {
-                                UiActions::Quit => {
-                                    app_cancel_token.cancel();
-                                }
-                                _ => {}
+                            if self.handle_async_action(action) {
+                                app_cancel_token.cancel();
                             }
                         }
                         None => {
@@ -732,6 +1114,15 @@ impl Application {
                         }
                     }
                 }
+                Some(msg) = self.diag_rx.recv() => {
+                    self.model.borrow_mut().diagnostics.handle(msg);
+                    while let Ok(msg) = self.diag_rx.try_recv() {
+                        self.model.borrow_mut().diagnostics.handle(msg);
+                    }
+                }
+                Some(msg) = self.check_rx.recv() => {
+                    self.model.borrow_mut().controller_check.handle(msg);
+                }
                 dmesg = dmesg_rx.recv() => {
                     match dmesg {
                         Some(entry) => {
@@ -755,6 +1146,10 @@ impl Application {
             }
         }
         info!("Cancelling tasks");
+        self.cancel_diagnostic();
+        if let Some(cancel) = self.check_cancel.take() {
+            cancel.cancel();
+        }
         timer_cancellation_token.cancel();
         kmsg_cancellation_token.cancel();
         terminal_cancel_token.cancel();
@@ -775,13 +1170,45 @@ impl Application {
         Ok(())
     }
 
+    pub(crate) fn handle_tick(&mut self) {
+        self.update_traffic();
+        let action = self.ui.handle_event(Event::Tick);
+        if let Some(action) = action {
+            trace!("Event loop got action on tick: {:?}", action);
+        }
+    }
+
+    pub(crate) fn handle_terminal_event(&mut self, event: Event) {
+        match event {
+            Event::Key(key) => {
+                let action = self.ui.handle_event(Event::Key(key));
+                if let Some(action) = action {
+                    info!("Event loop got action: {:?}", action);
+
+                    self.handle_action(action);
+                }
+            }
+            Event::TerminalResize(w, h) => {
+                info!("Terminal resized: {}x{}", w, h);
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle an action sent to the action channel. Returns true if the
+    /// application should quit
+    pub(crate) fn handle_async_action(&mut self, action: Action) -> bool {
+        info!("Async Action: {:?}", action);
+        matches!(action.action, UiActions::Quit)
+    }
+
     fn invalidate(&mut self) {
         self.action_tx
             .send(Action::new("app", UiActions::Redraw))
             .unwrap();
     }
 
-    fn draw_ui(&mut self, model: Rc<Model>) -> Result<()> {
+    pub(crate) fn draw_ui(&mut self, model: Rc<Model>) -> Result<()> {
         self.ui.draw(model);
         Ok(())
     }
@@ -798,9 +1225,145 @@ impl Application {
                     .find(|e| e.name == iface)
                     .cloned();
                 if let Some(iface_data) = iface_data {
-                    self.ui.show_ip_dialog(iface_data);
+                    let current_dpc = self.model.borrow().get_current_dpc().cloned();
+                    self.ui.show_ip_dialog(iface_data, current_dpc);
                 }
             }
+            UiActions::EditWifiConfig(iface) => {
+                let port = self
+                    .model
+                    .borrow()
+                    .get_current_dpc()
+                    .and_then(|dpc| dpc.get_port_by_name(&iface))
+                    .cloned();
+                match port {
+                    Some(port) if port.wireless_cfg.w_type == WirelessType::Wifi => {
+                        let networks = port.wireless_cfg.wifi.unwrap_or_default();
+                        self.ui.show_wifi_dialog(&iface, &networks);
+                    }
+                    Some(_) => {
+                        self.ui
+                            .message_box("WiFi", &format!("{} is not a WiFi port", iface));
+                    }
+                    None => {
+                        self.ui.message_box(
+                            "WiFi",
+                            &format!("{} is not in the current port configuration", iface),
+                        );
+                    }
+                }
+            }
+            UiActions::EditCellularConfig(iface) => {
+                let port = self
+                    .model
+                    .borrow()
+                    .get_current_dpc()
+                    .and_then(|dpc| dpc.get_port_by_name(&iface))
+                    .cloned();
+                match port {
+                    Some(port) if port.wireless_cfg.w_type == WirelessType::Cellular => {
+                        let (access_points, probe) =
+                            cellular_config(port.wireless_cfg.cellular_v2.as_ref());
+                        self.ui.show_cellular_dialog(&iface, &access_points, &probe);
+                    }
+                    Some(_) => {
+                        self.ui
+                            .message_box("Cellular", &format!("{} is not a WWAN port", iface));
+                    }
+                    None => {
+                        self.ui.message_box(
+                            "Cellular",
+                            &format!("{} is not in the current port configuration", iface),
+                        );
+                    }
+                }
+            }
+            UiActions::ShowCellularStatus(iface) => {
+                let is_cellular = self
+                    .model
+                    .borrow()
+                    .network
+                    .iter()
+                    .find(|i| i.name == iface)
+                    .is_some_and(|i| matches!(i.media, NetworkType::Cellular(_)));
+                if is_cellular {
+                    self.ui.show_cellular_page(&iface);
+                } else {
+                    self.ui
+                        .message_box("Cellular", &format!("{} is not a WWAN port", iface));
+                }
+            }
+            UiActions::ShowL2Topology => {
+                self.ui.show_l2_page();
+            }
+            create @ (UiActions::CreateVlan | UiActions::CreateBond) => {
+                let ports = self
+                    .model
+                    .borrow()
+                    .get_current_dpc()
+                    .map(|dpc| dpc.ports.clone());
+                match ports {
+                    Some(ports) => {
+                        let form = if create == UiActions::CreateVlan {
+                            L2LinkForm::Vlan(VlanState::default())
+                        } else {
+                            L2LinkForm::Bond(BondState::default())
+                        };
+                        self.ui.show_l2link_dialog(None, form, &ports);
+                    }
+                    None => {
+                        self.ui
+                            .message_box("L2 links", "There is no current port configuration");
+                    }
+                }
+            }
+            UiActions::EditL2Link(name) => {
+                let ports = self
+                    .model
+                    .borrow()
+                    .get_current_dpc()
+                    .map(|dpc| dpc.ports.clone())
+                    .unwrap_or_default();
+                let port = ports.iter().find(|p| port_label(p) == name);
+                let form = port.and_then(|port| match port.l2_link_config.l2_type {
+                    L2LinkType::L2LinkTypeVLAN => Some(L2LinkForm::Vlan(VlanState::from(port))),
+                    L2LinkType::L2LinkTypeBond => Some(L2LinkForm::Bond(BondState::from(port))),
+                    L2LinkType::L2LinkTypeNone => None,
+                });
+                match form {
+                    Some(form) => self.ui.show_l2link_dialog(Some(&name), form, &ports),
+                    None => {
+                        self.ui
+                            .message_box("L2 links", &format!("{} is not a VLAN or bond", name));
+                    }
+                }
+            }
+            UiActions::ShowDpcList => {
+                self.ui.show_dpc_list_page();
+            }
+            UiActions::ShowDpc(index) => {
+                let dpc = self.model.borrow().get_dpc_list().and_then(|list| {
+                    let dpc = list.port_config_list.as_ref()?.get(index)?.clone();
+                    let in_use = list.is_current(&dpc);
+                    Some((dpc, in_use))
+                });
+                if let Some((dpc, in_use)) = dpc {
+                    self.ui.show_dpc_page(&dpc, in_use);
+                }
+            }
+            UiActions::ShowDpcVerification => {
+                if self.model.borrow().dpc_verification.is_some() {
+                    self.ui.show_dpc_verify_page();
+                } else {
+                    self.ui.message_box(
+                        "Port configuration",
+                        "No port configuration was applied since the monitor started",
+                    );
+                }
+            }
+            UiActions::ShowKeyHelp(title, keys) => {
+                self.ui.show_key_help_dialog(&title, &keys);
+            }
             UiActions::ChangeServer => {
                 if self.model.borrow().node_status.is_onboarded() {
                     self.ui.message_box(
@@ -818,6 +1381,27 @@ impl Application {
                     self.ui.show_server_url_dialog(&url);
                 }
             }
+            UiActions::CheckServer => {
+                let url = self
+                    .model
+                    .borrow()
+                    .node_status
+                    .server
+                    .clone()
+                    .unwrap_or_default();
+                self.check_controller(url);
+            }
+            UiActions::AppCommand(uuid, command) => {
+                let name = self
+                    .model
+                    .borrow()
+                    .apps
+                    .get(&uuid)
+                    .map(|app| app.name.clone());
+                if let Some(name) = name {
+                    self.ui.show_app_command_dialog(&name, uuid, command);
+                }
+            }
             UiActions::AppAction(app_action) => match app_action {
                 MonActions::NetworkInterfaceUpdated(old, new) => {
                     debug!("Setting DPC for {}", &old.iface_name);
@@ -825,10 +1409,50 @@ impl Application {
                     debug!("NEW DPC: {:#?}", &new);
                     if old == new {
                         debug!("Not changed, not sending DPC");
+                        self.ui.pop_layer();
                     } else {
+                        // the dialog stays open if the settings are refused
                         self.send_dpc(old, new);
                     }
+                }
+                MonActions::WifiConfigUpdated(iface, networks) => {
+                    debug!("Setting {} WiFi networks for {}", networks.len(), &iface);
+                    self.ui.pop_layer();
+                    self.send_wifi_dpc(&iface, networks);
+                }
+                MonActions::CellularConfigUpdated(iface, access_points, probe) => {
+                    debug!(
+                        "Setting {} access points for {}",
+                        access_points.len(),
+                        &iface
+                    );
+                    self.ui.pop_layer();
+                    self.send_cellular_dpc(&iface, access_points, probe);
+                }
+                MonActions::L2LinkUpdated(edit) => {
+                    debug!("Updating L2 links: {:?}", &edit);
+                    // deleting is done from the topology page which stays open
+                    if matches!(edit, L2LinkEdit::Set { .. }) {
+                        self.ui.pop_layer();
+                    }
+                    self.send_l2_link_dpc(edit);
+                }
+                MonActions::DpcReverted(dpc) => {
+                    debug!("Reverting to DPC {} from {}", &dpc.key, dpc.time_priority);
                     self.ui.pop_layer();
+                    self.send_revert_dpc(&dpc);
+                }
+                MonActions::DpcConfirmed(dpc) => {
+                    self.ui.pop_layer();
+                    self.send_new_dpc(*dpc);
+                }
+                MonActions::DpcEditAgain => {
+                    self.ui.pop_layer();
+                    self.edit_verified_dpc();
+                }
+                MonActions::DpcRevertApplied => {
+                    self.ui.pop_layer();
+                    self.revert_verified_dpc();
                 }
                 MonActions::ServerUpdated(url) => {
                     debug!("Setting server URL to: {}", &url);
@@ -840,8 +1464,143 @@ impl Application {
                     );
                     self.ui.pop_layer();
                 }
+                MonActions::RunDiagnostic(request) => {
+                    self.run_diagnostic(request);
+                }
+                MonActions::CancelDiagnostic => {
+                    self.cancel_diagnostic();
+                }
+                MonActions::CheckController(url) => {
+                    self.check_controller(url);
+                }
+                MonActions::AppCommandConfirmed(uuid, command) => {
+                    self.ui.pop_layer();
+                    self.send_app_command(uuid, command);
+                }
+                MonActions::CrashReportAcknowledged(id) => {
+                    self.acknowledge_crash_report(&id);
+                    self.ui.pop_layer();
+                }
+                MonActions::CrashReportAddToBundle(id) => {
+                    let report = self.crash_reports.iter().find(|r| r.id() == id).cloned();
+                    self.ui.pop_layer();
+                    if let Some(report) = report {
+                        match report.add_to_support_bundle(self.config.support_bundle_dir()) {
+                            Ok(path) => {
+                                info!("Crash report {} copied to {:?}", id, path);
+                                self.acknowledge_crash_report(&id);
+                                self.ui.message_box(
+                                    "Support bundle",
+                                    &format!("Crash report saved to\n{}", path.display()),
+                                );
+                            }
+                            Err(e) => {
+                                error!("Failed to add crash report {} to bundle: {}", id, e);
+                                self.ui.message_box(
+                                    "ERROR",
+                                    &format!("Cannot add crash report to bundle: {}", e),
+                                );
+                            }
+                        }
+                    }
+                }
             },
             _ => {}
         }
     }
 }
+
+#[cfg(test)]
+impl AppConfig {
+    /// Development mode config with kmsg from test data and a socket that
+    /// does not exist. Nothing is written to disk unless the config is saved
+    pub(crate) fn for_tests() -> Self {
+        let test_data = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data");
+        let mut config = AppConfig::new(std::env::temp_dir().join("monitor-test-config.json"));
+        config.render_profile = RenderProfileMode::Unicode;
+        config.dev_mode = Some(DevModeConfig {
+            kmsg: test_data.join("kmsg").join("kmsg.txt"),
+            socket: test_data.join("no-such-monitor.sock"),
+            sysfs: test_data.join("sysfs"),
+        });
+        config.enable_dev_mode();
+        config
+    }
+}
+
+// hooks for the scripted test harness, see harness.rs
+#[cfg(test)]
+impl Application {
+    /// Route outgoing IPC messages to the returned channel instead of the
+    /// socket and mark the connection as established
+    pub(crate) fn connect_test_ipc(&mut self) -> UnboundedReceiver<IpcMessage> {
+        let (ipc_tx, ipc_rx) = mpsc::unbounded_channel::<IpcMessage>();
+        self.ipc_tx = Some(ipc_tx);
+        self.handle_ipc_message(IpcMessage::Ready);
+        ipc_rx
+    }
+
+    /// Handle actions queued on the action channel the same way the event loop
+    /// does. Returns true if the application should quit
+    pub(crate) fn handle_pending_actions(&mut self) -> bool {
+        let mut quit = false;
+        while let Ok(action) = self.action_rx.try_recv() {
+            quit |= self.handle_async_action(action);
+        }
+        quit
+    }
+
+    pub(crate) fn ui(&self) -> &Ui {
+        &self.ui
+    }
+
+    pub(crate) fn model(&self) -> Rc<Model> {
+        self.model.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::ui::UiTabs;
+    use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers};
+
+    #[tokio::test]
+    async fn test_run_with_scripted_keys() {
+        let ctrl_right = CrosstermEvent::Key(KeyEvent::new(KeyCode::Right, KeyModifiers::CONTROL));
+        // the Home tab between Summary and Network exists in debug builds only
+        let events = TerminalWrapper::scripted_stream(vec![ctrl_right; UiTabs::Network as usize]);
+        let terminal = TerminalWrapper::open_test_terminal(80, 24).unwrap();
+
+        let mut app = Application::new(AppConfig::for_tests(), terminal, events).unwrap();
+        // the application exits when the scripted stream ends
+        app.run().await.unwrap();
+
+        assert!(matches!(app.ui.selected_tab, UiTabs::Network));
+        let backend = app.ui.terminal.test_backend().unwrap();
+        let first_line = (0..80)
+            .map(|x| backend.buffer()[(x, 0)].symbol().to_string())
+            .collect::<String>();
+        assert!(first_line.contains("Use ctrl"));
+    }
+
+    #[test]
+    fn test_dev_mode_settings() {
+        let dir = std::env::temp_dir().join(format!("monitor-dev-mode-{}", std::process::id()));
+        assert!(!AppConfig::has_dev_mode(&dir));
+
+        // the section alone does not enable the development mode
+        let mut config = AppConfig::load_or_create_app_config(&dir);
+        config.dev_mode = Some(DevModeConfig::default());
+        config.save().unwrap();
+        assert!(AppConfig::has_dev_mode(&dir));
+        assert!(config.dev_mode_settings().is_none());
+
+        config.enable_dev_mode();
+        assert_eq!(
+            config.dev_mode_settings().unwrap().socket,
+            PathBuf::from("./persist/monitor.sock")
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use log::{debug, warn};
+use serde::Deserialize;
+
+/// Panic report written by human-panic into a log session directory
+/// (see `initialize_panic_handler` in main.rs)
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CrashReport {
+    /// full path to the report file. Not part of the report itself
+    #[serde(skip)]
+    pub path: PathBuf,
+    /// name of the log session directory the report was found in
+    #[serde(skip)]
+    pub session: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub operating_system: String,
+    #[serde(default)]
+    pub crate_version: String,
+    #[serde(default)]
+    pub explanation: String,
+    #[serde(default)]
+    pub cause: String,
+    #[serde(default)]
+    pub backtrace: String,
+}
+
+impl CrashReport {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = std::fs::read_to_string(&path)?;
+        let mut report: CrashReport = toml::from_str(&content)?;
+        report.path = path.as_ref().to_path_buf();
+        report.session = path
+            .as_ref()
+            .parent()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Ok(report)
+    }
+
+    /// Unique identifier of the report in form of `<session>/<file name>`.
+    /// Used to record acknowledged reports in the application config
+    pub fn id(&self) -> String {
+        let file_name = self
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        format!("{}/{}", self.session, file_name)
+    }
+
+    /// Short human readable summary: the first line of the panic message
+    pub fn summary(&self) -> String {
+        self.cause
+            .lines()
+            .map(str::trim)
+            .find(|l| !l.is_empty())
+            .unwrap_or("Unknown cause")
+            .to_string()
+    }
+
+    /// Source location of the panic. human-panic stores it in the explanation
+    /// as "Panic occurred in file '<file>' at line <line>"
+    pub fn location(&self) -> Option<String> {
+        self.explanation
+            .lines()
+            .map(str::trim)
+            .find(|l| l.starts_with("Panic occurred in file"))
+            .map(|l| {
+                l.trim_start_matches("Panic occurred in file")
+                    .trim()
+                    .replace('\'', "")
+                    .replace(" at line ", ":")
+            })
+    }
+
+    /// The innermost frame of the monitor itself in the backtrace, without
+    /// the symbol hash. Frames of std and of the panic handler are skipped
+    pub fn top_frame(&self) -> Option<String> {
+        self.backtrace
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.starts_with("at "))
+            .filter_map(|l| l.split_once(" - ").or_else(|| l.split_once(": ")))
+            .map(|(_, symbol)| symbol.trim())
+            .find(|symbol| symbol.starts_with("monitor::") && !symbol.contains("panic"))
+            .map(|symbol| match symbol.rsplit_once("::h") {
+                Some((name, hash))
+                    if hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit()) =>
+                {
+                    name.to_string()
+                }
+                _ => symbol.to_string(),
+            })
+    }
+
+    /// Copy the report into `bundle_dir` so it is collected together with
+    /// the rest of the support information. Returns the path of the copy
+    pub fn add_to_support_bundle<P: AsRef<Path>>(&self, bundle_dir: P) -> Result<PathBuf> {
+        let dst_dir = bundle_dir.as_ref().join(&self.session);
+        std::fs::create_dir_all(&dst_dir)?;
+        let dst = dst_dir.join(self.path.file_name().unwrap_or_default());
+        std::fs::copy(&self.path, &dst)?;
+        // the log of the crashed session is useful as well
+        let log_file = self.path.with_file_name("monitor.log");
+        if log_file.exists() {
+            std::fs::copy(&log_file, dst_dir.join("monitor.log"))?;
+        }
+        Ok(dst)
+    }
+}
+
+/// Scan log session directories under `base_log_dir` for human-panic reports.
+/// The session `current_session` and reports listed in `acknowledged` are skipped.
+/// Reports are sorted from the oldest session to the newest one
+pub fn find_crash_reports<P: AsRef<Path>>(
+    base_log_dir: P,
+    current_session: Option<&Path>,
+    acknowledged: &[String],
+) -> Vec<CrashReport> {
+    let Ok(sessions) = std::fs::read_dir(base_log_dir.as_ref()) else {
+        return Vec::new();
+    };
+
+    let mut sessions = sessions
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_dir())
+        .filter(|path| current_session != Some(path.as_path()))
+        .collect::<Vec<_>>();
+    // session directories are named %Y-%m-%d-%H-%M-%S so they sort chronologically
+    sessions.sort();
+
+    let mut reports = Vec::new();
+    for session in sessions {
+        let Ok(entries) = std::fs::read_dir(&session) else {
+            continue;
+        };
+        let mut files = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| is_report_file(path))
+            .collect::<Vec<_>>();
+        files.sort();
+
+        for file in files {
+            match CrashReport::load(&file) {
+                Ok(report) if acknowledged.contains(&report.id()) => {
+                    debug!("Crash report {} already acknowledged", report.id());
+                }
+                Ok(report) => reports.push(report),
+                Err(e) => warn!("Cannot parse crash report {:?}: {}", file, e),
+            }
+        }
+    }
+    reports
+}
+
+fn is_report_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| n.starts_with("report-") && n.ends_with(".toml"))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_data_path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data")
+            .join("crash-reports")
+    }
+
+    #[test]
+    fn test_find_crash_reports() {
+        let reports = find_crash_reports(get_test_data_path(), None, &[]);
+        assert_eq!(reports.len(), 1);
+        let report = &reports[0];
+        assert_eq!(report.session, "2025-01-20-10-15-42");
+        assert_eq!(report.crate_version, "0.6.2");
+        assert_eq!(report.summary(), "Manual panic requested");
+        assert_eq!(report.location().as_deref(), Some("src/ui/ui.rs:281"));
+        assert!(report
+            .backtrace
+            .contains("monitor::ui::ui::Ui::handle_event"));
+        assert_eq!(
+            report.top_frame().as_deref(),
+            Some("monitor::ui::ui::Ui::handle_event")
+        );
+        assert_eq!(
+            report.id(),
+            "2025-01-20-10-15-42/report-3f1c2d5e-8f9a-4b7e-9c3d-1a2b3c4d5e6f.toml"
+        );
+    }
+
+    #[test]
+    fn test_top_frame() {
+        let mut report = find_crash_reports(get_test_data_path(), None, &[]).remove(0);
+        // frames of std and the panic handler come first in real reports
+        report.backtrace = [
+            "   0: std::panicking::begin_panic_handler",
+            "   1: monitor::initialize_panic_handler::{{closure}}::h0123456789abcdef",
+            "   2: monitor::application::Application::run::{{closure}}",
+            "             at src/application.rs:688",
+        ]
+        .join("\n");
+        assert_eq!(
+            report.top_frame().as_deref(),
+            Some("monitor::application::Application::run::{{closure}}")
+        );
+
+        report.backtrace = String::new();
+        assert_eq!(report.top_frame(), None);
+    }
+
+    #[test]
+    fn test_skip_current_and_acknowledged() {
+        let base = get_test_data_path();
+        let current = base.join("2025-01-20-10-15-42");
+        assert!(find_crash_reports(&base, Some(&current), &[]).is_empty());
+
+        let id = "2025-01-20-10-15-42/report-3f1c2d5e-8f9a-4b7e-9c3d-1a2b3c4d5e6f.toml";
+        assert!(find_crash_reports(&base, None, &[id.to_string()]).is_empty());
+    }
+
+    #[test]
+    fn test_missing_log_dir() {
+        let reports = find_crash_reports(get_test_data_path().join("does-not-exist"), None, &[]);
+        assert!(reports.is_empty());
+    }
+}
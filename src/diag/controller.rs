@@ -0,0 +1,594 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reachability check of the controller URL.
+//!
+//! The check walks the same path EVE takes to the controller: the URL is
+//! parsed, the host name resolved, a TCP connection opened, directly or
+//! through the proxy configured on a management port, and a TLS handshake
+//! started. Every step is reported with its latency, followed by the
+//! certificate chain the server presented.
+
+use std::{
+    fmt::Display,
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    sync::mpsc::UnboundedSender,
+};
+use tokio_util::sync::CancellationToken;
+use url::Url;
+
+use crate::{
+    dpc::certificate::ProxyCertificate,
+    model::device::network::{NetworkInterfaceStatus, ProxyConfig},
+};
+
+use super::tls::fetch_server_handshake;
+
+const DEFAULT_PORT: u16 = 443;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const PROXY_TIMEOUT: Duration = Duration::from_secs(10);
+const TLS_TIMEOUT: Duration = Duration::from_secs(10);
+// connecting tries at most this many addresses
+const MAX_ADDRESSES: usize = 4;
+// limit of the proxy response headers
+const MAX_PROXY_RESPONSE: usize = 4096;
+
+/// HTTP proxy which tunnels TLS with CONNECT
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProxyServer {
+    pub host: String,
+    pub port: u16,
+}
+
+impl Display for ProxyServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.host, self.port)
+    }
+}
+
+/// Host and port of the controller. EVE stores the server as a bare host
+/// name, a URL must use https
+pub fn parse_server_url(server: &str) -> Result<(String, u16), String> {
+    let server = server.trim();
+    if server.is_empty() {
+        return Err("no server URL".to_string());
+    }
+    let url = if server.contains("://") {
+        server.to_string()
+    } else {
+        format!("https://{}", server)
+    };
+    let url = Url::parse(&url).map_err(|e| format!("invalid URL: {}", e))?;
+    if url.scheme() != "https" {
+        return Err(format!("unsupported scheme {}, use https", url.scheme()));
+    }
+    let host = url
+        .host_str()
+        .ok_or("the URL has no host")?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    Ok((host, url.port().unwrap_or(DEFAULT_PORT)))
+}
+
+/// Proxy for https traffic of a manual proxy configuration. PAC files and
+/// WPAD are not evaluated, the check connects directly then. Fails if the
+/// port is out of range
+fn proxy_server(config: &ProxyConfig) -> Option<Result<ProxyServer, String>> {
+    let ProxyConfig::Manual { http, https, .. } = config else {
+        return None;
+    };
+    https
+        .as_ref()
+        .or(http.as_ref())
+        .filter(|proxy| !proxy.server().is_empty())
+        .map(|proxy| {
+            let port = u16::try_from(proxy.port()).map_err(|_| {
+                format!(
+                    "port {} of proxy {} is out of range",
+                    proxy.port(),
+                    proxy.server()
+                )
+            })?;
+            Ok(ProxyServer {
+                host: proxy.server().to_string(),
+                port,
+            })
+        })
+}
+
+/// Proxy of the first management port which has one
+pub fn find_proxy(network: &[NetworkInterfaceStatus]) -> Option<Result<ProxyServer, String>> {
+    network
+        .iter()
+        .filter(|iface| iface.is_mgmt)
+        .find_map(|iface| proxy_server(&iface.proxy_config))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckStep {
+    pub name: String,
+    /// Details of the outcome
+    pub result: Result<String, String>,
+    pub elapsed: Option<Duration>,
+    /// A failed optional step is reported but does not fail the check
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckEvent {
+    Step(CheckStep),
+    Certificates(Vec<ProxyCertificate>),
+    Finished,
+}
+
+/// Event of the check with the given number, see [ControllerCheck::start]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckMessage {
+    pub run: u64,
+    pub event: CheckEvent,
+}
+
+/// Result of the last reachability check
+#[derive(Debug, Default)]
+pub struct ControllerCheck {
+    pub run: u64,
+    pub server: String,
+    pub proxy: Option<Result<ProxyServer, String>>,
+    pub steps: Vec<CheckStep>,
+    pub certificates: Vec<ProxyCertificate>,
+    pub running: bool,
+}
+
+impl ControllerCheck {
+    /// Forget the previous result. Returns the number of the new check
+    pub fn start(&mut self, server: String, proxy: Option<Result<ProxyServer, String>>) -> u64 {
+        self.run += 1;
+        self.server = server;
+        self.proxy = proxy;
+        self.steps.clear();
+        self.certificates.clear();
+        self.running = true;
+        self.run
+    }
+
+    /// Add a result of the current check. Results of cancelled checks are
+    /// dropped
+    pub fn handle(&mut self, msg: CheckMessage) {
+        if msg.run != self.run {
+            return;
+        }
+        match msg.event {
+            CheckEvent::Step(step) => self.steps.push(step),
+            CheckEvent::Certificates(certificates) => self.certificates = certificates,
+            CheckEvent::Finished => self.running = false,
+        }
+    }
+
+    /// True if every required step of a finished check succeeded
+    pub fn passed(&self) -> bool {
+        !self.running
+            && !self.steps.is_empty()
+            && self.steps.iter().all(|s| s.result.is_ok() || !s.required)
+    }
+}
+
+/// Sends the results of a check to the event loop
+pub struct CheckOutput {
+    run: u64,
+    tx: UnboundedSender<CheckMessage>,
+}
+
+impl CheckOutput {
+    pub fn new(run: u64, tx: UnboundedSender<CheckMessage>) -> Self {
+        Self { run, tx }
+    }
+
+    fn send(&self, event: CheckEvent) {
+        // the receiver is gone when the application exits
+        let _ = self.tx.send(CheckMessage {
+            run: self.run,
+            event,
+        });
+    }
+
+    fn step<S: Into<String>>(
+        &self,
+        name: S,
+        result: Result<String, String>,
+        start: Option<Instant>,
+    ) {
+        self.send_step(name.into(), result, start, true);
+    }
+
+    fn optional_step<S: Into<String>>(
+        &self,
+        name: S,
+        result: Result<String, String>,
+        start: Option<Instant>,
+    ) {
+        self.send_step(name.into(), result, start, false);
+    }
+
+    fn send_step(
+        &self,
+        name: String,
+        result: Result<String, String>,
+        start: Option<Instant>,
+        required: bool,
+    ) {
+        self.send(CheckEvent::Step(CheckStep {
+            name,
+            result,
+            elapsed: start.map(|s| s.elapsed()),
+            required,
+        }));
+    }
+}
+
+/// Check `server` until all steps are done, one fails or `cancel` is
+/// triggered. Always ends with [CheckEvent::Finished]
+pub async fn check_controller(
+    server: String,
+    proxy: Option<Result<ProxyServer, String>>,
+    out: CheckOutput,
+    cancel: CancellationToken,
+) {
+    tokio::select! {
+        _ = cancel.cancelled() => out.step("check", Err("cancelled".to_string()), None),
+        _ = run_check(&server, proxy.as_ref(), &out) => {}
+    }
+    out.send(CheckEvent::Finished);
+}
+
+async fn resolve(host: &str, port: u16, out: &CheckOutput) -> Option<Vec<SocketAddr>> {
+    let start = Instant::now();
+    let result = lookup(host, port).await;
+    let addrs = result.as_ref().ok().map(|(addrs, _)| addrs.clone());
+    out.step(
+        format!("DNS {}", host),
+        result.map(|(_, list)| list),
+        Some(start),
+    );
+    addrs
+}
+
+/// Addresses of `host` and their list for the report
+async fn lookup(host: &str, port: u16) -> Result<(Vec<SocketAddr>, String), String> {
+    let addrs = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| e.to_string())?
+        .collect::<Vec<_>>();
+    let list = addrs
+        .iter()
+        .map(|a| a.ip().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    Ok((addrs, list))
+}
+
+async fn connect(addrs: &[SocketAddr], out: &CheckOutput) -> Option<TcpStream> {
+    for addr in addrs.iter().take(MAX_ADDRESSES) {
+        let start = Instant::now();
+        let error = match tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect(addr)).await {
+            Ok(Ok(stream)) => {
+                out.step("TCP connect", Ok(addr.to_string()), Some(start));
+                return Some(stream);
+            }
+            Ok(Err(e)) => format!("{}: {}", addr, e),
+            Err(_) => format!("{}: timed out", addr),
+        };
+        out.step("TCP connect", Err(error), Some(start));
+    }
+    None
+}
+
+/// Open a tunnel to `host`:`port`. Returns the status line of the proxy
+async fn proxy_connect(stream: &mut TcpStream, host: &str, port: u16) -> Result<String, String> {
+    let target = if host.contains(':') {
+        format!("[{}]:{}", host, port)
+    } else {
+        format!("{}:{}", host, port)
+    };
+    let request = format!("CONNECT {0} HTTP/1.1\r\nHost: {0}\r\n\r\n", target);
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // read byte by byte, the TLS handshake follows the headers
+    let mut response = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() > MAX_PROXY_RESPONSE {
+            return Err("response is too long".to_string());
+        }
+        let byte = stream.read_u8().await.map_err(|e| match e.kind() {
+            std::io::ErrorKind::UnexpectedEof => "connection closed by the proxy".to_string(),
+            _ => e.to_string(),
+        })?;
+        response.push(byte);
+    }
+    let response = String::from_utf8_lossy(&response);
+    let status = response.lines().next().unwrap_or_default().to_string();
+    match status.split_whitespace().nth(1) {
+        Some(code) if code.starts_with('2') => Ok(status),
+        _ => Err(status),
+    }
+}
+
+async fn run_check(server: &str, proxy: Option<&Result<ProxyServer, String>>, out: &CheckOutput) {
+    let (host, port) = match parse_server_url(server) {
+        Ok((host, port)) => {
+            out.step("URL", Ok(format!("{} port {}", host, port)), None);
+            (host, port)
+        }
+        Err(e) => return out.step("URL", Err(e), None),
+    };
+    let proxy = match proxy {
+        Some(Ok(proxy)) => Some(proxy),
+        Some(Err(e)) => return out.step("Proxy", Err(e.clone()), None),
+        None => None,
+    };
+
+    // the controller name is resolved by the proxy. The local DNS is checked
+    // as well but a failure only matters for traffic bypassing the proxy
+    if proxy.is_some() {
+        let start = Instant::now();
+        let result = lookup(&host, port).await.map(|(_, list)| list);
+        out.optional_step(format!("DNS {}", host), result, Some(start));
+    }
+    let addrs = match proxy {
+        Some(proxy) => resolve(&proxy.host, proxy.port, out).await,
+        None => resolve(&host, port, out).await,
+    };
+    let Some(addrs) = addrs else {
+        return;
+    };
+    let Some(mut stream) = connect(&addrs, out).await else {
+        return;
+    };
+
+    if let Some(proxy) = proxy {
+        let start = Instant::now();
+        let result = tokio::time::timeout(PROXY_TIMEOUT, proxy_connect(&mut stream, &host, port))
+            .await
+            .unwrap_or_else(|_| Err("timed out".to_string()));
+        let failed = result.is_err();
+        out.step(format!("Proxy CONNECT via {}", proxy), result, Some(start));
+        if failed {
+            return;
+        }
+    }
+
+    let start = Instant::now();
+    let handshake =
+        match tokio::time::timeout(TLS_TIMEOUT, fetch_server_handshake(&mut stream, &host)).await {
+            Ok(Ok(handshake)) => handshake,
+            Ok(Err(e)) => return out.step("TLS handshake", Err(e.to_string()), Some(start)),
+            Err(_) => return out.step("TLS handshake", Err("timed out".to_string()), Some(start)),
+        };
+    out.step(
+        "TLS handshake",
+        Ok(format!(
+            "{}, {}",
+            handshake.version_name(),
+            handshake.cipher_name()
+        )),
+        Some(start),
+    );
+
+    let mut certificates = Vec::new();
+    for (i, der) in handshake.certificates.iter().enumerate() {
+        match ProxyCertificate::from_der(der) {
+            Ok(cert) => certificates.push(cert),
+            Err(e) => out.step(format!("Certificate {}", i), Err(e.to_string()), None),
+        }
+    }
+    out.send(CheckEvent::Certificates(certificates));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        diag::tls::tests::test_acceptor,
+        ipc::eve_types::{self, NetworkProxyType, ProxyEntry},
+    };
+    use tokio::{net::TcpListener, sync::mpsc};
+    use tokio_rustls::rustls::version::TLS13;
+
+    /// Accept one connection, answer an optional CONNECT and the handshake
+    async fn fake_server(proxy: bool) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let acceptor = test_acceptor(&[&TLS13]);
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            if proxy {
+                let mut request = Vec::new();
+                while !request.ends_with(b"\r\n\r\n") {
+                    request.push(stream.read_u8().await.unwrap());
+                }
+                assert!(request.starts_with(b"CONNECT zedcloud.local:443 HTTP/1.1\r\n"));
+                stream
+                    .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+                    .await
+                    .unwrap();
+            }
+            if let Ok(mut tls) = acceptor.accept(stream).await {
+                let _ = tls.read_to_end(&mut Vec::new()).await;
+            }
+        });
+        addr
+    }
+
+    async fn run(server: &str, proxy: Option<Result<ProxyServer, String>>) -> ControllerCheck {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut check = ControllerCheck::default();
+        let run = check.start(server.to_string(), proxy.clone());
+        check_controller(
+            server.to_string(),
+            proxy,
+            CheckOutput::new(run, tx),
+            CancellationToken::new(),
+        )
+        .await;
+        while let Ok(msg) = rx.try_recv() {
+            check.handle(msg);
+        }
+        check
+    }
+
+    #[test]
+    fn test_parse_server_url() {
+        assert_eq!(
+            parse_server_url("zedcloud.local.zededa.net"),
+            Ok(("zedcloud.local.zededa.net".to_string(), 443))
+        );
+        assert_eq!(
+            parse_server_url(" https://zedcloud.local:8443/api "),
+            Ok(("zedcloud.local".to_string(), 8443))
+        );
+        assert_eq!(
+            parse_server_url("[fd00::1]:444"),
+            Ok(("fd00::1".to_string(), 444))
+        );
+        assert!(parse_server_url("").is_err());
+        assert!(parse_server_url("http://zedcloud.local").is_err());
+        assert!(parse_server_url("https://").is_err());
+    }
+
+    #[test]
+    fn test_proxy_server() {
+        let mut config = eve_types::ProxyConfig {
+            proxies: Some(vec![ProxyEntry {
+                proxy_type: NetworkProxyType::HTTP,
+                server: "http.proxy".to_string(),
+                port: 8080,
+            }]),
+            ..Default::default()
+        };
+        assert_eq!(
+            proxy_server(&(&config).into()),
+            Some(Ok(ProxyServer {
+                host: "http.proxy".to_string(),
+                port: 8080
+            }))
+        );
+
+        // the https proxy is preferred
+        config.proxies.as_mut().unwrap().push(ProxyEntry {
+            proxy_type: NetworkProxyType::HTTPS,
+            server: "https.proxy".to_string(),
+            port: 3128,
+        });
+        assert_eq!(
+            proxy_server(&(&config).into())
+                .unwrap()
+                .unwrap()
+                .to_string(),
+            "https.proxy:3128"
+        );
+
+        config.proxies.as_mut().unwrap()[1].port = 70000;
+        assert_eq!(
+            proxy_server(&(&config).into()),
+            Some(Err(
+                "port 70000 of proxy https.proxy is out of range".to_string()
+            ))
+        );
+
+        config.pacfile = "http://wpad.local/proxy.pac".to_string();
+        assert_eq!(proxy_server(&(&config).into()), None);
+    }
+
+    #[tokio::test]
+    async fn test_direct_check() {
+        let addr = fake_server(false).await;
+        let check = run(&format!("https://{}", addr), None).await;
+        let names = check
+            .steps
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec!["URL", "DNS 127.0.0.1", "TCP connect", "TLS handshake"]
+        );
+        assert!(check.passed(), "{:?}", check.steps);
+        assert!(check.steps[3]
+            .result
+            .as_ref()
+            .is_ok_and(|r| r.starts_with("TLS 1.3, TLS13_")));
+        assert_eq!(check.certificates.len(), 2);
+        assert_eq!(check.certificates[0].common_name, "zedcloud.local");
+        assert_eq!(check.certificates[1].common_name, "Example Corp Proxy CA");
+    }
+
+    #[tokio::test]
+    async fn test_check_via_proxy() {
+        let addr = fake_server(true).await;
+        let proxy = ProxyServer {
+            host: "127.0.0.1".to_string(),
+            port: addr.port(),
+        };
+        // the controller name is resolved by the proxy, the local DNS
+        // failure is reported only
+        let check = run("zedcloud.local", Some(Ok(proxy))).await;
+        let names = check
+            .steps
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "URL",
+                "DNS zedcloud.local",
+                "DNS 127.0.0.1",
+                "TCP connect",
+                &format!("Proxy CONNECT via 127.0.0.1:{}", addr.port()),
+                "TLS handshake",
+            ]
+        );
+        assert!(!check.steps[1].required);
+        assert!(check.steps[1].result.is_err());
+        assert!(check.passed(), "{:?}", check.steps);
+        assert_eq!(check.certificates.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_proxy_port() {
+        let error = "port 70000 of proxy http.proxy is out of range".to_string();
+        let check = run("zedcloud.local", Some(Err(error.clone()))).await;
+        let names = check
+            .steps
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["URL", "Proxy"]);
+        assert_eq!(check.steps[1].result, Err(error));
+        assert!(!check.passed());
+    }
+
+    #[tokio::test]
+    async fn test_connection_refused() {
+        // nothing listens on the port once the listener is dropped
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let check = run(&addr.to_string(), None).await;
+        assert!(!check.running);
+        assert!(!check.passed());
+        assert_eq!(check.steps.last().unwrap().name, "TCP connect");
+        assert!(check.steps.last().unwrap().result.is_err());
+    }
+}
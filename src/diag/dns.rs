@@ -0,0 +1,328 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Minimal DNS client (RFC 1035) to query a chosen server over UDP.
+//!
+//! The system resolver always uses the servers from resolv.conf. To check a
+//! server configured on a particular port the query is sent directly, bound
+//! to that port, and the answer section is decoded for the record types shown
+//! to the user.
+
+use std::{
+    fmt::Display,
+    net::{Ipv4Addr, Ipv6Addr},
+};
+
+use thiserror::Error;
+
+pub const TYPE_A: u16 = 1;
+pub const TYPE_CNAME: u16 = 5;
+pub const TYPE_AAAA: u16 = 28;
+const CLASS_IN: u16 = 1;
+
+const HEADER_LEN: usize = 12;
+// recursion desired
+const FLAG_RD: u16 = 0x0100;
+const FLAG_QR: u16 = 0x8000;
+const FLAG_TC: u16 = 0x0200;
+// maximum number of compression pointers followed in a single name
+const MAX_POINTERS: usize = 16;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum DnsError {
+    #[error("invalid name {0:?}")]
+    InvalidName(String),
+    #[error("malformed response")]
+    Malformed,
+    #[error("response does not match the query")]
+    Mismatch,
+    #[error("response is truncated")]
+    Truncated,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordData {
+    A(Ipv4Addr),
+    Aaaa(Ipv6Addr),
+    Cname(String),
+    /// Record types which are not decoded
+    Other(u16),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DnsRecord {
+    pub name: String,
+    pub ttl: u32,
+    pub data: RecordData,
+}
+
+impl Display for DnsRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (rtype, data) = match &self.data {
+            RecordData::A(addr) => ("A".to_string(), addr.to_string()),
+            RecordData::Aaaa(addr) => ("AAAA".to_string(), addr.to_string()),
+            RecordData::Cname(name) => ("CNAME".to_string(), name.clone()),
+            RecordData::Other(rtype) => (format!("TYPE{}", rtype), String::new()),
+        };
+        write!(f, "{} {:<6}{} ttl {}", self.name, rtype, data, self.ttl)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DnsResponse {
+    /// RCODE of the header, 0 is no error
+    pub rcode: u8,
+    pub records: Vec<DnsRecord>,
+}
+
+impl DnsResponse {
+    pub fn rcode_name(&self) -> String {
+        match self.rcode {
+            0 => "NOERROR".to_string(),
+            1 => "FORMERR".to_string(),
+            2 => "SERVFAIL".to_string(),
+            3 => "NXDOMAIN".to_string(),
+            4 => "NOTIMP".to_string(),
+            5 => "REFUSED".to_string(),
+            rcode => format!("RCODE{}", rcode),
+        }
+    }
+}
+
+pub fn type_name(qtype: u16) -> &'static str {
+    match qtype {
+        TYPE_A => "A",
+        TYPE_AAAA => "AAAA",
+        TYPE_CNAME => "CNAME",
+        _ => "?",
+    }
+}
+
+/// Build a recursive query for `name` with a single question
+pub fn build_query(id: u16, name: &str, qtype: u16) -> Result<Vec<u8>, DnsError> {
+    let mut query = Vec::with_capacity(HEADER_LEN + name.len() + 6);
+    query.extend_from_slice(&id.to_be_bytes());
+    query.extend_from_slice(&FLAG_RD.to_be_bytes());
+    // one question, no answer, authority or additional records
+    query.extend_from_slice(&[0, 1, 0, 0, 0, 0, 0, 0]);
+
+    let name = name.strip_suffix('.').unwrap_or(name);
+    if name.is_empty() || name.len() > 253 {
+        return Err(DnsError::InvalidName(name.to_string()));
+    }
+    for label in name.split('.') {
+        if label.is_empty() || label.len() > 63 || !label.is_ascii() {
+            return Err(DnsError::InvalidName(name.to_string()));
+        }
+        query.push(label.len() as u8);
+        query.extend_from_slice(label.as_bytes());
+    }
+    query.push(0);
+    query.extend_from_slice(&qtype.to_be_bytes());
+    query.extend_from_slice(&CLASS_IN.to_be_bytes());
+    Ok(query)
+}
+
+struct Reader<'a> {
+    msg: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], DnsError> {
+        let bytes = self
+            .msg
+            .get(self.pos..self.pos + len)
+            .ok_or(DnsError::Malformed)?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16, DnsError> {
+        let b = self.bytes(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, DnsError> {
+        let b = self.bytes(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// Read a possibly compressed name starting at the current position
+    fn name(&mut self) -> Result<String, DnsError> {
+        let mut labels = Vec::new();
+        let mut pos = self.pos;
+        // position after the name in the original data, set by the first pointer
+        let mut end = None;
+        let mut pointers = 0;
+        loop {
+            let len = *self.msg.get(pos).ok_or(DnsError::Malformed)? as usize;
+            match len {
+                0 => {
+                    pos += 1;
+                    break;
+                }
+                l if l & 0xc0 == 0xc0 => {
+                    let low = *self.msg.get(pos + 1).ok_or(DnsError::Malformed)? as usize;
+                    pointers += 1;
+                    if pointers > MAX_POINTERS {
+                        return Err(DnsError::Malformed);
+                    }
+                    end.get_or_insert(pos + 2);
+                    pos = ((l & 0x3f) << 8) | low;
+                }
+                l if l <= 63 => {
+                    let label = self
+                        .msg
+                        .get(pos + 1..pos + 1 + l)
+                        .ok_or(DnsError::Malformed)?;
+                    labels.push(String::from_utf8_lossy(label).to_string());
+                    pos += 1 + l;
+                }
+                _ => return Err(DnsError::Malformed),
+            }
+        }
+        self.pos = end.unwrap_or(pos);
+        Ok(labels.join("."))
+    }
+}
+
+/// Parse the response to the query built by [build_query] with the same `id`
+pub fn parse_response(id: u16, msg: &[u8]) -> Result<DnsResponse, DnsError> {
+    let mut r = Reader { msg, pos: 0 };
+    if r.u16()? != id {
+        return Err(DnsError::Mismatch);
+    }
+    let flags = r.u16()?;
+    if flags & FLAG_QR == 0 {
+        return Err(DnsError::Mismatch);
+    }
+    if flags & FLAG_TC != 0 {
+        return Err(DnsError::Truncated);
+    }
+    let questions = r.u16()?;
+    let answers = r.u16()?;
+    // authority and additional records are not shown
+    r.bytes(4)?;
+
+    for _ in 0..questions {
+        r.name()?;
+        // type and class
+        r.bytes(4)?;
+    }
+
+    let mut records = Vec::new();
+    for _ in 0..answers {
+        let name = r.name()?;
+        let rtype = r.u16()?;
+        let _class = r.u16()?;
+        let ttl = r.u32()?;
+        let len = r.u16()? as usize;
+        let start = r.pos;
+        let data = match (rtype, len) {
+            (TYPE_A, 4) => {
+                let b = r.bytes(4)?;
+                RecordData::A(Ipv4Addr::new(b[0], b[1], b[2], b[3]))
+            }
+            (TYPE_AAAA, 16) => {
+                let b: [u8; 16] = r.bytes(16)?.try_into().map_err(|_| DnsError::Malformed)?;
+                RecordData::Aaaa(Ipv6Addr::from(b))
+            }
+            (TYPE_CNAME, _) => RecordData::Cname(r.name()?),
+            (TYPE_A | TYPE_AAAA, _) => return Err(DnsError::Malformed),
+            (rtype, _) => RecordData::Other(rtype),
+        };
+        // skip the rest of the data, e.g. of not decoded records
+        r.pos = start;
+        r.bytes(len)?;
+        records.push(DnsRecord { name, ttl, data });
+    }
+
+    Ok(DnsResponse {
+        rcode: (flags & 0x000f) as u8,
+        records,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // response of 1.1.1.1 to "A www.example.com" with a CNAME
+    fn response(id: u16, rcode: u8) -> Vec<u8> {
+        let mut msg = Vec::new();
+        msg.extend_from_slice(&id.to_be_bytes());
+        msg.extend_from_slice(&(0x8180u16 | rcode as u16).to_be_bytes());
+        msg.extend_from_slice(&[0, 1, 0, 2, 0, 0, 0, 0]);
+        // question at offset 12
+        msg.extend_from_slice(b"\x03www\x07example\x03com\x00\x00\x01\x00\x01");
+        // www.example.com CNAME example.com (pointer to offset 16)
+        msg.extend_from_slice(&[0xc0, 12, 0, 5, 0, 1, 0, 0, 0x0e, 0x10, 0, 2, 0xc0, 16]);
+        // example.com A 93.184.216.34
+        msg.extend_from_slice(&[0xc0, 16, 0, 1, 0, 1, 0, 0, 0x01, 0x2c, 0, 4]);
+        msg.extend_from_slice(&[93, 184, 216, 34]);
+        msg
+    }
+
+    #[test]
+    fn test_build_query() {
+        let query = build_query(0x1234, "www.example.com.", TYPE_AAAA).unwrap();
+        assert_eq!(
+            query,
+            b"\x12\x34\x01\x00\x00\x01\x00\x00\x00\x00\x00\x00\x03www\x07example\x03com\x00\x00\x1c\x00\x01"
+        );
+    }
+
+    #[test]
+    fn test_build_query_invalid_name() {
+        assert!(build_query(1, "", TYPE_A).is_err());
+        assert!(build_query(1, "a..b", TYPE_A).is_err());
+        assert!(build_query(1, &"a".repeat(64), TYPE_A).is_err());
+        assert!(build_query(1, "bücher.de", TYPE_A).is_err());
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let response = parse_response(7, &response(7, 0)).unwrap();
+        assert_eq!(response.rcode_name(), "NOERROR");
+        assert_eq!(
+            response.records,
+            vec![
+                DnsRecord {
+                    name: "www.example.com".to_string(),
+                    ttl: 3600,
+                    data: RecordData::Cname("example.com".to_string()),
+                },
+                DnsRecord {
+                    name: "example.com".to_string(),
+                    ttl: 300,
+                    data: RecordData::A(Ipv4Addr::new(93, 184, 216, 34)),
+                },
+            ]
+        );
+        assert_eq!(
+            response.records[1].to_string(),
+            "example.com A     93.184.216.34 ttl 300"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_response(8, &response(7, 0)), Err(DnsError::Mismatch));
+        let msg = response(7, 0);
+        assert_eq!(
+            parse_response(7, &msg[..msg.len() - 2]),
+            Err(DnsError::Malformed)
+        );
+        assert_eq!(parse_response(7, &[0, 7]), Err(DnsError::Malformed));
+
+        let nxdomain = parse_response(7, &response(7, 3)).unwrap();
+        assert_eq!(nxdomain.rcode_name(), "NXDOMAIN");
+
+        // a name pointing to itself
+        let mut msg = response(7, 0);
+        msg[12] = 0xc0;
+        msg[13] = 12;
+        assert_eq!(parse_response(7, &msg), Err(DnsError::Malformed));
+    }
+}
@@ -0,0 +1,644 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Network diagnostics tools of the Diagnostics page.
+//!
+//! A tool runs as a task which streams its output line by line to the event
+//! loop and can be cancelled at any time. Ping and traceroute run the system
+//! tools, TCP connect and DNS lookup are implemented here. Every tool can be
+//! bound to an interface (SO_BINDTODEVICE) or to a source address, so the
+//! connectivity of a single port can be checked while others are up.
+
+pub mod controller;
+pub mod dns;
+pub mod tls;
+
+use std::{
+    fmt::Display,
+    net::{IpAddr, SocketAddr},
+    os::fd::AsRawFd,
+    process::Stdio,
+    time::{Duration, Instant},
+};
+
+use strum::{Display, EnumIter};
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    net::{TcpSocket, UdpSocket},
+    process::Command,
+    sync::mpsc::UnboundedSender,
+};
+use tokio_util::sync::CancellationToken;
+
+use dns::{build_query, parse_response, type_name, TYPE_A, TYPE_AAAA};
+
+const PING_COUNT: &str = "4";
+const TCP_TIMEOUT: Duration = Duration::from_secs(5);
+const DNS_TIMEOUT: Duration = Duration::from_secs(3);
+const DNS_PORT: u16 = 53;
+// TCP connect tries at most this many addresses of a host name
+const MAX_TCP_ADDRESSES: usize = 4;
+// output lines kept for the page
+const MAX_OUTPUT_LINES: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Display, EnumIter)]
+pub enum DiagTool {
+    Ping,
+    #[strum(to_string = "TCP connect")]
+    TcpConnect,
+    #[strum(to_string = "DNS lookup")]
+    DnsLookup,
+    Traceroute,
+}
+
+/// Where the packets of a tool leave the device
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiagBinding {
+    /// Chosen by the routing table
+    Any,
+    Interface(String),
+    Source {
+        iface: String,
+        addr: IpAddr,
+    },
+}
+
+impl Display for DiagBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiagBinding::Any => write!(f, "any interface"),
+            DiagBinding::Interface(iface) => write!(f, "{}", iface),
+            DiagBinding::Source { iface, addr } => write!(f, "{} ({})", addr, iface),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagRequest {
+    pub tool: DiagTool,
+    /// Host name or IP address
+    pub target: String,
+    /// TCP connect only
+    pub port: u16,
+    /// DNS lookup only
+    pub dns_server: Option<IpAddr>,
+    pub binding: DiagBinding,
+}
+
+impl DiagRequest {
+    pub fn title(&self) -> String {
+        let target = match self.tool {
+            DiagTool::TcpConnect => format!("{} port {}", self.target, self.port),
+            DiagTool::DnsLookup => match self.dns_server {
+                Some(server) => format!("{} at {}", self.target, server),
+                None => self.target.clone(),
+            },
+            _ => self.target.clone(),
+        };
+        match &self.binding {
+            DiagBinding::Any => format!("{} {}", self.tool, target),
+            binding => format!("{} {} via {}", self.tool, target, binding),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiagEvent {
+    Output(String),
+    Finished(Result<(), String>),
+}
+
+/// Event of the run with the given number, see [DiagSession::start]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagMessage {
+    pub run: u64,
+    pub event: DiagEvent,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum DiagState {
+    #[default]
+    Idle,
+    Running,
+    Done,
+    Failed(String),
+}
+
+/// Output of the last tool shown on the page
+#[derive(Debug, Default)]
+pub struct DiagSession {
+    pub run: u64,
+    pub title: String,
+    pub lines: Vec<String>,
+    pub state: DiagState,
+}
+
+impl DiagSession {
+    /// Forget the previous output. Returns the number of the new run
+    pub fn start(&mut self, title: String) -> u64 {
+        self.run += 1;
+        self.title = title;
+        self.lines.clear();
+        self.state = DiagState::Running;
+        self.run
+    }
+
+    /// Add the output of the current run. Late output of cancelled runs is
+    /// dropped
+    pub fn handle(&mut self, msg: DiagMessage) {
+        if msg.run != self.run {
+            return;
+        }
+        match msg.event {
+            DiagEvent::Output(line) => {
+                if self.lines.len() == MAX_OUTPUT_LINES {
+                    self.lines.remove(0);
+                }
+                self.lines.push(line);
+            }
+            DiagEvent::Finished(Ok(())) => self.state = DiagState::Done,
+            DiagEvent::Finished(Err(e)) => self.state = DiagState::Failed(e),
+        }
+    }
+}
+
+/// Sends the output of a run to the event loop
+#[derive(Clone)]
+pub struct DiagOutput {
+    run: u64,
+    tx: UnboundedSender<DiagMessage>,
+}
+
+impl DiagOutput {
+    pub fn new(run: u64, tx: UnboundedSender<DiagMessage>) -> Self {
+        Self { run, tx }
+    }
+
+    fn send(&self, event: DiagEvent) {
+        // the receiver is gone when the application exits
+        let _ = self.tx.send(DiagMessage {
+            run: self.run,
+            event,
+        });
+    }
+
+    fn line<S: Into<String>>(&self, line: S) {
+        self.send(DiagEvent::Output(line.into()));
+    }
+}
+
+/// Run the tool until it finishes or `cancel` is triggered. Always ends with
+/// [DiagEvent::Finished]
+pub async fn run_diagnostic(request: DiagRequest, out: DiagOutput, cancel: CancellationToken) {
+    let result = tokio::select! {
+        _ = cancel.cancelled() => Err("cancelled".to_string()),
+        result = run_tool(&request, &out) => result,
+    };
+    out.send(DiagEvent::Finished(result));
+}
+
+async fn run_tool(request: &DiagRequest, out: &DiagOutput) -> Result<(), String> {
+    match request.tool {
+        DiagTool::Ping => run_command(&ping_command(request), out).await,
+        DiagTool::Traceroute => run_command(&traceroute_command(request), out).await,
+        DiagTool::TcpConnect => tcp_connect(request, out).await,
+        DiagTool::DnsLookup => dns_lookup(request, out).await,
+    }
+}
+
+pub fn ping_command(request: &DiagRequest) -> Vec<String> {
+    let mut args = vec!["ping", "-c", PING_COUNT, "-W", "2"]
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    // ping takes either an interface or an address
+    match &request.binding {
+        DiagBinding::Any => {}
+        DiagBinding::Interface(iface) => args.extend(["-I".to_string(), iface.clone()]),
+        DiagBinding::Source { addr, .. } => args.extend(["-I".to_string(), addr.to_string()]),
+    }
+    args.push(request.target.clone());
+    args
+}
+
+pub fn traceroute_command(request: &DiagRequest) -> Vec<String> {
+    // no reverse lookups, DNS may be what is broken
+    let mut args = vec!["traceroute", "-n", "-w", "2"]
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    match &request.binding {
+        DiagBinding::Any => {}
+        DiagBinding::Interface(iface) => args.extend(["-i".to_string(), iface.clone()]),
+        DiagBinding::Source { addr, .. } => args.extend(["-s".to_string(), addr.to_string()]),
+    }
+    args.push(request.target.clone());
+    args
+}
+
+/// Run a system tool and stream its stdout and stderr. The process is killed
+/// when the task is cancelled
+async fn run_command(args: &[String], out: &DiagOutput) -> Result<(), String> {
+    out.line(format!("$ {}", args.join(" ")));
+    let mut child = Command::new(&args[0])
+        .args(&args[1..])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("cannot run {}: {}", args[0], e))?;
+
+    let mut stdout = BufReader::new(child.stdout.take().unwrap()).lines();
+    let mut stderr = BufReader::new(child.stderr.take().unwrap()).lines();
+    let (mut stdout_open, mut stderr_open) = (true, true);
+    while stdout_open || stderr_open {
+        tokio::select! {
+            line = stdout.next_line(), if stdout_open => match line {
+                Ok(Some(line)) => out.line(line),
+                _ => stdout_open = false,
+            },
+            line = stderr.next_line(), if stderr_open => match line {
+                Ok(Some(line)) => out.line(line),
+                _ => stderr_open = false,
+            },
+        }
+    }
+
+    let status = child.wait().await.map_err(|e| e.to_string())?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} exited with {}", args[0], status))
+    }
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    format!("{:.1} ms", elapsed.as_secs_f64() * 1000.0)
+}
+
+/// The source address of a binding must be of the same family as the peer
+fn matches_binding(binding: &DiagBinding, peer: &IpAddr) -> bool {
+    match binding {
+        DiagBinding::Source { addr, .. } => addr.is_ipv4() == peer.is_ipv4(),
+        _ => true,
+    }
+}
+
+fn local_addr(binding: &DiagBinding) -> Option<SocketAddr> {
+    match binding {
+        DiagBinding::Source { addr, .. } => Some(SocketAddr::new(*addr, 0)),
+        _ => None,
+    }
+}
+
+/// tokio::net::UdpSocket has no bind_device
+fn bind_to_device(socket: &impl AsRawFd, iface: &str) -> Result<(), String> {
+    // SAFETY: the name is passed with its length and the fd is owned by socket
+    let ret = unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_BINDTODEVICE,
+            iface.as_ptr() as *const libc::c_void,
+            iface.len() as libc::socklen_t,
+        )
+    };
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(format!(
+            "cannot bind to {}: {}",
+            iface,
+            std::io::Error::last_os_error()
+        ))
+    }
+}
+
+async fn tcp_connect(request: &DiagRequest, out: &DiagOutput) -> Result<(), String> {
+    let addrs = tokio::net::lookup_host((request.target.as_str(), request.port))
+        .await
+        .map_err(|e| format!("cannot resolve {}: {}", request.target, e))?
+        .filter(|addr| matches_binding(&request.binding, &addr.ip()))
+        .take(MAX_TCP_ADDRESSES)
+        .collect::<Vec<_>>();
+    if addrs.is_empty() {
+        return Err(format!(
+            "{} has no address reachable from {}",
+            request.target, request.binding
+        ));
+    }
+
+    let mut connected = false;
+    for addr in addrs {
+        let socket = if addr.is_ipv4() {
+            TcpSocket::new_v4()
+        } else {
+            TcpSocket::new_v6()
+        }
+        .map_err(|e| e.to_string())?;
+        if let DiagBinding::Interface(iface) = &request.binding {
+            socket
+                .bind_device(Some(iface.as_bytes()))
+                .map_err(|e| format!("cannot bind to {}: {}", iface, e))?;
+        }
+        if let Some(local) = local_addr(&request.binding) {
+            socket
+                .bind(local)
+                .map_err(|e| format!("cannot bind to {}: {}", local.ip(), e))?;
+        }
+
+        let start = Instant::now();
+        match tokio::time::timeout(TCP_TIMEOUT, socket.connect(addr)).await {
+            Ok(Ok(stream)) => {
+                let from = stream
+                    .local_addr()
+                    .map(|a| a.ip().to_string())
+                    .unwrap_or_default();
+                out.line(format!(
+                    "{} connected in {} from {}",
+                    addr,
+                    format_elapsed(start.elapsed()),
+                    from
+                ));
+                connected = true;
+            }
+            Ok(Err(e)) => out.line(format!(
+                "{} failed after {}: {}",
+                addr,
+                format_elapsed(start.elapsed()),
+                e
+            )),
+            Err(_) => out.line(format!(
+                "{} timed out after {} s",
+                addr,
+                TCP_TIMEOUT.as_secs()
+            )),
+        }
+    }
+    if connected {
+        Ok(())
+    } else {
+        Err("no connection".to_string())
+    }
+}
+
+async fn dns_lookup(request: &DiagRequest, out: &DiagOutput) -> Result<(), String> {
+    let server = request.dns_server.ok_or("no DNS server")?;
+    if request.target.parse::<IpAddr>().is_ok() {
+        return Err("enter a host name to look up".to_string());
+    }
+    if !matches_binding(&request.binding, &server) {
+        return Err(format!("{} cannot reach {}", request.binding, server));
+    }
+    dns_lookup_at(request, out, SocketAddr::new(server, DNS_PORT)).await
+}
+
+async fn dns_lookup_at(
+    request: &DiagRequest,
+    out: &DiagOutput,
+    server: SocketAddr,
+) -> Result<(), String> {
+    let local = local_addr(&request.binding).unwrap_or_else(|| {
+        let unspecified: IpAddr = if server.is_ipv4() {
+            std::net::Ipv4Addr::UNSPECIFIED.into()
+        } else {
+            std::net::Ipv6Addr::UNSPECIFIED.into()
+        };
+        SocketAddr::new(unspecified, 0)
+    });
+    let socket = UdpSocket::bind(local)
+        .await
+        .map_err(|e| format!("cannot bind to {}: {}", local.ip(), e))?;
+    if let DiagBinding::Interface(iface) = &request.binding {
+        bind_to_device(&socket, iface)?;
+    }
+    socket.connect(server).await.map_err(|e| e.to_string())?;
+
+    let mut answered = false;
+    for (id, qtype) in [(1u16, TYPE_A), (2, TYPE_AAAA)] {
+        let query = build_query(id, &request.target, qtype).map_err(|e| e.to_string())?;
+        let start = Instant::now();
+        socket.send(&query).await.map_err(|e| e.to_string())?;
+
+        let mut buf = [0u8; 1500];
+        let response = loop {
+            let len = match tokio::time::timeout(DNS_TIMEOUT, socket.recv(&mut buf)).await {
+                Ok(Ok(len)) => len,
+                Ok(Err(e)) => break Err(e.to_string()),
+                Err(_) => break Err(format!("no answer in {} s", DNS_TIMEOUT.as_secs())),
+            };
+            // late answers to the previous query are skipped
+            match parse_response(id, &buf[..len]) {
+                Err(dns::DnsError::Mismatch) => continue,
+                result => break result.map_err(|e| e.to_string()),
+            }
+        };
+        match response {
+            Ok(response) => {
+                answered = true;
+                out.line(format!(
+                    "{} {} from {} in {}",
+                    type_name(qtype),
+                    response.rcode_name(),
+                    server,
+                    format_elapsed(start.elapsed())
+                ));
+                response
+                    .records
+                    .iter()
+                    .for_each(|record| out.line(format!("  {}", record)));
+            }
+            Err(e) => out.line(format!("{}: {}", type_name(qtype), e)),
+        }
+    }
+    if answered {
+        Ok(())
+    } else {
+        Err(format!("{} did not answer", server))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    fn request(tool: DiagTool, target: &str, binding: DiagBinding) -> DiagRequest {
+        DiagRequest {
+            tool,
+            target: target.to_string(),
+            port: 443,
+            dns_server: None,
+            binding,
+        }
+    }
+
+    async fn run(request: DiagRequest) -> Vec<DiagEvent> {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        run_diagnostic(request, DiagOutput::new(1, tx), CancellationToken::new()).await;
+        let mut events = Vec::new();
+        while let Ok(msg) = rx.try_recv() {
+            events.push(msg.event);
+        }
+        events
+    }
+
+    #[test]
+    fn test_commands() {
+        let source = DiagBinding::Source {
+            iface: "eth0".to_string(),
+            addr: "192.168.1.10".parse().unwrap(),
+        };
+        assert_eq!(
+            ping_command(&request(DiagTool::Ping, "8.8.8.8", source.clone())).join(" "),
+            "ping -c 4 -W 2 -I 192.168.1.10 8.8.8.8"
+        );
+        assert_eq!(
+            traceroute_command(&request(
+                DiagTool::Traceroute,
+                "zedcloud.local",
+                DiagBinding::Interface("eth1".to_string())
+            ))
+            .join(" "),
+            "traceroute -n -w 2 -i eth1 zedcloud.local"
+        );
+        assert_eq!(
+            traceroute_command(&request(DiagTool::Traceroute, "10.0.0.1", source)).join(" "),
+            "traceroute -n -w 2 -s 192.168.1.10 10.0.0.1"
+        );
+    }
+
+    #[test]
+    fn test_title() {
+        let mut req = request(
+            DiagTool::TcpConnect,
+            "zedcloud.local",
+            DiagBinding::Interface("eth0".to_string()),
+        );
+        assert_eq!(req.title(), "TCP connect zedcloud.local port 443 via eth0");
+        req.tool = DiagTool::DnsLookup;
+        req.dns_server = Some("1.1.1.1".parse().unwrap());
+        req.binding = DiagBinding::Any;
+        assert_eq!(req.title(), "DNS lookup zedcloud.local at 1.1.1.1");
+    }
+
+    #[test]
+    fn test_session_drops_old_runs() {
+        let mut session = DiagSession::default();
+        let first = session.start("Ping a".to_string());
+        let second = session.start("Ping b".to_string());
+        session.handle(DiagMessage {
+            run: first,
+            event: DiagEvent::Output("old".to_string()),
+        });
+        session.handle(DiagMessage {
+            run: second,
+            event: DiagEvent::Output("new".to_string()),
+        });
+        assert_eq!(session.state, DiagState::Running);
+        session.handle(DiagMessage {
+            run: second,
+            event: DiagEvent::Finished(Err("cancelled".to_string())),
+        });
+        assert_eq!(session.lines, vec!["new".to_string()]);
+        assert_eq!(session.state, DiagState::Failed("cancelled".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_tcp_connect() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut req = request(DiagTool::TcpConnect, "127.0.0.1", DiagBinding::Any);
+        req.port = listener.local_addr().unwrap().port();
+        let events = run(req.clone()).await;
+        assert!(
+            matches!(&events[0], DiagEvent::Output(line) if line.contains("connected in")),
+            "{:?}",
+            events
+        );
+        assert_eq!(events.last(), Some(&DiagEvent::Finished(Ok(()))));
+
+        // IPv6 source address for an IPv4 target
+        req.binding = DiagBinding::Source {
+            iface: "lo".to_string(),
+            addr: "::1".parse().unwrap(),
+        };
+        let events = run(req).await;
+        assert!(matches!(events.last(), Some(DiagEvent::Finished(Err(_)))));
+    }
+
+    #[tokio::test]
+    async fn test_dns_lookup() {
+        // answer every query with example.com A 93.184.216.34
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 512];
+            while let Ok((len, peer)) = server.recv_from(&mut buf).await {
+                let mut answer = buf[..len].to_vec();
+                answer[2] = 0x81;
+                answer[3] = 0x80;
+                answer[7] = 1;
+                answer.extend_from_slice(&[
+                    0xc0, 12, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4, 93, 184, 216, 34,
+                ]);
+                server.send_to(&answer, peer).await.unwrap();
+            }
+        });
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut req = request(DiagTool::DnsLookup, "example.com", DiagBinding::Any);
+        req.dns_server = Some(server_addr.ip());
+        // the test server does not listen on port 53
+        let out = DiagOutput::new(1, tx);
+        let result = dns_lookup_at(&req, &out, server_addr).await;
+        assert_eq!(result, Ok(()));
+        let mut lines = Vec::new();
+        while let Ok(DiagMessage {
+            event: DiagEvent::Output(line),
+            ..
+        }) = rx.try_recv()
+        {
+            lines.push(line);
+        }
+        assert!(
+            lines[0].starts_with("A NOERROR from 127.0.0.1:"),
+            "{:?}",
+            lines
+        );
+        assert_eq!(lines[1], "  example.com A     93.184.216.34 ttl 60");
+    }
+
+    #[tokio::test]
+    async fn test_dns_lookup_of_address() {
+        let mut req = request(DiagTool::DnsLookup, "10.0.0.1", DiagBinding::Any);
+        req.dns_server = Some("127.0.0.1".parse().unwrap());
+        let events = run(req).await;
+        assert_eq!(
+            events,
+            vec![DiagEvent::Finished(Err(
+                "enter a host name to look up".to_string()
+            ))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancel() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        // never answered
+        let req = DiagRequest {
+            dns_server: Some("192.0.2.1".parse().unwrap()),
+            ..request(DiagTool::DnsLookup, "example.com", DiagBinding::Any)
+        };
+        run_diagnostic(req, DiagOutput::new(3, tx), cancel).await;
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            DiagMessage {
+                run: 3,
+                event: DiagEvent::Finished(Err("cancelled".to_string())),
+            }
+        );
+    }
+}
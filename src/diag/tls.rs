@@ -0,0 +1,265 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! TLS handshake with the controller.
+//!
+//! The reachability check needs the certificate chain presented to the
+//! device, e.g. to spot a TLS-intercepting proxy. The handshake is a real
+//! one, but the chain is only recorded and never rejected; EVE verifies it
+//! itself when it connects. The handshake signatures are still checked, so
+//! the server has to hold the key of the certificate it presented.
+
+use std::sync::{Arc, Mutex};
+
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use tokio_rustls::{
+    rustls::{
+        self,
+        client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+        crypto::{ring, verify_tls12_signature, verify_tls13_signature, WebPkiSupportedAlgorithms},
+        pki_types::{CertificateDer, ServerName, UnixTime},
+        CipherSuite, ClientConfig, DigitallySignedStruct, ProtocolVersion, SignatureScheme,
+    },
+    TlsConnector,
+};
+
+#[derive(Error, Debug)]
+pub enum TlsError {
+    #[error("{0}")]
+    Io(std::io::Error),
+    #[error("connection closed by the server")]
+    Closed,
+    #[error("{0}")]
+    Config(#[from] rustls::Error),
+    #[error("invalid server name {0}")]
+    ServerName(String),
+    #[error("server sent no certificate")]
+    NoCertificate,
+}
+
+impl From<std::io::Error> for TlsError {
+    fn from(e: std::io::Error) -> Self {
+        if e.kind() == std::io::ErrorKind::UnexpectedEof {
+            TlsError::Closed
+        } else {
+            TlsError::Io(e)
+        }
+    }
+}
+
+/// What the server presented during the handshake
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerHandshake {
+    pub version: ProtocolVersion,
+    pub cipher: CipherSuite,
+    /// DER encoded, the server certificate first
+    pub certificates: Vec<Vec<u8>>,
+}
+
+impl ServerHandshake {
+    pub fn version_name(&self) -> String {
+        match self.version {
+            ProtocolVersion::TLSv1_2 => "TLS 1.2".to_string(),
+            ProtocolVersion::TLSv1_3 => "TLS 1.3".to_string(),
+            v => format!("{:?}", v),
+        }
+    }
+
+    pub fn cipher_name(&self) -> String {
+        format!("{:?}", self.cipher)
+    }
+}
+
+/// Accepts any certificate chain and keeps it for the report
+#[derive(Debug)]
+struct RecordingVerifier {
+    algorithms: WebPkiSupportedAlgorithms,
+    chain: Mutex<Vec<Vec<u8>>>,
+}
+
+impl ServerCertVerifier for RecordingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        *self.chain.lock().unwrap() = std::iter::once(end_entity)
+            .chain(intermediates)
+            .map(|cert| cert.to_vec())
+            .collect();
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.algorithms.supported_schemes()
+    }
+}
+
+/// Run a TLS handshake over `stream` and return the certificate chain of the
+/// server. The server name is only sent for host names, SNI does not allow
+/// addresses
+pub async fn fetch_server_handshake<S>(
+    stream: &mut S,
+    host: &str,
+) -> Result<ServerHandshake, TlsError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let provider = Arc::new(ring::default_provider());
+    let verifier = Arc::new(RecordingVerifier {
+        algorithms: provider.signature_verification_algorithms,
+        chain: Mutex::new(Vec::new()),
+    });
+    let config = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .dangerous()
+        .with_custom_certificate_verifier(verifier.clone())
+        .with_no_client_auth();
+    let server_name = ServerName::try_from(host.to_string())
+        .map_err(|_| TlsError::ServerName(host.to_string()))?;
+
+    let mut tls = TlsConnector::from(Arc::new(config))
+        .connect(server_name, stream)
+        .await?;
+    let (_, conn) = tls.get_ref();
+    let version = conn.protocol_version().ok_or(TlsError::Closed)?;
+    let cipher = conn
+        .negotiated_cipher_suite()
+        .ok_or(TlsError::Closed)?
+        .suite();
+    // the check is done, a failed close_notify does not matter
+    let _ = tls.shutdown().await;
+
+    let certificates = std::mem::take(&mut *verifier.chain.lock().unwrap());
+    if certificates.is_empty() {
+        return Err(TlsError::NoCertificate);
+    }
+    Ok(ServerHandshake {
+        version,
+        cipher,
+        certificates,
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::path::{Path, PathBuf};
+    use tokio::io::AsyncReadExt;
+    use tokio_rustls::{
+        rustls::{
+            pki_types::{pem::PemObject, PrivateKeyDer},
+            version::{TLS12, TLS13},
+            ServerConfig, SupportedProtocolVersion,
+        },
+        TlsAcceptor,
+    };
+
+    fn cert_path(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data/certs")
+            .join(name)
+    }
+
+    /// Server for zedcloud.local with a self-signed certificate, followed
+    /// by the CA of an intercepting proxy
+    pub(crate) fn test_acceptor(versions: &[&'static SupportedProtocolVersion]) -> TlsAcceptor {
+        let chain = ["zedcloud.pem", "proxy-ca.pem"]
+            .iter()
+            .map(|name| CertificateDer::from_pem_file(cert_path(name)).unwrap())
+            .collect();
+        let key = PrivateKeyDer::from_pem_file(cert_path("zedcloud-key.pem")).unwrap();
+        let config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_protocol_versions(versions)
+            .unwrap()
+            .with_no_client_auth()
+            .with_single_cert(chain, key)
+            .unwrap();
+        TlsAcceptor::from(Arc::new(config))
+    }
+
+    async fn handshake_with(
+        versions: &[&'static SupportedProtocolVersion],
+    ) -> Result<ServerHandshake, TlsError> {
+        let (mut client, server) = tokio::io::duplex(64 * 1024);
+        let acceptor = test_acceptor(versions);
+        tokio::spawn(async move {
+            if let Ok(mut tls) = acceptor.accept(server).await {
+                // wait for the close_notify of the client
+                let _ = tls.read_to_end(&mut Vec::new()).await;
+            }
+        });
+        fetch_server_handshake(&mut client, "zedcloud.local").await
+    }
+
+    #[tokio::test]
+    async fn test_fetch_server_handshake() {
+        let result = handshake_with(&[&TLS13]).await.unwrap();
+        assert_eq!(result.version_name(), "TLS 1.3");
+        assert!(result.cipher_name().starts_with("TLS13_"));
+        assert_eq!(result.certificates.len(), 2);
+        assert_eq!(
+            result.certificates[1],
+            CertificateDer::from_pem_file(cert_path("proxy-ca.pem"))
+                .unwrap()
+                .to_vec()
+        );
+
+        // the chain is the same with TLS 1.2, where it is not encrypted
+        let result = handshake_with(&[&TLS12]).await.unwrap();
+        assert_eq!(result.version_name(), "TLS 1.2");
+        assert!(result.cipher_name().starts_with("TLS_ECDHE_ECDSA_"));
+        assert_eq!(result.certificates.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_handshake_errors() {
+        // the server closes the connection after the ClientHello
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        tokio::spawn(async move {
+            let mut buf = [0u8; 5];
+            server.read_exact(&mut buf).await.unwrap();
+        });
+        assert!(matches!(
+            fetch_server_handshake(&mut client, "zedcloud.local").await,
+            Err(TlsError::Closed)
+        ));
+
+        // the server does not speak TLS
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        tokio::spawn(async move {
+            let mut buf = [0u8; 5];
+            server.read_exact(&mut buf).await.unwrap();
+            server
+                .write_all(b"HTTP/1.1 400 Bad Request\r\n\r\n")
+                .await
+                .unwrap();
+        });
+        let err = fetch_server_handshake(&mut client, "zedcloud.local")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TlsError::Io(_)), "{:?}", err);
+    }
+}
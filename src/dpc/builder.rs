@@ -0,0 +1,1045 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Build a new DevicePortConfig from the interface settings entered in the IP dialog.
+//!
+//! All user input is validated before anything is changed. Instead of a single
+//! error the builder returns every invalid field, so the dialog can highlight
+//! all of them at once.
+
+use std::net::{IpAddr, Ipv4Addr};
+
+use ipnet::{ipv4_mask_to_prefix, IpNet, Ipv4Net, Ipv6Net};
+use strum::Display;
+use thiserror::Error;
+
+use crate::{
+    ipc::eve_types::{DevicePortConfig, NetworkType},
+    ui::ipdialog::{validate_ipv6_cidr, InterfaceState},
+};
+
+/// Key of DPCs created by the monitor
+pub const MANUAL_DPC_KEY: &str = "manual";
+/// IPv6 requires links with an MTU of at least 1280 bytes
+pub const MIN_MTU: u16 = 1280;
+
+/// Interface setting a validation error refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+pub enum DpcField {
+    #[strum(to_string = "Interface")]
+    Interface,
+    #[strum(to_string = "IPv4")]
+    Ipv4,
+    #[strum(to_string = "Mask")]
+    Mask,
+    #[strum(to_string = "Gateway")]
+    Gateway,
+    #[strum(to_string = "IPv6")]
+    Ipv6,
+    #[strum(to_string = "Domain")]
+    Domain,
+    #[strum(to_string = "DNS")]
+    Dns,
+    #[strum(to_string = "NTP")]
+    Ntp,
+    #[strum(to_string = "SSID")]
+    Ssid,
+    #[strum(to_string = "Identity")]
+    Identity,
+    #[strum(to_string = "Password")]
+    Password,
+    #[strum(to_string = "Priority")]
+    Priority,
+    #[strum(to_string = "SIM slot")]
+    SimSlot,
+    #[strum(to_string = "APN")]
+    Apn,
+    #[strum(to_string = "Username")]
+    Username,
+    #[strum(to_string = "PLMNs")]
+    Plmns,
+    #[strum(to_string = "RATs")]
+    Rats,
+    #[strum(to_string = "Attach APN")]
+    AttachApn,
+    #[strum(to_string = "Probe host")]
+    ProbeHost,
+    #[strum(to_string = "Probe port")]
+    ProbePort,
+    #[strum(to_string = "Name")]
+    Name,
+    #[strum(to_string = "Parent port")]
+    ParentPort,
+    #[strum(to_string = "VLAN ID")]
+    VlanId,
+    #[strum(to_string = "Ports")]
+    BondPorts,
+    #[strum(to_string = "Interval")]
+    MonitorInterval,
+    #[strum(to_string = "Up delay")]
+    UpDelay,
+    #[strum(to_string = "Down delay")]
+    DownDelay,
+    #[strum(to_string = "ARP targets")]
+    ArpTargets,
+    #[strum(to_string = "Usage")]
+    Usage,
+    #[strum(to_string = "Cost")]
+    Cost,
+    #[strum(to_string = "MTU")]
+    Mtu,
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("{field}: {message}")]
+pub struct FieldError {
+    pub field: DpcField,
+    pub message: String,
+}
+
+impl FieldError {
+    pub(crate) fn new<S: Into<String>>(field: DpcField, message: S) -> Self {
+        Self {
+            field,
+            message: message.into(),
+        }
+    }
+}
+
+/// Validated static IP settings of an interface
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaticIpConfig {
+    pub addr_subnet: IpNet,
+    pub network_type: NetworkType,
+    pub gateway: Option<IpAddr>,
+    pub domain: String,
+    pub dns: Vec<IpAddr>,
+    pub ntp: Vec<String>,
+}
+
+/// Validated usage and link settings of a port
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortSettings {
+    pub is_mgmt: bool,
+    pub cost: u8,
+    /// 0 is the default MTU of EVE
+    pub mtu: u16,
+    pub alias: String,
+}
+
+/// Validate user input of the interface. Static IP settings are validated
+/// only if they are used, DHCP has nothing to check
+pub fn validate_interface(state: &InterfaceState) -> Result<(), Vec<FieldError>> {
+    let mut errors = Vec::new();
+    if !state.is_dhcp() {
+        if let Err(e) = validate_static_ip(state) {
+            errors.extend(e);
+        }
+    }
+    if let Err(e) = validate_port_settings(state) {
+        errors.extend(e);
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Parse the failover cost. Ports with a lower cost are preferred, empty
+/// input is the lowest cost
+pub fn parse_cost(input: &str) -> Result<u8, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(0);
+    }
+    input
+        .parse::<u8>()
+        .map_err(|_| format!("'{}' is not a number between 0 and 255", input))
+}
+
+/// Parse the MTU. Empty input keeps the default MTU
+pub fn parse_mtu(input: &str) -> Result<u16, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(0);
+    }
+    match input.parse::<u16>() {
+        Ok(mtu) if mtu >= MIN_MTU => Ok(mtu),
+        _ => Err(format!(
+            "'{}' is not a number between {} and {}",
+            input,
+            MIN_MTU,
+            u16::MAX
+        )),
+    }
+}
+
+/// Parse and validate cost and MTU of the port. Returns all invalid fields
+pub fn validate_port_settings(state: &InterfaceState) -> Result<PortSettings, Vec<FieldError>> {
+    let cost = parse_cost(&state.cost).map_err(|e| FieldError::new(DpcField::Cost, e));
+    let mtu = parse_mtu(&state.mtu).map_err(|e| FieldError::new(DpcField::Mtu, e));
+    match (cost, mtu) {
+        (Ok(cost), Ok(mtu)) => Ok(PortSettings {
+            is_mgmt: state.is_mgmt,
+            cost,
+            mtu,
+            alias: state.alias.trim().to_string(),
+        }),
+        (cost, mtu) => Err(cost.err().into_iter().chain(mtu.err()).collect()),
+    }
+}
+
+/// Parse and validate static IP settings. Returns all invalid fields.
+///
+/// EVE supports a single static address per port, so either IPv4 or IPv6 must
+/// be set but not both:
+/// - IPv4 address: IPv4 only, or dual-stack if the gateway or any DNS server
+///   is IPv6. IPv6 address is then autoconfigured
+/// - IPv6 address: IPv6 only, the gateway and DNS servers must be IPv6
+pub fn validate_static_ip(state: &InterfaceState) -> Result<StaticIpConfig, Vec<FieldError>> {
+    let mut errors = Vec::new();
+
+    let ipv4 = state.ipv4.trim();
+    let ipv6 = state.ipv6.trim();
+    let addr_subnet = match (ipv4.is_empty(), ipv6.is_empty()) {
+        (true, true) => Err(FieldError::new(
+            DpcField::Ipv4,
+            "IPv4 or IPv6 address is required",
+        )),
+        (false, false) => Err(FieldError::new(
+            DpcField::Ipv6,
+            "only one static address per port is supported. Leave IPv6 empty for dual-stack",
+        )),
+        (false, true) => parse_ipv4_subnet(ipv4, &state.mask).map(IpNet::V4),
+        (true, false) => parse_ipv6_subnet(ipv6).map(IpNet::V6),
+    }
+    .map_err(|e| errors.push(e));
+
+    // EVE accepts a single gateway per port
+    let gw = state.gw.trim();
+    let gateway = (!gw.is_empty())
+        .then(|| {
+            gw.parse::<IpAddr>().map_err(|_| {
+                FieldError::new(DpcField::Gateway, format!("invalid address '{}'", gw))
+            })
+        })
+        .transpose()
+        .and_then(|gateway| {
+            if let (Ok(subnet), Some(gateway)) = (&addr_subnet, &gateway) {
+                check_gateway(subnet, gateway)?;
+            }
+            Ok(gateway)
+        })
+        .map_err(|e| errors.push(e));
+
+    let domain = state.domain.trim().to_string();
+    if !domain.is_empty() && !is_valid_hostname(&domain) {
+        errors.push(FieldError::new(
+            DpcField::Domain,
+            format!("invalid domain name '{}'", domain),
+        ));
+    }
+
+    let dns = parse_list(&state.dns, |s| s.parse::<IpAddr>().ok())
+        .map_err(|item| FieldError::new(DpcField::Dns, format!("invalid address '{}'", item)))
+        .and_then(|dns| {
+            // IPv4 servers are not reachable without an IPv4 address
+            if let (Ok(IpNet::V6(_)), Some(server)) =
+                (&addr_subnet, dns.iter().find(|ip| ip.is_ipv4()))
+            {
+                return Err(FieldError::new(
+                    DpcField::Dns,
+                    format!("IPv4 server {} requires an IPv4 address", server),
+                ));
+            }
+            Ok(dns)
+        })
+        .map_err(|e| errors.push(e));
+
+    // NTP server can be either IP or FQDN
+    let ntp = parse_list(&state.ntp, |s| {
+        (s.parse::<IpAddr>().is_ok() || is_valid_hostname(s)).then(|| s.to_string())
+    })
+    .map_err(|item| {
+        errors.push(FieldError::new(
+            DpcField::Ntp,
+            format!("invalid server '{}'", item),
+        ))
+    });
+
+    match (addr_subnet, gateway, dns, ntp) {
+        (Ok(addr_subnet), Ok(gateway), Ok(dns), Ok(ntp)) if errors.is_empty() => {
+            let network_type = match addr_subnet {
+                IpNet::V6(_) => NetworkType::IPV6,
+                IpNet::V4(_) if gateway.iter().chain(dns.iter()).any(|ip| ip.is_ipv6()) => {
+                    NetworkType::DualStack
+                }
+                IpNet::V4(_) => NetworkType::IPv4,
+            };
+            Ok(StaticIpConfig {
+                addr_subnet,
+                network_type,
+                gateway,
+                domain,
+                dns,
+                ntp,
+            })
+        }
+        _ => Err(errors),
+    }
+}
+
+/// Create a new DPC from `current` with the settings of the interface changed
+/// from `old` to `new`. Other ports are copied unchanged
+pub fn build_dpc(
+    current: &DevicePortConfig,
+    old: &InterfaceState,
+    new: &InterfaceState,
+) -> Result<DevicePortConfig, Vec<FieldError>> {
+    let mut dpc = current.to_new_dpc_with_key(MANUAL_DPC_KEY);
+    let Some(port) = dpc.get_port_by_name_mut(&new.iface_name) else {
+        return Err(vec![FieldError::new(
+            DpcField::Interface,
+            format!("{} is not present in the current DPC", new.iface_name),
+        )]);
+    };
+
+    match (old.is_dhcp(), new.is_dhcp()) {
+        // Static -> DHCP
+        (false, true) => port.to_dhcp(),
+        // DHCP -> Static or Static -> Static
+        (_, false) => {
+            let cfg = validate_static_ip(new)?;
+            let gateway = cfg.gateway.map(|gw| gw.to_string()).unwrap_or_default();
+            port.to_static(
+                cfg.addr_subnet,
+                cfg.network_type,
+                gateway,
+                cfg.domain,
+                (!cfg.ntp.is_empty()).then_some(cfg.ntp),
+                (!cfg.dns.is_empty()).then_some(cfg.dns),
+            );
+        }
+        // DHCP -> DHCP: IP configuration stays the same, only proxy may change
+        (true, true) => {}
+    }
+
+    port.set_proxy_config(new.create_proxy_config());
+
+    // the dialog is filled from the port status which may differ from the
+    // DPC, e.g. the actual MTU. Only settings changed by the user are applied
+    let settings = validate_port_settings(new)?;
+    if old.is_mgmt != new.is_mgmt {
+        port.is_mgmt = settings.is_mgmt;
+    }
+    if old.cost != new.cost {
+        port.cost = settings.cost;
+    }
+    if old.mtu != new.mtu {
+        port.mtu = settings.mtu;
+    }
+    if old.alias != new.alias {
+        port.alias = settings.alias;
+    }
+
+    if !dpc.ports.iter().any(|p| p.is_mgmt) {
+        return Err(vec![FieldError::new(
+            DpcField::Usage,
+            "at least one port must be used for management",
+        )]);
+    }
+    Ok(dpc)
+}
+
+/// Parse an IPv4 address with an optional prefix length and a mask. The mask
+/// is either dotted (255.255.255.0) or a prefix length (24 or /24). It may be
+/// omitted if the address has a prefix length
+fn parse_ipv4_subnet(ipv4: &str, mask: &str) -> Result<Ipv4Net, FieldError> {
+    let mask = mask.trim();
+
+    let (addr, addr_prefix) = match ipv4.split_once('/') {
+        Some((addr, prefix)) => (addr, Some(prefix)),
+        None => (ipv4, None),
+    };
+    let addr = addr
+        .parse::<Ipv4Addr>()
+        .map_err(|_| FieldError::new(DpcField::Ipv4, format!("invalid address '{}'", addr)))?;
+    let addr_prefix = addr_prefix
+        .map(|p| parse_prefix(p, 32).ok_or_else(|| invalid_prefix(DpcField::Ipv4, p)))
+        .transpose()?;
+
+    let mask_prefix = if mask.is_empty() {
+        None
+    } else if let Some(prefix) = parse_prefix(mask.trim_start_matches('/'), 32) {
+        Some(prefix)
+    } else {
+        let mask_addr = mask
+            .parse::<Ipv4Addr>()
+            .map_err(|_| FieldError::new(DpcField::Mask, format!("invalid mask '{}'", mask)))?;
+        Some(ipv4_mask_to_prefix(mask_addr).map_err(|_| {
+            FieldError::new(DpcField::Mask, format!("{} is not a valid netmask", mask))
+        })?)
+    };
+
+    let prefix = match (addr_prefix, mask_prefix) {
+        (Some(a), Some(m)) if a != m => {
+            return Err(FieldError::new(
+                DpcField::Mask,
+                format!("does not match prefix length /{} of the address", a),
+            ))
+        }
+        (Some(prefix), _) | (None, Some(prefix)) => prefix,
+        (None, None) => return Err(FieldError::new(DpcField::Mask, "mask cannot be empty")),
+    };
+
+    if addr.is_unspecified() || addr.is_multicast() || addr.is_broadcast() {
+        return Err(FieldError::new(
+            DpcField::Ipv4,
+            format!("{} cannot be assigned to an interface", addr),
+        ));
+    }
+
+    let subnet = Ipv4Net::new(addr, prefix).map_err(|_| invalid_prefix(DpcField::Mask, mask))?;
+    // /31 and /32 have no network and broadcast addresses
+    if prefix < 31 {
+        if addr == subnet.network() {
+            return Err(FieldError::new(
+                DpcField::Ipv4,
+                format!("{} is the network address of {}", addr, subnet.trunc()),
+            ));
+        }
+        if addr == subnet.broadcast() {
+            return Err(FieldError::new(
+                DpcField::Ipv4,
+                format!("{} is the broadcast address of {}", addr, subnet.trunc()),
+            ));
+        }
+    }
+    Ok(subnet)
+}
+
+/// Parse an IPv6 address with a mandatory prefix length e.g. 2001:db8::5/64
+fn parse_ipv6_subnet(ipv6: &str) -> Result<Ipv6Net, FieldError> {
+    let (addr, prefix) =
+        validate_ipv6_cidr(ipv6).map_err(|e| FieldError::new(DpcField::Ipv6, e.to_string()))?;
+    if !ipv6.contains('/') {
+        return Err(FieldError::new(
+            DpcField::Ipv6,
+            "prefix length is required e.g. /64",
+        ));
+    }
+    if prefix == 0 {
+        return Err(invalid_prefix(DpcField::Ipv6, "0"));
+    }
+
+    if addr.is_unspecified() || addr.is_loopback() || addr.is_multicast() {
+        return Err(FieldError::new(
+            DpcField::Ipv6,
+            format!("{} cannot be assigned to an interface", addr),
+        ));
+    }
+    if addr.is_unicast_link_local() {
+        return Err(FieldError::new(
+            DpcField::Ipv6,
+            "link-local address is assigned automatically",
+        ));
+    }
+
+    let subnet = Ipv6Net::new(addr, prefix).map_err(|_| invalid_prefix(DpcField::Ipv6, ipv6))?;
+    // the first address of the subnet is the subnet-router anycast address
+    if prefix < 127 && addr == subnet.network() {
+        return Err(FieldError::new(
+            DpcField::Ipv6,
+            format!(
+                "{} is the subnet-router address of {}",
+                addr,
+                subnet.trunc()
+            ),
+        ));
+    }
+    Ok(subnet)
+}
+
+fn parse_prefix(s: &str, max: u8) -> Option<u8> {
+    s.parse::<u8>().ok().filter(|p| *p <= max)
+}
+
+fn invalid_prefix(field: DpcField, prefix: &str) -> FieldError {
+    FieldError::new(field, format!("invalid prefix length '{}'", prefix))
+}
+
+/// Gateway must be reachable directly. IPv4 gateway must be in the IPv4
+/// subnet. IPv6 gateway must be link-local or in the IPv6 subnet. IPv6
+/// gateway of an IPv4 interface is reached over the autoconfigured address
+fn check_gateway(subnet: &IpNet, gw: &IpAddr) -> Result<(), FieldError> {
+    if gw.is_unspecified() || gw.is_loopback() || gw.is_multicast() {
+        return Err(FieldError::new(
+            DpcField::Gateway,
+            format!("{} cannot be a gateway", gw),
+        ));
+    }
+    if *gw == subnet.addr() {
+        return Err(FieldError::new(
+            DpcField::Gateway,
+            format!("{} is the address of the interface", gw),
+        ));
+    }
+    let reachable = match (subnet, gw) {
+        (IpNet::V4(subnet), IpAddr::V4(gw)) => subnet.contains(gw),
+        (IpNet::V4(_), IpAddr::V6(_)) => true,
+        (IpNet::V6(subnet), IpAddr::V6(gw)) => gw.is_unicast_link_local() || subnet.contains(gw),
+        (IpNet::V6(_), IpAddr::V4(_)) => {
+            return Err(FieldError::new(
+                DpcField::Gateway,
+                format!("IPv4 gateway {} requires an IPv4 address", gw),
+            ))
+        }
+    };
+    if !reachable {
+        return Err(FieldError::new(
+            DpcField::Gateway,
+            format!("{} is not in subnet {}", gw, subnet.trunc()),
+        ));
+    }
+    Ok(())
+}
+
+/// Re-submit an older DPC. The ports are copied unchanged into a new manual
+/// DPC with the current time as priority, so it takes precedence over the
+/// DPC in use
+pub fn build_revert_dpc(dpc: &DevicePortConfig) -> DevicePortConfig {
+    dpc.to_new_dpc_with_key(MANUAL_DPC_KEY)
+}
+
+/// Parse a comma separated list. Empty items are skipped. Returns the first
+/// item `parse` fails on
+pub(crate) fn parse_list<T, F>(input: &str, parse: F) -> Result<Vec<T>, String>
+where
+    F: Fn(&str) -> Option<T>,
+{
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| parse(s).ok_or_else(|| s.to_string()))
+        .collect()
+}
+
+pub(crate) fn is_valid_hostname(name: &str) -> bool {
+    let name = name.strip_suffix('.').unwrap_or(name);
+    !name.is_empty()
+        && name.len() <= 253
+        && name.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dpc::{current_dpc, load_dpc_list};
+    use crate::ipc::eve_types::DhcpType;
+    use crate::ui::ipdialog::ProxyType;
+
+    fn dhcp_state() -> InterfaceState {
+        InterfaceState {
+            iface_name: "eth0".to_string(),
+            ip_dhcp: true,
+            proxy_type: ProxyType::None,
+            ipv4: "".to_string(),
+            ipv6: "".to_string(),
+            mask: "".to_string(),
+            gw: "".to_string(),
+            proxy_url: "".to_string(),
+            proxy_certificates: vec![],
+            pac_file: "".to_string(),
+            domain: "".to_string(),
+            dns: "".to_string(),
+            ntp: "".to_string(),
+            proxy_http: None,
+            proxy_https: None,
+            proxy_ftp: None,
+            proxy_socks: None,
+            is_mgmt: true,
+            cost: "0".to_string(),
+            mtu: "".to_string(),
+            alias: "".to_string(),
+        }
+    }
+
+    fn static_state() -> InterfaceState {
+        InterfaceState {
+            ip_dhcp: false,
+            ipv4: "192.168.1.10".to_string(),
+            mask: "255.255.255.0".to_string(),
+            gw: "192.168.1.1".to_string(),
+            dns: "1.1.1.1, 8.8.8.8".to_string(),
+            ntp: "pool.ntp.org".to_string(),
+            domain: "example.com".to_string(),
+            ..dhcp_state()
+        }
+    }
+
+    fn errors_for(state: &InterfaceState) -> Vec<DpcField> {
+        validate_static_ip(state)
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.field)
+            .collect()
+    }
+
+    #[test]
+    fn test_dhcp_to_static() {
+        let dpc = build_dpc(&current_dpc(), &dhcp_state(), &static_state()).unwrap();
+        assert_eq!(dpc.key, MANUAL_DPC_KEY);
+
+        let cfg = &dpc.get_port_by_name("eth0").unwrap().dhcp_config;
+        assert_eq!(cfg.dhcp, DhcpType::Static);
+        assert_eq!(cfg.dhcp_type, NetworkType::IPv4);
+        assert_eq!(cfg.addr_subnet, Some("192.168.1.10/24".parse().unwrap()));
+        assert_eq!(cfg.gateway, "192.168.1.1");
+        assert_eq!(cfg.domain_name, "example.com");
+        assert_eq!(
+            cfg.dns_servers,
+            Some(vec!["1.1.1.1".parse().unwrap(), "8.8.8.8".parse().unwrap()])
+        );
+        assert_eq!(cfg.ntp_servers, Some(vec!["pool.ntp.org".to_string()]));
+
+        // other ports are not touched
+        let eth1 = &dpc.get_port_by_name("eth1").unwrap().dhcp_config;
+        assert_eq!(eth1.dhcp, DhcpType::Client);
+    }
+
+    #[test]
+    fn test_static_to_dhcp() {
+        let static_dpc = build_dpc(&current_dpc(), &dhcp_state(), &static_state()).unwrap();
+        let dpc = build_dpc(&static_dpc, &static_state(), &dhcp_state()).unwrap();
+        let cfg = &dpc.get_port_by_name("eth0").unwrap().dhcp_config;
+        assert_eq!(cfg.dhcp, DhcpType::Client);
+        assert_eq!(cfg.addr_subnet, None);
+        assert_eq!(cfg.gateway, "");
+        assert_eq!(cfg.dns_servers, None);
+        assert_eq!(cfg.ntp_servers, None);
+    }
+
+    #[test]
+    fn test_dhcp_to_dhcp_keeps_ip_config() {
+        let current = current_dpc();
+        let dpc = build_dpc(&current, &dhcp_state(), &dhcp_state()).unwrap();
+        assert_eq!(
+            dpc.get_port_by_name("eth0").unwrap().dhcp_config,
+            current.get_port_by_name("eth0").unwrap().dhcp_config
+        );
+    }
+
+    #[test]
+    fn test_empty_lists_are_omitted() {
+        let state = InterfaceState {
+            dns: " , ".to_string(),
+            ntp: "".to_string(),
+            gw: "".to_string(),
+            ..static_state()
+        };
+        let dpc = build_dpc(&current_dpc(), &dhcp_state(), &state).unwrap();
+        let cfg = &dpc.get_port_by_name("eth0").unwrap().dhcp_config;
+        assert_eq!(cfg.dns_servers, None);
+        assert_eq!(cfg.ntp_servers, None);
+        assert_eq!(cfg.gateway, "");
+    }
+
+    #[test]
+    fn test_unknown_interface() {
+        let state = InterfaceState {
+            iface_name: "wlan0".to_string(),
+            ..static_state()
+        };
+        let errors = build_dpc(&current_dpc(), &dhcp_state(), &state).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, DpcField::Interface);
+    }
+
+    #[test]
+    fn test_mask_formats() {
+        for (ipv4, mask) in [
+            ("10.0.0.5", "255.255.255.0"),
+            ("10.0.0.5", "24"),
+            ("10.0.0.5", "/24"),
+            ("10.0.0.5/24", ""),
+            ("10.0.0.5/24", "255.255.255.0"),
+        ] {
+            let state = InterfaceState {
+                ipv4: ipv4.to_string(),
+                mask: mask.to_string(),
+                gw: "10.0.0.1".to_string(),
+                ..static_state()
+            };
+            let cfg = validate_static_ip(&state).unwrap();
+            assert_eq!(
+                cfg.addr_subnet,
+                "10.0.0.5/24".parse().unwrap(),
+                "{ipv4} {mask}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_ipv4() {
+        for ipv4 in [
+            "",
+            "10.0.0",
+            "10.0.0.256",
+            "0.0.0.0",
+            "224.0.0.1",
+            "10.0.0.5/33",
+        ] {
+            let state = InterfaceState {
+                ipv4: ipv4.to_string(),
+                ..static_state()
+            };
+            assert_eq!(errors_for(&state), vec![DpcField::Ipv4], "{ipv4}");
+        }
+    }
+
+    #[test]
+    fn test_network_and_broadcast_address() {
+        for ipv4 in ["192.168.1.0", "192.168.1.255"] {
+            let state = InterfaceState {
+                ipv4: ipv4.to_string(),
+                ..static_state()
+            };
+            assert_eq!(errors_for(&state), vec![DpcField::Ipv4], "{ipv4}");
+        }
+        // point-to-point links have neither
+        let state = InterfaceState {
+            ipv4: "192.168.1.0".to_string(),
+            mask: "31".to_string(),
+            gw: "192.168.1.1".to_string(),
+            ..static_state()
+        };
+        assert!(validate_static_ip(&state).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_mask() {
+        for mask in ["", "255.0.255.0", "255.255.255", "33", "/40"] {
+            let state = InterfaceState {
+                mask: mask.to_string(),
+                ..static_state()
+            };
+            assert_eq!(errors_for(&state), vec![DpcField::Mask], "{mask}");
+        }
+        // prefix of the address and the mask differ
+        let state = InterfaceState {
+            ipv4: "192.168.1.10/16".to_string(),
+            ..static_state()
+        };
+        assert_eq!(errors_for(&state), vec![DpcField::Mask]);
+    }
+
+    #[test]
+    fn test_invalid_gateway() {
+        for gw in [
+            "192.168.1",
+            "10.0.0.1",
+            "192.168.1.10",
+            "192.168.1.1, foo",
+            "192.168.1.1, 192.168.1.2",
+            "ff02::2",
+        ] {
+            let state = InterfaceState {
+                gw: gw.to_string(),
+                ..static_state()
+            };
+            assert_eq!(errors_for(&state), vec![DpcField::Gateway], "{gw}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_lists() {
+        let state = InterfaceState {
+            dns: "1.1.1.1, dns.google".to_string(),
+            ntp: "pool.ntp.org, -bad-".to_string(),
+            domain: "exa mple.com".to_string(),
+            ..static_state()
+        };
+        assert_eq!(
+            errors_for(&state),
+            vec![DpcField::Domain, DpcField::Dns, DpcField::Ntp]
+        );
+    }
+
+    fn ipv6_state() -> InterfaceState {
+        InterfaceState {
+            ipv4: "".to_string(),
+            mask: "".to_string(),
+            ipv6: "2001:db8::5/64".to_string(),
+            gw: "fe80::1".to_string(),
+            dns: "2001:4860:4860::8888".to_string(),
+            ..static_state()
+        }
+    }
+
+    #[test]
+    fn test_ipv6_only() {
+        let dpc = build_dpc(&current_dpc(), &dhcp_state(), &ipv6_state()).unwrap();
+        let cfg = &dpc.get_port_by_name("eth0").unwrap().dhcp_config;
+        assert_eq!(cfg.dhcp, DhcpType::Static);
+        assert_eq!(cfg.dhcp_type, NetworkType::IPV6);
+        assert_eq!(cfg.addr_subnet, Some("2001:db8::5/64".parse().unwrap()));
+        assert_eq!(cfg.gateway, "fe80::1");
+        assert_eq!(
+            cfg.dns_servers,
+            Some(vec!["2001:4860:4860::8888".parse().unwrap()])
+        );
+    }
+
+    #[test]
+    fn test_ipv4_only() {
+        let cfg = validate_static_ip(&static_state()).unwrap();
+        assert_eq!(cfg.network_type, NetworkType::IPv4);
+        assert_eq!(cfg.addr_subnet, "192.168.1.10/24".parse().unwrap());
+        assert_eq!(cfg.gateway, Some("192.168.1.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_dual_stack() {
+        // IPv6 gateway, IPv6 address is autoconfigured
+        let state = InterfaceState {
+            gw: "fe80::1".to_string(),
+            ..static_state()
+        };
+        let dpc = build_dpc(&current_dpc(), &dhcp_state(), &state).unwrap();
+        let cfg = &dpc.get_port_by_name("eth0").unwrap().dhcp_config;
+        assert_eq!(cfg.dhcp, DhcpType::Static);
+        assert_eq!(cfg.dhcp_type, NetworkType::DualStack);
+        assert_eq!(cfg.addr_subnet, Some("192.168.1.10/24".parse().unwrap()));
+        assert_eq!(cfg.gateway, "fe80::1");
+
+        // IPv6 DNS server with an IPv4 gateway
+        let state = InterfaceState {
+            dns: "1.1.1.1, 2606:4700:4700::1111".to_string(),
+            ..static_state()
+        };
+        let dpc = build_dpc(&current_dpc(), &dhcp_state(), &state).unwrap();
+        let cfg = &dpc.get_port_by_name("eth0").unwrap().dhcp_config;
+        assert_eq!(cfg.dhcp_type, NetworkType::DualStack);
+        assert_eq!(cfg.gateway, "192.168.1.1");
+        assert_eq!(
+            cfg.dns_servers,
+            Some(vec![
+                "1.1.1.1".parse().unwrap(),
+                "2606:4700:4700::1111".parse().unwrap()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_one_static_address() {
+        let state = InterfaceState {
+            ipv4: "192.168.1.10".to_string(),
+            mask: "24".to_string(),
+            ..ipv6_state()
+        };
+        assert_eq!(errors_for(&state), vec![DpcField::Ipv6]);
+        assert!(validate_static_ip(&state).unwrap_err()[0]
+            .message
+            .contains("Leave IPv6 empty for dual-stack"));
+
+        let state = InterfaceState {
+            ipv6: " ".to_string(),
+            ..ipv6_state()
+        };
+        assert_eq!(errors_for(&state), vec![DpcField::Ipv4]);
+    }
+
+    #[test]
+    fn test_invalid_ipv6() {
+        for ipv6 in [
+            "2001:db8::zz/64",
+            "2001:db8::5",
+            "2001:db8::5/129",
+            "2001:db8::5/0",
+            "::/64",
+            "::1/128",
+            "ff02::1/64",
+            "fe80::5/64",
+            "2001:db8::/64",
+        ] {
+            let state = InterfaceState {
+                ipv6: ipv6.to_string(),
+                ..ipv6_state()
+            };
+            assert_eq!(errors_for(&state), vec![DpcField::Ipv6], "{ipv6}");
+        }
+        // mask is not used for IPv6
+        let state = InterfaceState {
+            mask: "garbage".to_string(),
+            ..ipv6_state()
+        };
+        assert!(validate_static_ip(&state).is_ok());
+    }
+
+    #[test]
+    fn test_ipv6_gateway_and_dns() {
+        for gw in ["192.168.1.1", "2001:db9::1", "2001:db8::5", "ff02::2"] {
+            let state = InterfaceState {
+                gw: gw.to_string(),
+                ..ipv6_state()
+            };
+            assert_eq!(errors_for(&state), vec![DpcField::Gateway], "{gw}");
+        }
+        let state = InterfaceState {
+            dns: "2001:4860:4860::8888, 8.8.8.8".to_string(),
+            ..ipv6_state()
+        };
+        assert_eq!(errors_for(&state), vec![DpcField::Dns]);
+    }
+
+    #[test]
+    fn test_all_errors_are_reported() {
+        let state = InterfaceState {
+            ipv4: "foo".to_string(),
+            gw: "bar".to_string(),
+            dns: "baz".to_string(),
+            ..static_state()
+        };
+        let errors = validate_static_ip(&state).unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].to_string(), "IPv4: invalid address 'foo'");
+        assert!(build_dpc(&current_dpc(), &dhcp_state(), &state).is_err());
+    }
+
+    #[test]
+    fn test_validate_interface() {
+        // nothing to validate for DHCP even if static fields are garbage
+        let state = InterfaceState {
+            ipv4: "foo".to_string(),
+            ..dhcp_state()
+        };
+        assert!(validate_interface(&state).is_ok());
+        assert!(validate_interface(&static_state()).is_ok());
+        let state = InterfaceState {
+            ip_dhcp: false,
+            ..state
+        };
+        assert!(validate_interface(&state).is_err());
+    }
+
+    #[test]
+    fn test_port_settings() {
+        let old = InterfaceState {
+            mtu: "1500".to_string(),
+            ..dhcp_state()
+        };
+        let new = InterfaceState {
+            is_mgmt: false,
+            cost: "10".to_string(),
+            alias: " uplink-1 ".to_string(),
+            ..old.clone()
+        };
+        let dpc = build_dpc(&current_dpc(), &old, &new).unwrap();
+        let port = dpc.get_port_by_name("eth0").unwrap();
+        assert!(!port.is_mgmt);
+        assert_eq!(port.cost, 10);
+        assert_eq!(port.alias, "uplink-1");
+        // the MTU of the status is not copied into the DPC
+        assert_eq!(port.mtu, 0);
+
+        let new = InterfaceState {
+            mtu: "9000".to_string(),
+            ..old.clone()
+        };
+        let dpc = build_dpc(&current_dpc(), &old, &new).unwrap();
+        assert_eq!(dpc.get_port_by_name("eth0").unwrap().mtu, 9000);
+        // back to the default
+        let new = InterfaceState {
+            mtu: "".to_string(),
+            ..old.clone()
+        };
+        let dpc = build_dpc(&dpc, &old, &new).unwrap();
+        assert_eq!(dpc.get_port_by_name("eth0").unwrap().mtu, 0);
+    }
+
+    #[test]
+    fn test_invalid_port_settings() {
+        for (cost, mtu, fields) in [
+            ("256", "", vec![DpcField::Cost]),
+            ("-1", "1500", vec![DpcField::Cost]),
+            ("0", "1279", vec![DpcField::Mtu]),
+            ("0", "65536", vec![DpcField::Mtu]),
+            ("x", "y", vec![DpcField::Cost, DpcField::Mtu]),
+        ] {
+            let state = InterfaceState {
+                cost: cost.to_string(),
+                mtu: mtu.to_string(),
+                ..dhcp_state()
+            };
+            let errors = validate_interface(&state).unwrap_err();
+            assert_eq!(
+                errors.iter().map(|e| e.field).collect::<Vec<_>>(),
+                fields,
+                "{} {}",
+                cost,
+                mtu
+            );
+        }
+        for (cost, mtu) in [("", ""), ("255", "1280"), (" 5 ", "65535")] {
+            let state = InterfaceState {
+                cost: cost.to_string(),
+                mtu: mtu.to_string(),
+                ..dhcp_state()
+            };
+            assert!(validate_interface(&state).is_ok(), "{} {}", cost, mtu);
+        }
+    }
+
+    #[test]
+    fn test_last_management_port() {
+        let app_only = InterfaceState {
+            is_mgmt: false,
+            ..dhcp_state()
+        };
+        let dpc = build_dpc(&current_dpc(), &dhcp_state(), &app_only).unwrap();
+        let eth1 = InterfaceState {
+            iface_name: "eth1".to_string(),
+            ..dhcp_state()
+        };
+        let eth1_app_only = InterfaceState {
+            is_mgmt: false,
+            ..eth1.clone()
+        };
+        let errors = build_dpc(&dpc, &eth1, &eth1_app_only).unwrap_err();
+        assert_eq!(errors[0].field, DpcField::Usage);
+    }
+
+    #[test]
+    fn test_revert_dpc() {
+        let list = load_dpc_list("eve_ipc_message-err-1.json");
+        // the last known-good manual DPC
+        let old = &list.port_config_list.as_ref().unwrap()[2];
+        let dpc = build_revert_dpc(old);
+        assert_eq!(dpc.key, MANUAL_DPC_KEY);
+        assert_eq!(dpc.ports, old.ports);
+        assert!(dpc.time_priority > old.time_priority);
+        assert!(!dpc.test_results.is_error());
+        assert!(!list.is_current(&dpc));
+        assert!(list.is_current(&list.get_current_dpc_cloned().unwrap()));
+    }
+
+    #[test]
+    fn test_hostname() {
+        assert!(is_valid_hostname("pool.ntp.org"));
+        assert!(is_valid_hostname("ntp1"));
+        assert!(is_valid_hostname("example.com."));
+        assert!(!is_valid_hostname(""));
+        assert!(!is_valid_hostname("a..b"));
+        assert!(!is_valid_hostname("-a.b"));
+        assert!(!is_valid_hostname("a_b.com"));
+        assert!(!is_valid_hostname(&"a".repeat(64)));
+    }
+}
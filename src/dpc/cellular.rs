@@ -0,0 +1,590 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Validate access points and probe settings entered in the cellular dialog
+//! and put them into a new DPC.
+
+use std::{collections::HashSet, net::IpAddr};
+
+use thiserror::Error;
+
+use crate::{
+    ipc::eve_types::{
+        CellNetPortConfig, CellularAccessPoint, CipherBlockStatus, ConnectivityProbe,
+        ConnectivityProbeMethod, DevicePortConfig, WirelessType, WwanAuthProtocol,
+        WwanCleartextCredentials, WwanProbe, WwanRAT,
+    },
+    ui::cellulardialog::{AccessPointState, ProbeState},
+};
+
+use super::builder::{is_valid_hostname, DpcField, FieldError, MANUAL_DPC_KEY};
+
+/// Maximum length of APN in bytes (3GPP TS 23.003)
+const MAX_APN_LEN: usize = 100;
+
+/// Validation error of one of the access points or of the probe settings
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum CellularFieldError {
+    #[error("access point {}: {error}", .index + 1)]
+    AccessPoint { index: usize, error: FieldError },
+    #[error("probe: {0}")]
+    Probe(FieldError),
+}
+
+impl AccessPointState {
+    /// Credentials received from the controller are kept if the user entered
+    /// neither username nor password
+    pub fn keeps_encrypted_credentials(&self) -> bool {
+        self.original.encrypted_credentials.is_cipher
+            && self.username.is_empty()
+            && self.password.is_empty()
+    }
+}
+
+/// Validate all access points and the probe. Returns access points in the
+/// same order and the probe or all invalid fields
+pub fn validate_cellular_config(
+    access_points: &[AccessPointState],
+    probe: &ProbeState,
+) -> Result<(Vec<CellularAccessPoint>, WwanProbe), Vec<CellularFieldError>> {
+    let mut errors = Vec::new();
+    let mut configs = Vec::new();
+    let mut slots = HashSet::new();
+
+    for (index, ap) in access_points.iter().enumerate() {
+        match validate_access_point(ap) {
+            Ok(config) => {
+                if !slots.insert(config.sim_slot) {
+                    errors.push(CellularFieldError::AccessPoint {
+                        index,
+                        error: FieldError::new(
+                            DpcField::SimSlot,
+                            format!("SIM slot {} is configured more than once", config.sim_slot),
+                        ),
+                    });
+                }
+                configs.push(config);
+            }
+            Err(e) => errors.extend(
+                e.into_iter()
+                    .map(|error| CellularFieldError::AccessPoint { index, error }),
+            ),
+        }
+    }
+
+    let probe = validate_probe(probe).map_err(|e| {
+        errors.extend(e.into_iter().map(CellularFieldError::Probe));
+    });
+
+    match probe {
+        Ok(probe) if errors.is_empty() => Ok((configs, probe)),
+        _ => Err(errors),
+    }
+}
+
+/// Validate a single access point. Settings not shown in the dialog are
+/// taken from the original access point
+pub fn validate_access_point(
+    ap: &AccessPointState,
+) -> Result<CellularAccessPoint, Vec<FieldError>> {
+    let mut errors = Vec::new();
+
+    // 0 means the currently activated or the only available SIM card
+    let sim_slot = match ap.sim_slot.trim() {
+        "" => 0,
+        s => s.parse::<u8>().unwrap_or_else(|_| {
+            errors.push(FieldError::new(
+                DpcField::SimSlot,
+                format!("invalid SIM slot '{}'", s),
+            ));
+            0
+        }),
+    };
+
+    let apn = ap.apn.trim().to_string();
+    if apn.is_empty() {
+        errors.push(FieldError::new(DpcField::Apn, "APN cannot be empty"));
+    } else if let Err(e) = check_apn(&apn) {
+        errors.push(FieldError::new(DpcField::Apn, e));
+    }
+
+    let attach_apn = ap.attach_apn.trim().to_string();
+    if !attach_apn.is_empty() {
+        if let Err(e) = check_apn(&attach_apn) {
+            errors.push(FieldError::new(DpcField::AttachApn, e));
+        }
+    }
+
+    let keep_encrypted = ap.keeps_encrypted_credentials();
+    if ap.auth_protocol != WwanAuthProtocol::None && !keep_encrypted {
+        if ap.username.trim().is_empty() {
+            errors.push(FieldError::new(
+                DpcField::Username,
+                "username is required for authentication",
+            ));
+        }
+        if ap.password.is_empty() {
+            errors.push(FieldError::new(
+                DpcField::Password,
+                "password is required for authentication",
+            ));
+        }
+    }
+
+    let preferred_plmns = parse_plmns(&ap.preferred_plmns).unwrap_or_else(|e| {
+        errors.push(FieldError::new(DpcField::Plmns, e));
+        vec![]
+    });
+    let preferred_rats = parse_rats(&ap.preferred_rats).unwrap_or_else(|e| {
+        errors.push(FieldError::new(DpcField::Rats, e));
+        vec![]
+    });
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    // an empty cipher block is kept as received so an unchanged access point
+    // produces the same config
+    let (cleartext_credentials, encrypted_credentials) = match ap.auth_protocol {
+        WwanAuthProtocol::None if ap.original.encrypted_credentials.is_cipher => Default::default(),
+        WwanAuthProtocol::None => (
+            WwanCleartextCredentials::default(),
+            ap.original.encrypted_credentials.clone(),
+        ),
+        _ if keep_encrypted => (
+            WwanCleartextCredentials::default(),
+            ap.original.encrypted_credentials.clone(),
+        ),
+        _ => (
+            WwanCleartextCredentials {
+                username: ap.username.trim().to_string(),
+                password: ap.password.clone(),
+            },
+            CipherBlockStatus::default(),
+        ),
+    };
+
+    Ok(CellularAccessPoint {
+        sim_slot,
+        apn,
+        ip_type: ap.ip_type.clone(),
+        auth_protocol: ap.auth_protocol.clone(),
+        cleartext_credentials,
+        encrypted_credentials,
+        preferred_plmns: (!preferred_plmns.is_empty()).then_some(preferred_plmns),
+        preferred_rats: (!preferred_rats.is_empty()).then_some(preferred_rats),
+        forbid_roaming: ap.forbid_roaming,
+        attach_apn,
+        ..ap.original.clone()
+    })
+}
+
+/// Validate probe settings. The host is optional for ICMP, EVE probes its
+/// default address in this case
+pub fn validate_probe(probe: &ProbeState) -> Result<WwanProbe, Vec<FieldError>> {
+    let mut errors = Vec::new();
+
+    let host = probe.host.trim().to_string();
+    let port = probe.port.trim();
+    let user_defined_probe = match probe.method {
+        ConnectivityProbeMethod::ConnectivityProbeMethodNone => ConnectivityProbe::default(),
+        ConnectivityProbeMethod::ConnectivityProbeMethodICMP => {
+            if !host.is_empty() && !is_valid_probe_host(&host) {
+                errors.push(FieldError::new(
+                    DpcField::ProbeHost,
+                    format!("invalid address '{}'", host),
+                ));
+            }
+            ConnectivityProbe {
+                method: probe.method.clone(),
+                probe_host: host,
+                probe_port: 0,
+            }
+        }
+        ConnectivityProbeMethod::ConnectivityProbeMethodTCP => {
+            if host.is_empty() {
+                errors.push(FieldError::new(
+                    DpcField::ProbeHost,
+                    "host is required for TCP probe",
+                ));
+            } else if !is_valid_probe_host(&host) {
+                errors.push(FieldError::new(
+                    DpcField::ProbeHost,
+                    format!("invalid address '{}'", host),
+                ));
+            }
+            let probe_port = match port.parse::<u16>() {
+                Ok(p) if p != 0 => p,
+                _ if port.is_empty() => {
+                    errors.push(FieldError::new(
+                        DpcField::ProbePort,
+                        "port is required for TCP probe",
+                    ));
+                    0
+                }
+                _ => {
+                    errors.push(FieldError::new(
+                        DpcField::ProbePort,
+                        format!("invalid port '{}'", port),
+                    ));
+                    0
+                }
+            };
+            ConnectivityProbe {
+                method: probe.method.clone(),
+                probe_host: host,
+                probe_port,
+            }
+        }
+    };
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(WwanProbe {
+        disable: probe.disable,
+        user_defined_probe,
+    })
+}
+
+/// Create a new DPC from `current` with access points and probe of
+/// `iface_name` replaced
+pub fn build_cellular_dpc(
+    current: &DevicePortConfig,
+    iface_name: &str,
+    access_points: Vec<CellularAccessPoint>,
+    probe: WwanProbe,
+) -> Result<DevicePortConfig, FieldError> {
+    let mut dpc = current.to_new_dpc_with_key(MANUAL_DPC_KEY);
+    let port = dpc.get_port_by_name_mut(iface_name).ok_or_else(|| {
+        FieldError::new(
+            DpcField::Interface,
+            format!("{} is not present in the current DPC", iface_name),
+        )
+    })?;
+    if port.wireless_cfg.w_type != WirelessType::Cellular {
+        return Err(FieldError::new(
+            DpcField::Interface,
+            format!("{} is not a cellular port", iface_name),
+        ));
+    }
+    let cellular = port
+        .wireless_cfg
+        .cellular_v2
+        .get_or_insert_with(Default::default);
+    cellular.access_points = (!access_points.is_empty()).then_some(access_points);
+    cellular.probe = probe;
+    Ok(dpc)
+}
+
+/// Access points and probe of a cellular port, empty if nothing is configured yet
+pub fn cellular_config(cfg: Option<&CellNetPortConfig>) -> (Vec<CellularAccessPoint>, WwanProbe) {
+    cfg.map(|c| (c.access_points.clone().unwrap_or_default(), c.probe.clone()))
+        .unwrap_or_default()
+}
+
+/// APN network identifier consists of labels separated by dots. Labels
+/// follow the host name rules
+fn check_apn(apn: &str) -> Result<(), String> {
+    if apn.len() > MAX_APN_LEN {
+        return Err(format!("APN is longer than {} bytes", MAX_APN_LEN));
+    }
+    if !is_valid_hostname(apn) {
+        return Err(format!("invalid APN '{}'", apn));
+    }
+    Ok(())
+}
+
+/// Comma separated list of PLMN codes formatted as MCC-MNC
+fn parse_plmns(s: &str) -> Result<Vec<String>, String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|plmn| {
+            let valid = plmn.split_once('-').is_some_and(|(mcc, mnc)| {
+                mcc.len() == 3
+                    && (2..=3).contains(&mnc.len())
+                    && mcc.chars().chain(mnc.chars()).all(|c| c.is_ascii_digit())
+            });
+            if valid {
+                Ok(plmn.to_string())
+            } else {
+                Err(format!("invalid PLMN '{}', expected MCC-MNC", plmn))
+            }
+        })
+        .collect()
+}
+
+/// Comma separated list of radio access technologies
+fn parse_rats(s: &str) -> Result<Vec<WwanRAT>, String> {
+    let mut rats = Vec::new();
+    for rat in s.split(',').map(str::trim).filter(|r| !r.is_empty()) {
+        let rat = match rat.to_lowercase().as_str() {
+            "gsm" => WwanRAT::WwanRATGSM,
+            "umts" => WwanRAT::WwanRATUMTS,
+            "lte" => WwanRAT::WwanRATLTE,
+            "5gnr" => WwanRAT::WwanRAT5GNR,
+            _ => {
+                return Err(format!(
+                    "unknown RAT '{}', expected gsm, umts, lte or 5gnr",
+                    rat
+                ))
+            }
+        };
+        if !rats.contains(&rat) {
+            rats.push(rat);
+        }
+    }
+    Ok(rats)
+}
+
+fn is_valid_probe_host(host: &str) -> bool {
+    host.parse::<IpAddr>().is_ok() || is_valid_hostname(host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dpc::load_dpc_list;
+
+    // DPC in use with a cellular port wwan0
+    fn current_dpc() -> DevicePortConfig {
+        load_dpc_list("cellular_access_point.json")
+            .get_current_dpc_cloned()
+            .unwrap()
+    }
+
+    fn current_config() -> (Vec<CellularAccessPoint>, WwanProbe) {
+        let dpc = current_dpc();
+        let port = dpc.get_port_by_name("wwan0").unwrap();
+        cellular_config(port.wireless_cfg.cellular_v2.as_ref())
+    }
+
+    fn ap_state() -> AccessPointState {
+        AccessPointState {
+            sim_slot: "1".to_string(),
+            apn: "internet.example".to_string(),
+            ip_type: "ipv4".to_string(),
+            auth_protocol: WwanAuthProtocol::None,
+            username: String::new(),
+            password: String::new(),
+            preferred_plmns: String::new(),
+            preferred_rats: String::new(),
+            forbid_roaming: false,
+            attach_apn: String::new(),
+            original: CellularAccessPoint::default(),
+        }
+    }
+
+    fn fields(errors: Vec<FieldError>) -> Vec<DpcField> {
+        errors.into_iter().map(|e| e.field).collect()
+    }
+
+    #[test]
+    fn test_unchanged_access_point() {
+        let (aps, _) = current_config();
+        let ap = validate_access_point(&AccessPointState::from(&aps[0])).unwrap();
+        assert_eq!(ap, aps[0]);
+    }
+
+    #[test]
+    fn test_access_point() {
+        let state = AccessPointState {
+            auth_protocol: WwanAuthProtocol::Chap,
+            username: " user ".to_string(),
+            password: "pw".to_string(),
+            preferred_plmns: "262-01, 310-260".to_string(),
+            preferred_rats: "LTE, 5gnr, lte".to_string(),
+            forbid_roaming: true,
+            attach_apn: "ims".to_string(),
+            ..ap_state()
+        };
+        let ap = validate_access_point(&state).unwrap();
+        assert_eq!(ap.sim_slot, 1);
+        assert_eq!(ap.apn, "internet.example");
+        assert_eq!(ap.cleartext_credentials.username, "user");
+        assert_eq!(ap.cleartext_credentials.password, "pw");
+        assert_eq!(
+            ap.preferred_plmns,
+            Some(vec!["262-01".to_string(), "310-260".to_string()])
+        );
+        assert_eq!(
+            ap.preferred_rats,
+            Some(vec![WwanRAT::WwanRATLTE, WwanRAT::WwanRAT5GNR])
+        );
+        assert!(ap.forbid_roaming);
+        assert_eq!(ap.attach_apn, "ims");
+    }
+
+    #[test]
+    fn test_invalid_access_point() {
+        let state = AccessPointState {
+            sim_slot: "first".to_string(),
+            apn: "bad apn".to_string(),
+            auth_protocol: WwanAuthProtocol::Pap,
+            preferred_plmns: "26201".to_string(),
+            preferred_rats: "wimax".to_string(),
+            attach_apn: "-ims".to_string(),
+            ..ap_state()
+        };
+        assert_eq!(
+            fields(validate_access_point(&state).unwrap_err()),
+            vec![
+                DpcField::SimSlot,
+                DpcField::Apn,
+                DpcField::AttachApn,
+                DpcField::Username,
+                DpcField::Password,
+                DpcField::Plmns,
+                DpcField::Rats,
+            ]
+        );
+
+        let state = AccessPointState {
+            apn: " ".to_string(),
+            ..ap_state()
+        };
+        assert_eq!(
+            fields(validate_access_point(&state).unwrap_err()),
+            vec![DpcField::Apn]
+        );
+    }
+
+    #[test]
+    fn test_credentials() {
+        let encrypted = CellularAccessPoint {
+            auth_protocol: WwanAuthProtocol::Pap,
+            encrypted_credentials: CipherBlockStatus {
+                cipher_block_id: "block-1".to_string(),
+                is_cipher: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let state = AccessPointState {
+            auth_protocol: WwanAuthProtocol::Pap,
+            original: encrypted.clone(),
+            ..ap_state()
+        };
+        // nothing entered: keep credentials from the controller
+        let ap = validate_access_point(&state).unwrap();
+        assert_eq!(ap.encrypted_credentials, encrypted.encrypted_credentials);
+
+        // entered credentials replace them
+        let ap = validate_access_point(&AccessPointState {
+            username: "user".to_string(),
+            password: "pw".to_string(),
+            ..state.clone()
+        })
+        .unwrap();
+        assert!(!ap.encrypted_credentials.is_cipher);
+        assert_eq!(ap.cleartext_credentials.username, "user");
+
+        // no authentication: all credentials dropped
+        let ap = validate_access_point(&AccessPointState {
+            auth_protocol: WwanAuthProtocol::None,
+            username: "user".to_string(),
+            ..state
+        })
+        .unwrap();
+        assert_eq!(
+            ap.cleartext_credentials,
+            WwanCleartextCredentials::default()
+        );
+        assert_eq!(ap.encrypted_credentials, CipherBlockStatus::default());
+    }
+
+    #[test]
+    fn test_probe() {
+        let probe = ProbeState {
+            disable: false,
+            method: ConnectivityProbeMethod::ConnectivityProbeMethodTCP,
+            host: "example.com".to_string(),
+            port: "443".to_string(),
+        };
+        let wwan_probe = validate_probe(&probe).unwrap();
+        assert_eq!(wwan_probe.user_defined_probe.probe_port, 443);
+
+        // ICMP does not use the port and may use the default host
+        let wwan_probe = validate_probe(&ProbeState {
+            method: ConnectivityProbeMethod::ConnectivityProbeMethodICMP,
+            host: String::new(),
+            ..probe.clone()
+        })
+        .unwrap();
+        assert_eq!(wwan_probe.user_defined_probe.probe_port, 0);
+
+        let errors = validate_probe(&ProbeState {
+            host: "bad host".to_string(),
+            port: "0".to_string(),
+            ..probe.clone()
+        })
+        .unwrap_err();
+        assert_eq!(
+            fields(errors),
+            vec![DpcField::ProbeHost, DpcField::ProbePort]
+        );
+
+        // default probing drops user defined settings
+        let wwan_probe = validate_probe(&ProbeState {
+            disable: true,
+            method: ConnectivityProbeMethod::ConnectivityProbeMethodNone,
+            ..probe
+        })
+        .unwrap();
+        assert!(wwan_probe.disable);
+        assert_eq!(wwan_probe.user_defined_probe, ConnectivityProbe::default());
+    }
+
+    #[test]
+    fn test_validate_cellular_config() {
+        let probe = ProbeState {
+            disable: false,
+            method: ConnectivityProbeMethod::ConnectivityProbeMethodTCP,
+            host: String::new(),
+            port: "80".to_string(),
+        };
+        let errors = validate_cellular_config(&[ap_state(), ap_state()], &probe).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0]
+            .to_string()
+            .starts_with("access point 2: SIM slot:"));
+        assert!(errors[1].to_string().starts_with("probe: Probe host:"));
+    }
+
+    #[test]
+    fn test_build_cellular_dpc() {
+        let current = current_dpc();
+        let (mut aps, probe) = current_config();
+        aps.push(
+            validate_access_point(&AccessPointState {
+                sim_slot: "2".to_string(),
+                ..ap_state()
+            })
+            .unwrap(),
+        );
+        let dpc = build_cellular_dpc(&current, "wwan0", aps.clone(), probe.clone()).unwrap();
+        assert_eq!(dpc.key, MANUAL_DPC_KEY);
+        let port = dpc.get_port_by_name("wwan0").unwrap();
+        let cellular = port.wireless_cfg.cellular_v2.as_ref().unwrap();
+        assert_eq!(cellular.access_points.as_ref(), Some(&aps));
+        assert_eq!(cellular.probe, probe);
+        // settings not edited in the dialog are kept
+        assert!(cellular.location_tracking);
+        assert_eq!(
+            port.dhcp_config,
+            current.get_port_by_name("wwan0").unwrap().dhcp_config
+        );
+    }
+
+    #[test]
+    fn test_build_cellular_dpc_errors() {
+        let current = current_dpc();
+        let err = build_cellular_dpc(&current, "wlan0", vec![], WwanProbe::default()).unwrap_err();
+        assert_eq!(err.field, DpcField::Interface);
+        let err = build_cellular_dpc(&current, "wwan1", vec![], WwanProbe::default()).unwrap_err();
+        assert_eq!(err.field, DpcField::Interface);
+    }
+}
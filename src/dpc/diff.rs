@@ -0,0 +1,630 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Field by field difference between two DevicePortConfigs.
+//!
+//! Ports are matched by interface name. Lists such as DNS servers or WiFi
+//! networks are compared with the LCS machinery from [crate::diff]; entries
+//! with the same semantic key (e.g. the SSID) are reported as modified rather
+//! than as removed and added. Entries of lists where the order matters, e.g.
+//! DNS servers, are reported as moved when only their position changed.
+
+use std::fmt::Display;
+
+use crate::{
+    diff::{
+        lcs::{collect_diff, compute_lcs},
+        semantic::{diff_semantic, LcsSemanticKey},
+    },
+    ipc::eve_types::{DevicePortConfig, DhcpType, L2LinkType, NetworkPortConfig, WirelessType},
+};
+
+/// Change of a single value or list entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueChange {
+    Unchanged(String),
+    Added(String),
+    Removed(String),
+    Modified(String, String),
+    /// Same entry at another position of an ordered list
+    Moved(String),
+}
+
+impl ValueChange {
+    fn is_unchanged(&self) -> bool {
+        matches!(self, ValueChange::Unchanged(_))
+    }
+}
+
+/// Changes of one port setting. Unchanged entries of a list are kept to give
+/// context to the changed ones
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    pub field: &'static str,
+    pub changes: Vec<ValueChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PortChange {
+    Added,
+    Removed,
+    Modified,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortDiff {
+    pub name: String,
+    pub change: PortChange,
+    pub fields: Vec<FieldDiff>,
+}
+
+/// Differences between the DPC in use and the one about to be sent
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DpcDiff {
+    pub ports: Vec<PortDiff>,
+}
+
+impl DpcDiff {
+    pub fn is_empty(&self) -> bool {
+        self.ports.is_empty()
+    }
+}
+
+/// List entry as it is compared. `value` is shown to the user, `data` holds
+/// everything else which must be equal, e.g. credentials which are not shown
+#[derive(Debug, PartialEq)]
+struct Entry {
+    key: String,
+    value: String,
+    data: String,
+}
+
+impl Entry {
+    fn new(value: impl Into<String>) -> Self {
+        let value = value.into();
+        Self {
+            key: value.clone(),
+            value,
+            data: String::new(),
+        }
+    }
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl<'a> LcsSemanticKey<'a, String> for Entry {
+    fn semantic_key(&'a self) -> String {
+        self.key.clone()
+    }
+}
+
+/// Compare two lists. The result follows the order of the new list, removed
+/// entries come last. Entries out of the common sequence are reported as moved
+/// if the list is `ordered`
+fn diff_list(old: &[Entry], new: &[Entry], ordered: bool) -> Vec<ValueChange> {
+    let lcs = compute_lcs::<Entry, _, _>(old, new);
+    let (del, ins) = collect_diff(old, new, &lcs);
+    let (mut del, added, mods) = diff_semantic(old, new, &del, &ins);
+    del.sort();
+
+    let mut changes = new
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            if added.contains(&i) {
+                ValueChange::Added(entry.to_string())
+            } else if let Some((o, _)) = mods.iter().find(|(_, n)| *n == i) {
+                let new_value = if old[*o].value == entry.value {
+                    format!("{} (settings changed)", entry)
+                } else {
+                    entry.to_string()
+                };
+                ValueChange::Modified(old[*o].to_string(), new_value)
+            } else if ordered && !lcs.iter().any(|(_, n)| *n == i) {
+                ValueChange::Moved(entry.to_string())
+            } else {
+                ValueChange::Unchanged(entry.to_string())
+            }
+        })
+        .collect::<Vec<_>>();
+    changes.extend(
+        del.iter()
+            .map(|i| ValueChange::Removed(old[*i].to_string())),
+    );
+    changes
+}
+
+fn diff_value(old: &str, new: &str) -> ValueChange {
+    match (old.is_empty(), new.is_empty()) {
+        _ if old == new => ValueChange::Unchanged(new.to_string()),
+        (true, false) => ValueChange::Added(new.to_string()),
+        (false, true) => ValueChange::Removed(old.to_string()),
+        _ => ValueChange::Modified(old.to_string(), new.to_string()),
+    }
+}
+
+fn ip_mode(port: &NetworkPortConfig) -> String {
+    match port.dhcp_config.dhcp {
+        DhcpType::Client => "DHCP".to_string(),
+        DhcpType::Static => "static".to_string(),
+        DhcpType::None => "none".to_string(),
+        ref other => format!("{:?}", other),
+    }
+}
+
+fn usage(port: &NetworkPortConfig) -> String {
+    let usage = if port.is_mgmt {
+        "management"
+    } else {
+        "app-shared"
+    };
+    if port.is_l3_port {
+        format!("{}, L3", usage)
+    } else {
+        usage.to_string()
+    }
+}
+
+fn l2_link(port: &NetworkPortConfig) -> String {
+    let l2 = &port.l2_link_config;
+    match l2.l2_type {
+        L2LinkType::L2LinkTypeNone => String::new(),
+        L2LinkType::L2LinkTypeVLAN => l2.vlan.as_ref().map_or(String::new(), |v| {
+            format!("VLAN {} on {}", v.id, v.parent_port)
+        }),
+        L2LinkType::L2LinkTypeBond => l2.bond.as_ref().map_or(String::new(), |b| {
+            let ports = b.aggregated_ports.clone().unwrap_or_default().join(", ");
+            format!("bond {} of {}", b.mode, ports)
+        }),
+    }
+}
+
+fn wireless(port: &NetworkPortConfig) -> String {
+    match port.wireless_cfg.w_type {
+        WirelessType::None => String::new(),
+        WirelessType::Wifi => "WiFi".to_string(),
+        WirelessType::Cellular => "cellular".to_string(),
+    }
+}
+
+fn dns_entries(port: &NetworkPortConfig) -> Vec<Entry> {
+    port.dhcp_config
+        .dns_servers
+        .iter()
+        .flatten()
+        .map(|ip| Entry::new(ip.to_string()))
+        .collect()
+}
+
+fn ntp_entries(port: &NetworkPortConfig) -> Vec<Entry> {
+    port.dhcp_config
+        .ntp_servers
+        .iter()
+        .flatten()
+        .map(Entry::new)
+        .collect()
+}
+
+// only one proxy per type is allowed
+fn proxy_entries(port: &NetworkPortConfig) -> Vec<Entry> {
+    let proxy = &port.proxy_config;
+    let mut entries = proxy
+        .proxies
+        .iter()
+        .flatten()
+        .map(|p| Entry {
+            key: p.proxy_type.to_string(),
+            value: format!("{} {}:{}", p.proxy_type, p.server, p.port),
+            data: String::new(),
+        })
+        .collect::<Vec<_>>();
+    if !proxy.exceptions.is_empty() {
+        entries.push(Entry {
+            key: "exceptions".to_string(),
+            value: format!("exceptions {}", proxy.exceptions),
+            data: String::new(),
+        });
+    }
+    if !proxy.pacfile.is_empty() {
+        // the PAC file can be large, show that it has changed only
+        entries.push(Entry {
+            key: "pac".to_string(),
+            value: "PAC file".to_string(),
+            data: proxy.pacfile.clone(),
+        });
+    }
+    if proxy.network_proxy_enable {
+        entries.push(Entry {
+            key: "wpad".to_string(),
+            value: format!("WPAD {}", proxy.network_proxy_url),
+            data: String::new(),
+        });
+    }
+    let certs = proxy.proxy_cert_pem.as_ref().map_or(0, |c| c.len());
+    if certs > 0 {
+        entries.push(Entry {
+            key: "certs".to_string(),
+            value: format!("{} CA certificates", certs),
+            data: format!("{:?}", proxy.proxy_cert_pem),
+        });
+    }
+    entries
+}
+
+fn wifi_entries(port: &NetworkPortConfig) -> Vec<Entry> {
+    port.wireless_cfg
+        .wifi
+        .iter()
+        .flatten()
+        .map(|wifi| Entry {
+            key: wifi.ssid.clone(),
+            value: format!(
+                "{} ({}, priority {})",
+                wifi.ssid, wifi.key_scheme, wifi.priority
+            ),
+            // credentials are compared but never shown
+            data: format!("{:?}", wifi),
+        })
+        .collect()
+}
+
+fn apn_entries(port: &NetworkPortConfig) -> Vec<Entry> {
+    let Some(cellular) = port.wireless_cfg.cellular_v2.as_ref() else {
+        return vec![];
+    };
+    let mut entries = cellular
+        .access_points
+        .iter()
+        .flatten()
+        .map(|ap| Entry {
+            key: format!("SIM {}", ap.sim_slot),
+            value: format!("SIM {}: {}", ap.sim_slot, ap.apn),
+            data: format!("{:?}", ap),
+        })
+        .collect::<Vec<_>>();
+    let probe = &cellular.probe;
+    if !probe.disable {
+        entries.push(Entry {
+            key: "probe".to_string(),
+            value: "connectivity probe".to_string(),
+            data: format!("{:?}", probe),
+        });
+    }
+    entries
+}
+
+fn push_field(fields: &mut Vec<FieldDiff>, field: &'static str, changes: Vec<ValueChange>) {
+    if changes.iter().any(|c| !c.is_unchanged()) {
+        fields.push(FieldDiff { field, changes });
+    }
+}
+
+fn diff_port(old: &NetworkPortConfig, new: &NetworkPortConfig) -> Vec<FieldDiff> {
+    let mut fields = Vec::new();
+    let scalar = |f: fn(&NetworkPortConfig) -> String| vec![diff_value(&f(old), &f(new))];
+    let address = |p: &NetworkPortConfig| {
+        p.dhcp_config
+            .addr_subnet
+            .map_or(String::new(), |a| a.to_string())
+    };
+    let gateway = |p: &NetworkPortConfig| p.dhcp_config.gateway.clone();
+    let domain = |p: &NetworkPortConfig| p.dhcp_config.domain_name.clone();
+    let alias = |p: &NetworkPortConfig| p.alias.clone();
+    let cost = |p: &NetworkPortConfig| p.cost.to_string();
+    let mtu = |p: &NetworkPortConfig| {
+        if p.mtu == 0 {
+            String::new()
+        } else {
+            p.mtu.to_string()
+        }
+    };
+
+    push_field(&mut fields, "Usage", scalar(usage));
+    push_field(&mut fields, "IP mode", scalar(ip_mode));
+    push_field(&mut fields, "Address", scalar(address));
+    push_field(&mut fields, "Gateway", scalar(gateway));
+    push_field(&mut fields, "Domain", scalar(domain));
+    push_field(
+        &mut fields,
+        "DNS",
+        diff_list(&dns_entries(old), &dns_entries(new), true),
+    );
+    push_field(
+        &mut fields,
+        "NTP",
+        diff_list(&ntp_entries(old), &ntp_entries(new), true),
+    );
+    push_field(
+        &mut fields,
+        "Proxy",
+        diff_list(&proxy_entries(old), &proxy_entries(new), false),
+    );
+    push_field(&mut fields, "L2", scalar(l2_link));
+    push_field(&mut fields, "Wireless", scalar(wireless));
+    push_field(
+        &mut fields,
+        "WiFi",
+        diff_list(&wifi_entries(old), &wifi_entries(new), true),
+    );
+    push_field(
+        &mut fields,
+        "Cellular",
+        diff_list(&apn_entries(old), &apn_entries(new), false),
+    );
+    push_field(&mut fields, "Alias", scalar(alias));
+    push_field(&mut fields, "Cost", scalar(cost));
+    push_field(&mut fields, "MTU", scalar(mtu));
+    fields
+}
+
+/// Describe a port which is not in the DPC in use
+fn describe_new_port(port: &NetworkPortConfig) -> Vec<FieldDiff> {
+    // compare with an empty port of the same usage, then add the usage
+    let empty = NetworkPortConfig {
+        is_mgmt: port.is_mgmt,
+        is_l3_port: port.is_l3_port,
+        ..Default::default()
+    };
+    let mut fields = vec![FieldDiff {
+        field: "Usage",
+        changes: vec![ValueChange::Added(usage(port))],
+    }];
+    fields.extend(diff_port(&empty, port));
+    fields
+}
+
+/// Compute the difference between the DPC `old` and `new`. Ports which did
+/// not change are skipped
+pub fn diff_dpc(old: &DevicePortConfig, new: &DevicePortConfig) -> DpcDiff {
+    let mut ports = Vec::new();
+    for new_port in &new.ports {
+        match old.get_port_by_name(&new_port.if_name) {
+            Some(old_port) => {
+                let fields = diff_port(old_port, new_port);
+                if !fields.is_empty() {
+                    ports.push(PortDiff {
+                        name: new_port.if_name.clone(),
+                        change: PortChange::Modified,
+                        fields,
+                    });
+                }
+            }
+            None => ports.push(PortDiff {
+                name: new_port.if_name.clone(),
+                change: PortChange::Added,
+                fields: describe_new_port(new_port),
+            }),
+        }
+    }
+    for old_port in &old.ports {
+        if new.get_port_by_name(&old_port.if_name).is_none() {
+            ports.push(PortDiff {
+                name: old_port.if_name.clone(),
+                change: PortChange::Removed,
+                fields: vec![],
+            });
+        }
+    }
+    DpcDiff { ports }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dpc::{builder::build_dpc, current_dpc},
+        ipc::eve_types::{WifiConfig, WifiKeySchemeType},
+        ui::ipdialog::{InterfaceState, ProxyType},
+    };
+
+    fn entries(values: &[&str]) -> Vec<Entry> {
+        values.iter().map(|v| Entry::new(*v)).collect()
+    }
+
+    #[test]
+    fn test_no_changes() {
+        let dpc = current_dpc();
+        assert!(diff_dpc(&dpc, &dpc.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_list() {
+        let changes = diff_list(
+            &entries(&["1.1.1.1", "8.8.8.8", "9.9.9.9"]),
+            &entries(&["8.8.8.8", "1.1.1.1", "4.4.4.4"]),
+            false,
+        );
+        assert_eq!(
+            changes,
+            vec![
+                ValueChange::Unchanged("8.8.8.8".to_string()),
+                ValueChange::Unchanged("1.1.1.1".to_string()),
+                ValueChange::Added("4.4.4.4".to_string()),
+                ValueChange::Removed("9.9.9.9".to_string()),
+            ]
+        );
+        assert!(diff_list(&entries(&["a"]), &entries(&["a"]), true)
+            .iter()
+            .all(|c| c.is_unchanged()));
+    }
+
+    #[test]
+    fn test_static_ip() {
+        let dpc = current_dpc();
+        let old = InterfaceState {
+            iface_name: "eth0".to_string(),
+            ip_dhcp: true,
+            proxy_type: ProxyType::None,
+            ipv4: "".to_string(),
+            ipv6: "".to_string(),
+            mask: "".to_string(),
+            gw: "".to_string(),
+            proxy_url: "".to_string(),
+            proxy_certificates: vec![],
+            pac_file: "".to_string(),
+            domain: "".to_string(),
+            dns: "".to_string(),
+            ntp: "".to_string(),
+            proxy_http: None,
+            proxy_https: None,
+            proxy_ftp: None,
+            proxy_socks: None,
+            is_mgmt: true,
+            cost: "0".to_string(),
+            mtu: "".to_string(),
+            alias: "".to_string(),
+        };
+        let new = InterfaceState {
+            ip_dhcp: false,
+            ipv4: "192.168.1.10".to_string(),
+            mask: "255.255.255.0".to_string(),
+            gw: "192.168.1.1".to_string(),
+            dns: "1.1.1.1, 8.8.8.8".to_string(),
+            ..old.clone()
+        };
+        let new_dpc = build_dpc(&dpc, &old, &new).unwrap();
+        let diff = diff_dpc(&dpc, &new_dpc);
+        assert_eq!(diff.ports.len(), 1);
+        let port = &diff.ports[0];
+        assert_eq!(port.name, "eth0");
+        assert_eq!(port.change, PortChange::Modified);
+        let field = |name: &str| {
+            port.fields
+                .iter()
+                .find(|f| f.field == name)
+                .map(|f| f.changes.clone())
+        };
+        assert_eq!(
+            field("IP mode"),
+            Some(vec![ValueChange::Modified(
+                "DHCP".to_string(),
+                "static".to_string()
+            )])
+        );
+        assert_eq!(
+            field("Address"),
+            Some(vec![ValueChange::Added("192.168.1.10/24".to_string())])
+        );
+        assert_eq!(
+            field("Gateway"),
+            Some(vec![ValueChange::Added("192.168.1.1".to_string())])
+        );
+        assert_eq!(
+            field("DNS"),
+            Some(vec![
+                ValueChange::Added("1.1.1.1".to_string()),
+                ValueChange::Added("8.8.8.8".to_string()),
+            ])
+        );
+        assert_eq!(field("NTP"), None);
+    }
+
+    #[test]
+    fn test_wifi_networks() {
+        let wifi = |ssid: &str, password: &str| WifiConfig {
+            ssid: ssid.to_string(),
+            key_scheme: WifiKeySchemeType::KeySchemeWpaPsk,
+            password: password.to_string(),
+            priority: 1,
+            ..Default::default()
+        };
+        let mut old = NetworkPortConfig {
+            if_name: "wlan0".to_string(),
+            ..Default::default()
+        };
+        old.wireless_cfg.w_type = WirelessType::Wifi;
+        old.wireless_cfg.wifi = Some(vec![wifi("home", "secret"), wifi("office", "x")]);
+        let mut new = old.clone();
+        new.wireless_cfg.wifi = Some(vec![wifi("home", "changed"), wifi("lab", "y")]);
+
+        let fields = diff_port(&old, &new);
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].field, "WiFi");
+        assert_eq!(
+            fields[0].changes,
+            vec![
+                ValueChange::Modified(
+                    "home (WPA-PSK, priority 1)".to_string(),
+                    "home (WPA-PSK, priority 1) (settings changed)".to_string()
+                ),
+                ValueChange::Added("lab (WPA-PSK, priority 1)".to_string()),
+                ValueChange::Removed("office (WPA-PSK, priority 1)".to_string()),
+            ]
+        );
+        // passwords are never shown
+        assert!(!format!("{:?}", fields).contains("secret"));
+    }
+
+    #[test]
+    fn test_order_changes() {
+        let wifi = |ssid: &str| WifiConfig {
+            ssid: ssid.to_string(),
+            key_scheme: WifiKeySchemeType::KeySchemeWpaPsk,
+            priority: 1,
+            ..Default::default()
+        };
+        let mut old = NetworkPortConfig {
+            if_name: "wlan0".to_string(),
+            ..Default::default()
+        };
+        old.dhcp_config.dns_servers =
+            Some(vec!["1.1.1.1".parse().unwrap(), "8.8.8.8".parse().unwrap()]);
+        old.dhcp_config.ntp_servers = Some(vec!["a.ntp.org".into(), "b.ntp.org".into()]);
+        old.wireless_cfg.w_type = WirelessType::Wifi;
+        old.wireless_cfg.wifi = Some(vec![wifi("home"), wifi("office")]);
+        let mut new = old.clone();
+        new.dhcp_config.dns_servers =
+            Some(vec!["8.8.8.8".parse().unwrap(), "1.1.1.1".parse().unwrap()]);
+        new.dhcp_config.ntp_servers = Some(vec!["b.ntp.org".into(), "a.ntp.org".into()]);
+        new.wireless_cfg.wifi = Some(vec![wifi("office"), wifi("home")]);
+
+        let fields = diff_port(&old, &new);
+        let field = |name: &str| fields.iter().find(|f| f.field == name).cloned();
+        assert_eq!(
+            field("DNS").unwrap().changes,
+            vec![
+                ValueChange::Unchanged("8.8.8.8".to_string()),
+                ValueChange::Moved("1.1.1.1".to_string()),
+            ]
+        );
+        assert!(field("NTP").is_some());
+        assert!(field("WiFi").is_some());
+
+        // a DPC with reordered DNS servers only is not empty
+        let mut dpc = current_dpc();
+        dpc.ports[0].dhcp_config.dns_servers = old.dhcp_config.dns_servers.clone();
+        let mut reordered = dpc.clone();
+        reordered.ports[0].dhcp_config.dns_servers = new.dhcp_config.dns_servers.clone();
+        assert!(!diff_dpc(&dpc, &reordered).is_empty());
+    }
+
+    #[test]
+    fn test_added_and_removed_ports() {
+        let old = current_dpc();
+        let mut new = old.clone();
+        let mut vlan = new.ports[0].clone();
+        vlan.if_name = "eth0.100".to_string();
+        new.ports.push(vlan);
+        new.ports.retain(|p| p.if_name != "eth1");
+
+        let diff = diff_dpc(&old, &new);
+        let changes = diff
+            .ports
+            .iter()
+            .map(|p| (p.name.as_str(), p.change))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                ("eth0.100", PortChange::Added),
+                ("eth1", PortChange::Removed)
+            ]
+        );
+        assert_eq!(diff.ports[0].fields[0].field, "Usage");
+        assert!(diff.ports[1].fields.is_empty());
+    }
+}
@@ -0,0 +1,994 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Create, edit and delete VLAN sub-interfaces and bonds of a DPC.
+//!
+//! VLANs and bonds are ports of the DPC just like physical adapters. They
+//! refer to their parent and aggregated ports by logical label. A VLAN can be
+//! created on top of a physical Ethernet port or a bond. Ports aggregated by a
+//! bond carry L2 traffic only, so they have neither IP configuration nor VLANs
+//! of their own.
+
+use std::{
+    collections::HashSet,
+    net::{IpAddr, Ipv4Addr},
+};
+
+use crate::{
+    ipc::eve_types::{
+        BondArpMonitor, BondConfig, BondMIIMonitor, BondMode, DevicePortConfig, DhcpConfig,
+        DhcpType, L2LinkConfig, L2LinkType, LacpRate, NetworkPortConfig, NetworkType, VLANConfig,
+        WirelessType,
+    },
+    ui::l2linkdialog::{BondMonitor, BondState, VlanState},
+};
+
+use super::builder::{parse_list, DpcField, FieldError, MANUAL_DPC_KEY};
+
+/// Linux limits interface names to 15 characters
+const MAX_IFNAME_LEN: usize = 15;
+/// VLAN IDs 0 and 4095 are reserved
+const MAX_VLAN_ID: u16 = 4094;
+
+/// VLAN or bond as it is stored in a DPC port
+#[derive(Debug, Clone, PartialEq)]
+pub struct L2Link {
+    /// Interface name and logical label of the port
+    pub name: String,
+    pub config: L2LinkConfig,
+}
+
+/// Change of the L2 topology requested by the user
+#[derive(Debug, Clone, PartialEq)]
+pub enum L2LinkEdit {
+    /// Add a new link or replace the link named `original`
+    Set {
+        original: Option<String>,
+        link: L2Link,
+    },
+    /// Delete the link with the given name
+    Delete(String),
+}
+
+/// Kind of a node in the L2 topology tree
+#[derive(Debug, Clone, PartialEq)]
+pub enum L2NodeKind {
+    /// Physical adapter
+    Port,
+    /// Physical adapter aggregated by the parent bond
+    BondMember,
+    Bond(BondMode),
+    Vlan(u16),
+}
+
+/// Row of the L2 topology tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct L2Node {
+    pub name: String,
+    pub kind: L2NodeKind,
+    /// 0 for roots, 1 for VLANs and bond members
+    pub depth: usize,
+    /// The node is the last child of its parent
+    pub last: bool,
+}
+
+impl L2Node {
+    /// VLANs and bonds can be edited, physical ports cannot
+    pub fn is_l2_link(&self) -> bool {
+        matches!(self.kind, L2NodeKind::Bond(_) | L2NodeKind::Vlan(_))
+    }
+}
+
+/// Ports are referenced by logical label. Fall back to the interface name for
+/// ports without one
+pub fn port_label(port: &NetworkPortConfig) -> &str {
+    if port.logical_label.is_empty() {
+        &port.if_name
+    } else {
+        &port.logical_label
+    }
+}
+
+fn find_port<'a>(ports: &'a [NetworkPortConfig], label: &str) -> Option<&'a NetworkPortConfig> {
+    ports.iter().find(|p| port_label(p) == label)
+}
+
+fn vlan_of(port: &NetworkPortConfig) -> Option<&VLANConfig> {
+    match port.l2_link_config.l2_type {
+        L2LinkType::L2LinkTypeVLAN => port.l2_link_config.vlan.as_ref(),
+        _ => None,
+    }
+}
+
+fn bond_of(port: &NetworkPortConfig) -> Option<&BondConfig> {
+    match port.l2_link_config.l2_type {
+        L2LinkType::L2LinkTypeBond => port.l2_link_config.bond.as_ref(),
+        _ => None,
+    }
+}
+
+fn aggregated_ports(bond: &BondConfig) -> &[String] {
+    bond.aggregated_ports.as_deref().unwrap_or_default()
+}
+
+/// Bond aggregating the port `label`
+fn bond_aggregating<'a>(ports: &'a [NetworkPortConfig], label: &str) -> Option<&'a str> {
+    ports
+        .iter()
+        .find(|p| bond_of(p).is_some_and(|b| aggregated_ports(b).iter().any(|m| m == label)))
+        .map(port_label)
+}
+
+fn is_physical_ethernet(port: &NetworkPortConfig) -> bool {
+    port.l2_link_config.l2_type == L2LinkType::L2LinkTypeNone
+        && port.wireless_cfg.w_type == WirelessType::None
+}
+
+/// Build the tree of physical ports, bonds and VLANs. Ports aggregated by a
+/// bond are shown under the bond, VLANs under their parent. VLANs with a
+/// missing parent are shown as roots
+pub fn l2_topology(ports: &[NetworkPortConfig]) -> Vec<L2Node> {
+    let members = ports
+        .iter()
+        .filter_map(bond_of)
+        .flat_map(|b| aggregated_ports(b).iter().map(String::as_str))
+        .collect::<HashSet<_>>();
+    let roots = ports
+        .iter()
+        .filter(|p| vlan_of(p).is_none() && !members.contains(port_label(p)))
+        .map(port_label)
+        .collect::<HashSet<_>>();
+
+    let vlans_on = |parent: &str| {
+        ports
+            .iter()
+            .filter_map(|p| vlan_of(p).map(|v| (p, v)))
+            .filter(|(_, v)| v.parent_port == parent)
+            .map(|(p, v)| L2Node {
+                name: port_label(p).to_string(),
+                kind: L2NodeKind::Vlan(v.id),
+                depth: 1,
+                last: false,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut nodes = Vec::new();
+    for port in ports {
+        let label = port_label(port);
+        let (kind, mut children) = match (&port.l2_link_config.l2_type, bond_of(port)) {
+            (L2LinkType::L2LinkTypeBond, Some(bond)) => {
+                let mut children = aggregated_ports(bond)
+                    .iter()
+                    .map(|m| L2Node {
+                        name: m.clone(),
+                        kind: L2NodeKind::BondMember,
+                        depth: 1,
+                        last: false,
+                    })
+                    .collect::<Vec<_>>();
+                children.extend(vlans_on(label));
+                (L2NodeKind::Bond(bond.mode.clone()), children)
+            }
+            (L2LinkType::L2LinkTypeVLAN, _) => match vlan_of(port) {
+                Some(vlan) if !roots.contains(vlan.parent_port.as_str()) => {
+                    (L2NodeKind::Vlan(vlan.id), vec![])
+                }
+                _ => continue,
+            },
+            _ if members.contains(label) => continue,
+            _ => (L2NodeKind::Port, vlans_on(label)),
+        };
+        if let Some(last) = children.last_mut() {
+            last.last = true;
+        }
+        nodes.push(L2Node {
+            name: label.to_string(),
+            kind,
+            depth: 0,
+            last: false,
+        });
+        nodes.append(&mut children);
+    }
+    nodes
+}
+
+/// Ports a VLAN can be created on: physical Ethernet ports and bonds which
+/// are not aggregated by a bond
+pub fn vlan_parent_candidates(ports: &[NetworkPortConfig]) -> Vec<String> {
+    ports
+        .iter()
+        .filter(|p| is_physical_ethernet(p) || bond_of(p).is_some())
+        .map(port_label)
+        .filter(|label| bond_aggregating(ports, label).is_none())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Physical Ethernet ports the bond `bond` can aggregate. `bond` is None for
+/// a new bond
+pub fn bond_member_candidates(ports: &[NetworkPortConfig], bond: Option<&str>) -> Vec<String> {
+    ports
+        .iter()
+        .filter(|p| is_physical_ethernet(p))
+        .map(port_label)
+        .filter(|label| {
+            bond_aggregating(ports, label).is_none_or(|b| Some(b) == bond)
+                && !ports
+                    .iter()
+                    .filter_map(vlan_of)
+                    .any(|v| v.parent_port == *label)
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+fn is_valid_ifname(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_IFNAME_LEN
+        && name != "."
+        && name != ".."
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+fn parse_name(name: &str, errors: &mut Vec<FieldError>) -> String {
+    let name = name.trim();
+    if name.is_empty() {
+        errors.push(FieldError::new(DpcField::Name, "name is required"));
+    } else if !is_valid_ifname(name) {
+        errors.push(FieldError::new(
+            DpcField::Name,
+            format!("up to {} letters, digits, '-', '_' or '.'", MAX_IFNAME_LEN),
+        ));
+    }
+    name.to_string()
+}
+
+fn parse_number(field: DpcField, input: &str, errors: &mut Vec<FieldError>) -> u32 {
+    match input.trim() {
+        "" => 0,
+        s => s.parse::<u32>().unwrap_or_else(|_| {
+            errors.push(FieldError::new(field, format!("invalid number '{}'", s)));
+            0
+        }),
+    }
+}
+
+/// Append errors of `check` for fields without a parsing error
+fn merge_errors(errors: &mut Vec<FieldError>, check: Vec<FieldError>) {
+    let fields = errors.iter().map(|e| e.field).collect::<HashSet<_>>();
+    errors.extend(check.into_iter().filter(|e| !fields.contains(&e.field)));
+}
+
+/// Parse and validate the VLAN dialog. `original` is the name of the edited
+/// VLAN, None for a new one
+pub fn validate_vlan(
+    state: &VlanState,
+    original: Option<&str>,
+    ports: &[NetworkPortConfig],
+) -> Result<L2Link, Vec<FieldError>> {
+    let mut errors = Vec::new();
+    let name = parse_name(&state.name, &mut errors);
+
+    let id = match state.id.trim() {
+        "" => {
+            errors.push(FieldError::new(DpcField::VlanId, "VLAN ID is required"));
+            0
+        }
+        s => s.parse::<u16>().unwrap_or_else(|_| {
+            errors.push(FieldError::new(
+                DpcField::VlanId,
+                format!("must be between 1 and {}", MAX_VLAN_ID),
+            ));
+            0
+        }),
+    };
+
+    let link = L2Link {
+        name,
+        config: L2LinkConfig {
+            l2_type: L2LinkType::L2LinkTypeVLAN,
+            vlan: Some(VLANConfig {
+                parent_port: state.parent.clone(),
+                id,
+            }),
+            bond: None,
+        },
+    };
+    merge_errors(&mut errors, check_l2_link(&link, original, ports));
+
+    if errors.is_empty() {
+        Ok(link)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Parse and validate the bond dialog. `original` is the name of the edited
+/// bond, None for a new one
+pub fn validate_bond(
+    state: &BondState,
+    original: Option<&str>,
+    ports: &[NetworkPortConfig],
+) -> Result<L2Link, Vec<FieldError>> {
+    let mut errors = Vec::new();
+    let name = parse_name(&state.name, &mut errors);
+
+    // parse_list only fails if the closure does
+    let members = parse_list(&state.ports, |s| Some(s.to_string())).unwrap_or_default();
+
+    let mut mii_monitor = BondMIIMonitor::default();
+    let mut arp_monitor = BondArpMonitor::default();
+    match state.monitor {
+        BondMonitor::None => {}
+        BondMonitor::Mii => {
+            mii_monitor = BondMIIMonitor {
+                enabled: true,
+                interval: parse_number(DpcField::MonitorInterval, &state.interval, &mut errors),
+                up_delay: parse_number(DpcField::UpDelay, &state.up_delay, &mut errors),
+                down_delay: parse_number(DpcField::DownDelay, &state.down_delay, &mut errors),
+            };
+        }
+        BondMonitor::Arp => {
+            let ip_targets = parse_list(&state.arp_targets, |s| {
+                s.parse::<Ipv4Addr>().ok().map(IpAddr::V4)
+            })
+            .unwrap_or_else(|item| {
+                errors.push(FieldError::new(
+                    DpcField::ArpTargets,
+                    format!("invalid IPv4 address '{}'", item),
+                ));
+                vec![]
+            });
+            arp_monitor = BondArpMonitor {
+                enabled: true,
+                interval: parse_number(DpcField::MonitorInterval, &state.interval, &mut errors),
+                ip_targets: (!ip_targets.is_empty()).then_some(ip_targets),
+            };
+        }
+    }
+
+    // LACP rate only applies to 802.3ad
+    let lacp_rate = if state.mode == BondMode::BondMode802Dot3AD {
+        state.lacp_rate.clone()
+    } else {
+        LacpRate::LacpRateUnspecified
+    };
+
+    let link = L2Link {
+        name,
+        config: L2LinkConfig {
+            l2_type: L2LinkType::L2LinkTypeBond,
+            vlan: None,
+            bond: Some(BondConfig {
+                aggregated_ports: (!members.is_empty()).then_some(members),
+                mode: state.mode.clone(),
+                lacp_rate,
+                mii_monitor,
+                arp_monitor,
+            }),
+        },
+    };
+    merge_errors(&mut errors, check_l2_link(&link, original, ports));
+
+    if errors.is_empty() {
+        Ok(link)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Check `link` against the other ports of the DPC. `original` is the name of
+/// the edited link, None for a new one. Returns all errors
+pub fn check_l2_link(
+    link: &L2Link,
+    original: Option<&str>,
+    ports: &[NetworkPortConfig],
+) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    // the edited link is replaced, so it does not conflict with itself
+    let others = ports
+        .iter()
+        .filter(|p| Some(port_label(p)) != original)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if !is_valid_ifname(&link.name) {
+        errors.push(FieldError::new(
+            DpcField::Name,
+            format!("'{}' is not a valid interface name", link.name),
+        ));
+    } else if others
+        .iter()
+        .any(|p| p.if_name == link.name || p.logical_label == link.name)
+    {
+        errors.push(FieldError::new(
+            DpcField::Name,
+            format!("port '{}' already exists", link.name),
+        ));
+    }
+
+    match (&link.config.l2_type, &link.config.vlan, &link.config.bond) {
+        (L2LinkType::L2LinkTypeVLAN, Some(vlan), _) => check_vlan(vlan, &others, &mut errors),
+        (L2LinkType::L2LinkTypeBond, _, Some(bond)) => check_bond(bond, &others, &mut errors),
+        _ => errors.push(FieldError::new(
+            DpcField::Interface,
+            format!("'{}' is neither a VLAN nor a bond", link.name),
+        )),
+    }
+    errors
+}
+
+fn check_vlan(vlan: &VLANConfig, others: &[NetworkPortConfig], errors: &mut Vec<FieldError>) {
+    let parent = vlan.parent_port.as_str();
+    match find_port(others, parent) {
+        None if parent.is_empty() => {
+            errors.push(FieldError::new(
+                DpcField::ParentPort,
+                "parent port is required",
+            ));
+        }
+        None => errors.push(FieldError::new(
+            DpcField::ParentPort,
+            format!("port '{}' does not exist", parent),
+        )),
+        Some(p) if vlan_of(p).is_some() => errors.push(FieldError::new(
+            DpcField::ParentPort,
+            "VLANs cannot be nested",
+        )),
+        Some(p) if !is_physical_ethernet(p) && bond_of(p).is_none() => {
+            errors.push(FieldError::new(
+                DpcField::ParentPort,
+                format!("'{}' is neither an Ethernet port nor a bond", parent),
+            ))
+        }
+        Some(_) => {
+            if let Some(bond) = bond_aggregating(others, parent) {
+                errors.push(FieldError::new(
+                    DpcField::ParentPort,
+                    format!("'{}' is aggregated by bond '{}'", parent, bond),
+                ));
+            }
+        }
+    }
+
+    if !(1..=MAX_VLAN_ID).contains(&vlan.id) {
+        errors.push(FieldError::new(
+            DpcField::VlanId,
+            format!("must be between 1 and {}", MAX_VLAN_ID),
+        ));
+    } else if let Some(p) = others
+        .iter()
+        .find(|p| vlan_of(p).is_some_and(|v| v.parent_port == parent && v.id == vlan.id))
+    {
+        errors.push(FieldError::new(
+            DpcField::VlanId,
+            format!(
+                "VLAN {} on '{}' already exists as '{}'",
+                vlan.id,
+                parent,
+                port_label(p)
+            ),
+        ));
+    }
+}
+
+fn check_bond(bond: &BondConfig, others: &[NetworkPortConfig], errors: &mut Vec<FieldError>) {
+    let members = aggregated_ports(bond);
+    let mut seen = HashSet::new();
+    let member_error = members.iter().find_map(|member| {
+        let message = match find_port(others, member) {
+            _ if !seen.insert(member) => format!("'{}' is listed more than once", member),
+            None => format!("port '{}' does not exist", member),
+            Some(p) if !is_physical_ethernet(p) => {
+                format!("'{}' is not a physical Ethernet port", member)
+            }
+            Some(_) => {
+                if let Some(other) = bond_aggregating(others, member) {
+                    format!("'{}' is aggregated by bond '{}'", member, other)
+                } else if others
+                    .iter()
+                    .filter_map(vlan_of)
+                    .any(|v| v.parent_port == *member)
+                {
+                    format!("'{}' has VLANs", member)
+                } else {
+                    return None;
+                }
+            }
+        };
+        Some(message)
+    });
+    if members.is_empty() {
+        errors.push(FieldError::new(
+            DpcField::BondPorts,
+            "at least one port is required",
+        ));
+    } else if let Some(message) = member_error {
+        errors.push(FieldError::new(DpcField::BondPorts, message));
+    }
+
+    let (mii, arp) = (&bond.mii_monitor, &bond.arp_monitor);
+    if mii.enabled && arp.enabled {
+        errors.push(FieldError::new(
+            DpcField::MonitorInterval,
+            "MII and ARP monitors cannot be used together",
+        ));
+    } else if (mii.enabled && mii.interval == 0) || (arp.enabled && arp.interval == 0) {
+        errors.push(FieldError::new(
+            DpcField::MonitorInterval,
+            "interval in milliseconds is required",
+        ));
+    }
+    if arp.enabled {
+        let targets = arp.ip_targets.as_deref().unwrap_or_default();
+        if matches!(
+            bond.mode,
+            BondMode::BondMode802Dot3AD
+                | BondMode::BondModeBalanceTLB
+                | BondMode::BondModeBalanceALB
+        ) {
+            errors.push(FieldError::new(
+                DpcField::ArpTargets,
+                format!("ARP monitor is not supported in {} mode", bond.mode),
+            ));
+        } else if targets.is_empty() {
+            errors.push(FieldError::new(
+                DpcField::ArpTargets,
+                "at least one target is required",
+            ));
+        } else if targets.iter().any(|ip| !ip.is_ipv4()) {
+            errors.push(FieldError::new(
+                DpcField::ArpTargets,
+                "only IPv4 targets are supported",
+            ));
+        }
+    }
+}
+
+/// New VLAN or bond. It is a management port with DHCP like the ports of
+/// the default configuration
+fn new_l2_port(link: &L2Link) -> NetworkPortConfig {
+    NetworkPortConfig {
+        if_name: link.name.clone(),
+        logical_label: link.name.clone(),
+        is_mgmt: true,
+        is_l3_port: true,
+        dhcp_config: DhcpConfig {
+            dhcp: DhcpType::Client,
+            dhcp_type: NetworkType::IPv4,
+            ..Default::default()
+        },
+        l2_link_config: link.config.clone(),
+        ..Default::default()
+    }
+}
+
+/// Update ports aggregated by bonds: new members become L2-only ports, ports
+/// released by a bond become management ports with DHCP
+fn update_bond_members(ports: &mut [NetworkPortConfig], before: &[String], after: &[String]) {
+    for port in ports.iter_mut() {
+        let label = port_label(port).to_string();
+        match (before.contains(&label), after.contains(&label)) {
+            (false, true) => {
+                port.is_l3_port = false;
+                port.is_mgmt = false;
+                port.dhcp_config = DhcpConfig {
+                    dhcp: DhcpType::None,
+                    ..Default::default()
+                };
+            }
+            (true, false) => {
+                port.is_l3_port = true;
+                port.is_mgmt = true;
+                port.dhcp_config.dhcp_type = NetworkType::IPv4;
+                port.to_dhcp();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn members_of(ports: &[NetworkPortConfig], label: Option<&str>) -> Vec<String> {
+    label
+        .and_then(|l| find_port(ports, l))
+        .and_then(bond_of)
+        .map(|b| aggregated_ports(b).to_vec())
+        .unwrap_or_default()
+}
+
+/// Create a new DPC from `current` with `edit` applied
+pub fn build_l2_link_dpc(
+    current: &DevicePortConfig,
+    edit: L2LinkEdit,
+) -> Result<DevicePortConfig, FieldError> {
+    let mut dpc = current.to_new_dpc_with_key(MANUAL_DPC_KEY);
+    match edit {
+        L2LinkEdit::Set { original, link } => {
+            if let Some(e) = check_l2_link(&link, original.as_deref(), &dpc.ports)
+                .into_iter()
+                .next()
+            {
+                return Err(e);
+            }
+            let before = members_of(&dpc.ports, original.as_deref());
+            let after = link
+                .config
+                .bond
+                .as_ref()
+                .map(|b| aggregated_ports(b).to_vec())
+                .unwrap_or_default();
+
+            match original {
+                Some(original) => {
+                    let port = dpc
+                        .ports
+                        .iter_mut()
+                        .find(|p| port_label(p) == original)
+                        .filter(|p| p.l2_link_config.l2_type != L2LinkType::L2LinkTypeNone)
+                        .ok_or_else(|| {
+                            FieldError::new(
+                                DpcField::Interface,
+                                format!("'{}' is not a VLAN or bond of the current DPC", original),
+                            )
+                        })?;
+                    port.if_name = link.name.clone();
+                    port.logical_label = link.name.clone();
+                    port.l2_link_config = link.config.clone();
+                    // VLANs refer to a renamed bond by its old name
+                    for port in dpc.ports.iter_mut() {
+                        if let Some(vlan) = port.l2_link_config.vlan.as_mut() {
+                            if vlan.parent_port == original {
+                                vlan.parent_port = link.name.clone();
+                            }
+                        }
+                    }
+                }
+                None => dpc.ports.push(new_l2_port(&link)),
+            }
+            update_bond_members(&mut dpc.ports, &before, &after);
+        }
+        L2LinkEdit::Delete(name) => {
+            let index = dpc
+                .ports
+                .iter()
+                .position(|p| port_label(p) == name)
+                .ok_or_else(|| {
+                    FieldError::new(
+                        DpcField::Interface,
+                        format!("'{}' is not present in the current DPC", name),
+                    )
+                })?;
+            if dpc.ports[index].l2_link_config.l2_type == L2LinkType::L2LinkTypeNone {
+                return Err(FieldError::new(
+                    DpcField::Interface,
+                    format!("'{}' is a physical port and cannot be deleted", name),
+                ));
+            }
+            if let Some(vlan) = dpc
+                .ports
+                .iter()
+                .find(|p| vlan_of(p).is_some_and(|v| v.parent_port == name))
+            {
+                return Err(FieldError::new(
+                    DpcField::Interface,
+                    format!("delete VLAN '{}' on '{}' first", port_label(vlan), name),
+                ));
+            }
+            let before = members_of(&dpc.ports, Some(&name));
+            dpc.ports.remove(index);
+            update_bond_members(&mut dpc.ports, &before, &[]);
+        }
+    }
+    Ok(dpc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dpc::current_dpc;
+
+    fn vlan_state(name: &str, parent: &str, id: &str) -> VlanState {
+        VlanState {
+            name: name.to_string(),
+            parent: parent.to_string(),
+            id: id.to_string(),
+        }
+    }
+
+    fn bond_state(name: &str, ports: &str) -> BondState {
+        BondState {
+            name: name.to_string(),
+            ports: ports.to_string(),
+            mode: BondMode::BondModeActiveBackup,
+            ..Default::default()
+        }
+    }
+
+    fn set(original: Option<&str>, link: L2Link) -> L2LinkEdit {
+        L2LinkEdit::Set {
+            original: original.map(str::to_string),
+            link,
+        }
+    }
+
+    // eth0.100 and bond0 of eth1 with VLAN bond0.20 on top
+    fn topology_dpc() -> DevicePortConfig {
+        let dpc = current_dpc();
+        let vlan = validate_vlan(&vlan_state("eth0.100", "eth0", "100"), None, &dpc.ports).unwrap();
+        let dpc = build_l2_link_dpc(&dpc, set(None, vlan)).unwrap();
+        let bond = validate_bond(&bond_state("bond0", "eth1"), None, &dpc.ports).unwrap();
+        let dpc = build_l2_link_dpc(&dpc, set(None, bond)).unwrap();
+        let vlan = validate_vlan(&vlan_state("bond0.20", "bond0", "20"), None, &dpc.ports).unwrap();
+        build_l2_link_dpc(&dpc, set(None, vlan)).unwrap()
+    }
+
+    fn fields(errors: Vec<FieldError>) -> Vec<DpcField> {
+        errors.into_iter().map(|e| e.field).collect()
+    }
+
+    #[test]
+    fn test_topology() {
+        let dpc = topology_dpc();
+        let tree = l2_topology(&dpc.ports)
+            .into_iter()
+            .map(|n| (n.name, n.kind, n.depth, n.last))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tree,
+            vec![
+                ("eth0".to_string(), L2NodeKind::Port, 0, false),
+                ("eth0.100".to_string(), L2NodeKind::Vlan(100), 1, true),
+                (
+                    "bond0".to_string(),
+                    L2NodeKind::Bond(BondMode::BondModeActiveBackup),
+                    0,
+                    false
+                ),
+                ("eth1".to_string(), L2NodeKind::BondMember, 1, false),
+                ("bond0.20".to_string(), L2NodeKind::Vlan(20), 1, true),
+            ]
+        );
+        assert_eq!(vlan_parent_candidates(&dpc.ports), vec!["eth0", "bond0"]);
+        assert_eq!(
+            bond_member_candidates(&dpc.ports, None),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            bond_member_candidates(&dpc.ports, Some("bond0")),
+            vec!["eth1"]
+        );
+    }
+
+    #[test]
+    fn test_new_vlan() {
+        let dpc = topology_dpc();
+        let vlan = dpc.get_port_by_name("eth0.100").unwrap();
+        assert_eq!(dpc.key, MANUAL_DPC_KEY);
+        assert_eq!(vlan.logical_label, "eth0.100");
+        assert!(vlan.is_dhcp() && vlan.is_mgmt && vlan.is_l3_port);
+        assert_eq!(
+            vlan.l2_link_config.vlan,
+            Some(VLANConfig {
+                parent_port: "eth0".to_string(),
+                id: 100
+            })
+        );
+        // the parent keeps its IP configuration
+        assert!(dpc.get_port_by_name("eth0").unwrap().is_l3_port);
+    }
+
+    #[test]
+    fn test_invalid_vlan() {
+        let dpc = topology_dpc();
+        let errors =
+            |state: VlanState| fields(validate_vlan(&state, None, &dpc.ports).unwrap_err());
+
+        assert_eq!(
+            errors(vlan_state("", "eth0", "")),
+            vec![DpcField::Name, DpcField::VlanId]
+        );
+        assert_eq!(
+            errors(vlan_state("a-very-long-vlan-name", "eth0", "4095")),
+            vec![DpcField::Name, DpcField::VlanId]
+        );
+        assert_eq!(
+            errors(vlan_state("vlan", "eth0", "x")),
+            vec![DpcField::VlanId]
+        );
+        // parent does not exist, is a VLAN or is aggregated by a bond
+        assert_eq!(
+            errors(vlan_state("vlan", "eth9", "1")),
+            vec![DpcField::ParentPort]
+        );
+        assert_eq!(
+            errors(vlan_state("vlan", "eth0.100", "1")),
+            vec![DpcField::ParentPort]
+        );
+        assert_eq!(
+            errors(vlan_state("vlan", "eth1", "1")),
+            vec![DpcField::ParentPort]
+        );
+        // names and VLAN IDs on the same parent are unique
+        assert_eq!(
+            errors(vlan_state("eth1", "eth0", "1")),
+            vec![DpcField::Name]
+        );
+        assert_eq!(
+            errors(vlan_state("vlan", "eth0", "100")),
+            vec![DpcField::VlanId]
+        );
+        assert!(validate_vlan(&vlan_state("vlan", "bond0", "100"), None, &dpc.ports).is_ok());
+        // an edited VLAN does not conflict with itself
+        assert!(validate_vlan(
+            &vlan_state("eth0.100", "eth0", "100"),
+            Some("eth0.100"),
+            &dpc.ports
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_new_bond() {
+        let dpc = topology_dpc();
+        let bond = dpc.get_port_by_name("bond0").unwrap();
+        let config = bond.l2_link_config.bond.as_ref().unwrap();
+        assert_eq!(config.aggregated_ports, Some(vec!["eth1".to_string()]));
+        assert_eq!(config.mode, BondMode::BondModeActiveBackup);
+        // the member is used for L2 only
+        let member = dpc.get_port_by_name("eth1").unwrap();
+        assert!(!member.is_l3_port && !member.is_mgmt);
+        assert_eq!(member.dhcp_config.dhcp, DhcpType::None);
+    }
+
+    #[test]
+    fn test_bond_monitors() {
+        let dpc = current_dpc();
+        let mut state = bond_state("bond0", "eth0, eth1");
+        state.monitor = BondMonitor::Mii;
+        state.interval = "100".to_string();
+        state.up_delay = "200".to_string();
+        let link = validate_bond(&state, None, &dpc.ports).unwrap();
+        let bond = link.config.bond.unwrap();
+        assert!(bond.mii_monitor.enabled);
+        assert_eq!(bond.mii_monitor.up_delay, 200);
+        assert!(!bond.arp_monitor.enabled);
+
+        state.monitor = BondMonitor::Arp;
+        state.arp_targets = "10.0.0.1, 10.0.0.2".to_string();
+        let bond = validate_bond(&state, None, &dpc.ports)
+            .unwrap()
+            .config
+            .bond
+            .unwrap();
+        assert_eq!(bond.arp_monitor.ip_targets.unwrap().len(), 2);
+        assert!(!bond.mii_monitor.enabled);
+
+        let errors =
+            |state: &BondState| fields(validate_bond(state, None, &dpc.ports).unwrap_err());
+        state.arp_targets = "fe80::1".to_string();
+        assert_eq!(errors(&state), vec![DpcField::ArpTargets]);
+        state.arp_targets = String::new();
+        state.interval = String::new();
+        assert_eq!(
+            errors(&state),
+            vec![DpcField::MonitorInterval, DpcField::ArpTargets]
+        );
+        state.arp_targets = "10.0.0.1".to_string();
+        state.interval = "100".to_string();
+        state.mode = BondMode::BondMode802Dot3AD;
+        assert_eq!(errors(&state), vec![DpcField::ArpTargets]);
+    }
+
+    #[test]
+    fn test_lacp_rate() {
+        let dpc = current_dpc();
+        let mut state = bond_state("bond0", "eth0");
+        state.lacp_rate = LacpRate::LacpRateFast;
+        let bond = validate_bond(&state, None, &dpc.ports)
+            .unwrap()
+            .config
+            .bond
+            .unwrap();
+        assert_eq!(bond.lacp_rate, LacpRate::LacpRateUnspecified);
+        state.mode = BondMode::BondMode802Dot3AD;
+        let bond = validate_bond(&state, None, &dpc.ports)
+            .unwrap()
+            .config
+            .bond
+            .unwrap();
+        assert_eq!(bond.lacp_rate, LacpRate::LacpRateFast);
+    }
+
+    #[test]
+    fn test_invalid_bond() {
+        let dpc = topology_dpc();
+        let errors =
+            |state: BondState| fields(validate_bond(&state, None, &dpc.ports).unwrap_err());
+        assert_eq!(errors(bond_state("bond1", "")), vec![DpcField::BondPorts]);
+        assert_eq!(
+            errors(bond_state("bond1", "eth9")),
+            vec![DpcField::BondPorts]
+        );
+        // eth0 has VLANs, eth1 is aggregated by bond0
+        assert_eq!(
+            errors(bond_state("bond1", "eth0")),
+            vec![DpcField::BondPorts]
+        );
+        assert_eq!(
+            errors(bond_state("bond1", "eth1")),
+            vec![DpcField::BondPorts]
+        );
+        assert_eq!(
+            errors(bond_state("bond1", "bond0")),
+            vec![DpcField::BondPorts]
+        );
+        // the existing bond0 still aggregates eth1
+        assert_eq!(
+            errors(bond_state("bond0", "eth1")),
+            vec![DpcField::Name, DpcField::BondPorts]
+        );
+        assert!(validate_bond(&bond_state("bond0", "eth1"), Some("bond0"), &dpc.ports).is_ok());
+        assert_eq!(
+            errors(bond_state("bond 1", "eth1, eth1")),
+            vec![DpcField::Name, DpcField::BondPorts]
+        );
+    }
+
+    #[test]
+    fn test_rename_bond() {
+        let dpc = topology_dpc();
+        let bond = validate_bond(&bond_state("uplink", "eth1"), Some("bond0"), &dpc.ports).unwrap();
+        let dpc = build_l2_link_dpc(&dpc, set(Some("bond0"), bond)).unwrap();
+        assert!(dpc.get_port_by_name("bond0").is_none());
+        let vlan = dpc.get_port_by_name("bond0.20").unwrap();
+        assert_eq!(
+            vlan.l2_link_config.vlan.as_ref().unwrap().parent_port,
+            "uplink"
+        );
+    }
+
+    #[test]
+    fn test_delete() {
+        let dpc = topology_dpc();
+        let delete = |dpc: &DevicePortConfig, name: &str| {
+            build_l2_link_dpc(dpc, L2LinkEdit::Delete(name.to_string()))
+        };
+        assert!(delete(&dpc, "eth0").is_err());
+        assert!(delete(&dpc, "eth9").is_err());
+        // VLANs on top of the bond must be deleted first
+        assert!(delete(&dpc, "bond0").is_err());
+
+        let dpc = delete(&dpc, "bond0.20").unwrap();
+        let dpc = delete(&dpc, "bond0").unwrap();
+        assert!(dpc.get_port_by_name("bond0").is_none());
+        // the released port is a management port again
+        let eth1 = dpc.get_port_by_name("eth1").unwrap();
+        assert!(eth1.is_l3_port && eth1.is_mgmt && eth1.is_dhcp());
+        assert_eq!(l2_topology(&dpc.ports).len(), 3);
+    }
+
+    #[test]
+    fn test_build_checks_current_dpc() {
+        let dpc = current_dpc();
+        let link = L2Link {
+            name: "vlan".to_string(),
+            config: L2LinkConfig {
+                l2_type: L2LinkType::L2LinkTypeVLAN,
+                vlan: Some(VLANConfig {
+                    parent_port: "eth9".to_string(),
+                    id: 10,
+                }),
+                bond: None,
+            },
+        };
+        let err = build_l2_link_dpc(&dpc, set(None, link)).unwrap_err();
+        assert_eq!(err.field, DpcField::ParentPort);
+        // only L2 links can be edited
+        let bond = validate_bond(&bond_state("bond0", "eth1"), None, &dpc.ports).unwrap();
+        let err = build_l2_link_dpc(&dpc, set(Some("eth0"), bond)).unwrap_err();
+        assert_eq!(err.field, DpcField::Interface);
+    }
+}
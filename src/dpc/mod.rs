@@ -0,0 +1,34 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod builder;
+pub mod cellular;
+pub mod certificate;
+pub mod diff;
+pub mod l2link;
+pub mod verification;
+pub mod wifi;
+
+/// Load the DPC list of an IPC message from `test_data/ipc-tests`
+#[cfg(test)]
+pub(crate) fn load_dpc_list(file: &str) -> crate::ipc::eve_types::DevicePortConfigList {
+    use crate::ipc::message::IpcMessage;
+
+    let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("test_data")
+        .join("ipc-tests")
+        .join(file);
+    let content = std::fs::read_to_string(path).unwrap();
+    let IpcMessage::DPCList(list) = serde_json::from_str::<IpcMessage>(&content).unwrap() else {
+        panic!("DPCList expected");
+    };
+    list
+}
+
+/// DPC in use with two DHCP ports, eth0 and eth1
+#[cfg(test)]
+pub(crate) fn current_dpc() -> crate::ipc::eve_types::DevicePortConfig {
+    load_dpc_list("eve_ipc_message-102.json")
+        .get_current_dpc_cloned()
+        .unwrap()
+}
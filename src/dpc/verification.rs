@@ -0,0 +1,245 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Follow a DPC sent to EVE until it is in use or EVE falls back.
+//!
+//! EVE adds the new DPC to the DPC list with the highest priority and tests
+//! it. While testing, `DeviceNetworkStatus.testing` is set and the DPC state
+//! goes through the wait states. If the test succeeds the DPC becomes the
+//! current one, otherwise EVE marks it as failed and goes back to a DPC which
+//! worked before. The DPC is identified by its key and time priority.
+
+use crate::ipc::eve_types::{
+    DPCState, DeviceNetworkStatus, DevicePortConfig, DevicePortConfigList,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VerificationStage {
+    /// The DPC is not in the DPC list yet
+    Pending,
+    /// EVE is testing the DPC
+    Testing(DPCState),
+    /// The DPC is in use
+    Applied,
+    /// The DPC failed and EVE uses the DPC with the given key
+    FellBack(String),
+    /// EVE refused the request, the DPC in use did not change
+    Rejected,
+}
+
+#[derive(Debug, Clone)]
+pub struct DpcVerification {
+    /// The DPC as it was sent
+    pub dpc: DevicePortConfig,
+    /// The DPC which was in use when `dpc` was sent
+    pub previous: DevicePortConfig,
+    pub stage: VerificationStage,
+    /// EVE is testing connectivity
+    pub testing: bool,
+    /// `TestResults.last_error` of the DPC
+    pub last_error: String,
+}
+
+impl DpcVerification {
+    pub fn new(dpc: DevicePortConfig, previous: DevicePortConfig) -> Self {
+        Self {
+            dpc,
+            previous,
+            stage: VerificationStage::Pending,
+            testing: false,
+            last_error: String::new(),
+        }
+    }
+
+    pub fn update_dpc_list(&mut self, list: &DevicePortConfigList) {
+        if self.stage == VerificationStage::Rejected {
+            return;
+        }
+        let dpcs = list.port_config_list.as_deref().unwrap_or_default();
+        let Some(index) = dpcs
+            .iter()
+            .position(|d| d.key == self.dpc.key && d.time_priority == self.dpc.time_priority)
+        else {
+            // EVE may drop a failed DPC once another one is in use
+            if self.stage != VerificationStage::Pending {
+                let current = list.get_current_dpc_key().unwrap_or_default();
+                self.stage = VerificationStage::FellBack(current.to_string());
+            }
+            return;
+        };
+        let dpc = &dpcs[index];
+        let in_use = index == list.current_index as usize;
+        self.last_error = dpc.test_results.last_error.clone();
+        self.stage = match &dpc.state {
+            DPCState::Success if in_use => VerificationStage::Applied,
+            DPCState::Fail | DPCState::FailWithIPAndDNS if !in_use => {
+                let current = list.get_current_dpc_key().unwrap_or_default();
+                VerificationStage::FellBack(current.to_string())
+            }
+            state => VerificationStage::Testing(state.clone()),
+        };
+    }
+
+    /// EVE returned `error` for the request with the DPC
+    pub fn reject(&mut self, error: &str) {
+        self.stage = VerificationStage::Rejected;
+        self.last_error = error.to_string();
+    }
+
+    pub fn update_network_status(&mut self, status: &DeviceNetworkStatus) {
+        self.testing = status.testing;
+    }
+
+    /// EVE will not change the state of the DPC on its own anymore
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self.stage,
+            VerificationStage::Applied
+                | VerificationStage::FellBack(_)
+                | VerificationStage::Rejected
+        )
+    }
+
+    /// What the user should do next
+    pub fn guidance(&self) -> String {
+        match &self.stage {
+            VerificationStage::Pending => {
+                "Waiting for EVE to accept the new port configuration".to_string()
+            }
+            VerificationStage::Testing(state) => format!(
+                "EVE is verifying the new port configuration ({}). Connectivity may be interrupted for a while",
+                state
+            ),
+            VerificationStage::Applied => "The new port configuration is in use".to_string(),
+            VerificationStage::FellBack(key) => format!(
+                "EVE could not verify the new port configuration and went back to '{}'. Check the error, then edit the configuration again or revert to the previous one",
+                key
+            ),
+            VerificationStage::Rejected => "EVE did not accept the new port configuration, the previous one is still in use. Check the error, then edit the configuration again".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn dpc(key: &str, secs: i64, state: DPCState, error: &str) -> DevicePortConfig {
+        let mut dpc = DevicePortConfig {
+            key: key.to_string(),
+            time_priority: Utc.timestamp_opt(secs, 0).unwrap(),
+            state,
+            ..Default::default()
+        };
+        dpc.test_results.last_error = error.to_string();
+        dpc
+    }
+
+    fn list(current_index: i32, dpcs: Vec<DevicePortConfig>) -> DevicePortConfigList {
+        DevicePortConfigList {
+            current_index,
+            port_config_list: Some(dpcs),
+        }
+    }
+
+    fn verification() -> DpcVerification {
+        DpcVerification::new(
+            dpc("manual", 200, DPCState::None, ""),
+            dpc("zedagent", 100, DPCState::Success, ""),
+        )
+    }
+
+    #[test]
+    fn test_pending_until_listed() {
+        let mut v = verification();
+        v.update_dpc_list(&list(0, vec![dpc("zedagent", 100, DPCState::Success, "")]));
+        assert_eq!(v.stage, VerificationStage::Pending);
+        assert!(!v.is_finished());
+    }
+
+    #[test]
+    fn test_testing_then_applied() {
+        let mut v = verification();
+        v.update_dpc_list(&list(
+            0,
+            vec![
+                dpc("manual", 200, DPCState::IPDNSWait, ""),
+                dpc("zedagent", 100, DPCState::Success, ""),
+            ],
+        ));
+        assert_eq!(v.stage, VerificationStage::Testing(DPCState::IPDNSWait));
+
+        v.update_dpc_list(&list(
+            0,
+            vec![
+                dpc("manual", 200, DPCState::Success, ""),
+                dpc("zedagent", 100, DPCState::Success, ""),
+            ],
+        ));
+        assert_eq!(v.stage, VerificationStage::Applied);
+        assert!(v.is_finished());
+    }
+
+    #[test]
+    fn test_rejected() {
+        let mut v = verification();
+        v.reject("invalid port config");
+        assert_eq!(v.stage, VerificationStage::Rejected);
+        assert_eq!(v.last_error, "invalid port config");
+        assert!(v.is_finished());
+
+        // a DPC with the same key and priority does not change the outcome
+        v.update_dpc_list(&list(0, vec![dpc("manual", 200, DPCState::IPDNSWait, "")]));
+        assert_eq!(v.stage, VerificationStage::Rejected);
+    }
+
+    #[test]
+    fn test_fallback() {
+        let mut v = verification();
+        v.update_dpc_list(&list(
+            1,
+            vec![
+                dpc("manual", 200, DPCState::Fail, "no IP address on eth0"),
+                dpc("zedagent", 100, DPCState::Success, ""),
+            ],
+        ));
+        assert_eq!(v.stage, VerificationStage::FellBack("zedagent".to_string()));
+        assert_eq!(v.last_error, "no IP address on eth0");
+        assert!(v.is_finished());
+    }
+
+    #[test]
+    fn test_failed_while_current_is_still_testing() {
+        let mut v = verification();
+        v.update_dpc_list(&list(
+            0,
+            vec![
+                dpc("manual", 200, DPCState::Fail, "timeout"),
+                dpc("zedagent", 100, DPCState::Success, ""),
+            ],
+        ));
+        assert_eq!(v.stage, VerificationStage::Testing(DPCState::Fail));
+    }
+
+    #[test]
+    fn test_older_dpc_with_same_key_is_ignored() {
+        let mut v = verification();
+        v.update_dpc_list(&list(0, vec![dpc("manual", 50, DPCState::Success, "")]));
+        assert_eq!(v.stage, VerificationStage::Pending);
+    }
+
+    #[test]
+    fn test_dropped_dpc() {
+        let mut v = verification();
+        v.update_dpc_list(&list(
+            0,
+            vec![
+                dpc("manual", 200, DPCState::AsyncWait, ""),
+                dpc("zedagent", 100, DPCState::Success, ""),
+            ],
+        ));
+        v.update_dpc_list(&list(0, vec![dpc("zedagent", 100, DPCState::Success, "")]));
+        assert_eq!(v.stage, VerificationStage::FellBack("zedagent".to_string()));
+    }
+}
@@ -0,0 +1,409 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Validate WiFi networks entered in the WiFi dialog and put them into a new DPC.
+
+use std::collections::HashSet;
+
+use thiserror::Error;
+
+use crate::{
+    ipc::eve_types::{
+        CipherBlockStatus, DevicePortConfig, WifiConfig, WifiKeySchemeType, WirelessType,
+    },
+    ui::wifidialog::WifiNetworkState,
+};
+
+use super::builder::{DpcField, FieldError, MANUAL_DPC_KEY};
+
+/// Maximum length of SSID in bytes
+const MAX_SSID_LEN: usize = 32;
+
+/// Validation error of one of the networks in the dialog
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("network {}: {error}", .index + 1)]
+pub struct WifiFieldError {
+    pub index: usize,
+    pub error: FieldError,
+}
+
+impl WifiNetworkState {
+    /// Credentials received from the controller are kept if the user entered
+    /// neither identity nor password
+    pub fn keeps_encrypted_credentials(&self) -> bool {
+        self.cipher.is_cipher && self.identity.is_empty() && self.password.is_empty()
+    }
+}
+
+/// Validate all networks. Returns WiFi configs in the same order or all
+/// invalid fields
+pub fn validate_wifi_networks(
+    networks: &[WifiNetworkState],
+) -> Result<Vec<WifiConfig>, Vec<WifiFieldError>> {
+    let mut errors = Vec::new();
+    let mut configs = Vec::new();
+    let mut ssids = HashSet::new();
+
+    for (index, network) in networks.iter().enumerate() {
+        match validate_wifi_network(network) {
+            Ok(config) => {
+                if !ssids.insert(config.ssid.clone()) {
+                    errors.push(WifiFieldError {
+                        index,
+                        error: FieldError::new(
+                            DpcField::Ssid,
+                            format!("'{}' is configured more than once", config.ssid),
+                        ),
+                    });
+                }
+                configs.push(config);
+            }
+            Err(e) => errors.extend(e.into_iter().map(|error| WifiFieldError { index, error })),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(configs)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Validate a single network
+pub fn validate_wifi_network(network: &WifiNetworkState) -> Result<WifiConfig, Vec<FieldError>> {
+    let mut errors = Vec::new();
+
+    // SSID may contain spaces, keep it as is
+    let ssid = network.ssid.clone();
+    if ssid.trim().is_empty() {
+        errors.push(FieldError::new(DpcField::Ssid, "SSID cannot be empty"));
+    } else if ssid.len() > MAX_SSID_LEN {
+        errors.push(FieldError::new(
+            DpcField::Ssid,
+            format!("SSID is longer than {} bytes", MAX_SSID_LEN),
+        ));
+    }
+
+    let priority = match network.priority.trim() {
+        "" => 0,
+        p => p.parse::<i32>().unwrap_or_else(|_| {
+            errors.push(FieldError::new(
+                DpcField::Priority,
+                format!("invalid priority '{}'", p),
+            ));
+            0
+        }),
+    };
+
+    let keep_encrypted = network.keeps_encrypted_credentials();
+    match network.key_scheme {
+        WifiKeySchemeType::KeySchemeWpaPsk
+            if !keep_encrypted && !is_valid_psk(&network.password) =>
+        {
+            errors.push(FieldError::new(
+                DpcField::Password,
+                "must be 8 to 63 ASCII characters or 64 hex digits",
+            ));
+        }
+        WifiKeySchemeType::KeySchemeWpaEap if !keep_encrypted => {
+            if network.identity.trim().is_empty() {
+                errors.push(FieldError::new(
+                    DpcField::Identity,
+                    "identity is required for WPA-EAP",
+                ));
+            }
+            if network.password.is_empty() {
+                errors.push(FieldError::new(
+                    DpcField::Password,
+                    "password is required for WPA-EAP",
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let (identity, password, cipher_block_status) = match network.key_scheme {
+        WifiKeySchemeType::KeySchemeNone => (String::new(), String::new(), Default::default()),
+        _ if keep_encrypted => (String::new(), String::new(), network.cipher.clone()),
+        WifiKeySchemeType::KeySchemeWpaEap => (
+            network.identity.trim().to_string(),
+            network.password.clone(),
+            CipherBlockStatus::default(),
+        ),
+        _ => (
+            String::new(),
+            network.password.clone(),
+            CipherBlockStatus::default(),
+        ),
+    };
+
+    Ok(WifiConfig {
+        ssid,
+        key_scheme: network.key_scheme.clone(),
+        identity,
+        password,
+        priority,
+        cipher_block_status,
+    })
+}
+
+/// Create a new DPC from `current` with WiFi networks of `iface_name`
+/// replaced by `networks`
+pub fn build_wifi_dpc(
+    current: &DevicePortConfig,
+    iface_name: &str,
+    networks: Vec<WifiConfig>,
+) -> Result<DevicePortConfig, FieldError> {
+    let mut dpc = current.to_new_dpc_with_key(MANUAL_DPC_KEY);
+    let port = dpc.get_port_by_name_mut(iface_name).ok_or_else(|| {
+        FieldError::new(
+            DpcField::Interface,
+            format!("{} is not present in the current DPC", iface_name),
+        )
+    })?;
+    if port.wireless_cfg.w_type != WirelessType::Wifi {
+        return Err(FieldError::new(
+            DpcField::Interface,
+            format!("{} is not a WiFi port", iface_name),
+        ));
+    }
+    port.wireless_cfg.wifi = (!networks.is_empty()).then_some(networks);
+    Ok(dpc)
+}
+
+/// WPA passphrase is 8..63 printable ASCII characters. A raw PSK is 64 hex digits
+fn is_valid_psk(password: &str) -> bool {
+    let passphrase = (8..=63).contains(&password.len())
+        && password
+            .chars()
+            .all(|c| c.is_ascii() && !c.is_ascii_control());
+    let raw = password.len() == 64 && password.chars().all(|c| c.is_ascii_hexdigit());
+    passphrase || raw
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::eve_types::WirelessConfig;
+
+    // DPC from the test data with eth1 turned into a WiFi port
+    fn current_dpc() -> DevicePortConfig {
+        let mut dpc = crate::dpc::current_dpc();
+        dpc.get_port_by_name_mut("eth1").unwrap().wireless_cfg = WirelessConfig {
+            w_type: WirelessType::Wifi,
+            wifi: Some(vec![encrypted_config()]),
+            ..Default::default()
+        };
+        dpc
+    }
+
+    fn encrypted_config() -> WifiConfig {
+        WifiConfig {
+            ssid: "office".to_string(),
+            key_scheme: WifiKeySchemeType::KeySchemeWpaPsk,
+            priority: 10,
+            cipher_block_status: CipherBlockStatus {
+                cipher_block_id: "block-1".to_string(),
+                is_cipher: true,
+                cipher_data: Some("c2VjcmV0".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn psk_network() -> WifiNetworkState {
+        WifiNetworkState {
+            ssid: "home".to_string(),
+            key_scheme: WifiKeySchemeType::KeySchemeWpaPsk,
+            identity: String::new(),
+            password: "secret-passphrase".to_string(),
+            priority: "5".to_string(),
+            cipher: CipherBlockStatus::default(),
+        }
+    }
+
+    fn fields(errors: Vec<FieldError>) -> Vec<DpcField> {
+        errors.into_iter().map(|e| e.field).collect()
+    }
+
+    #[test]
+    fn test_psk_network() {
+        let cfg = validate_wifi_network(&psk_network()).unwrap();
+        assert_eq!(cfg.ssid, "home");
+        assert_eq!(cfg.password, "secret-passphrase");
+        assert_eq!(cfg.priority, 5);
+        assert!(!cfg.cipher_block_status.is_cipher);
+    }
+
+    #[test]
+    fn test_psk_length() {
+        for (password, valid) in [
+            ("short", false),
+            ("12345678", true),
+            (&"a".repeat(63), true),
+            (&"a".repeat(64), true),
+            (&"g".repeat(64), false),
+            (&"a".repeat(65), false),
+            ("pass\tword", false),
+        ] {
+            let network = WifiNetworkState {
+                password: password.to_string(),
+                ..psk_network()
+            };
+            assert_eq!(
+                validate_wifi_network(&network).is_ok(),
+                valid,
+                "{password:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_eap_network() {
+        let network = WifiNetworkState {
+            key_scheme: WifiKeySchemeType::KeySchemeWpaEap,
+            identity: " user@example.com ".to_string(),
+            password: "pw".to_string(),
+            ..psk_network()
+        };
+        let cfg = validate_wifi_network(&network).unwrap();
+        assert_eq!(cfg.identity, "user@example.com");
+        assert_eq!(cfg.password, "pw");
+
+        let network = WifiNetworkState {
+            identity: String::new(),
+            password: String::new(),
+            ..network
+        };
+        assert_eq!(
+            fields(validate_wifi_network(&network).unwrap_err()),
+            vec![DpcField::Identity, DpcField::Password]
+        );
+    }
+
+    #[test]
+    fn test_open_network_drops_credentials() {
+        let network = WifiNetworkState {
+            key_scheme: WifiKeySchemeType::KeySchemeNone,
+            identity: "user".to_string(),
+            password: "x".to_string(),
+            cipher: encrypted_config().cipher_block_status,
+            ..psk_network()
+        };
+        let cfg = validate_wifi_network(&network).unwrap();
+        assert!(cfg.identity.is_empty());
+        assert!(cfg.password.is_empty());
+        assert_eq!(cfg.cipher_block_status, CipherBlockStatus::default());
+    }
+
+    #[test]
+    fn test_encrypted_credentials() {
+        // nothing entered: keep credentials from the controller
+        let network = WifiNetworkState::from(&encrypted_config());
+        assert!(network.password.is_empty());
+        let cfg = validate_wifi_network(&network).unwrap();
+        assert_eq!(cfg, encrypted_config());
+
+        // new password replaces them
+        let network = WifiNetworkState {
+            password: "new-passphrase".to_string(),
+            ..network
+        };
+        let cfg = validate_wifi_network(&network).unwrap();
+        assert_eq!(cfg.password, "new-passphrase");
+        assert!(!cfg.cipher_block_status.is_cipher);
+    }
+
+    #[test]
+    fn test_invalid_ssid_and_priority() {
+        for ssid in ["", "  ", &"x".repeat(33)] {
+            let network = WifiNetworkState {
+                ssid: ssid.to_string(),
+                ..psk_network()
+            };
+            assert_eq!(
+                fields(validate_wifi_network(&network).unwrap_err()),
+                vec![DpcField::Ssid]
+            );
+        }
+        let network = WifiNetworkState {
+            priority: "high".to_string(),
+            ..psk_network()
+        };
+        assert_eq!(
+            fields(validate_wifi_network(&network).unwrap_err()),
+            vec![DpcField::Priority]
+        );
+    }
+
+    #[test]
+    fn test_validate_networks() {
+        let networks = vec![
+            psk_network(),
+            WifiNetworkState::from(&encrypted_config()),
+            WifiNetworkState {
+                password: "short".to_string(),
+                ..psk_network()
+            },
+        ];
+        let errors = validate_wifi_networks(&networks).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].index, 2);
+        assert_eq!(errors[0].error.field, DpcField::Password);
+
+        // duplicate SSID
+        let networks = vec![psk_network(), psk_network()];
+        let errors = validate_wifi_networks(&networks).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].index, 1);
+        assert_eq!(errors[0].error.field, DpcField::Ssid);
+        assert!(errors[0].to_string().starts_with("network 2: SSID:"));
+    }
+
+    #[test]
+    fn test_build_wifi_dpc() {
+        let current = current_dpc();
+        let networks =
+            validate_wifi_networks(&[WifiNetworkState::from(&encrypted_config()), psk_network()])
+                .unwrap();
+        let dpc = build_wifi_dpc(&current, "eth1", networks).unwrap();
+        assert_eq!(dpc.key, MANUAL_DPC_KEY);
+        let wifi = dpc
+            .get_port_by_name("eth1")
+            .unwrap()
+            .wireless_cfg
+            .wifi
+            .as_ref()
+            .unwrap();
+        assert_eq!(wifi.len(), 2);
+        assert_eq!(wifi[0], encrypted_config());
+        assert_eq!(wifi[1].ssid, "home");
+
+        // IP settings are not touched
+        assert_eq!(
+            dpc.get_port_by_name("eth1").unwrap().dhcp_config,
+            current.get_port_by_name("eth1").unwrap().dhcp_config
+        );
+
+        // all networks removed
+        let dpc = build_wifi_dpc(&current, "eth1", vec![]).unwrap();
+        assert_eq!(
+            dpc.get_port_by_name("eth1").unwrap().wireless_cfg.wifi,
+            None
+        );
+    }
+
+    #[test]
+    fn test_build_wifi_dpc_errors() {
+        let current = current_dpc();
+        let err = build_wifi_dpc(&current, "eth0", vec![]).unwrap_err();
+        assert_eq!(err.field, DpcField::Interface);
+        let err = build_wifi_dpc(&current, "wlan0", vec![]).unwrap_err();
+        assert_eq!(err.field, DpcField::Interface);
+    }
+}
@@ -0,0 +1,688 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Scripted test harness for the whole application.
+//!
+//! `AppHarness` feeds terminal events, ticks and IPC messages into the same
+//! handlers the event loop in `Application::run` uses, but synchronously and
+//! in a deterministic order. Outgoing IPC requests are captured instead of
+//! being sent to EVE and every step is rendered into a test terminal.
+//!
+//! ```ignore
+//! let mut h = AppHarness::new(120, 40);
+//! h.ipc_fixture("eve_ipc_message-102.json")
+//!     .keys("Ctrl+Right, Ctrl+Right, Enter")
+//!     .advance(Duration::from_secs(1));
+//! assert!(h.screen().contains("eth0"));
+//! ```
+
+use std::{path::PathBuf, rc::Rc, time::Duration};
+
+use anyhow::{anyhow, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use crate::{
+    application::{AppConfig, Application, TICK_PERIOD_MS},
+    events::Event,
+    ipc::message::{IpcMessage, Request},
+    model::model::Model,
+    terminal::TerminalWrapper,
+};
+
+pub struct AppHarness {
+    app: Application,
+    ipc_rx: UnboundedReceiver<IpcMessage>,
+    // requests sent by the application and not yet taken by the test
+    requests: Vec<(u64, Request)>,
+    last_request_id: Option<u64>,
+    // virtual time since the start that is not yet converted into ticks
+    pending_time: Duration,
+    quit: bool,
+}
+
+impl AppHarness {
+    /// Create the application with a test terminal of the given size. The IPC
+    /// connection is reported as established
+    pub fn new(width: u16, height: u16) -> Self {
+        let terminal = TerminalWrapper::open_test_terminal(width, height).unwrap();
+        let events = TerminalWrapper::scripted_stream(Vec::new());
+        let mut app = Application::new(AppConfig::for_tests(), terminal, events).unwrap();
+        let ipc_rx = app.connect_test_ipc();
+        let mut harness = Self {
+            app,
+            ipc_rx,
+            requests: Vec::new(),
+            last_request_id: None,
+            pending_time: Duration::ZERO,
+            quit: false,
+        };
+        harness.settle();
+        harness
+    }
+
+    /// Deliver a message as if it was received from EVE
+    pub fn ipc(&mut self, msg: IpcMessage) -> &mut Self {
+        self.app.handle_ipc_message(msg);
+        self.settle();
+        self
+    }
+
+    /// Deliver a recorded message from `test_data/ipc-tests`
+    pub fn ipc_fixture(&mut self, name: &str) -> &mut Self {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data")
+            .join("ipc-tests")
+            .join(name);
+        let content = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("Cannot read fixture {:?}: {}", path, e));
+        let msg = serde_json::from_str::<IpcMessage>(&content)
+            .unwrap_or_else(|e| panic!("Cannot parse fixture {:?}: {}", path, e));
+        self.ipc(msg)
+    }
+
+    /// Reply to the last request sent by the application
+    pub fn respond(&mut self, result: core::result::Result<String, String>) -> &mut Self {
+        let id = self
+            .last_request_id
+            .expect("No request was sent by the application");
+        self.ipc(IpcMessage::Response { result, id })
+    }
+
+    /// Send a key sequence, see [`parse_keys`] for the syntax
+    pub fn keys(&mut self, script: &str) -> &mut Self {
+        let keys = parse_keys(script).unwrap_or_else(|e| panic!("{}", e));
+        for key in keys {
+            self.app.handle_terminal_event(Event::Key(key));
+            self.settle();
+        }
+        self
+    }
+
+    /// Advance virtual time. One `Event::Tick` is delivered for every
+    /// `TICK_PERIOD_MS` that has passed
+    pub fn advance(&mut self, duration: Duration) -> &mut Self {
+        let period = Duration::from_millis(TICK_PERIOD_MS);
+        self.pending_time += duration;
+        while self.pending_time >= period {
+            self.pending_time -= period;
+            self.app.handle_tick();
+            self.settle();
+        }
+        self
+    }
+
+    /// Requests sent to EVE since the last call
+    pub fn take_requests(&mut self) -> Vec<Request> {
+        self.requests
+            .drain(..)
+            .map(|(_, request)| request)
+            .collect()
+    }
+
+    /// The last rendered screen. Trailing spaces are removed from every line
+    pub fn screen(&self) -> String {
+        let buffer = self.app.ui().terminal.test_backend().unwrap().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                let line = (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>();
+                line.trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn assert_screen_contains(&self, text: &str) -> &Self {
+        let screen = self.screen();
+        assert!(
+            screen.contains(text),
+            "Screen does not contain {:?}:\n{}",
+            text,
+            screen
+        );
+        self
+    }
+
+    pub fn model(&self) -> Rc<Model> {
+        self.app.model()
+    }
+
+    /// True if the application requested to quit
+    pub fn has_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Run the rest of a loop iteration: handle queued actions, collect sent
+    /// requests and redraw
+    fn settle(&mut self) {
+        self.quit |= self.app.handle_pending_actions();
+        while let Ok(msg) = self.ipc_rx.try_recv() {
+            if let IpcMessage::Request { request, id } = msg {
+                self.last_request_id = Some(id);
+                self.requests.push((id, request));
+            }
+        }
+        self.app.draw_ui(self.app.model()).unwrap();
+    }
+}
+
+/// Parse a comma separated key sequence e.g. "Ctrl+Right, Enter, type 10.0.0.5, Tab".
+/// Every item is either `type <text>` which types the text character by
+/// character or a key name with optional `Ctrl+`, `Alt+` and `Shift+` modifiers
+pub fn parse_keys(script: &str) -> Result<Vec<KeyEvent>> {
+    let mut keys = Vec::new();
+    for item in script.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        if let Some(text) = item.strip_prefix("type ") {
+            keys.extend(
+                text.chars()
+                    .map(|c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)),
+            );
+            continue;
+        }
+
+        let mut modifiers = KeyModifiers::NONE;
+        let mut name = item;
+        loop {
+            if let Some(rest) = name.strip_prefix("Ctrl+") {
+                modifiers |= KeyModifiers::CONTROL;
+                name = rest;
+            } else if let Some(rest) = name.strip_prefix("Alt+") {
+                modifiers |= KeyModifiers::ALT;
+                name = rest;
+            } else if let Some(rest) = name.strip_prefix("Shift+") {
+                modifiers |= KeyModifiers::SHIFT;
+                name = rest;
+            } else {
+                break;
+            }
+        }
+
+        let code = match name {
+            "Enter" => KeyCode::Enter,
+            "Esc" => KeyCode::Esc,
+            "Tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "Tab" => KeyCode::Tab,
+            "BackTab" => KeyCode::BackTab,
+            "Backspace" => KeyCode::Backspace,
+            "Delete" => KeyCode::Delete,
+            "Insert" => KeyCode::Insert,
+            "Up" => KeyCode::Up,
+            "Down" => KeyCode::Down,
+            "Left" => KeyCode::Left,
+            "Right" => KeyCode::Right,
+            "Home" => KeyCode::Home,
+            "End" => KeyCode::End,
+            "PageUp" => KeyCode::PageUp,
+            "PageDown" => KeyCode::PageDown,
+            "Space" => KeyCode::Char(' '),
+            _ if name.len() > 1 && name.starts_with('F') => {
+                let n = name[1..]
+                    .parse::<u8>()
+                    .map_err(|_| anyhow!("Unknown key: {}", item))?;
+                KeyCode::F(n)
+            }
+            _ if name.chars().count() == 1 => KeyCode::Char(name.chars().next().unwrap()),
+            _ => return Err(anyhow!("Unknown key: {}", item)),
+        };
+        keys.push(KeyEvent::new(code, modifiers));
+    }
+    Ok(keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::eve_types::{
+        AppInstanceStatus, DPCState, DevicePortConfigList, DhcpType, Inprogress, SwState,
+    };
+
+    // debug builds have an additional Home tab
+    const TO_NETWORK_TAB: &str = if cfg!(debug_assertions) {
+        "Ctrl+Right, Ctrl+Right"
+    } else {
+        "Ctrl+Right"
+    };
+
+    #[test]
+    fn test_parse_keys() {
+        let keys = parse_keys("Ctrl+Right, Enter, type 10.0.0.5/24, Shift+Tab, F12, s").unwrap();
+        assert_eq!(keys.len(), 16);
+        assert_eq!(
+            keys[0],
+            KeyEvent::new(KeyCode::Right, KeyModifiers::CONTROL)
+        );
+        assert_eq!(keys[1].code, KeyCode::Enter);
+        assert_eq!(keys[2].code, KeyCode::Char('1'));
+        assert_eq!(keys[12].code, KeyCode::Char('4'));
+        assert_eq!(keys[13].code, KeyCode::BackTab);
+        assert_eq!(keys[14].code, KeyCode::F(12));
+        assert_eq!(keys[15].code, KeyCode::Char('s'));
+
+        assert!(parse_keys("Ctrl+Rigth").is_err());
+    }
+
+    #[test]
+    fn test_ticks_update_clock() {
+        let mut h = AppHarness::new(80, 24);
+        h.assert_screen_contains("Clock");
+        h.advance(Duration::from_millis(400));
+        h.assert_screen_contains("Clock");
+        h.advance(Duration::from_millis(100));
+        assert!(!h.screen().contains("Clock"));
+    }
+
+    #[test]
+    fn test_ticks_read_traffic_counters() {
+        let mut h = AppHarness::new(120, 40);
+        h.ipc_fixture("eve_ipc_message-103.json") // NetworkStatus
+            .keys(TO_NETWORK_TAB)
+            .keys("Down")
+            .assert_screen_contains("Traffic    N/A");
+
+        // counters are read from test_data/sysfs
+        h.advance(Duration::from_secs(1))
+            .assert_screen_contains("carrier up, 1000 Mbit/s, full duplex, 2 carrier changes")
+            .assert_screen_contains("1.8 GB in 1523402 packets, 0 errors, 12 dropped")
+            .assert_screen_contains("RX 0 bit/s");
+
+        h.keys("Down")
+            .assert_screen_contains("carrier down, 1 carrier changes");
+    }
+
+    #[test]
+    fn test_edit_interface_sends_dpc() {
+        let mut h = AppHarness::new(100, 40);
+        h.ipc_fixture("eve_ipc_message-102.json") // DPCList
+            .ipc_fixture("eve_ipc_message-103.json") // NetworkStatus
+            .keys(TO_NETWORK_TAB)
+            .keys("Down, Enter")
+            .assert_screen_contains("◄  DHCP  ►");
+
+        // switch to static and replace the last octet of 192.168.1.10
+        h.keys("Right, Tab, End, Backspace, Backspace, type 5")
+            .assert_screen_contains("192.168.1.5");
+        assert!(h.take_requests().is_empty());
+
+        // mask, gw, ipv6, domain, dns, ntp, ok
+        h.keys("Tab, Tab, Tab, Tab, Tab, Tab, Tab, Enter")
+            .assert_screen_contains("Confirm port configuration changes")
+            .assert_screen_contains("DHCP → static")
+            .assert_screen_contains("+ 192.168.1.5/24");
+        assert!(h.take_requests().is_empty());
+
+        // apply
+        h.keys("Enter")
+            .assert_screen_contains("Port configuration verification")
+            .assert_screen_contains("waiting for EVE");
+        h.keys("Esc").assert_screen_contains("Network Interfaces");
+        assert!(!h.screen().contains("Static"));
+
+        let requests = h.take_requests();
+        assert_eq!(requests.len(), 1);
+        let Request::SetDPC(dpc) = &requests[0] else {
+            panic!("Unexpected request {:?}", requests[0]);
+        };
+        assert_eq!(dpc.key, "manual");
+        let port = dpc.get_port_by_name("eth0").unwrap();
+        assert_eq!(port.dhcp_config.dhcp, DhcpType::Static);
+        assert_eq!(
+            port.dhcp_config.addr_subnet,
+            Some("192.168.1.5/24".parse().unwrap())
+        );
+        // other ports are not touched
+        let eth1 = dpc.get_port_by_name("eth1").unwrap();
+        assert_eq!(eth1.dhcp_config.dhcp, DhcpType::Client);
+
+        h.respond(Ok(String::new()));
+        assert!(h.model().borrow().ipc_connected);
+        assert!(!h.has_quit());
+    }
+
+    #[test]
+    fn test_invalid_interface_keeps_dialog_open() {
+        let mut h = AppHarness::new(100, 40);
+        h.ipc_fixture("eve_ipc_message-102.json") // DPCList
+            .ipc_fixture("eve_ipc_message-103.json") // NetworkStatus
+            .keys(TO_NETWORK_TAB)
+            .keys("Down, Enter");
+
+        // 192.168.1.0/24 is the network address
+        h.keys("Right, Tab, End, Backspace, Backspace, type 0")
+            .keys("Tab, Tab, Tab, Tab, Tab, Tab, Tab, Enter")
+            .assert_screen_contains("Static")
+            .assert_screen_contains("network address");
+        assert!(h.take_requests().is_empty());
+
+        // the focus is back on IPv4, fix it and submit again
+        h.keys("End, Backspace, type 7")
+            .keys("Tab, Tab, Tab, Tab, Tab, Tab, Tab, Enter")
+            .assert_screen_contains("+ 192.168.1.7/24");
+        h.keys("Enter")
+            .assert_screen_contains("Port configuration verification");
+        let requests = h.take_requests();
+        assert_eq!(requests.len(), 1);
+        let Request::SetDPC(dpc) = &requests[0] else {
+            panic!("Unexpected request {:?}", requests[0]);
+        };
+        assert_eq!(
+            dpc.get_port_by_name("eth0")
+                .unwrap()
+                .dhcp_config
+                .addr_subnet,
+            Some("192.168.1.7/24".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_last_management_port_keeps_dialog_open() {
+        let mut h = AppHarness::new(100, 40);
+        h.ipc_fixture("eve_ipc_message-102.json") // DPCList
+            .ipc_fixture("eve_ipc_message-103.json"); // NetworkStatus
+        {
+            let model = h.model();
+            let mut model = model.borrow_mut();
+            let dpcs = model.dpc_list.as_mut().unwrap();
+            let dpc = &mut dpcs.port_config_list.as_mut().unwrap()[0];
+            dpc.get_port_by_name_mut("eth1").unwrap().is_mgmt = false;
+        }
+
+        // Port settings: App only, cost, mtu, alias, ok
+        h.keys(TO_NETWORK_TAB)
+            .keys("Down, Enter, Ctrl+Right, Ctrl+Right, Right")
+            .keys("Tab, Tab, Tab, Tab, Enter")
+            .assert_screen_contains("╔eth0")
+            .assert_screen_contains("Usage: at least one port must be used");
+        assert!(!h.screen().contains("Confirm port configuration changes"));
+        assert!(h.take_requests().is_empty());
+
+        // the focus is on the usage, back to Management closes the unchanged dialog
+        h.keys("Left, Tab, Tab, Tab, Tab, Enter")
+            .assert_screen_contains("Network Interfaces");
+        assert!(!h.screen().contains("╔eth0"));
+        assert!(h.take_requests().is_empty());
+    }
+
+    #[test]
+    fn test_cancel_dpc_preview() {
+        let mut h = AppHarness::new(100, 40);
+        h.ipc_fixture("eve_ipc_message-102.json") // DPCList
+            .ipc_fixture("eve_ipc_message-103.json") // NetworkStatus
+            .keys(TO_NETWORK_TAB)
+            .keys("Down, Enter")
+            .keys("Right, Tab, End, Backspace, Backspace, type 5")
+            .keys("Tab, Tab, Tab, Tab, Tab, Tab, Tab, Enter")
+            .assert_screen_contains("Confirm port configuration changes");
+
+        h.keys("Esc").assert_screen_contains("Network Interfaces");
+        assert!(!h.screen().contains("Confirm port configuration changes"));
+        assert!(h.take_requests().is_empty());
+    }
+
+    #[test]
+    fn test_dpc_verification_fallback() {
+        let mut h = AppHarness::new(120, 40);
+        h.ipc_fixture("eve_ipc_message-102.json") // DPCList
+            .ipc_fixture("eve_ipc_message-103.json") // NetworkStatus
+            .keys(TO_NETWORK_TAB)
+            .keys("Down, Enter")
+            .keys("Right, Tab, End, Backspace, Backspace, type 5")
+            .keys("Tab, Tab, Tab, Tab, Tab, Tab, Tab, Enter, Enter");
+        let requests = h.take_requests();
+        let Request::SetDPC(sent) = &requests[0] else {
+            panic!("Unexpected request {:?}", requests[0]);
+        };
+
+        // EVE tests the new DPC first
+        let current = h.model().borrow().get_current_dpc().cloned().unwrap();
+        let mut testing = sent.clone();
+        testing.state = DPCState::IPDNSWait;
+        h.ipc(IpcMessage::DPCList(DevicePortConfigList {
+            current_index: 0,
+            port_config_list: Some(vec![testing, current.clone()]),
+        }))
+        .assert_screen_contains("waiting for IP and DNS");
+
+        // then falls back to the previous one
+        let mut failed = sent.clone();
+        failed.state = DPCState::Fail;
+        failed.test_results.last_error = "not enough working ports: eth0: no IP address".into();
+        h.ipc(IpcMessage::DPCList(DevicePortConfigList {
+            current_index: 1,
+            port_config_list: Some(vec![failed, current]),
+        }))
+        .assert_screen_contains("failed")
+        .assert_screen_contains("went back to 'zedagent'")
+        .assert_screen_contains("no IP address");
+
+        // close, edit
+        h.keys("Tab, Enter")
+            .assert_screen_contains("╔eth0")
+            .assert_screen_contains("◄  DHCP  ►");
+        assert!(h.take_requests().is_empty());
+    }
+
+    #[test]
+    fn test_dpc_rejected() {
+        let mut h = AppHarness::new(120, 40);
+        h.ipc_fixture("eve_ipc_message-102.json") // DPCList
+            .ipc_fixture("eve_ipc_message-103.json") // NetworkStatus
+            .keys(TO_NETWORK_TAB)
+            .keys("Down, Enter")
+            .keys("Right, Tab, End, Backspace, Backspace, type 5")
+            .keys("Tab, Tab, Tab, Tab, Tab, Tab, Tab, Enter, Enter")
+            .assert_screen_contains("waiting for EVE");
+        assert_eq!(h.take_requests().len(), 1);
+
+        h.respond(Err("invalid port config".to_string()))
+            .assert_screen_contains("rejected")
+            .assert_screen_contains("did not accept")
+            .assert_screen_contains("invalid port config");
+
+        // there is nothing to revert, the previous DPC is still in use
+        h.keys("Tab, Tab, Enter")
+            .assert_screen_contains("There is nothing to change");
+        assert!(h.take_requests().is_empty());
+    }
+
+    #[test]
+    fn test_dpc_not_sent_while_disconnected() {
+        let mut h = AppHarness::new(120, 40);
+        h.ipc_fixture("eve_ipc_message-102.json") // DPCList
+            .ipc_fixture("eve_ipc_message-103.json") // NetworkStatus
+            .keys(TO_NETWORK_TAB)
+            .keys("Down, Enter")
+            .keys("Right, Tab, End, Backspace, Backspace, type 5")
+            .keys("Tab, Tab, Tab, Tab, Tab, Tab, Tab, Enter")
+            .assert_screen_contains("Confirm port configuration changes");
+
+        // the connection is lost before the popup is shown
+        h.model().borrow_mut().ipc_connected = false;
+        h.keys("Enter")
+            .assert_screen_contains("EVE is not connected");
+        assert!(h.take_requests().is_empty());
+        assert!(h.model().borrow().dpc_verification.is_none());
+    }
+
+    #[test]
+    fn test_diagnostics_form() {
+        let mut h = AppHarness::new(100, 40);
+        h.ipc_fixture("eve_ipc_message-103.json") // NetworkStatus
+            .keys(TO_NETWORK_TAB)
+            .keys("Ctrl+Right")
+            .assert_screen_contains("Network diagnostics");
+
+        // the target is required and must not look like an option
+        h.keys("Tab, Tab, Tab, Enter")
+            .assert_screen_contains("enter a host name or IP address");
+        h.keys("type -f 10.0.0.1, Enter")
+            .assert_screen_contains("invalid host name");
+
+        // the port is shown for TCP connect only
+        h.keys("Shift+Tab, Shift+Tab, Right")
+            .assert_screen_contains("TCP connect")
+            .assert_screen_contains("Port");
+
+        // configured DNS servers can be chosen for DNS lookup
+        h.keys("Right, Tab, Right, Right")
+            .assert_screen_contains("192.168.1.10 (eth0)");
+        h.keys("Tab").assert_screen_contains("192.168.1.3 (eth0)");
+        // the last choice is a custom server
+        h.keys("Left")
+            .assert_screen_contains("custom")
+            .assert_screen_contains("DNS server");
+        assert!(h.model().borrow().diagnostics.lines.is_empty());
+    }
+
+    #[test]
+    fn test_import_proxy_certificate() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_data/certs/proxy-ca.pem");
+        let mut h = AppHarness::new(100, 40);
+        h.ipc_fixture("eve_ipc_message-102.json") // DPCList
+            .ipc_fixture("eve_ipc_message-103.json") // NetworkStatus
+            .keys(TO_NETWORK_TAB)
+            .keys("Down, Enter, Ctrl+Right")
+            .assert_screen_contains("no certificates");
+
+        h.keys("Tab, type /nonexistent.pem, Tab, Enter")
+            .assert_screen_contains("cannot read /nonexistent.pem");
+
+        // replace the path and import
+        h.keys("Shift+Tab, Home");
+        for _ in 0.."/nonexistent.pem".len() {
+            h.keys("Delete");
+        }
+        h.keys(&format!("type {}, Tab, Enter", path))
+            .assert_screen_contains("1/1 Example Corp Proxy CA")
+            .assert_screen_contains("Issuer   C=US, O=Example Corp")
+            .assert_screen_contains("A6:78:FB:76:70:5F:A5:D7");
+
+        // the same certificate is not added twice
+        h.keys("Enter")
+            .assert_screen_contains("already imported")
+            .assert_screen_contains("1/1 Example Corp Proxy CA");
+
+        // remove it, the focus moves back to the path, then import again
+        h.keys("Tab, Enter")
+            .assert_screen_contains("no certificates");
+        h.keys("Tab, Enter")
+            .assert_screen_contains("1/1 Example Corp Proxy CA");
+
+        // import, remove, ok
+        h.keys("Tab, Tab, Enter")
+            .assert_screen_contains("Confirm port configuration changes")
+            .assert_screen_contains("+ 1 CA certificates");
+        h.keys("Enter");
+        let requests = h.take_requests();
+        let Request::SetDPC(dpc) = &requests[0] else {
+            panic!("Unexpected request {:?}", requests[0]);
+        };
+        let port = dpc.get_port_by_name("eth0").unwrap();
+        let pem = port.proxy_config.proxy_cert_pem.as_ref().unwrap();
+        assert_eq!(pem.len(), 1);
+        assert_eq!(pem[0], std::fs::read(path).unwrap());
+    }
+
+    // the spin boxes of the Diagnostics page take Ctrl+Left/Right, move the
+    // focus to the target input before switching to the next tab
+    const DIAGNOSTICS_TO_APPS_TAB: &str = "Ctrl+Right, Tab, Tab, Ctrl+Right";
+
+    /// The AppStatus of cs_nginx-qemu-2 changed by `update`
+    fn app_status(update: impl FnOnce(&mut AppInstanceStatus)) -> IpcMessage {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_data/ipc-tests/eve_ipc_message-122.json"
+        );
+        let content = std::fs::read_to_string(path).unwrap();
+        let IpcMessage::AppStatus(mut status) = serde_json::from_str(&content).unwrap() else {
+            panic!("{} is not an AppStatus", path);
+        };
+        update(&mut status);
+        IpcMessage::AppStatus(status)
+    }
+
+    #[test]
+    fn test_restart_app() {
+        let mut h = AppHarness::new(120, 40);
+        h.ipc(app_status(|_| {}))
+            .keys(TO_NETWORK_TAB)
+            .keys(DIAGNOSTICS_TO_APPS_TAB)
+            .keys("Down")
+            .assert_screen_contains("cs_nginx-qemu-2 Details");
+
+        // cancel
+        h.keys("r")
+            .assert_screen_contains("Confirm app instance command")
+            .assert_screen_contains("Restart cs_nginx-qemu-2?");
+        h.keys("Esc");
+        assert!(h.take_requests().is_empty());
+        assert!(!h.screen().contains("Confirm app instance command"));
+
+        h.keys("r, Enter")
+            .assert_screen_contains("Command    restart requested");
+        let requests = h.take_requests();
+        assert_eq!(requests.len(), 1);
+        let Request::RestartApp(uuid) = &requests[0] else {
+            panic!("Unexpected request {:?}", requests[0]);
+        };
+        assert_eq!(uuid.to_string(), "5132a812-3eb5-4b14-bc20-3e7c953a9e57");
+
+        h.respond(Ok(String::new()))
+            .assert_screen_contains("Command    restart accepted");
+        h.ipc(app_status(|status| {
+            status.restart_inprogress = Inprogress::BringDown;
+            status.state = SwState::Halting;
+        }))
+        .assert_screen_contains("Command    restart: bringing down")
+        .assert_screen_contains("Restart    bringing down");
+        h.ipc(app_status(|status| status.state = SwState::Running))
+            .assert_screen_contains("Command    restart done");
+    }
+
+    #[test]
+    fn test_start_halted_app() {
+        let mut h = AppHarness::new(120, 40);
+        h.ipc(app_status(|status| status.state = SwState::Halted))
+            .keys(TO_NETWORK_TAB)
+            .keys(DIAGNOSTICS_TO_APPS_TAB)
+            .keys("Down, t")
+            .assert_screen_contains("Start cs_nginx-qemu-2?");
+        // the focus wraps around the buttons
+        h.keys("Tab, Tab, Enter");
+        assert!(matches!(h.take_requests()[..], [Request::StartApp(_)]));
+    }
+
+    #[test]
+    fn test_app_command_refused() {
+        let mut h = AppHarness::new(120, 40);
+        h.ipc(app_status(|_| {}))
+            .keys(TO_NETWORK_TAB)
+            .keys(DIAGNOSTICS_TO_APPS_TAB)
+            .keys("Down, r, Enter")
+            .assert_screen_contains("Command    restart requested");
+        assert_eq!(h.take_requests().len(), 1);
+
+        h.respond(Err("app is not running".to_string()))
+            .assert_screen_contains("did not accept the restart")
+            .assert_screen_contains("app is not running");
+        h.keys("Enter")
+            .assert_screen_contains("Command    restart failed: app is not running");
+    }
+
+    #[test]
+    fn test_app_command_not_sent_while_disconnected() {
+        let mut h = AppHarness::new(120, 40);
+        h.ipc(app_status(|_| {}))
+            .keys(TO_NETWORK_TAB)
+            .keys(DIAGNOSTICS_TO_APPS_TAB)
+            .keys("Down, r")
+            .assert_screen_contains("Restart cs_nginx-qemu-2?");
+
+        h.model().borrow_mut().ipc_connected = false;
+        h.keys("Enter")
+            .assert_screen_contains("EVE is not connected");
+        assert!(h.take_requests().is_empty());
+        assert!(h.model().borrow().app_commands.is_empty());
+    }
+}
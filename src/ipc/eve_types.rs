@@ -300,6 +300,7 @@ pub struct NetworkPortStatus {
 /// 2. If network_proxy_enable is false, then one of the proxies from the proxies list is used
 /// 3. Only one entry per proxy type  is possible in the proxies list
 /// 4. If [ProxyConfig::pacfile] is used then proxy configuration is taken from the .pac file
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "PascalCase", default)]
 pub struct ProxyConfig {
@@ -311,6 +312,8 @@ pub struct ProxyConfig {
     pub network_proxy_url: String,
     #[serde(rename = "WpadURL")]
     pub wpad_url: String,
+    /// PEM encoded proxy CA certificates, one per entry
+    #[serde_as(as = "Option<Vec<Base64>>")]
     #[serde(rename = "pubsub-large-ProxyCertPEM")]
     pub proxy_cert_pem: Option<Vec<Vec<u8>>>,
 }
@@ -318,10 +321,10 @@ pub struct ProxyConfig {
 #[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "PascalCase", default)]
 pub struct L2LinkConfig {
-    l2_type: L2LinkType,
+    pub l2_type: L2LinkType,
     #[serde(rename = "VLAN")]
-    vlan: Option<VLANConfig>,
-    bond: Option<BondConfig>,
+    pub vlan: Option<VLANConfig>,
+    pub bond: Option<BondConfig>,
 }
 
 #[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
@@ -350,11 +353,11 @@ impl TestResults {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all = "PascalCase")]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
+#[serde(rename_all = "PascalCase", default)]
 pub struct WirelessStatus {
-    w_type: WirelessType,
-    cellular: WwanNetworkStatus,
+    pub w_type: WirelessType,
+    pub cellular: WwanNetworkStatus,
 }
 
 #[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
@@ -422,12 +425,16 @@ pub struct CipherContext {
 }
 
 #[repr(u8)]
-#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq, Clone, Default)]
+#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq, Clone, Default, Display)]
 pub enum WifiKeySchemeType {
     #[default]
+    #[strum(to_string = "open")]
     KeySchemeNone = 0,
+    #[strum(to_string = "WPA-PSK")]
     KeySchemeWpaPsk = 1,
+    #[strum(to_string = "WPA-EAP")]
     KeySchemeWpaEap = 2,
+    #[strum(to_string = "other")]
     KeySchemeOther = 3,
 }
 
@@ -452,7 +459,9 @@ pub struct WwanNetworkStatus {
     pub probe_error: String,
     pub current_provider: WwanProvider,
     pub visible_providers: Option<Vec<WwanProvider>>,
+    #[serde(rename = "CurrentRATs")]
     pub current_rats: Option<Vec<WwanRAT>>,
+    // unix timestamp in seconds, 0 if not connected
     pub connected_at: u64,
     #[serde(rename = "IPSettings")]
     pub ip_settings: WwanIPSettings,
@@ -488,35 +497,148 @@ pub struct WwanIPSettings {
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "PascalCase", default)]
 pub struct WwanPhysAddrs {
-    // Define fields here
+    // Interface name, e.g. wwan0
+    pub interface: String,
+    // USB address in the format "<BUS>:[<PORT>]", with nested ports separated by dots
+    #[serde(rename = "USB")]
+    pub usb: String,
+    // PCI address in the long format
+    #[serde(rename = "PCI")]
+    pub pci: String,
+    // Device file, e.g. /dev/cdc-wdm0
+    pub dev: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "PascalCase", default)]
 pub struct WwanCellModule {
-    // Define fields here
+    pub name: String,
+    #[serde(rename = "IMEI")]
+    pub imei: String,
+    pub model: String,
+    pub manufacturer: String,
+    // firmware version
+    pub revision: String,
+    // "qmi" or "mbim"
+    pub control_protocol: String,
+    // "connected", "radio-off", "online", "online-and-connected" or "unrecognized"
+    pub op_mode: String,
+}
+
+#[repr(u8)]
+#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq, Clone, Default, Display)]
+pub enum WwanSimType {
+    #[default]
+    #[strum(to_string = "unspecified")]
+    Unspecified = 0,
+    #[strum(to_string = "physical")]
+    Physical = 1,
+    #[strum(to_string = "embedded")]
+    Embedded = 2,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "PascalCase", default)]
 pub struct WwanSimCard {
-    // Define fields here
+    pub name: String,
+    // SIM slot number, starting from 1
+    pub slot_number: u8,
+    // true if the modem uses this slot
+    pub slot_activated: bool,
+    // Integrated Circuit Card Identifier
+    #[serde(rename = "ICCID")]
+    pub iccid: String,
+    // International Mobile Subscriber Identity
+    #[serde(rename = "IMSI")]
+    pub imsi: String,
+    #[serde(rename = "Type")]
+    pub sim_type: WwanSimType,
+    // e.g. "present", "absent", "inactive", "error"
+    pub state: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "PascalCase", default)]
 pub struct WwanProvider {
-    // Define fields here
+    // Public Land Mobile Network identifier, MCC-MNC
+    #[serde(rename = "PLMN")]
+    pub plmn: String,
+    // Name of the provider
+    pub description: String,
+    // true if this provider is currently serving the modem
+    pub current_serving: bool,
+    pub roaming: bool,
+    pub forbidden: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
-#[serde(rename_all = "PascalCase")]
+#[serde(rename_all = "PascalCase", default)]
+pub struct WwanSignalInfo {
+    // Received signal strength indicator in dBm
+    #[serde(rename = "RSSI")]
+    pub rssi: i32,
+    // Reference Signal Received Quality in dB
+    #[serde(rename = "RSRQ")]
+    pub rsrq: i32,
+    // Reference Signal Receive Power in dBm
+    #[serde(rename = "RSRP")]
+    pub rsrp: i32,
+    // Signal-to-Noise Ratio in dB
+    #[serde(rename = "SNR")]
+    pub snr: i32,
+}
+
+impl WwanSignalInfo {
+    /// The modem reports values it cannot measure as the integer limits
+    pub fn value(v: i32) -> Option<i32> {
+        (v != i32::MAX && v != i32::MIN).then_some(v)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct WwanPacketStats {
+    pub rx_bytes: u64,
+    pub rx_packets: u64,
+    pub rx_drops: u64,
+    pub tx_bytes: u64,
+    pub tx_packets: u64,
+    pub tx_drops: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct WwanNetworkMetrics {
+    pub logical_label: String,
+    pub phys_addrs: WwanPhysAddrs,
+    pub packet_stats: WwanPacketStats,
+    pub signal_info: WwanSignalInfo,
+}
+
+/// Metrics of all cellular modems published by the wwan microservice
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct WwanMetrics {
+    pub networks: Option<Vec<WwanNetworkMetrics>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, Display)]
 pub enum WwanRAT {
     #[default]
+    #[serde(rename = "")]
+    #[strum(to_string = "unspecified")]
     WwanRATUnspecified,
+    #[serde(rename = "gsm")]
+    #[strum(to_string = "GSM")]
     WwanRATGSM,
+    #[serde(rename = "umts")]
+    #[strum(to_string = "UMTS")]
     WwanRATUMTS,
+    #[serde(rename = "lte")]
+    #[strum(to_string = "LTE")]
     WwanRATLTE,
+    #[serde(rename = "5gnr")]
+    #[strum(to_string = "5G NR")]
     WwanRAT5GNR,
 }
 
@@ -531,9 +653,9 @@ pub struct CellNetPortConfig {
 #[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "PascalCase", default)]
 pub struct WwanProbe {
-    disable: bool,
+    pub disable: bool,
     // IP/FQDN address to periodically probe to determine connection status.
-    user_defined_probe: ConnectivityProbe,
+    pub user_defined_probe: ConnectivityProbe,
 }
 
 #[repr(u8)]
@@ -556,8 +678,9 @@ pub struct ConnectivityProbe {
     pub probe_port: u16,
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Default)]
 pub enum WwanAuthProtocol {
+    #[default]
     None,
     Pap,
     Chap,
@@ -594,14 +717,14 @@ where
     serializer.serialize_str(&s)
 }
 
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "PascalCase")]
 pub struct WwanCleartextCredentials {
-    username: String,
-    password: String,
+    pub username: String,
+    pub password: String,
 }
 
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "PascalCase")]
 pub struct CellularAccessPoint {
     // 0 - unspecified (apply to currently activated or the only available)
@@ -666,30 +789,39 @@ pub enum L2LinkType {
 #[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "PascalCase", default)]
 pub struct VLANConfig {
-    parent_port: String,
+    // logical label of the parent port
+    pub parent_port: String,
     #[serde(rename = "ID")]
-    id: u16,
+    pub id: u16,
 }
 
 #[repr(u8)]
-#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq, Clone, Default)]
+#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq, Clone, Default, Display)]
 pub enum BondMode {
     // BondModeUnspecified : default is Round-Robin
     #[default]
+    #[strum(to_string = "default")]
     BondModeUnspecified = 0,
     // BondModeBalanceRR : Round-Robin
+    #[strum(to_string = "balance-rr")]
     BondModeBalanceRR = 1,
     // BondModeActiveBackup : Active/Backup
+    #[strum(to_string = "active-backup")]
     BondModeActiveBackup = 2,
     // BondModeBalanceXOR : select slave for a packet using a hash function
+    #[strum(to_string = "balance-xor")]
     BondModeBalanceXOR = 3,
     // BondModeBroadcast : send every packet on all slaves
+    #[strum(to_string = "broadcast")]
     BondModeBroadcast = 4,
     // BondMode802Dot3AD : IEEE 802.3ad Dynamic link aggregation
+    #[strum(to_string = "802.3ad")]
     BondMode802Dot3AD = 5,
     // BondModeBalanceTLB : Adaptive transmit load balancing
+    #[strum(to_string = "balance-tlb")]
     BondModeBalanceTLB = 6,
     // BondModeBalanceALB : Adaptive load balancing
+    #[strum(to_string = "balance-alb")]
     BondModeBalanceALB = 7,
 }
 
@@ -719,16 +851,19 @@ pub struct BondMIIMonitor {
 pub struct BondArpMonitor {
     pub enabled: bool,
     #[serde(rename = "IPTargets")]
-    pub ip_targets: Option<String>,
+    pub ip_targets: Option<Vec<IpAddr>>,
     pub interval: u32,
 }
 
 #[repr(u8)]
-#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq, Clone, Default)]
+#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq, Clone, Default, Display)]
 pub enum LacpRate {
     #[default]
+    #[strum(to_string = "default")]
     LacpRateUnspecified = 0,
+    #[strum(to_string = "slow")]
     LacpRateSlow = 1,
+    #[strum(to_string = "fast")]
     LacpRateFast = 2,
 }
 
@@ -755,17 +890,26 @@ pub enum DhcpType {
 
 // DPCState enum
 #[repr(u8)]
-#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq, Clone, Default)]
+#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq, Clone, Default, Display)]
 pub enum DPCState {
     #[default]
+    #[strum(to_string = "none")]
     None = 0,
+    #[strum(to_string = "failed")]
     Fail = 1,
+    #[strum(to_string = "failed with IP and DNS")]
     FailWithIPAndDNS = 2,
+    #[strum(to_string = "success")]
     Success = 3,
+    #[strum(to_string = "waiting for IP and DNS")]
     IPDNSWait = 4,
+    #[strum(to_string = "waiting for PCI")]
     PCIWait = 5,
+    #[strum(to_string = "waiting for interfaces")]
     IntfWait = 6,
+    #[strum(to_string = "waiting for controller")]
     RemoteWait = 7,
+    #[strum(to_string = "testing")]
     AsyncWait = 8,
 }
 
@@ -784,7 +928,7 @@ pub enum NetworkType {
 
 // NetworkProxyType enum
 #[repr(u8)]
-#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq, Clone, Default)]
+#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq, Clone, Default, Display)]
 pub enum NetworkProxyType {
     HTTP = 0,
     HTTPS = 1,
@@ -887,6 +1031,14 @@ impl DevicePortConfigList {
             .and_then(|list| list.iter().find(|dpc| dpc.key == key))
     }
 
+    /// Several DPCs may share the key, the time priority tells them apart
+    pub fn get_dpc(&self, key: &str, time_priority: &DateTime<Utc>) -> Option<&DevicePortConfig> {
+        self.port_config_list.as_ref().and_then(|list| {
+            list.iter()
+                .find(|dpc| dpc.key == key && dpc.time_priority == *time_priority)
+        })
+    }
+
     pub fn get_current_dpc_ref(&self) -> Option<&DevicePortConfig> {
         self.port_config_list
             .as_ref()
@@ -906,6 +1058,12 @@ impl DevicePortConfigList {
     pub fn get_current_dpc_cloned(&self) -> Option<DevicePortConfig> {
         self.get_current_dpc_ref().map(|dpc| dpc.clone())
     }
+
+    /// DPCs are identified by the key and the time priority
+    pub fn is_current(&self, dpc: &DevicePortConfig) -> bool {
+        self.get_current_dpc_ref()
+            .is_some_and(|c| c.key == dpc.key && c.time_priority == dpc.time_priority)
+    }
 }
 
 // NetworkPortConfig struct
@@ -965,6 +1123,7 @@ impl NetworkPortConfig {
     pub fn into_static(
         mut self,
         addr_subnet: IpNet,
+        network_type: NetworkType,
         gateway: String,
         domain_name: String,
         ntp_server: Option<Vec<String>>,
@@ -972,6 +1131,7 @@ impl NetworkPortConfig {
     ) -> Self {
         self.dhcp_config.dhcp = DhcpType::Static;
         self.dhcp_config.addr_subnet = Some(addr_subnet);
+        self.dhcp_config.dhcp_type = network_type;
         self.dhcp_config.gateway = gateway;
         self.dhcp_config.domain_name = domain_name;
         self.dhcp_config.ntp_servers = ntp_server;
@@ -992,6 +1152,7 @@ impl NetworkPortConfig {
     pub fn to_static(
         &mut self,
         addr_subnet: IpNet,
+        network_type: NetworkType,
         gateway: String,
         domain_name: String,
         ntp_server: Option<Vec<String>>,
@@ -999,6 +1160,7 @@ impl NetworkPortConfig {
     ) {
         self.dhcp_config.dhcp = DhcpType::Static;
         self.dhcp_config.addr_subnet = Some(addr_subnet);
+        self.dhcp_config.dhcp_type = network_type;
         self.dhcp_config.gateway = gateway;
         self.dhcp_config.domain_name = domain_name;
         self.dhcp_config.ntp_servers = ntp_server;
@@ -1080,7 +1242,8 @@ pub struct ErrorDescription {
 }
 
 #[repr(i32)]
-#[derive(Serialize_repr, Deserialize_repr, PartialEq, Debug, Clone, Default)]
+#[derive(Serialize_repr, Deserialize_repr, PartialEq, Debug, Clone, Default, Display)]
+#[strum(serialize_all = "lowercase")]
 pub enum ErrorSeverity {
     #[default]
     Unspecified = 0,
@@ -1220,19 +1383,19 @@ pub struct AppInstanceStatus {
     pub volume_ref_status_list: Vec<VolumeRefStatus>,
     #[serde(skip)]
     pub app_net_adapters: Vec<AppNetAdapterStatus>,
-    pub boot_time: String, // Replace with a suitable time type
+    pub boot_time: DateTime<Utc>,
     #[serde(skip)]
     pub io_adapter_list: Vec<IoAdapter>,
     pub restart_inprogress: Inprogress,
-    pub restart_started_at: String, // Replace with a suitable time type
+    pub restart_started_at: DateTime<Utc>,
     pub purge_inprogress: Inprogress,
-    pub purge_started_at: String, // Replace with a suitable time type
+    pub purge_started_at: DateTime<Utc>,
     pub state: SwState,
     pub missing_network: bool,
     pub missing_memory: bool,
     #[serde(flatten)]
     pub error_and_time_with_source: ErrorAndTimeWithSource,
-    pub start_time: String, // Replace with a suitable time type
+    pub start_time: DateTime<Utc>,
     #[serde(skip)]
     pub snap_status: SnapshottingStatus,
     pub mem_overhead: u64,
@@ -1317,7 +1480,7 @@ pub struct VmConfig {
 }
 
 #[repr(u8)]
-#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq, Clone, Default)]
+#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq, Clone, Default, Display)]
 pub enum VmMode {
     #[default]
     PV = 0,
@@ -1355,9 +1518,10 @@ pub struct VolumeRefStatus {
 }
 
 #[repr(i32)]
-#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq, Clone, Default)]
+#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq, Clone, Default, Display)]
 pub enum Format {
     #[default]
+    #[strum(to_string = "unknown")]
     FmtUnknown = 0,
     RAW = 1,
     QCOW = 2,
@@ -1392,13 +1556,18 @@ pub struct ErrorAndTimeWithSource {
 }
 
 #[repr(u8)]
-#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq, Clone, Default)]
+#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq, Clone, Default, Display)]
 pub enum Inprogress {
     #[default]
+    #[strum(to_string = "not in progress")]
     NotInprogress = 0,
+    #[strum(to_string = "downloading and verifying")]
     DownloadAndVerify = 1,
+    #[strum(to_string = "bringing down")]
     BringDown = 2,
+    #[strum(to_string = "recreating volumes")]
     RecreateVolumes = 3,
+    #[strum(to_string = "bringing up")]
     BringUp = 4,
 }
 
@@ -11,6 +11,7 @@ use bytes::BytesMut;
 use log::error;
 use serde::Deserialize;
 use serde::Serialize;
+use uuid::Uuid;
 
 use super::eve_types::AppInstanceStatus;
 use super::eve_types::AppInstanceSummary;
@@ -26,6 +27,7 @@ use super::eve_types::LedBlinkCounter;
 use super::eve_types::PhysicalIOAdapterList;
 use super::eve_types::TpmLogs;
 use super::eve_types::TuiEveConfig;
+use super::eve_types::WwanMetrics;
 use super::eve_types::ZedAgentStatus;
 
 pub type RequestId = u64;
@@ -45,6 +47,10 @@ static MSG_INDEX: AtomicIdGenerator = AtomicIdGenerator(AtomicU64::new(1));
 pub enum Request {
     SetDPC(DevicePortConfig),
     SetServer(String),
+    RestartApp(Uuid),
+    StopApp(Uuid),
+    StartApp(Uuid),
+    PurgeApp(Uuid),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -71,6 +77,7 @@ pub enum IpcMessage {
     ZedAgentStatus(ZedAgentStatus),
     TUIConfig(TuiEveConfig),
     TpmLogs(TpmLogs),
+    WwanMetrics(WwanMetrics),
     Response {
         #[serde(flatten)]
         result: core::result::Result<String, String>,
@@ -15,6 +15,7 @@ use eve_types::LedBlinkCounter;
 use eve_types::PhysicalIOAdapterList;
 use eve_types::TpmLogs;
 use eve_types::TuiEveConfig;
+use eve_types::WwanMetrics;
 use eve_types::ZedAgentStatus;
 use format_serde_error::SerdeError;
 use std::path::PathBuf;
@@ -48,6 +49,7 @@ enum TestMessageType {
     Response,
     TUIConfig,
     TpmLogs,
+    WwanMetrics,
     Unknown(String),
 }
 
@@ -69,6 +71,7 @@ impl From<&str> for TestMessageType {
             "Response" => TestMessageType::Response,
             "TUIConfig" => TestMessageType::TUIConfig,
             "TpmLogs" => TestMessageType::TpmLogs,
+            "WwanMetrics" => TestMessageType::WwanMetrics,
             _ => TestMessageType::Unknown(s.to_string()),
         }
     }
@@ -169,6 +172,10 @@ fn test_from_device_files() -> Result<()> {
                 let _ = serde_json::from_str::<TpmLogs>(&data)
                     .map_err(|err| SerdeError::new(data.to_string(), err))?;
             }
+            TestMessageType::WwanMetrics => {
+                let _ = serde_json::from_str::<WwanMetrics>(&data)
+                    .map_err(|err| SerdeError::new(data.to_string(), err))?;
+            }
             TestMessageType::Response => {}
             TestMessageType::Unknown(s) => {
                 println!("Unknown message type: {}", s);
@@ -0,0 +1,129 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{path::Path, time::Duration};
+
+use anyhow::Result;
+use rmesg::entry::{Entry, LogLevel};
+
+/// Parse a line of a kmsg fixture file. Two formats are accepted:
+/// - /dev/kmsg records: `<prio>,<seq>,<usec>,<flags>[,...];<message>`
+/// - `dmesg` output: `[<seconds>] <message>`
+///
+/// Anything else is taken as a plain message. Empty lines are skipped
+pub fn parse_kmsg_line(line: &str) -> Option<Entry> {
+    let line = line.trim_end();
+    if line.is_empty() {
+        return None;
+    }
+    Some(
+        parse_kmsg_record(line)
+            .or_else(|| parse_dmesg_line(line))
+            .unwrap_or_else(|| Entry {
+                facility: None,
+                level: None,
+                sequence_num: None,
+                timestamp_from_system_start: None,
+                message: line.to_string(),
+            }),
+    )
+}
+
+fn parse_kmsg_record(line: &str) -> Option<Entry> {
+    let (prefix, message) = line.split_once(';')?;
+    let mut fields = prefix.split(',');
+    let prio = fields.next()?.parse::<u32>().ok()?;
+    let sequence_num = fields.next()?.parse::<usize>().ok()?;
+    let usec = fields.next()?.parse::<u64>().ok()?;
+
+    Some(Entry {
+        facility: None,
+        level: Some(log_level(prio & 0x7)),
+        sequence_num: Some(sequence_num),
+        timestamp_from_system_start: Some(Duration::from_micros(usec)),
+        message: message.to_string(),
+    })
+}
+
+fn parse_dmesg_line(line: &str) -> Option<Entry> {
+    let rest = line.strip_prefix('[')?;
+    let (ts, message) = rest.split_once(']')?;
+    let ts = ts.trim().parse::<f64>().ok()?;
+
+    Some(Entry {
+        facility: None,
+        level: None,
+        sequence_num: None,
+        timestamp_from_system_start: Some(Duration::from_secs_f64(ts)),
+        message: message.trim_start().to_string(),
+    })
+}
+
+fn log_level(level: u32) -> LogLevel {
+    match level {
+        0 => LogLevel::Emergency,
+        1 => LogLevel::Alert,
+        2 => LogLevel::Critical,
+        3 => LogLevel::Error,
+        4 => LogLevel::Warning,
+        5 => LogLevel::Notice,
+        6 => LogLevel::Info,
+        _ => LogLevel::Debug,
+    }
+}
+
+/// Read all entries from a kmsg fixture file
+pub fn read_kmsg_file<P: AsRef<Path>>(path: P) -> Result<Vec<Entry>> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(content.lines().filter_map(parse_kmsg_line).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_parse_kmsg_record() {
+        let e =
+            parse_kmsg_line("3,1234,5678901,-;usb 1-1: device not accepting address 2").unwrap();
+        assert_eq!(e.level, Some(LogLevel::Error));
+        assert_eq!(e.sequence_num, Some(1234));
+        assert_eq!(
+            e.timestamp_from_system_start,
+            Some(Duration::from_micros(5678901))
+        );
+        assert_eq!(e.message, "usb 1-1: device not accepting address 2");
+    }
+
+    #[test]
+    fn test_parse_dmesg_line() {
+        let e = parse_kmsg_line("[    1.500000] Linux version 6.1.0").unwrap();
+        assert_eq!(e.level, None);
+        assert_eq!(
+            e.timestamp_from_system_start,
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(e.message, "Linux version 6.1.0");
+    }
+
+    #[test]
+    fn test_parse_plain_and_empty_lines() {
+        assert!(parse_kmsg_line("   ").is_none());
+        let e = parse_kmsg_line("just a message").unwrap();
+        assert_eq!(e.message, "just a message");
+        assert_eq!(e.timestamp_from_system_start, None);
+    }
+
+    #[test]
+    fn test_read_kmsg_file() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data")
+            .join("kmsg")
+            .join("kmsg.txt");
+        let entries = read_kmsg_file(path).unwrap();
+        assert_eq!(entries.len(), 6);
+        assert_eq!(entries[0].level, Some(LogLevel::Notice));
+        assert!(entries[5].message.contains("eth0: link up"));
+    }
+}
@@ -3,10 +3,16 @@
 
 mod actions;
 mod application;
+mod crash_report;
+mod diag;
 mod diff;
+mod dpc;
 mod efi;
 mod events;
+#[cfg(test)]
+mod harness;
 mod ipc;
+mod kmsg;
 mod model;
 mod tcg;
 mod terminal;
@@ -19,7 +25,7 @@ use std::{
 };
 
 use anyhow::Result;
-use application::{AppConfig, Application};
+use application::{AppConfig, Application, DevModeConfig};
 use libc::{EXIT_FAILURE, EXIT_SUCCESS};
 use log::{info, warn, LevelFilter};
 use terminal::TerminalWrapper;
@@ -27,21 +33,21 @@ use terminal::TerminalWrapper;
 const EVE_MONITOR_BASE_DIR_EVE: &str = "/persist/monitor/";
 const EVE_MONITOR_BASE_DIR_PC: &str = "./persist/monitor/";
 
-fn get_base_dir() -> PathBuf {
-    // we use XDG_RUNTIME_DIR to detect the fact that we are running on desktop linux
-    // FIXME: is there a better way?
-    if let Ok(_dir) = std::env::var("XDG_RUNTIME_DIR") {
+// development mode is enabled either by --dev command line flag or by
+// "dev_mode" section in ./persist/monitor/config/config.json
+fn is_dev_mode() -> bool {
+    std::env::args().skip(1).any(|arg| arg == "--dev")
+        || AppConfig::has_dev_mode(Path::new(EVE_MONITOR_BASE_DIR_PC))
+}
+
+fn get_base_dir(dev_mode: bool) -> PathBuf {
+    if dev_mode {
         EVE_MONITOR_BASE_DIR_PC.into()
     } else {
         EVE_MONITOR_BASE_DIR_EVE.into()
     }
 }
 
-fn get_base_log_dir() -> PathBuf {
-    let base_dir = get_base_dir();
-    base_dir.join("log")
-}
-
 fn remove_old_log_sessions<T: AsRef<Path>>(log_dir: T, rotate_count: usize) -> Result<()> {
     // go over log directory and remove old sessions
     // starting from the oldest one while we do not reach rotate_count
@@ -73,13 +79,11 @@ fn remove_old_log_sessions<T: AsRef<Path>>(log_dir: T, rotate_count: usize) -> R
     Ok(())
 }
 
-fn init_logging(log_level: &str) -> log2::Handle {
-    let base_log_dir = get_base_log_dir();
-
+fn init_logging(base_log_dir: &Path, log_level: &str) -> log2::Handle {
     let log_level = LevelFilter::from_str(log_level).unwrap_or(LevelFilter::Info);
 
     // remove old log directories. store result until we initialize logging
-    let remove_result = remove_old_log_sessions(&base_log_dir, 3);
+    let remove_result = remove_old_log_sessions(base_log_dir, 3);
 
     // get current data and time and use it as a subdirectory name for logs
     let current_dir = chrono::Local::now().format("%Y-%m-%d-%H-%M-%S").to_string();
@@ -185,12 +189,23 @@ fn log_system_info() {
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let config = AppConfig::load_or_create_app_config(&get_base_dir());
-    let _log2 = init_logging(&config.log_level);
+    let dev_mode = is_dev_mode();
+    let mut config = AppConfig::load_or_create_app_config(&get_base_dir(dev_mode));
+    if dev_mode {
+        if config.dev_mode.is_none() {
+            // store default settings so they can be adjusted in the config
+            config.dev_mode = Some(DevModeConfig::default());
+            config.save()?;
+        }
+        config.enable_dev_mode();
+    }
+    let _log2 = init_logging(&config.log_dir(), &config.log_level);
     initialize_panic_handler()?;
     log_system_info();
 
-    let mut app = Application::new(config)?;
+    let terminal = TerminalWrapper::open_terminal(dev_mode)?;
+    let mut app = Application::new(config, terminal, TerminalWrapper::get_stream())?;
+    app.show_crash_reports();
     let result = app.run().await;
     if let Err(e) = &result {
         log::error!("Application error: {}", e);
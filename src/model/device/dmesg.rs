@@ -4,6 +4,7 @@
 use crate::model::model::Model;
 use crate::ui::action::Action;
 use crate::ui::activity::Activity;
+use crate::ui::theme;
 use crate::ui::traits::IntoRatatuiStyle;
 use std::cmp;
 use std::rc::Rc;
@@ -155,9 +156,13 @@ impl IPresenter for DmesgViewer {
 
         // render vertical scrollbar on the right
         let mut scrollbar_state = ScrollbarState::new(self.buffer_len).position(self.buffer_index);
-        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
-            .begin_symbol(Some("↑"))
-            .end_symbol(Some("↓"));
+        let (up, down) = theme::scroll_arrows();
+        let mut scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some(up))
+            .end_symbol(Some(down));
+        if theme::profile().ascii {
+            scrollbar = scrollbar.track_symbol(Some("|")).thumb_symbol("#");
+        }
 
         frame.render_widget(Paragraph::new(lines), *area);
 
@@ -6,6 +6,7 @@ pub mod network;
 pub mod summary;
 pub mod tpmlog;
 pub mod tpmlog_diff;
+pub mod traffic;
 
 #[cfg(test)]
 mod tests;
@@ -1,9 +1,19 @@
 // Copyright (c) 2024-2025 Zededa, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
-
-use crate::ipc::eve_types::{DhcpType, NetworkPortStatus, NetworkProxyType, WirelessType};
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use crate::dpc::l2link::port_label;
+use crate::ipc::eve_types::{
+    AddrInfo, BondMode, DevicePortConfig, DhcpType, L2LinkConfig, L2LinkType, LacpRate,
+    NetworkPortConfig, NetworkPortStatus, NetworkProxyType, WirelessConfig, WirelessType,
+    WwanCellModule, WwanIPSettings, WwanNetworkStatus, WwanPhysAddrs, WwanProvider, WwanRAT,
+    WwanSimCard,
+};
+use chrono::{DateTime, Utc};
 use ipnet::IpNet;
 use macaddr::MacAddr;
 
@@ -17,9 +27,50 @@ pub struct SimStatus {
     pub slot: u32,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct CellularStatus {
-    sims: Option<Vec<SimStatus>>,
+    // access points configured for the modem
+    pub sims: Option<Vec<SimStatus>>,
+    pub logical_label: String,
+    pub phys_addrs: WwanPhysAddrs,
+    pub module: WwanCellModule,
+    pub sim_cards: Vec<WwanSimCard>,
+    pub current_provider: Option<WwanProvider>,
+    pub visible_providers: Vec<WwanProvider>,
+    pub current_rats: Vec<WwanRAT>,
+    pub connected_at: Option<DateTime<Utc>>,
+    pub ip_settings: WwanIPSettings,
+    pub config_error: Option<String>,
+    pub probe_error: Option<String>,
+}
+
+impl From<&WwanNetworkStatus> for CellularStatus {
+    fn from(status: &WwanNetworkStatus) -> Self {
+        let non_empty = |s: &String| (!s.is_empty()).then(|| s.clone());
+        CellularStatus {
+            sims: None,
+            logical_label: status.logical_label.clone(),
+            phys_addrs: status.phys_addrs.clone(),
+            module: status.module.clone(),
+            sim_cards: status.sim_cards.clone().unwrap_or_default(),
+            current_provider: (!status.current_provider.plmn.is_empty())
+                .then(|| status.current_provider.clone()),
+            visible_providers: status.visible_providers.clone().unwrap_or_default(),
+            current_rats: status.current_rats.clone().unwrap_or_default(),
+            connected_at: (status.connected_at != 0)
+                .then(|| DateTime::from_timestamp(status.connected_at as i64, 0))
+                .flatten(),
+            ip_settings: status.ip_settings.clone(),
+            config_error: non_empty(&status.config_error),
+            probe_error: non_empty(&status.probe_error),
+        }
+    }
+}
+
+impl CellularStatus {
+    pub fn is_connected(&self) -> bool {
+        self.connected_at.is_some()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -27,19 +78,133 @@ pub struct WiFiStatus {
     pub ssid: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct VlanStatus {
+    /// Logical label of the parent port
+    pub parent: String,
+    /// Interface name of the parent port if it is in the DPC
+    pub parent_ifname: Option<String>,
+    pub id: u16,
+}
+
+impl VlanStatus {
+    /// The parent label, with the interface name when it differs
+    pub fn parent_name(&self) -> String {
+        match &self.parent_ifname {
+            Some(ifname) if *ifname != self.parent => format!("{} ({})", self.parent, ifname),
+            _ => self.parent.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BondStatus {
+    /// Logical labels of the aggregated ports
+    pub members: Vec<String>,
+    pub mode: BondMode,
+    pub lacp_rate: LacpRate,
+}
+
+/// Media specific part of the interface status
 #[derive(Debug, Clone, PartialEq)]
 pub enum NetworkType {
     Ethernet,
     WiFi(WiFiStatus),
-    Cellular(CellularStatus),
+    // the modem status is large, keep it on the heap
+    Cellular(Box<CellularStatus>),
+    Vlan(VlanStatus),
+    Bond(BondStatus),
 }
 
-impl NetworkType {
-    pub fn to_string(&self) -> String {
+impl fmt::Display for NetworkType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            NetworkType::Ethernet => "Ethernet".to_string(),
-            NetworkType::WiFi(_) => "WiFi".to_string(),
-            NetworkType::Cellular(_) => "Cellular".to_string(),
+            NetworkType::Ethernet => write!(f, "Ethernet"),
+            NetworkType::WiFi(_) => write!(f, "WiFi"),
+            NetworkType::Cellular(_) => write!(f, "Cellular"),
+            NetworkType::Vlan(_) => write!(f, "VLAN"),
+            NetworkType::Bond(_) => write!(f, "Bond"),
+        }
+    }
+}
+
+impl NetworkType {
+    /// Derive the media from the port status and its config in the DPC. EVE
+    /// reports the actual wireless type in `WirelessStatus` while
+    /// `WirelessCfg` of the status may be empty, so the config is only a
+    /// fallback. L2 links are wired ports, they are never wireless
+    fn from_port(
+        port: &NetworkPortStatus,
+        config: Option<&NetworkPortConfig>,
+        dpc: Option<&DevicePortConfig>,
+    ) -> Self {
+        let config_wireless = config.map(|c| &c.wireless_cfg);
+        let w_type = [
+            Some(&port.wireless_status.w_type),
+            Some(&port.wireless_cfg.w_type),
+            config_wireless.map(|w| &w.w_type),
+        ]
+        .into_iter()
+        .flatten()
+        .find(|w_type| **w_type != WirelessType::None);
+        // configuration of the status if any, otherwise from the DPC
+        let wireless = |has_config: fn(&WirelessConfig) -> bool| {
+            Some(&port.wireless_cfg)
+                .filter(|w| has_config(w))
+                .or(config_wireless.filter(|w| has_config(w)))
+        };
+
+        match w_type {
+            Some(WirelessType::Wifi) => NetworkType::WiFi(WiFiStatus {
+                ssid: wireless(|w| w.wifi.as_ref().is_some_and(|wifi| !wifi.is_empty()))
+                    .and_then(|w| w.wifi.as_ref()?.first())
+                    .map(|wifi| wifi.ssid.clone()),
+            }),
+            Some(WirelessType::Cellular) => NetworkType::Cellular(Box::new(CellularStatus {
+                // A modem can have 0 or multiple sims
+                sims: wireless(|w| w.cellular_v2.is_some())
+                    .and_then(|w| w.cellular_v2.as_ref()?.access_points.as_ref())
+                    .map(|access_points| {
+                        access_points
+                            .iter()
+                            .map(|s| SimStatus {
+                                apn: s.apn.clone(),
+                                slot: u32::from(s.sim_slot),
+                            })
+                            .collect()
+                    }),
+                ..CellularStatus::from(&port.wireless_status.cellular)
+            })),
+            _ => {
+                let l2 = Some(&port.l2_link_config)
+                    .filter(|l2| l2.l2_type != L2LinkType::L2LinkTypeNone)
+                    .or(config.map(|c| &c.l2_link_config));
+                match l2 {
+                    Some(L2LinkConfig {
+                        l2_type: L2LinkType::L2LinkTypeVLAN,
+                        vlan: Some(vlan),
+                        ..
+                    }) => NetworkType::Vlan(VlanStatus {
+                        parent: vlan.parent_port.clone(),
+                        parent_ifname: dpc
+                            .and_then(|dpc| {
+                                dpc.ports.iter().find(|p| port_label(p) == vlan.parent_port)
+                            })
+                            .map(|p| p.if_name.clone()),
+                        id: vlan.id,
+                    }),
+                    Some(L2LinkConfig {
+                        l2_type: L2LinkType::L2LinkTypeBond,
+                        bond: Some(bond),
+                        ..
+                    }) => NetworkType::Bond(BondStatus {
+                        members: bond.aggregated_ports.clone().unwrap_or_default(),
+                        mode: bond.mode.clone(),
+                        lacp_rate: bond.lacp_rate.clone(),
+                    }),
+                    _ => NetworkType::Ethernet,
+                }
+            }
         }
     }
 }
@@ -67,6 +232,14 @@ pub struct ProxyHost {
 }
 
 impl ProxyHost {
+    pub fn server(&self) -> &str {
+        &self.server
+    }
+
+    pub fn port(&self) -> u32 {
+        self.port
+    }
+
     pub fn to_url(&self) -> String {
         format!("{}:{}", self.server, self.port)
     }
@@ -183,13 +356,16 @@ pub struct InterfaceConfig {
     pub proxy_certificate: Option<String>,
 }
 
-//TODO: convert to enum and create a separate struct for common fields
+/// Status of a network port: the fields common to all media and the media
+/// specific status in `media`
 #[derive(Debug, Clone, PartialEq)]
 pub struct NetworkInterfaceStatus {
     pub name: String,
     pub is_mgmt: bool,
     pub ipv4: Option<Vec<Ipv4Addr>>,
     pub ipv6: Option<Vec<Ipv6Addr>>,
+    /// Public addresses EVE has looked up for the addresses of the interface
+    pub geo: Vec<AddressGeo>,
     pub routes: Option<Vec<IpAddr>>,
     pub mac: Option<MacAddr>,
     pub ntp_servers: Option<Vec<String>>,
@@ -197,13 +373,62 @@ pub struct NetworkInterfaceStatus {
     pub media: NetworkType,
     pub dns: Option<Vec<IpAddr>>,
     pub subnet: Option<IpNet>,
+    pub ipv6_subnets: Option<Vec<IpNet>>,
     pub is_dhcp: bool,
     pub proxy_config: ProxyConfig,
+    /// PEM encoded CA certificates of the proxy
+    pub proxy_certificates: Vec<Vec<u8>>,
     pub domain: Option<String>,
     pub cost: u8,
+    pub mtu: u16,
+    pub alias: String,
     pub errors: Option<Vec<String>>,
 }
 
+/// Public IP, ISP and location of traffic leaving through a local address,
+/// as reported by the geolocation service EVE queries
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddressGeo {
+    /// Local address of the interface
+    pub addr: IpAddr,
+    pub public_ip: String,
+    pub hostname: String,
+    pub org: String,
+    pub city: String,
+    pub region: String,
+    pub country: String,
+    /// Time of the lookup
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl AddressGeo {
+    /// Geolocation of the address or None if EVE has not looked it up yet
+    pub fn from_addr_info(addr_info: &AddrInfo) -> Option<Self> {
+        let geo = addr_info.geo.as_ref().filter(|geo| !geo.ip.is_empty())?;
+        Some(Self {
+            addr: addr_info.addr,
+            public_ip: geo.ip.clone(),
+            hostname: geo.hostname.clone(),
+            org: geo.org.clone(),
+            city: geo.city.clone(),
+            region: geo.region.clone(),
+            country: geo.country.clone(),
+            // zero time of Go is sent when there was no lookup
+            updated_at: addr_info.last_geo_timestamp.filter(|t| t.timestamp() > 0),
+        })
+    }
+
+    /// City, region and country, skipping the unknown ones
+    pub fn location(&self) -> String {
+        [&self.city, &self.region, &self.country]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
 pub trait ToInnerIpAddr {
     fn to_ipv4(&self) -> Option<Ipv4Addr>;
     fn to_ipv6(&self) -> Option<Ipv6Addr>;
@@ -237,6 +462,14 @@ impl ToInnerIpAddr for IpAddr {
 
 impl From<&NetworkPortStatus> for NetworkInterfaceStatus {
     fn from(port: &NetworkPortStatus) -> Self {
+        NetworkInterfaceStatus::from_port(port, None)
+    }
+}
+
+impl NetworkInterfaceStatus {
+    /// Status of `port`. The DPC the status was reported for completes the
+    /// media specific status, e.g. with the parent port of a VLAN
+    pub fn from_port(port: &NetworkPortStatus, dpc: Option<&DevicePortConfig>) -> Self {
         // parse address list
         let ipv4 = port.addr_info_list.as_ref().map(|addr_info_list| {
             addr_info_list
@@ -256,33 +489,18 @@ impl From<&NetworkPortStatus> for NetworkInterfaceStatus {
                 .collect()
         });
 
-        // set media type
-        let media = match port.wireless_cfg.w_type {
-            WirelessType::None => NetworkType::Ethernet,
-            WirelessType::Wifi => NetworkType::WiFi(WiFiStatus {
-                //FIXME: why we have a Vec of WifiConfig?
-                ssid: port
-                    .wireless_cfg
-                    .wifi
-                    .as_ref()
-                    .and_then(|w| Some(w[0].ssid.clone())),
-            }),
-            WirelessType::Cellular => NetworkType::Cellular(CellularStatus {
-                // A modem can have 0 or multiple sims
-                sims: port.wireless_cfg.cellular_v2.as_ref().and_then(|c| {
-                    c.access_points.as_ref().and_then(|a| {
-                        Some(
-                            a.iter()
-                                .map(|s| SimStatus {
-                                    apn: s.apn.clone(),
-                                    slot: u32::from(s.sim_slot),
-                                })
-                                .collect(),
-                        )
-                    })
-                }),
-            }),
-        };
+        let geo = port
+            .addr_info_list
+            .as_ref()
+            .map_or_else(Vec::new, |addr_info_list| {
+                addr_info_list
+                    .iter()
+                    .filter_map(AddressGeo::from_addr_info)
+                    .collect()
+            });
+
+        let config = dpc.and_then(|dpc| dpc.get_port_by_name(&port.if_name));
+        let media = NetworkType::from_port(port, config, dpc);
 
         let is_dhcp = port.dhcp == DhcpType::Client;
 
@@ -328,6 +546,7 @@ impl From<&NetworkPortStatus> for NetworkInterfaceStatus {
             name: port.if_name.clone(),
             ipv4,
             ipv6,
+            geo,
             is_mgmt: port.is_mgmt,
             routes: port.default_routers.clone(),
             mac: port.mac_addr,
@@ -336,21 +555,257 @@ impl From<&NetworkPortStatus> for NetworkInterfaceStatus {
             media,
             dns,
             subnet: port.ipv4_subnet.clone(),
+            ipv6_subnets: port
+                .ipv6_subnets
+                .as_ref()
+                .map(|subnets| subnets.iter().flatten().cloned().collect()),
             is_dhcp,
             cost: port.cost,
+            mtu: port.mtu,
+            alias: port.alias.clone(),
             domain: if port.domain_name.is_empty() {
                 None
             } else {
                 Some(port.domain_name.clone())
             },
             proxy_config: (&port.proxy_config).into(),
+            proxy_certificates: port.proxy_config.proxy_cert_pem.clone().unwrap_or_default(),
             errors: last_error.clone(),
         }
     }
-}
 
-impl NetworkInterfaceStatus {
     pub fn is_connected(&self) -> bool {
         self.errors.is_none() && self.up
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::eve_types::{
+        BondConfig, DeviceNetworkStatus, VLANConfig, WifiConfig, WwanSimType,
+    };
+
+    fn load_wwan_port() -> NetworkPortStatus {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data/ipc-tests/wwan_network_status.json");
+        let data = std::fs::read_to_string(path).unwrap();
+        let mut json: serde_json::Value = serde_json::from_str(&data).unwrap();
+        let status: DeviceNetworkStatus = serde_json::from_value(json["message"].take()).unwrap();
+        status
+            .ports
+            .unwrap()
+            .into_iter()
+            .find(|p| p.if_name == "wwan0")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_geo_from_port() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data/ipc-tests/eve_ipc_message-103.json");
+        let data = std::fs::read_to_string(path).unwrap();
+        let mut json: serde_json::Value = serde_json::from_str(&data).unwrap();
+        let addr_info_list = &mut json["message"]["Ports"][0]["AddrInfoList"];
+        addr_info_list[0]["Geo"] = serde_json::json!({
+            "ip": "203.0.113.7",
+            "hostname": "gw.example.net",
+            "city": "Berlin",
+            "region": "",
+            "country": "DE",
+            "loc": "52.5200,13.4050",
+            "org": "AS64500 Example ISP",
+            "postal": "10115"
+        });
+        addr_info_list[0]["LastGeoTimestamp"] = "2025-05-01T10:00:00Z".into();
+        let status: DeviceNetworkStatus = serde_json::from_value(json["message"].take()).unwrap();
+        let ports = status.ports.unwrap();
+
+        let iface = NetworkInterfaceStatus::from(&ports[0]);
+        assert_eq!(iface.geo.len(), 1);
+        let geo = &iface.geo[0];
+        assert_eq!(geo.addr, "192.168.1.10".parse::<IpAddr>().unwrap());
+        assert_eq!(geo.public_ip, "203.0.113.7");
+        assert_eq!(geo.org, "AS64500 Example ISP");
+        assert_eq!(geo.location(), "Berlin, DE");
+        assert_eq!(geo.updated_at, "2025-05-01T10:00:00Z".parse().ok());
+
+        // empty geo with zero timestamp: no lookup yet
+        let iface = NetworkInterfaceStatus::from(&ports[1]);
+        assert!(iface.geo.is_empty());
+    }
+
+    #[test]
+    fn test_cellular_status_from_port() {
+        let iface = NetworkInterfaceStatus::from(&load_wwan_port());
+        let NetworkType::Cellular(status) = iface.media else {
+            panic!("wwan0 is not a cellular port");
+        };
+
+        assert_eq!(status.module.imei, "353533100123456");
+        assert_eq!(status.module.op_mode, "online-and-connected");
+        assert_eq!(status.sim_cards.len(), 2);
+        assert_eq!(status.sim_cards[0].iccid, "8944110068256270054");
+        assert_eq!(status.sim_cards[0].sim_type, WwanSimType::Physical);
+        assert_eq!(status.sim_cards[1].state, "absent");
+        assert_eq!(status.current_provider.unwrap().plmn, "234-10");
+        assert_eq!(status.visible_providers.len(), 2);
+        assert!(status.visible_providers[1].forbidden);
+        assert_eq!(status.current_rats, vec![WwanRAT::WwanRATLTE]);
+        assert_eq!(status.connected_at, DateTime::from_timestamp(1759480000, 0));
+        assert_eq!(
+            status.ip_settings.address.unwrap().to_string(),
+            "10.44.12.57/21"
+        );
+        assert_eq!(status.config_error, None);
+        assert_eq!(status.probe_error, None);
+    }
+
+    #[test]
+    fn test_cellular_status_disconnected() {
+        let mut port = load_wwan_port();
+        let cellular = &mut port.wireless_status.cellular;
+        cellular.connected_at = 0;
+        cellular.current_provider = WwanProvider::default();
+        cellular.probe_error = "no connectivity".to_string();
+
+        let NetworkType::Cellular(status) = NetworkInterfaceStatus::from(&port).media else {
+            panic!("wwan0 is not a cellular port");
+        };
+        assert!(!status.is_connected());
+        assert_eq!(status.current_provider, None);
+        assert_eq!(status.probe_error, Some("no connectivity".to_string()));
+    }
+
+    fn load_ethernet_ports() -> Vec<NetworkPortStatus> {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data/ipc-tests/eve_ipc_message-103.json");
+        let data = std::fs::read_to_string(path).unwrap();
+        let mut json: serde_json::Value = serde_json::from_str(&data).unwrap();
+        let status: DeviceNetworkStatus = serde_json::from_value(json["message"].take()).unwrap();
+        status.ports.unwrap()
+    }
+
+    fn dpc_port(if_name: &str, label: &str) -> NetworkPortConfig {
+        NetworkPortConfig {
+            if_name: if_name.to_string(),
+            logical_label: label.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_media_from_wireless_status() {
+        // the config part of the status is empty, the status is authoritative
+        let mut port = load_wwan_port();
+        port.wireless_cfg = WirelessConfig::default();
+        let iface = NetworkInterfaceStatus::from(&port);
+        assert!(matches!(iface.media, NetworkType::Cellular(_)));
+        assert_eq!(iface.media.to_string(), "Cellular");
+
+        // SSID comes from the DPC if the status has none
+        let mut port = load_ethernet_ports().remove(0);
+        port.wireless_status.w_type = WirelessType::Wifi;
+        let mut config = dpc_port("eth0", "ethernet0");
+        config.wireless_cfg.wifi = Some(vec![WifiConfig {
+            ssid: "office".to_string(),
+            ..Default::default()
+        }]);
+        let dpc = DevicePortConfig {
+            ports: vec![config],
+            ..Default::default()
+        };
+        let iface = NetworkInterfaceStatus::from_port(&port, Some(&dpc));
+        assert_eq!(
+            iface.media,
+            NetworkType::WiFi(WiFiStatus {
+                ssid: Some("office".to_string())
+            })
+        );
+
+        // WiFi without any configuration
+        let iface = NetworkInterfaceStatus::from(&port);
+        assert_eq!(iface.media, NetworkType::WiFi(WiFiStatus { ssid: None }));
+    }
+
+    #[test]
+    fn test_media_of_l2_links() {
+        let mut ports = load_ethernet_ports();
+        let dpc = DevicePortConfig {
+            ports: vec![dpc_port("eth0", "ethernet0"), dpc_port("eth1", "ethernet1")],
+            ..Default::default()
+        };
+        assert_eq!(
+            NetworkInterfaceStatus::from_port(&ports[0], Some(&dpc)).media,
+            NetworkType::Ethernet
+        );
+
+        let vlan = &mut ports[0];
+        vlan.if_name = "vlan100".to_string();
+        vlan.l2_link_config = L2LinkConfig {
+            l2_type: L2LinkType::L2LinkTypeVLAN,
+            vlan: Some(VLANConfig {
+                parent_port: "ethernet0".to_string(),
+                id: 100,
+            }),
+            bond: None,
+        };
+        let iface = NetworkInterfaceStatus::from_port(&ports[0], Some(&dpc));
+        assert_eq!(
+            iface.media,
+            NetworkType::Vlan(VlanStatus {
+                parent: "ethernet0".to_string(),
+                parent_ifname: Some("eth0".to_string()),
+                id: 100,
+            })
+        );
+        assert_eq!(iface.media.to_string(), "VLAN");
+
+        let bond = &mut ports[1];
+        bond.if_name = "bond0".to_string();
+        bond.l2_link_config = L2LinkConfig {
+            l2_type: L2LinkType::L2LinkTypeBond,
+            vlan: None,
+            bond: Some(BondConfig {
+                aggregated_ports: Some(vec!["ethernet0".to_string(), "ethernet1".to_string()]),
+                mode: BondMode::BondModeActiveBackup,
+                lacp_rate: LacpRate::LacpRateFast,
+                ..Default::default()
+            }),
+        };
+        let NetworkType::Bond(status) = NetworkInterfaceStatus::from(&ports[1]).media else {
+            panic!("bond0 is not a bond");
+        };
+        assert_eq!(status.members, ["ethernet0", "ethernet1"]);
+        assert_eq!(status.mode, BondMode::BondModeActiveBackup);
+        assert_eq!(status.lacp_rate, LacpRate::LacpRateFast);
+    }
+
+    #[test]
+    fn test_l2_link_from_dpc() {
+        // the status does not report the L2 link, the DPC does
+        let port = load_ethernet_ports().remove(1);
+        let mut config = dpc_port("eth1", "ethernet1");
+        config.l2_link_config = L2LinkConfig {
+            l2_type: L2LinkType::L2LinkTypeVLAN,
+            vlan: Some(VLANConfig {
+                parent_port: "uplink".to_string(),
+                id: 7,
+            }),
+            bond: None,
+        };
+        let dpc = DevicePortConfig {
+            ports: vec![config],
+            ..Default::default()
+        };
+        let iface = NetworkInterfaceStatus::from_port(&port, Some(&dpc));
+        assert_eq!(
+            iface.media,
+            NetworkType::Vlan(VlanStatus {
+                parent: "uplink".to_string(),
+                parent_ifname: None,
+                id: 7,
+            })
+        );
+    }
+}
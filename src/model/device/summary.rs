@@ -36,9 +36,10 @@ impl DeviceSummary {
 }
 
 fn get_usb() -> Vec<String> {
-    let cmd_out = Command::new("lsusb")
-        .output()
-        .expect("failed to execute process");
+    // the tool may be missing e.g. on a development host
+    let Ok(cmd_out) = Command::new("lsusb").output() else {
+        return vec!["No devices detected".to_string()];
+    };
     if cmd_out.status.success() {
         if let Some(string) = String::from_utf8(cmd_out.stdout).ok() {
             return string.split('\n').map(|s| s.to_string()).collect();
@@ -49,9 +50,10 @@ fn get_usb() -> Vec<String> {
 }
 
 fn get_pci() -> Vec<String> {
-    let cmd_out = Command::new("lspci")
-        .output()
-        .expect("failed to execute process");
+    // the tool may be missing e.g. on a development host
+    let Ok(cmd_out) = Command::new("lspci").output() else {
+        return vec!["No devices detected".to_string()];
+    };
     if cmd_out.status.success() {
         if let Some(string) = String::from_utf8(cmd_out.stdout).ok() {
             return string.split('\n').map(|s| s.to_string()).collect();
@@ -0,0 +1,282 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Traffic counters and link state of the network interfaces.
+//!
+//! The counters are read from `<sysfs>/class/net/<iface>` on every tick and
+//! the difference between two samples gives the throughput. The sysfs root is
+//! configurable so the reader can run against a fixture tree.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    path::Path,
+    time::Instant,
+};
+
+pub const SYSFS_ROOT: &str = "/sys";
+/// Number of throughput samples kept for every interface
+pub const HISTORY_LENGTH: usize = 240;
+
+/// Counters of one direction from `statistics/{rx,tx}_*`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Counters {
+    pub bytes: u64,
+    pub packets: u64,
+    pub errors: u64,
+    pub dropped: u64,
+}
+
+impl Counters {
+    fn read(statistics: &Path, direction: &str) -> io::Result<Self> {
+        let counter = |name: &str| read_u64(&statistics.join(format!("{direction}_{name}")));
+        Ok(Self {
+            bytes: counter("bytes")?,
+            packets: counter("packets")?,
+            errors: counter("errors")?,
+            dropped: counter("dropped")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinkStatistics {
+    pub rx: Counters,
+    pub tx: Counters,
+    /// Link speed in Mbit/s. Not reported while the link is down
+    pub speed: Option<u32>,
+    pub duplex: Option<String>,
+    pub carrier: Option<bool>,
+    pub carrier_changes: Option<u64>,
+}
+
+impl LinkStatistics {
+    /// Read the statistics of `iface`. Fails only if the counters cannot be
+    /// read, the link attributes are optional and e.g. `speed` returns EINVAL
+    /// while the carrier is down
+    pub fn read(sysfs: &Path, iface: &str) -> io::Result<Self> {
+        let dir = sysfs.join("class").join("net").join(iface);
+        let statistics = dir.join("statistics");
+        Ok(Self {
+            rx: Counters::read(&statistics, "rx")?,
+            tx: Counters::read(&statistics, "tx")?,
+            // virtual interfaces report -1
+            speed: read_u64(&dir.join("speed"))
+                .ok()
+                .and_then(|speed| u32::try_from(speed).ok()),
+            duplex: read_string(&dir.join("duplex"))
+                .ok()
+                .filter(|duplex| duplex != "unknown"),
+            carrier: read_u64(&dir.join("carrier")).ok().map(|c| c == 1),
+            carrier_changes: read_u64(&dir.join("carrier_changes")).ok(),
+        })
+    }
+}
+
+fn read_string(path: &Path) -> io::Result<String> {
+    Ok(std::fs::read_to_string(path)?.trim().to_string())
+}
+
+fn read_u64(path: &Path) -> io::Result<u64> {
+    read_string(path)?
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[derive(Debug, Default)]
+pub struct InterfaceTraffic {
+    pub stats: LinkStatistics,
+    /// Received bytes per second, the oldest sample first
+    pub rx_rate: VecDeque<u64>,
+    /// Transmitted bytes per second, the oldest sample first
+    pub tx_rate: VecDeque<u64>,
+    sampled_at: Option<Instant>,
+}
+
+impl InterfaceTraffic {
+    fn update(&mut self, stats: LinkStatistics, now: Instant) {
+        if let Some(sampled_at) = self.sampled_at {
+            let elapsed = now.duration_since(sampled_at).as_secs_f64();
+            if elapsed > 0.0 {
+                // counters go back to zero when the driver is reloaded
+                let rate = |new: u64, old: u64| (new.saturating_sub(old) as f64 / elapsed) as u64;
+                push_sample(&mut self.rx_rate, rate(stats.rx.bytes, self.stats.rx.bytes));
+                push_sample(&mut self.tx_rate, rate(stats.tx.bytes, self.stats.tx.bytes));
+            }
+        }
+        self.stats = stats;
+        self.sampled_at = Some(now);
+    }
+
+    /// The last received bytes per second
+    pub fn rx_throughput(&self) -> u64 {
+        self.rx_rate.back().copied().unwrap_or_default()
+    }
+
+    /// The last transmitted bytes per second
+    pub fn tx_throughput(&self) -> u64 {
+        self.tx_rate.back().copied().unwrap_or_default()
+    }
+}
+
+fn push_sample(history: &mut VecDeque<u64>, sample: u64) {
+    if history.len() == HISTORY_LENGTH {
+        history.pop_front();
+    }
+    history.push_back(sample);
+}
+
+/// Traffic of all interfaces known to the model
+#[derive(Debug, Default)]
+pub struct TrafficStats {
+    interfaces: HashMap<String, InterfaceTraffic>,
+}
+
+impl TrafficStats {
+    /// Sample the counters of `names`. Interfaces that are gone or cannot be
+    /// read are forgotten
+    pub fn update<'a>(
+        &mut self,
+        sysfs: &Path,
+        names: impl IntoIterator<Item = &'a str>,
+        now: Instant,
+    ) {
+        let mut interfaces = HashMap::new();
+        for name in names {
+            match LinkStatistics::read(sysfs, name) {
+                Ok(stats) => {
+                    let mut traffic = self.interfaces.remove(name).unwrap_or_default();
+                    traffic.update(stats, now);
+                    interfaces.insert(name.to_string(), traffic);
+                }
+                Err(e) => log::trace!("Cannot read statistics of {}: {}", name, e),
+            }
+        }
+        self.interfaces = interfaces;
+    }
+
+    pub fn get(&self, iface: &str) -> Option<&InterfaceTraffic> {
+        self.interfaces.get(iface)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn insert(&mut self, iface: &str, traffic: InterfaceTraffic) {
+        self.interfaces.insert(iface.to_string(), traffic);
+    }
+}
+
+#[cfg(test)]
+impl InterfaceTraffic {
+    /// Traffic with the given statistics and throughput history
+    pub(crate) fn with_history(stats: LinkStatistics, rx_rate: &[u64], tx_rate: &[u64]) -> Self {
+        Self {
+            stats,
+            rx_rate: rx_rate.iter().copied().collect(),
+            tx_rate: tx_rate.iter().copied().collect(),
+            sampled_at: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::*;
+
+    fn fixture() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data")
+            .join("sysfs")
+    }
+
+    /// Copy of the fixture tree that can be modified by the test
+    fn fixture_copy(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("monitor-sysfs-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        for iface in ["eth0", "eth1"] {
+            let src = fixture().join("class/net").join(iface);
+            let dst = root.join("class/net").join(iface);
+            std::fs::create_dir_all(dst.join("statistics")).unwrap();
+            for dir in ["", "statistics"] {
+                for entry in std::fs::read_dir(src.join(dir)).unwrap() {
+                    let entry = entry.unwrap();
+                    if entry.file_type().unwrap().is_file() {
+                        std::fs::copy(entry.path(), dst.join(dir).join(entry.file_name())).unwrap();
+                    }
+                }
+            }
+        }
+        root
+    }
+
+    #[test]
+    fn test_read_link_statistics() {
+        let stats = LinkStatistics::read(&fixture(), "eth0").unwrap();
+        assert_eq!(
+            stats.rx,
+            Counters {
+                bytes: 1843526711,
+                packets: 1523402,
+                errors: 0,
+                dropped: 12,
+            }
+        );
+        assert_eq!(stats.tx.bytes, 95712344);
+        assert_eq!(stats.tx.errors, 3);
+        assert_eq!(stats.speed, Some(1000));
+        assert_eq!(stats.duplex.as_deref(), Some("full"));
+        assert_eq!(stats.carrier, Some(true));
+        assert_eq!(stats.carrier_changes, Some(2));
+
+        // link is down: no speed, unknown duplex
+        let stats = LinkStatistics::read(&fixture(), "eth1").unwrap();
+        assert_eq!(stats.speed, None);
+        assert_eq!(stats.duplex, None);
+        assert_eq!(stats.carrier, Some(false));
+
+        assert!(LinkStatistics::read(&fixture(), "wlan0").is_err());
+    }
+
+    #[test]
+    fn test_throughput() {
+        let root = fixture_copy("throughput");
+        let statistics = root.join("class/net/eth0/statistics");
+        let start = Instant::now();
+        let mut traffic = TrafficStats::default();
+
+        traffic.update(&root, ["eth0", "eth1", "wlan0"], start);
+        let eth0 = traffic.get("eth0").unwrap();
+        assert!(eth0.rx_rate.is_empty());
+        assert_eq!(eth0.rx_throughput(), 0);
+        assert!(traffic.get("wlan0").is_none());
+
+        std::fs::write(statistics.join("rx_bytes"), "1843527711\n").unwrap();
+        std::fs::write(statistics.join("tx_bytes"), "95712444\n").unwrap();
+        traffic.update(&root, ["eth0", "eth1"], start + Duration::from_millis(500));
+        let eth0 = traffic.get("eth0").unwrap();
+        assert_eq!(eth0.rx_throughput(), 2000);
+        assert_eq!(eth0.tx_throughput(), 200);
+        assert_eq!(traffic.get("eth1").unwrap().rx_rate, [0]);
+
+        // counters were reset
+        std::fs::write(statistics.join("rx_bytes"), "10\n").unwrap();
+        traffic.update(&root, ["eth0"], start + Duration::from_secs(1));
+        let eth0 = traffic.get("eth0").unwrap();
+        assert_eq!(eth0.rx_rate, [2000, 0]);
+        assert!(traffic.get("eth1").is_none());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_history_length() {
+        let mut history = VecDeque::new();
+        for sample in 0..HISTORY_LENGTH as u64 + 5 {
+            push_sample(&mut history, sample);
+        }
+        assert_eq!(history.len(), HISTORY_LENGTH);
+        assert_eq!(history.front(), Some(&5));
+    }
+}
@@ -1,19 +1,27 @@
 // Copyright (c) 2024-2025 Zededa, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{cell::RefCell, collections::HashMap};
+use std::{cell::RefCell, collections::HashMap, fmt};
 
 use chrono::{DateTime, Utc};
 use log::{error, info};
+use strum::Display;
 use uuid::Uuid;
 
 use crate::{
-    ipc::eve_types::{
-        AppInstanceStatus, AppInstanceSummary, AppsList, DataSecAtRestStatus, DeviceNetworkStatus,
-        DevicePortConfig, DevicePortConfigList, DownloaderStatus, ErrorAndTime, EveNodeStatus,
-        EveOnboardingStatus, EveVaultStatus, PCRStatus, SwState, TpmLogs, ZedAgentStatus,
+    diag::{controller::ControllerCheck, DiagSession},
+    dpc::verification::DpcVerification,
+    ipc::{
+        eve_types::{
+            AppInstanceStatus, AppInstanceSummary, AppsList, DataSecAtRestStatus,
+            DeviceNetworkStatus, DevicePortConfig, DevicePortConfigList, DownloaderStatus,
+            ErrorAndTime, ErrorAndTimeWithSource, ErrorSeverity, EveNodeStatus,
+            EveOnboardingStatus, EveVaultStatus, Inprogress, PCRStatus, SwState, TpmLogs, VmConfig,
+            VolumeRefStatus, WwanMetrics, WwanNetworkMetrics, ZedAgentStatus,
+        },
+        message::Request,
     },
-    model::device::tpmlog_diff::TpmLogDiff,
+    model::device::{tpmlog_diff::TpmLogDiff, traffic::TrafficStats},
 };
 
 use super::device::network::NetworkInterfaceStatus;
@@ -43,7 +51,48 @@ impl NodeStatus {
 #[derive(Debug)]
 pub enum AppInstanceState {
     Normal(SwState),
-    Error(SwState, String),
+    Error(SwState, AppError),
+}
+
+/// Error of an app instance or of its volume with the agent that reported it
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppError {
+    pub error: String,
+    /// Type of the object the error comes from, e.g. `types.VolumeStatus`
+    pub source: String,
+    pub time: DateTime<Utc>,
+    pub severity: ErrorSeverity,
+    pub retry_condition: String,
+}
+
+impl AppError {
+    /// None if there is no error
+    pub fn from_status(status: &ErrorAndTimeWithSource) -> Option<Self> {
+        let description = &status.error_description;
+        (!description.error.is_empty()).then(|| Self {
+            error: description.error.clone(),
+            source: status.error_source_type.clone(),
+            time: description.error_time,
+            severity: description.error_severity.clone(),
+            retry_condition: description.error_retry_condition.clone(),
+        })
+    }
+}
+
+/// Operation on an app instance that is in progress
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppOperation {
+    pub stage: Inprogress,
+    pub started_at: Option<DateTime<Utc>>,
+}
+
+impl AppOperation {
+    fn new(stage: &Inprogress, started_at: DateTime<Utc>) -> Option<Self> {
+        (*stage != Inprogress::NotInprogress).then(|| Self {
+            stage: stage.clone(),
+            started_at: non_zero_time(started_at),
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -52,6 +101,143 @@ pub struct AppInstance {
     pub uuid: Uuid,
     pub version: String,
     pub state: AppInstanceState,
+    pub activated: bool,
+    pub activate_in_progress: bool,
+    pub resources: VmConfig,
+    pub volumes: Vec<VolumeRefStatus>,
+    pub boot_time: Option<DateTime<Utc>>,
+    pub start_time: Option<DateTime<Utc>>,
+    pub restart: Option<AppOperation>,
+    pub purge: Option<AppOperation>,
+    // start of the last restart and purge, also once they are done
+    pub restart_started_at: Option<DateTime<Utc>>,
+    pub purge_started_at: Option<DateTime<Utc>>,
+    pub missing_network: bool,
+    pub missing_memory: bool,
+}
+
+impl AppInstance {
+    pub fn sw_state(&self) -> SwState {
+        match &self.state {
+            AppInstanceState::Normal(state) | AppInstanceState::Error(state, _) => *state,
+        }
+    }
+}
+
+/// Lifecycle command sent to an app instance from the monitor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum AppCommand {
+    Restart,
+    Stop,
+    Start,
+    Purge,
+}
+
+impl AppCommand {
+    pub fn request(self, uuid: Uuid) -> Request {
+        match self {
+            AppCommand::Restart => Request::RestartApp(uuid),
+            AppCommand::Stop => Request::StopApp(uuid),
+            AppCommand::Start => Request::StartApp(uuid),
+            AppCommand::Purge => Request::PurgeApp(uuid),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppCommandProgress {
+    /// Sent to EVE, no response yet
+    Requested,
+    /// EVE accepted the command and did not report any progress yet
+    Accepted,
+    /// Stage of the restart or purge, or the state the app went through
+    InProgress(String),
+    Done,
+    /// EVE refused the command
+    Failed(String),
+}
+
+/// The last command sent to an app instance. The progress is taken from the
+/// `AppStatus` messages that follow the command
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppCommandStatus {
+    pub command: AppCommand,
+    pub progress: AppCommandProgress,
+    pub sent_at: DateTime<Utc>,
+}
+
+impl AppCommandStatus {
+    pub fn new(command: AppCommand, sent_at: DateTime<Utc>) -> Self {
+        Self {
+            command,
+            progress: AppCommandProgress::Requested,
+            sent_at,
+        }
+    }
+
+    fn accept(&mut self) {
+        if self.progress == AppCommandProgress::Requested {
+            self.progress = AppCommandProgress::Accepted;
+        }
+    }
+
+    fn update(&mut self, app: &AppInstance) {
+        // a refused command is not followed by any progress
+        if matches!(self.progress, AppCommandProgress::Failed(_)) {
+            return;
+        }
+        let state = app.sw_state();
+        let in_progress = matches!(self.progress, AppCommandProgress::InProgress(_));
+        self.progress = match self.command {
+            // restart and purge report their stages, the command is done when
+            // the stage goes back to not in progress. A quick one may be over
+            // before any stage is reported, only its start time is left then
+            AppCommand::Restart | AppCommand::Purge => {
+                let (operation, started_at) = match self.command {
+                    AppCommand::Restart => (&app.restart, app.restart_started_at),
+                    _ => (&app.purge, app.purge_started_at),
+                };
+                match operation {
+                    Some(operation) => AppCommandProgress::InProgress(operation.stage.to_string()),
+                    None if in_progress => AppCommandProgress::Done,
+                    None if started_at.is_some_and(|time| time > self.sent_at) => {
+                        AppCommandProgress::Done
+                    }
+                    None => return,
+                }
+            }
+            AppCommand::Stop => match state {
+                SwState::Halted => AppCommandProgress::Done,
+                SwState::Halting | SwState::Pausing => {
+                    AppCommandProgress::InProgress(state.to_string())
+                }
+                _ => return,
+            },
+            AppCommand::Start => match state {
+                SwState::Running => AppCommandProgress::Done,
+                SwState::Halted => return,
+                _ => AppCommandProgress::InProgress(state.to_string()),
+            },
+        };
+    }
+}
+
+impl fmt::Display for AppCommandStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.progress {
+            AppCommandProgress::Requested => write!(f, "{} requested", self.command),
+            AppCommandProgress::Accepted => write!(f, "{} accepted", self.command),
+            AppCommandProgress::InProgress(stage) => write!(f, "{}: {}", self.command, stage),
+            AppCommandProgress::Done => write!(f, "{} done", self.command),
+            AppCommandProgress::Failed(error) => write!(f, "{} failed: {}", self.command, error),
+        }
+    }
+}
+
+/// Go sends the zero time for timestamps that are not set
+fn non_zero_time(time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    (time.timestamp() > 0).then_some(time)
 }
 
 #[derive(Debug)]
@@ -92,14 +278,29 @@ pub struct MonitorModel {
     pub app_version: String,
     pub dmesg: Vec<rmesg::entry::Entry>,
     pub network: Vec<NetworkInterfaceStatus>,
+    /// Traffic counters and throughput history of the network interfaces
+    pub traffic: TrafficStats,
     pub downloader: Option<DownloaderStatus>,
     pub node_status: NodeStatus,
     pub apps: HashMap<Uuid, AppInstance>,
+    /// The last lifecycle command sent to each app instance
+    pub app_commands: HashMap<Uuid, AppCommandStatus>,
     pub vault_status: VaultStatus,
     pub dpc_list: Option<DevicePortConfigList>,
     pub dpc_key: Option<String>,
+    /// Time priority of the DPC in use, DPCs sharing the key differ by it
+    pub dpc_time_priority: Option<DateTime<Utc>>,
+    /// The last network status, the interfaces are derived from it and the DPC
+    pub network_status: Option<DeviceNetworkStatus>,
+    /// The last DPC sent to EVE
+    pub dpc_verification: Option<DpcVerification>,
     pub z_status: Option<ZedAgentStatus>,
     pub tpm: Option<TpmLogDiff>,
+    pub wwan_metrics: Option<WwanMetrics>,
+    /// Output of the last tool run on the Diagnostics page
+    pub diagnostics: DiagSession,
+    /// Result of the last controller reachability check
+    pub controller_check: ControllerCheck,
     pub error_log: Vec<String>,
     pub status_bar_tips: Option<String>,
     /// Whether the IPC connection to EVE is currently established
@@ -132,18 +333,9 @@ impl From<EveVaultStatus> for VaultStatus {
 
 impl From<AppInstanceStatus> for AppInstance {
     fn from(app: AppInstanceStatus) -> Self {
-        let state = if !app
-            .error_and_time_with_source
-            .error_description
-            .error
-            .is_empty()
-        {
-            AppInstanceState::Error(
-                app.state,
-                app.error_and_time_with_source.error_description.error,
-            )
-        } else {
-            AppInstanceState::Normal(app.state)
+        let state = match AppError::from_status(&app.error_and_time_with_source) {
+            Some(error) => AppInstanceState::Error(app.state, error),
+            None => AppInstanceState::Normal(app.state),
         };
 
         AppInstance {
@@ -151,6 +343,18 @@ impl From<AppInstanceStatus> for AppInstance {
             uuid: app.uuid_and_version.uuid,
             version: app.uuid_and_version.version,
             state,
+            activated: app.activated,
+            activate_in_progress: app.activate_inprogress,
+            resources: app.fixed_resources,
+            volumes: app.volume_ref_status_list,
+            boot_time: non_zero_time(app.boot_time),
+            start_time: non_zero_time(app.start_time),
+            restart: AppOperation::new(&app.restart_inprogress, app.restart_started_at),
+            purge: AppOperation::new(&app.purge_inprogress, app.purge_started_at),
+            restart_started_at: non_zero_time(app.restart_started_at),
+            purge_started_at: non_zero_time(app.purge_started_at),
+            missing_network: app.missing_network,
+            missing_memory: app.missing_memory,
         }
     }
 }
@@ -181,23 +385,55 @@ impl From<EveNodeStatus> for NodeStatus {
 }
 
 impl MonitorModel {
-    fn get_network_settings(
-        &self,
-        network_status: &DeviceNetworkStatus,
-    ) -> Option<Vec<NetworkInterfaceStatus>> {
-        let ports = network_status.ports.as_ref()?;
-        Some(ports.iter().map(|p| p.into()).collect())
+    fn get_network_settings(&self) -> Option<Vec<NetworkInterfaceStatus>> {
+        let ports = self.network_status.as_ref()?.ports.as_ref()?;
+        let dpc = self.get_current_dpc();
+        Some(
+            ports
+                .iter()
+                .map(|p| NetworkInterfaceStatus::from_port(p, dpc))
+                .collect(),
+        )
     }
     pub fn update_app_status(&mut self, state: AppInstanceStatus) {
         let app_guid = &state.uuid_and_version.uuid;
-        self.apps
+        let app = self
+            .apps
             .entry(*app_guid)
             .and_modify(|e| *e = AppInstance::from(state.clone()))
             .or_insert(AppInstance::from(state));
+        if let Some(command) = self.app_commands.get_mut(&app.uuid) {
+            command.update(app);
+        }
+    }
+
+    /// A lifecycle command was sent to the app instance
+    pub fn request_app_command(&mut self, uuid: Uuid, command: AppCommand) {
+        self.app_commands
+            .insert(uuid, AppCommandStatus::new(command, Utc::now()));
+    }
+
+    /// EVE refused `command`. A command sent after it is left as it is
+    pub fn fail_app_command(&mut self, uuid: Uuid, command: AppCommand, error: &str) {
+        if let Some(status) = self.app_commands.get_mut(&uuid) {
+            if status.command == command {
+                status.progress = AppCommandProgress::Failed(error.to_string());
+            }
+        }
+    }
+
+    /// EVE accepted the last command sent to the app instance
+    pub fn accept_app_command(&mut self, uuid: Uuid) {
+        if let Some(command) = self.app_commands.get_mut(&uuid) {
+            command.accept();
+        }
     }
 
     pub fn update_app_list(&mut self, apps_list: AppsList) {
         self.apps = HashMap::from(apps_list);
+        // commands of deleted app instances
+        let apps = &self.apps;
+        self.app_commands.retain(|uuid, _| apps.contains_key(uuid));
     }
 
     pub fn update_downloader_status(&mut self, status: DownloaderStatus) {
@@ -212,9 +448,29 @@ impl MonitorModel {
         self.node_status.app_summary = app_summary;
     }
 
+    /// The status only names the DPC by the key and its index in the DPC
+    /// list of EVE, which may differ from the list received last
+    fn find_status_dpc(&self, net_status: &DeviceNetworkStatus) -> Option<&DevicePortConfig> {
+        let list = self.get_dpc_list()?;
+        let key = &net_status.dpc_key;
+        usize::try_from(net_status.current_index)
+            .ok()
+            .and_then(|index| list.port_config_list.as_ref()?.get(index))
+            .filter(|dpc| dpc.key == *key)
+            .or_else(|| list.get_current_dpc_ref().filter(|dpc| dpc.key == *key))
+            .or_else(|| list.get_dpc_by_key(key))
+    }
+
     pub fn update_network_status(&mut self, net_status: DeviceNetworkStatus) {
-        self.network = self.get_network_settings(&net_status).unwrap_or_default();
-        self.dpc_key = Some(net_status.dpc_key);
+        if let Some(verification) = self.dpc_verification.as_mut() {
+            verification.update_network_status(&net_status);
+        }
+        self.dpc_time_priority = self
+            .find_status_dpc(&net_status)
+            .map(|dpc| dpc.time_priority);
+        self.dpc_key = Some(net_status.dpc_key.clone());
+        self.network_status = Some(net_status);
+        self.network = self.get_network_settings().unwrap_or_default();
     }
 
     pub fn update_vault_status(&mut self, vault_status: EveVaultStatus) {
@@ -226,7 +482,19 @@ impl MonitorModel {
     }
 
     pub fn set_dpc_list(&mut self, dpc_list: DevicePortConfigList) {
+        if let Some(verification) = self.dpc_verification.as_mut() {
+            verification.update_dpc_list(&dpc_list);
+        }
         self.dpc_list = Some(dpc_list);
+        // the DPC of the last status is only known once the list arrived
+        if self.dpc_time_priority.is_none() {
+            self.dpc_time_priority = self
+                .network_status
+                .as_ref()
+                .and_then(|status| self.find_status_dpc(status))
+                .map(|dpc| dpc.time_priority);
+        }
+        self.network = self.get_network_settings().unwrap_or_default();
     }
 
     pub fn get_dpc_list(&self) -> Option<&DevicePortConfigList> {
@@ -234,14 +502,31 @@ impl MonitorModel {
     }
 
     pub fn get_current_dpc(&self) -> Option<&DevicePortConfig> {
-        let key = self.dpc_key.clone()?;
-        self.get_dpc_list()?.get_dpc_by_key(&key)
+        let key = self.dpc_key.as_ref()?;
+        let list = self.get_dpc_list()?;
+        match &self.dpc_time_priority {
+            Some(time_priority) => list.get_dpc(key, time_priority),
+            None => list.get_dpc_by_key(key),
+        }
     }
 
     pub fn update_zed_agent_status(&mut self, status: ZedAgentStatus) {
         self.z_status = Some(status);
     }
 
+    pub fn update_wwan_metrics(&mut self, metrics: WwanMetrics) {
+        self.wwan_metrics = Some(metrics);
+    }
+
+    pub fn get_wwan_metrics(&self, logical_label: &str) -> Option<&WwanNetworkMetrics> {
+        self.wwan_metrics
+            .as_ref()?
+            .networks
+            .as_ref()?
+            .iter()
+            .find(|n| n.logical_label == logical_label)
+    }
+
     pub fn update_tpm_logs(&mut self, logs: TpmLogs) {
         info!("Got TPM logs from EVE");
 
@@ -292,17 +577,251 @@ impl Default for MonitorModel {
             app_version,
             dmesg: Vec::with_capacity(1000),
             network: Vec::new(),
+            traffic: TrafficStats::default(),
             downloader: None,
             node_status: NodeStatus::default(),
             apps: HashMap::new(),
+            app_commands: HashMap::new(),
             vault_status: VaultStatus::Unknown,
             dpc_list: None,
             dpc_key: None,
+            dpc_time_priority: None,
+            network_status: None,
+            dpc_verification: None,
             z_status: None,
             tpm: None,
+            wwan_metrics: None,
+            diagnostics: DiagSession::default(),
+            controller_check: ControllerCheck::default(),
             error_log: Vec::new(),
             status_bar_tips: None,
             ipc_connected: false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app_status() -> AppInstanceStatus {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data/ipc-tests/eve_ipc_message-122.json");
+        let data = std::fs::read_to_string(path).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&data).unwrap();
+        serde_json::from_value(json["message"].clone()).unwrap()
+    }
+
+    #[test]
+    fn test_app_instance_from_status() {
+        let app = AppInstance::from(app_status());
+        assert_eq!(app.name, "cs_nginx-qemu-2");
+        assert!(matches!(
+            app.state,
+            AppInstanceState::Normal(SwState::Downloading)
+        ));
+        assert_eq!(app.resources.vcpus, 1);
+        assert_eq!(app.resources.memory, 524288);
+        assert_eq!(app.resources.vnc_display, 1);
+        assert_eq!(app.volumes.len(), 1);
+        assert_eq!(app.volumes[0].display_name, "cs_nginx-qemu-2_0_m_0");
+        // zero Go time means not set
+        assert_eq!(app.boot_time, None);
+        assert_eq!(
+            app.start_time.unwrap().to_rfc3339(),
+            "2025-07-28T13:14:39.211122761+00:00"
+        );
+        assert_eq!(app.restart, None);
+        assert_eq!(app.purge, None);
+    }
+
+    #[test]
+    fn test_app_error_and_operations() {
+        let mut status = app_status();
+        let time = "2025-07-28T13:20:00Z".parse::<DateTime<Utc>>().unwrap();
+        status.error_and_time_with_source.error_source_type = "types.VolumeStatus".to_string();
+        let description = &mut status.error_and_time_with_source.error_description;
+        description.error = "no space left on device".to_string();
+        description.error_time = time;
+        description.error_severity = ErrorSeverity::Error;
+        status.restart_inprogress = Inprogress::BringDown;
+        status.restart_started_at = time;
+        status.purge_inprogress = Inprogress::RecreateVolumes;
+
+        let app = AppInstance::from(status);
+        let AppInstanceState::Error(SwState::Downloading, error) = &app.state else {
+            panic!("Unexpected state {:?}", app.state);
+        };
+        assert_eq!(error.error, "no space left on device");
+        assert_eq!(error.source, "types.VolumeStatus");
+        assert_eq!(error.time, time);
+        assert_eq!(error.severity.to_string(), "error");
+        assert_eq!(
+            app.restart,
+            Some(AppOperation {
+                stage: Inprogress::BringDown,
+                started_at: Some(time),
+            })
+        );
+        assert_eq!(app.purge.unwrap().started_at, None);
+    }
+
+    fn network_status(file: &str) -> DeviceNetworkStatus {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data/ipc-tests")
+            .join(file);
+        let data = std::fs::read_to_string(path).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&data).unwrap();
+        serde_json::from_value(json["message"].clone()).unwrap()
+    }
+
+    #[test]
+    fn test_network_status_dpc() {
+        let dpc_list = || crate::dpc::load_dpc_list("eve_ipc_message-err-1.json");
+        let dpcs = dpc_list().port_config_list.unwrap();
+        // several DPCs use the key "manual", EVE applied the third one
+        let status = |index| {
+            let mut status = network_status("eve_ipc_message-err-5.json");
+            assert_eq!(status.dpc_key, "manual");
+            status.current_index = index;
+            status
+        };
+
+        // the status arrives before the DPC list
+        let mut model = MonitorModel::default();
+        model.update_network_status(status(2));
+        assert_eq!(model.get_current_dpc(), None);
+        assert!(!model.network.is_empty());
+        model.set_dpc_list(dpc_list());
+        assert_eq!(model.get_current_dpc(), Some(&dpcs[2]));
+        let expected = status(2)
+            .ports
+            .unwrap()
+            .iter()
+            .map(|p| NetworkInterfaceStatus::from_port(p, Some(&dpcs[2])))
+            .collect::<Vec<_>>();
+        assert_eq!(model.network, expected);
+
+        // a new DPC moves the applied one down the list
+        let mut longer = dpc_list();
+        let mut added = dpcs[0].clone();
+        added.time_priority = dpcs[0].time_priority + chrono::Duration::seconds(1);
+        longer.port_config_list.as_mut().unwrap().insert(0, added);
+        model.set_dpc_list(longer);
+        assert_eq!(model.get_current_dpc(), Some(&dpcs[2]));
+        assert_eq!(model.network, expected);
+
+        // the index does not match the key, the key is all there is
+        let mut model = MonitorModel::default();
+        model.set_dpc_list(dpc_list());
+        model.update_network_status(status(1));
+        assert_eq!(model.get_current_dpc(), Some(&dpcs[0]));
+    }
+
+    #[test]
+    fn test_app_command_progress() {
+        let mut model = MonitorModel::default();
+        let status = app_status();
+        let uuid = status.uuid_and_version.uuid;
+        model.update_app_status(status.clone());
+
+        model.request_app_command(uuid, AppCommand::Restart);
+        let progress = |model: &MonitorModel| model.app_commands[&uuid].progress.clone();
+        assert_eq!(progress(&model), AppCommandProgress::Requested);
+        model.accept_app_command(uuid);
+        assert_eq!(progress(&model), AppCommandProgress::Accepted);
+
+        // a status sent before EVE started the restart
+        model.update_app_status(status.clone());
+        assert_eq!(progress(&model), AppCommandProgress::Accepted);
+
+        let mut restarting = status.clone();
+        restarting.restart_inprogress = Inprogress::BringDown;
+        model.update_app_status(restarting);
+        assert_eq!(
+            model.app_commands[&uuid].to_string(),
+            "restart: bringing down"
+        );
+
+        model.update_app_status(status.clone());
+        assert_eq!(progress(&model), AppCommandProgress::Done);
+
+        model.request_app_command(uuid, AppCommand::Stop);
+        let mut halting = status.clone();
+        halting.state = SwState::Halting;
+        model.update_app_status(halting);
+        assert_eq!(
+            progress(&model),
+            AppCommandProgress::InProgress("Halting".to_string())
+        );
+        let mut halted = status.clone();
+        halted.state = SwState::Halted;
+        model.update_app_status(halted);
+        assert_eq!(model.app_commands[&uuid].to_string(), "stop done");
+
+        // the error for an older command does not touch the last one
+        model.request_app_command(uuid, AppCommand::Start);
+        model.fail_app_command(uuid, AppCommand::Stop, "app is halted");
+        assert_eq!(progress(&model), AppCommandProgress::Requested);
+        model.fail_app_command(uuid, AppCommand::Start, "no memory");
+        assert_eq!(
+            model.app_commands[&uuid].to_string(),
+            "start failed: no memory"
+        );
+        // and no status changes it afterwards
+        let mut running = status;
+        running.state = SwState::Running;
+        model.update_app_status(running);
+        assert_eq!(
+            progress(&model),
+            AppCommandProgress::Failed("no memory".to_string())
+        );
+    }
+
+    #[test]
+    fn test_quick_restart_is_done() {
+        let mut model = MonitorModel::default();
+        let status = app_status();
+        let uuid = status.uuid_and_version.uuid;
+        let sent_at: DateTime<Utc> = "2025-07-28T13:20:00Z".parse().unwrap();
+        for command in [AppCommand::Restart, AppCommand::Purge] {
+            model.request_app_command(uuid, command);
+            model.app_commands.get_mut(&uuid).unwrap().sent_at = sent_at;
+
+            // the last restart and purge started before the command was sent
+            let mut before = status.clone();
+            before.restart_started_at = sent_at - chrono::Duration::minutes(5);
+            before.purge_started_at = sent_at - chrono::Duration::minutes(5);
+            model.update_app_status(before);
+            assert_eq!(
+                model.app_commands[&uuid].progress,
+                AppCommandProgress::Requested
+            );
+
+            // the operation was over before any stage was reported
+            let mut after = status.clone();
+            after.restart_started_at = sent_at + chrono::Duration::seconds(1);
+            after.purge_started_at = sent_at + chrono::Duration::seconds(1);
+            model.update_app_status(after);
+            assert_eq!(
+                model.app_commands[&uuid].to_string(),
+                format!("{} done", command)
+            );
+        }
+    }
+
+    #[test]
+    fn test_app_list_drops_commands_of_deleted_apps() {
+        let mut model = MonitorModel::default();
+        let status = app_status();
+        let uuid = status.uuid_and_version.uuid;
+        model.update_app_status(status.clone());
+        model.request_app_command(uuid, AppCommand::Stop);
+
+        model.update_app_list(AppsList { apps: vec![status] });
+        assert!(model.app_commands.contains_key(&uuid));
+
+        model.update_app_list(AppsList { apps: vec![] });
+        assert!(model.app_commands.is_empty());
+    }
+}
@@ -10,42 +10,161 @@ use crossterm::{
     },
 };
 
+use futures::Stream;
 use std::{
     fs::{self, File},
+    io,
     ops::{Deref, DerefMut},
+    os::fd::AsFd,
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
 };
 
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{
+    backend::{Backend, ClearType, CrosstermBackend, WindowSize},
+    buffer::Cell,
+    layout::{Position, Size},
+    Terminal,
+};
+
+#[cfg(test)]
+use ratatui::backend::TestBackend;
+
+// set in development mode. The UI is rendered to stdout of the current
+// terminal instead of /dev/tty
+static USE_STDOUT: AtomicBool = AtomicBool::new(false);
+
+/// Source of terminal events consumed by `Application::run`
+pub type TerminalEventStream =
+    Pin<Box<dyn Stream<Item = io::Result<crossterm::event::Event>> + Send>>;
+
+/// Backend the UI is rendered to. ratatui `Backend` is not object safe, so
+/// the real terminal and the test backend are wrapped into an enum
+#[derive(Debug)]
+pub enum TerminalBackend {
+    Crossterm(CrosstermBackend<File>),
+    #[cfg(test)]
+    Test(TestBackend),
+}
+
+macro_rules! dispatch {
+    ($self:ident, $backend:ident => $call:expr) => {
+        match $self {
+            TerminalBackend::Crossterm($backend) => $call,
+            #[cfg(test)]
+            TerminalBackend::Test($backend) => $call,
+        }
+    };
+}
+
+impl Backend for TerminalBackend {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        dispatch!(self, b => b.draw(content))
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        dispatch!(self, b => b.append_lines(n))
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        dispatch!(self, b => b.hide_cursor())
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        dispatch!(self, b => b.show_cursor())
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        dispatch!(self, b => b.get_cursor_position())
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        dispatch!(self, b => b.set_cursor_position(position))
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        dispatch!(self, b => b.clear())
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        dispatch!(self, b => b.clear_region(clear_type))
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        dispatch!(self, b => b.size())
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        dispatch!(self, b => b.window_size())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        dispatch!(self, b => Backend::flush(b))
+    }
+}
 
 #[derive(Debug)]
 pub struct TerminalWrapper {
-    terminal: Terminal<CrosstermBackend<File>>,
+    terminal: Terminal<TerminalBackend>,
 }
 
 impl TerminalWrapper {
     fn tty_fd() -> Result<File> {
+        if USE_STDOUT.load(Ordering::Relaxed) {
+            let fd = std::io::stdout().as_fd().try_clone_to_owned()?;
+            return Ok(File::from(fd));
+        }
         Ok(fs::OpenOptions::new()
             .read(true)
             .write(true)
             .open("/dev/tty")?)
     }
 
-    fn init_terminal(file: File) -> Result<Terminal<CrosstermBackend<File>>> {
+    fn init_terminal(file: File) -> Result<Terminal<TerminalBackend>> {
         println!("Initializing terminal");
         // No stdout after this point
         execute!(&file, EnterAlternateScreen, cursor::Hide)?;
         enable_raw_mode()?;
-        let mut terminal = Terminal::new(CrosstermBackend::new(file))?;
+        let backend = TerminalBackend::Crossterm(CrosstermBackend::new(file));
+        let mut terminal = Terminal::new(backend)?;
         terminal.clear()?;
         Ok(terminal)
     }
 
-    pub fn open_terminal() -> Result<Self> {
+    pub fn open_terminal(use_stdout: bool) -> Result<Self> {
+        USE_STDOUT.store(use_stdout, Ordering::Relaxed);
         let file = Self::tty_fd()?;
         let terminal = Self::init_terminal(file)?;
         Ok(Self { terminal })
     }
 
+    /// Create an off-screen terminal of the given size for tests. The real
+    /// terminal is not touched
+    #[cfg(test)]
+    pub fn open_test_terminal(width: u16, height: u16) -> Result<Self> {
+        let backend = TerminalBackend::Test(TestBackend::new(width, height));
+        Ok(Self {
+            terminal: Terminal::new(backend)?,
+        })
+    }
+
+    /// Test backend if the terminal was created with `open_test_terminal`.
+    /// Gives access to the rendered buffer
+    #[cfg(test)]
+    pub fn test_backend(&self) -> Option<&TestBackend> {
+        match self.terminal.backend() {
+            TerminalBackend::Test(backend) => Some(backend),
+            TerminalBackend::Crossterm(_) => None,
+        }
+    }
+
+    fn is_test(&self) -> bool {
+        !matches!(self.terminal.backend(), TerminalBackend::Crossterm(_))
+    }
+
     pub fn close_terminal() -> Result<()> {
         if is_raw_mode_enabled()? {
             let mut file = Self::tty_fd()?;
@@ -58,20 +177,30 @@ impl TerminalWrapper {
         Ok(())
     }
 
-    pub fn get_stream() -> crossterm::event::EventStream {
-        crossterm::event::EventStream::new()
+    pub fn get_stream() -> TerminalEventStream {
+        Box::pin(crossterm::event::EventStream::new())
+    }
+
+    /// Event stream replaying `events`. The stream ends after the last event
+    /// which makes the application exit
+    #[cfg(test)]
+    pub fn scripted_stream(events: Vec<crossterm::event::Event>) -> TerminalEventStream {
+        Box::pin(futures::stream::iter(events.into_iter().map(Ok)))
     }
 }
 
 impl Drop for TerminalWrapper {
     fn drop(&mut self) {
+        if self.is_test() {
+            return;
+        }
         let _ = self.terminal.clear();
         let _ = Self::close_terminal();
     }
 }
 
 impl Deref for TerminalWrapper {
-    type Target = Terminal<CrosstermBackend<File>>;
+    type Target = Terminal<TerminalBackend>;
 
     fn deref(&self) -> &Self::Target {
         &self.terminal
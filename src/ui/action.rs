@@ -1,7 +1,10 @@
 // Copyright (c) 2024-2025 Zededa, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{actions::MonActions, traits::IAction};
+use uuid::Uuid;
+
+use crate::{actions::MonActions, model::model::AppCommand, traits::IAction};
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum UiActions {
     Quit,
@@ -13,8 +16,22 @@ pub enum UiActions {
     DismissDialog,
     AppAction(MonActions),
     EditIfaceConfig(String),
+    EditWifiConfig(String),
+    EditCellularConfig(String),
+    ShowCellularStatus(String),
+    ShowL2Topology,
+    CreateVlan,
+    CreateBond,
+    EditL2Link(String),
+    ShowDpcList,
+    ShowDpc(usize),
+    ShowDpcVerification,
+    // title and the key bindings of a page with their description
+    ShowKeyHelp(String, Vec<(String, String)>),
     TabChanged(String, String),
     ChangeServer,
+    CheckServer,
+    AppCommand(Uuid, AppCommand),
 }
 
 #[derive(Debug, Clone)]
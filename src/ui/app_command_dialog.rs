@@ -0,0 +1,166 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::rc::Rc;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use log::debug;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Margin, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use uuid::Uuid;
+
+use crate::{
+    actions::MonActions,
+    model::model::{AppCommand, Model},
+    traits::IWindow,
+    ui::action::UiActions,
+};
+
+use super::{
+    action::Action, tools::centered_rect_fixed, widgets::button::ButtonElement, window::Window,
+};
+
+struct AppCommandDialogState {
+    app_name: String,
+    uuid: Uuid,
+    command: AppCommand,
+}
+
+fn on_init(w: &mut Window<AppCommandDialogState>) {
+    let command = w.state.command.to_string();
+    w.add_widget(&command, ButtonElement::new(&command));
+    w.add_widget("cancel", ButtonElement::new("cancel"));
+    w.set_focus_tracker_tab_order(vec![command.as_str(), "cancel"]);
+}
+
+fn consequence(command: AppCommand) -> &'static str {
+    match command {
+        AppCommand::Restart => "The app instance is shut down and booted again.",
+        AppCommand::Stop => "The app instance is shut down until it is started again.",
+        AppCommand::Start => "The app instance is booted.",
+        AppCommand::Purge => {
+            "The app instance is shut down and its volumes are recreated. \
+             All data written to the volumes is lost."
+        }
+    }
+}
+
+fn do_render(
+    w: &mut Window<AppCommandDialogState>,
+    _rect: &Rect,
+    frame: &mut Frame<'_>,
+    _model: &Rc<Model>,
+) {
+    let frame_rect = w.get_layout("frame");
+    let content_rect = w.get_layout("content");
+
+    frame.render_widget(Clear {}, frame_rect);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(Color::White))
+        .style(Style::default().bg(Color::Black))
+        .title(w.name.clone());
+    frame.render_widget(block, frame_rect);
+
+    let state = &w.state;
+    let consequence = match state.command {
+        AppCommand::Purge => consequence(state.command).red(),
+        _ => consequence(state.command).white(),
+    };
+    let text = Text::from(vec![
+        Line::from(vec![
+            format!("{} ", capitalize(&state.command.to_string())).white(),
+            state.app_name.clone().white().bold(),
+            "?".white(),
+        ]),
+        Line::from(state.uuid.to_string()).dark_gray(),
+        Line::default(),
+        Line::from(consequence),
+    ]);
+    frame.render_widget(
+        Paragraph::new(text).wrap(Wrap { trim: false }),
+        content_rect,
+    );
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn do_layout(w: &mut Window<AppCommandDialogState>, rect: &Rect, _model: &Rc<Model>) {
+    let rect = centered_rect_fixed(60, 11, *rect);
+    let content_with_buttons = rect.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+
+    w.update_layout("frame", rect);
+
+    let [content, buttons] = Layout::vertical(vec![Constraint::Fill(1), Constraint::Length(3)])
+        .areas(content_with_buttons);
+    w.update_layout("content", content);
+
+    let command = w.state.command.to_string();
+    let [confirm, cancel] = Layout::horizontal(vec![
+        Constraint::Length(command.len() as u16 + 4),
+        Constraint::Length(10),
+    ])
+    .flex(Flex::End)
+    .areas(buttons);
+    w.update_layout(&command, confirm);
+    w.update_layout("cancel", cancel);
+}
+
+fn on_key_event(w: &mut Window<AppCommandDialogState>, key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => Some(Action::new(&w.name, UiActions::DismissDialog)),
+        _ => None,
+    }
+}
+
+fn on_child_ui_action(
+    w: &mut Window<AppCommandDialogState>,
+    source: &String,
+    action: &UiActions,
+) -> Option<Action> {
+    debug!("on_child_ui_action: {}:{:?}", source, action);
+    match action {
+        UiActions::ButtonClicked(_) if *source == w.state.command.to_string() => Some(Action::new(
+            &w.name,
+            UiActions::AppAction(MonActions::AppCommandConfirmed(
+                w.state.uuid,
+                w.state.command,
+            )),
+        )),
+        UiActions::ButtonClicked(_) => Some(Action::new(&w.name, UiActions::DismissDialog)),
+        _ => None,
+    }
+}
+
+/// Ask for confirmation before `command` is sent to the app instance
+pub fn create_app_command_dialog(app_name: &str, uuid: Uuid, command: AppCommand) -> impl IWindow {
+    Window::builder("Confirm app instance command")
+        .with_on_init(on_init)
+        .with_layout(do_layout)
+        .with_render(do_render)
+        .with_on_key_event(on_key_event)
+        .with_on_child_ui_action(on_child_ui_action)
+        .with_state(AppCommandDialogState {
+            app_name: app_name.to_string(),
+            uuid,
+            command,
+        })
+        .build()
+        .unwrap()
+}
@@ -1,36 +1,67 @@
 // Copyright (c) 2024-2025 Zededa, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::rc::Rc;
+use std::{cmp::Ordering, collections::HashMap};
 
-use crossterm::event::{KeyCode, KeyModifiers};
+use chrono::{DateTime, Utc};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
-    layout::{Alignment, Constraint, Rect},
+    layout::{Alignment, Constraint, Layout, Rect},
     style::{Color, Style, Stylize},
-    text::Text,
+    text::{Line, Span, Text},
     widgets::{
-        Block, BorderType, Borders, Cell, HighlightSpacing, Padding, Row, StatefulWidget, Table,
-        TableState,
+        Block, BorderType, Borders, Cell, HighlightSpacing, Padding, Paragraph, Row,
+        StatefulWidget, Table, TableState, Wrap,
     },
     Frame,
 };
+use strum::Display;
+use uuid::Uuid;
 
 use crate::{
     events::Event,
-    model::model::{AppInstance, AppInstanceState, Model},
+    ipc::eve_types::{SwState, VolumeRefStatus},
+    model::model::{
+        AppCommand, AppCommandProgress, AppCommandStatus, AppError, AppInstance, AppInstanceState,
+        AppOperation, Model,
+    },
     traits::{IEventHandler, IPresenter, IWindow},
 };
 
-use super::traits::{ISelectable, ISelector};
+use super::{
+    action::{Action, UiActions},
+    networkpage::format_bytes,
+    theme,
+    traits::{ISelectable, ISelector},
+};
+
+const LABEL_LENGTH: usize = 11;
+
+// all keys of the page, the status bar has room for a few of them only
+const KEY_HELP: [(&str, &str); 11] = [
+    ("Up/Down", "select an application"),
+    ("Ctrl+Home/End", "select the first/last application"),
+    ("Enter", "expand/collapse the details"),
+    ("s", "change the sort order"),
+    ("f", "change the filter"),
+    ("/", "search by name"),
+    ("Esc", "clear the search"),
+    ("r", "restart the application"),
+    ("t", "start/stop the application"),
+    ("p", "purge the application"),
+    ("?", "this help"),
+];
 
 #[derive(Debug, Default)]
 struct ApplicationList {
     state: TableState,
     size: usize,
+    // uuids of the apps in the order they are displayed
+    uuids: Vec<Uuid>,
 }
 
 impl ISelectable for ApplicationList {
-    type Item = String;
+    type Item = Uuid;
 
     fn current_index(&self) -> Option<usize> {
         self.state.selected()
@@ -45,13 +76,98 @@ impl ISelectable for ApplicationList {
     }
 
     fn selected_item(&self) -> Option<Self::Item> {
-        None
+        self.state
+            .selected()
+            .and_then(|i| self.uuids.get(i).copied())
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Display)]
+#[strum(serialize_all = "lowercase")]
+enum AppSortKey {
+    #[default]
+    Name,
+    State,
+    #[strum(to_string = "UUID")]
+    Uuid,
+}
+
+impl AppSortKey {
+    fn next(self) -> Self {
+        match self {
+            AppSortKey::Name => AppSortKey::State,
+            AppSortKey::State => AppSortKey::Uuid,
+            AppSortKey::Uuid => AppSortKey::Name,
+        }
+    }
+
+    fn compare(self, a: &AppInstance, b: &AppInstance) -> Ordering {
+        // the name and the UUID break ties so the order is stable
+        let by_name = a.name.cmp(&b.name).then(a.uuid.cmp(&b.uuid));
+        match self {
+            AppSortKey::Name => by_name,
+            AppSortKey::State => (a.sw_state() as u8)
+                .cmp(&(b.sw_state() as u8))
+                .then(by_name),
+            AppSortKey::Uuid => a.uuid.cmp(&b.uuid),
+        }
+    }
+}
+
+/// Groups of app states the list can be filtered by
+#[derive(Debug, Default, Clone, Copy, PartialEq, Display)]
+#[strum(serialize_all = "lowercase")]
+enum AppFilter {
+    #[default]
+    All,
+    Running,
+    Error,
+    Starting,
+    Halted,
+}
+
+impl AppFilter {
+    fn next(self) -> Self {
+        match self {
+            AppFilter::All => AppFilter::Running,
+            AppFilter::Running => AppFilter::Error,
+            AppFilter::Error => AppFilter::Starting,
+            AppFilter::Starting => AppFilter::Halted,
+            AppFilter::Halted => AppFilter::All,
+        }
+    }
+
+    fn matches(self, app: &AppInstance) -> bool {
+        let state = app.sw_state();
+        let failed = matches!(app.state, AppInstanceState::Error(..))
+            || matches!(state, SwState::Broken | SwState::Failed | SwState::Unknown);
+        let halted = matches!(
+            state,
+            SwState::Pausing | SwState::Paused | SwState::Halting | SwState::Halted
+        );
+        match self {
+            AppFilter::All => true,
+            AppFilter::Error => failed,
+            AppFilter::Running => !failed && state == SwState::Running,
+            AppFilter::Halted => !failed && halted,
+            AppFilter::Starting => !failed && !halted && state != SwState::Running,
+        }
     }
 }
 
 #[derive(Debug, Default)]
 pub struct ApplicationsPage {
     list: ApplicationList,
+    // the details of the selected app fill the page
+    expanded: bool,
+    sort: AppSortKey,
+    filter: AppFilter,
+    // case insensitive substring of the app name
+    search: String,
+    // typed characters go to the search string
+    searching: bool,
+    // the selected app is halted and can be started
+    selected_halted: bool,
 }
 
 impl ApplicationsPage {
@@ -60,7 +176,13 @@ impl ApplicationsPage {
             ..Default::default()
         }
     }
-    fn render_app_list(&mut self, model: &Rc<Model>, list_rect: Rect, frame: &mut Frame) {
+    fn render_app_list(
+        &mut self,
+        apps: &[&AppInstance],
+        total: usize,
+        list_rect: Rect,
+        frame: &mut Frame,
+    ) {
         // create header for the table
         let header = Row::new(vec![
             Cell::from("Name").style(Style::default()),
@@ -68,15 +190,22 @@ impl ApplicationsPage {
             Cell::from("Status").style(Style::default()),
         ]);
 
-        // create list items from the interface
-        let rows = model
-            .borrow()
-            .apps
+        let rows = apps
             .iter()
-            .map(|(_, app)| info_row_from_app(app))
+            .map(|app| info_row_from_app(app))
             .collect::<Vec<_>>();
 
-        self.list.size = rows.len();
+        let mut view = format!(
+            "{}/{} | sort: {} | filter: {}",
+            rows.len(),
+            total,
+            self.sort,
+            self.filter
+        );
+        if self.searching || !self.search.is_empty() {
+            let cursor = if self.searching { "_" } else { "" };
+            view.push_str(&format!(" | /{}{}", self.search, cursor));
+        }
 
         // create a surrounding block for the list
         let block = Block::default()
@@ -86,9 +215,16 @@ impl ApplicationsPage {
             .border_type(BorderType::Plain)
             // .border_style(Style::default().fg(Color::White).bg(Color::Black))
             // .style(Style::default().bg(Color::Black));
-            .padding(Padding::new(1, 1, 1, 1));
+            .padding(Padding::new(1, 1, 0, 1));
+
+        // the sort order, the filter and the search take the first line
+        let [view_rect, table_rect] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)])
+                .areas(block.inner(list_rect));
+        frame.render_widget(block, list_rect);
+        frame.render_widget(Line::from(view).right_aligned().dark_gray(), view_rect);
 
-        let bar = " █ ";
+        let bar = theme::highlight_bar();
 
         // Create a List from all list items and highlight the currently selected one
         let list = Table::new(
@@ -99,7 +235,6 @@ impl ApplicationsPage {
                 Constraint::Fill(14),
             ],
         )
-        .block(block)
         .row_highlight_style(Style::new().bg(Color::DarkGray))
         // .highlight_symbol(">")
         .highlight_symbol(Text::from(vec![
@@ -113,20 +248,131 @@ impl ApplicationsPage {
         .highlight_spacing(HighlightSpacing::Always)
         .header(header);
 
-        StatefulWidget::render(list, list_rect, frame.buffer_mut(), &mut self.list.state);
+        StatefulWidget::render(list, table_rect, frame.buffer_mut(), &mut self.list.state);
+    }
+
+    /// Apps that pass the filter and the search in the selected order
+    fn visible_apps<'a>(&self, apps: &'a HashMap<Uuid, AppInstance>) -> Vec<&'a AppInstance> {
+        let search = self.search.to_lowercase();
+        let mut apps = apps
+            .values()
+            .filter(|app| self.filter.matches(app))
+            .filter(|app| app.name.to_lowercase().contains(&search))
+            .collect::<Vec<_>>();
+        apps.sort_by(|a, b| self.sort.compare(a, b));
+        apps
+    }
+
+    /// Keep the selected app selected when the list is sorted or filtered
+    fn keep_selection(&mut self, uuids: Vec<Uuid>) {
+        let index = match self.list.selected_item() {
+            Some(uuid) => uuids.iter().position(|u| *u == uuid),
+            None => self.list.state.selected(),
+        };
+        let last = uuids.len().checked_sub(1);
+        self.list
+            .state
+            .select(index.zip(last).map(|(i, last)| i.min(last)));
+        self.list.uuids = uuids;
+    }
+
+    fn handle_search_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.search.push(c)
+            }
+            KeyCode::Backspace => {
+                self.search.pop();
+            }
+            KeyCode::Enter => self.searching = false,
+            KeyCode::Esc => {
+                self.searching = false;
+                self.search.clear();
+            }
+            KeyCode::Up => self.list.select_previous(),
+            KeyCode::Down => self.list.select_next(),
+            _ => {}
+        }
+    }
+
+    /// Ask for confirmation of a command for the selected app
+    fn app_command(&self, command: AppCommand) -> Option<Action> {
+        let uuid = self.list.selected_item()?;
+        Some(Action::new("apps", UiActions::AppCommand(uuid, command)))
+    }
+
+    fn render_app_details(
+        &self,
+        app: &AppInstance,
+        command: Option<&AppCommandStatus>,
+        rect: Rect,
+        frame: &mut Frame,
+    ) {
+        let block = Block::default()
+            .title(format!("{} Details", app.name))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Plain);
+
+        let paragraph = Paragraph::new(details_text(app, command))
+            .block(block)
+            .wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, rect);
     }
 }
 
-impl IWindow for ApplicationsPage {}
+impl IWindow for ApplicationsPage {
+    fn status_bar_tips(&self) -> Option<String> {
+        let (up, down) = theme::scroll_arrows();
+        if self.searching {
+            Some("type to search by name | Enter - done | Esc - clear search".to_string())
+        } else if self.expanded {
+            Some(format!(
+                "{up}/{down} - navigate | Enter/Esc - collapse details"
+            ))
+        } else {
+            Some(format!(
+                "{up}/{down} - navigate | Enter - expand details | / - search | ? - more keys"
+            ))
+        }
+    }
+}
 
 impl IEventHandler for ApplicationsPage {
-    fn handle_event(&mut self, event: Event) -> Option<super::action::Action> {
+    fn handle_event(&mut self, event: Event) -> Option<Action> {
         match event {
+            Event::Key(key) if self.searching => self.handle_search_key(key),
             Event::Key(key) => match key.code {
                 KeyCode::Up => self.list.select_previous(),
                 KeyCode::Down => self.list.select_next(),
                 KeyCode::Home if key.modifiers == KeyModifiers::CONTROL => self.list.select_first(),
                 KeyCode::End if key.modifiers == KeyModifiers::CONTROL => self.list.select_last(),
+                KeyCode::Enter if self.list.selected_item().is_some() => {
+                    self.expanded = !self.expanded
+                }
+                KeyCode::Esc if self.expanded => self.expanded = false,
+                KeyCode::Esc => self.search.clear(),
+                KeyCode::Char('s') => self.sort = self.sort.next(),
+                KeyCode::Char('f') => self.filter = self.filter.next(),
+                KeyCode::Char('/') => {
+                    self.expanded = false;
+                    self.searching = true;
+                }
+                KeyCode::Char('r') => return self.app_command(AppCommand::Restart),
+                KeyCode::Char('t') if self.selected_halted => {
+                    return self.app_command(AppCommand::Start)
+                }
+                KeyCode::Char('t') => return self.app_command(AppCommand::Stop),
+                KeyCode::Char('p') => return self.app_command(AppCommand::Purge),
+                KeyCode::Char('?') => {
+                    let keys = KEY_HELP
+                        .iter()
+                        .map(|(key, help)| (key.to_string(), help.to_string()))
+                        .collect();
+                    return Some(Action::new(
+                        "apps",
+                        UiActions::ShowKeyHelp("Application keys".to_string(), keys),
+                    ));
+                }
                 _ => {}
             },
             _ => {}
@@ -152,6 +398,150 @@ fn info_row_from_app<'a, 'b>(app: &'a AppInstance) -> Row<'b> {
     Row::new(cells).height(height)
 }
 
+fn field<'a>(label: &str, value: impl Into<Span<'a>>) -> Line<'a> {
+    Line::from(vec![
+        format!("{:<LABEL_LENGTH$}", label).yellow(),
+        value.into(),
+    ])
+}
+
+fn format_time(time: Option<DateTime<Utc>>) -> String {
+    match time {
+        Some(time) => time.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        None => "N/A".to_string(),
+    }
+}
+
+fn operation_line<'a>(label: &str, operation: &AppOperation) -> Line<'a> {
+    let mut value = operation.stage.to_string();
+    if let Some(started_at) = operation.started_at {
+        value.push_str(&format!(" since {}", format_time(Some(started_at))));
+    }
+    field(label, value.white())
+}
+
+fn error_lines<'a>(error: &AppError, indent: usize) -> Vec<Line<'a>> {
+    let label = |label: &str| format!("{}{:<LABEL_LENGTH$}", " ".repeat(indent), label);
+    let mut lines = vec![
+        Line::from(vec![label("Error").yellow(), error.error.clone().red()]),
+        Line::from(vec![label("Source").yellow(), error.source.clone().white()]),
+        Line::from(vec![
+            label("Time").yellow(),
+            format_time(Some(error.time)).white(),
+            format!(" ({})", error.severity).dark_gray(),
+        ]),
+    ];
+    if !error.retry_condition.is_empty() {
+        lines.push(Line::from(vec![
+            label("Retry").yellow(),
+            error.retry_condition.clone().white(),
+        ]));
+    }
+    lines
+}
+
+fn volume_lines<'a>(volume: &VolumeRefStatus) -> Vec<Line<'a>> {
+    let mut summary = vec![
+        "  ".into(),
+        volume.display_name.clone().white(),
+        format!(" {}", volume.state).green(),
+        format!(" {}", volume.content_format).white(),
+    ];
+    if volume.max_vol_size > 0 {
+        summary.push(format!(" {}", format_bytes(volume.max_vol_size)).white());
+    }
+    if volume.read_only {
+        summary.push(" read-only".dark_gray());
+    }
+    let mut lines = vec![Line::from(summary)];
+    if let Some(error) = AppError::from_status(&volume.error_and_time_with_source) {
+        lines.extend(error_lines(&error, 4));
+    }
+    lines
+}
+
+fn details_text<'a>(app: &AppInstance, command: Option<&AppCommandStatus>) -> Text<'a> {
+    let (state, error) = match &app.state {
+        AppInstanceState::Normal(st) => (st.to_string().green(), None),
+        AppInstanceState::Error(st, error) => (st.to_string().red(), Some(error)),
+    };
+    let activation = if app.activate_in_progress {
+        " (activating)"
+    } else if app.activated {
+        " (activated)"
+    } else {
+        " (not activated)"
+    };
+
+    let resources = &app.resources;
+    // memory is in KiB
+    let vcpus = if resources.vcpus == 1 {
+        "vCPU"
+    } else {
+        "vCPUs"
+    };
+    let vnc = if resources.enable_vnc {
+        format!("display {}", resources.vnc_display)
+    } else {
+        "disabled".to_string()
+    };
+
+    let mut lines = vec![
+        field("Name", app.name.clone().white()),
+        field("UUID", app.uuid.to_string().white()),
+        field("Version", app.version.clone().white()),
+        Line::from(vec![
+            format!("{:<LABEL_LENGTH$}", "State").yellow(),
+            state,
+            activation.dark_gray(),
+        ]),
+        field(
+            "Resources",
+            format!(
+                "{} {}, {} MiB memory, {}",
+                resources.vcpus,
+                vcpus,
+                resources.memory / 1024,
+                resources.virtualization_mode
+            )
+            .white(),
+        ),
+        field("VNC", vnc.white()),
+        field("Boot time", format_time(app.boot_time).white()),
+        field("Start time", format_time(app.start_time).white()),
+    ];
+    if let Some(command) = command {
+        let progress = match command.progress {
+            AppCommandProgress::Done => command.to_string().green(),
+            AppCommandProgress::Failed(_) => command.to_string().red(),
+            _ => command.to_string().yellow(),
+        };
+        lines.push(field("Command", progress));
+    }
+    if let Some(restart) = &app.restart {
+        lines.push(operation_line("Restart", restart));
+    }
+    if let Some(purge) = &app.purge {
+        lines.push(operation_line("Purge", purge));
+    }
+    if app.missing_network {
+        lines.push(Line::from("Network instance is missing").red());
+    }
+    if app.missing_memory {
+        lines.push(Line::from("Not enough memory to start").red());
+    }
+    if let Some(error) = error {
+        lines.extend(error_lines(error, 0));
+    }
+
+    lines.push(Line::from("Volumes").yellow());
+    if app.volumes.is_empty() {
+        lines.push(Line::from("  none").dark_gray());
+    }
+    lines.extend(app.volumes.iter().flat_map(volume_lines));
+    Text::from(lines)
+}
+
 impl IPresenter for ApplicationsPage {
     fn render(
         &mut self,
@@ -160,6 +550,34 @@ impl IPresenter for ApplicationsPage {
         model: &std::rc::Rc<Model>,
         _focused: bool,
     ) {
-        self.render_app_list(model, *area, frame);
+        // apps are stored in a HashMap, sort them so the order is stable
+        // between redraws and sessions
+        let model = model.borrow();
+        let apps = self.visible_apps(&model.apps);
+        self.keep_selection(apps.iter().map(|app| app.uuid).collect());
+        self.list.size = apps.len();
+
+        let Some(app) = self
+            .list
+            .selected_item()
+            .and_then(|uuid| model.apps.get(&uuid))
+        else {
+            self.expanded = false;
+            self.render_app_list(&apps, model.apps.len(), *area, frame);
+            return;
+        };
+
+        self.selected_halted = app.sw_state() == SwState::Halted;
+        let command = model.app_commands.get(&app.uuid);
+
+        if self.expanded {
+            self.render_app_details(app, command, *area, frame);
+            return;
+        }
+
+        let [list_rect, details_rect] =
+            Layout::vertical([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(*area);
+        self.render_app_list(&apps, model.apps.len(), list_rect, frame);
+        self.render_app_details(app, command, details_rect, frame);
     }
 }
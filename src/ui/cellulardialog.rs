@@ -0,0 +1,761 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::rc::Rc;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use log::debug;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Margin, Rect},
+    style::{Color, Style},
+    widgets::{Block, BorderType, Borders, Clear},
+    Frame,
+};
+
+use crate::{
+    actions::MonActions,
+    dpc::{
+        builder::{DpcField, FieldError},
+        cellular::{validate_cellular_config, CellularFieldError},
+    },
+    ipc::eve_types::{
+        CellularAccessPoint, ConnectivityProbeMethod, WwanAuthProtocol, WwanProbe, WwanRAT,
+    },
+    model::model::Model,
+    traits::{IWindow, TextInput},
+};
+
+use super::{
+    action::{Action, UiActions},
+    theme,
+    tools::centered_rect_min,
+    widgets::{
+        button::ButtonElement, input_field::InputFieldElement, label::LabelElement,
+        spin_box::SpinBoxElement, tab::TabElement,
+    },
+    window::Window,
+};
+
+const TAB_ACCESS_POINTS: &str = "Access points";
+const TAB_PROBE: &str = "Probe";
+
+// IP type values used by EVE and their labels
+const IP_TYPES: [(&str, &str); 4] = [
+    ("", "IP type: unspecified"),
+    ("ipv4", "IP type: IPv4"),
+    ("ipv4v6", "IP type: IPv4 and IPv6"),
+    ("ipv6", "IP type: IPv6"),
+];
+const AUTH_PROTOCOLS: [&str; 4] = ["Auth: none", "Auth: PAP", "Auth: CHAP", "Auth: PAP or CHAP"];
+const ROAMING: [&str; 2] = ["Roaming: allowed", "Roaming: forbidden"];
+const PROBE_STATES: [&str; 2] = ["Probe: enabled", "Probe: disabled"];
+const PROBE_METHODS: [&str; 3] = ["Method: default", "Method: ICMP", "Method: TCP"];
+
+const AP_INPUT_FIELDS: [&str; 7] = [
+    "sim_slot",
+    "apn",
+    "username",
+    "password",
+    "plmns",
+    "rats",
+    "attach_apn",
+];
+const PROBE_INPUT_FIELDS: [&str; 2] = ["probe_host", "probe_port"];
+
+/// Cellular access point as it is edited in the dialog
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessPointState {
+    pub sim_slot: String,
+    pub apn: String,
+    pub ip_type: String,
+    pub auth_protocol: WwanAuthProtocol,
+    pub username: String,
+    pub password: String,
+    // comma separated lists
+    pub preferred_plmns: String,
+    pub preferred_rats: String,
+    pub forbid_roaming: bool,
+    pub attach_apn: String,
+    // access point received from the controller. Keeps encrypted credentials
+    // and attach settings which are not edited in the dialog
+    pub original: CellularAccessPoint,
+}
+
+impl Default for AccessPointState {
+    fn default() -> Self {
+        Self::from(&CellularAccessPoint::default())
+    }
+}
+
+impl From<&CellularAccessPoint> for AccessPointState {
+    fn from(ap: &CellularAccessPoint) -> Self {
+        let rats = ap
+            .preferred_rats
+            .iter()
+            .flatten()
+            .filter_map(|rat| match rat {
+                WwanRAT::WwanRATGSM => Some("gsm"),
+                WwanRAT::WwanRATUMTS => Some("umts"),
+                WwanRAT::WwanRATLTE => Some("lte"),
+                WwanRAT::WwanRAT5GNR => Some("5gnr"),
+                WwanRAT::WwanRATUnspecified => None,
+            })
+            .collect::<Vec<_>>();
+        Self {
+            sim_slot: ap.sim_slot.to_string(),
+            apn: ap.apn.clone(),
+            ip_type: ap.ip_type.clone(),
+            auth_protocol: ap.auth_protocol.clone(),
+            username: ap.cleartext_credentials.username.clone(),
+            password: ap.cleartext_credentials.password.clone(),
+            preferred_plmns: ap.preferred_plmns.clone().unwrap_or_default().join(", "),
+            preferred_rats: rats.join(", "),
+            forbid_roaming: ap.forbid_roaming,
+            attach_apn: ap.attach_apn.clone(),
+            original: ap.clone(),
+        }
+    }
+}
+
+impl AccessPointState {
+    fn credentials_status(&self) -> String {
+        let cipher = &self.original.encrypted_credentials;
+        match self.auth_protocol {
+            WwanAuthProtocol::None => "Credentials: not required".to_string(),
+            _ if self.keeps_encrypted_credentials() => {
+                if cipher.error_and_time.is_error() {
+                    format!(
+                        "Credentials: encrypted by the controller, error: {}",
+                        cipher.error_and_time.error_description.error
+                    )
+                } else {
+                    "Credentials: encrypted by the controller".to_string()
+                }
+            }
+            _ if cipher.is_cipher => {
+                "Credentials: encrypted by the controller, will be replaced".to_string()
+            }
+            _ => "Credentials: not encrypted".to_string(),
+        }
+    }
+
+    fn label(&self) -> String {
+        let slot = match self.sim_slot.trim() {
+            "" | "0" => "any SIM".to_string(),
+            s => format!("SIM {}", s),
+        };
+        let apn = if self.apn.is_empty() {
+            "<new>"
+        } else {
+            &self.apn
+        };
+        format!("{}: {}", slot, apn)
+    }
+}
+
+/// Connectivity probe settings as they are edited in the dialog
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeState {
+    pub disable: bool,
+    pub method: ConnectivityProbeMethod,
+    pub host: String,
+    pub port: String,
+}
+
+impl From<&WwanProbe> for ProbeState {
+    fn from(probe: &WwanProbe) -> Self {
+        let p = &probe.user_defined_probe;
+        Self {
+            disable: probe.disable,
+            method: p.method.clone(),
+            host: p.probe_host.clone(),
+            port: if p.probe_port == 0 {
+                String::new()
+            } else {
+                p.probe_port.to_string()
+            },
+        }
+    }
+}
+
+fn auth_index(auth: &WwanAuthProtocol) -> usize {
+    match auth {
+        WwanAuthProtocol::None => 0,
+        WwanAuthProtocol::Pap => 1,
+        WwanAuthProtocol::Chap => 2,
+        WwanAuthProtocol::PapChap => 3,
+    }
+}
+
+fn auth_from_index(index: usize) -> WwanAuthProtocol {
+    match index {
+        1 => WwanAuthProtocol::Pap,
+        2 => WwanAuthProtocol::Chap,
+        3 => WwanAuthProtocol::PapChap,
+        _ => WwanAuthProtocol::None,
+    }
+}
+
+fn method_index(method: &ConnectivityProbeMethod) -> usize {
+    match method {
+        ConnectivityProbeMethod::ConnectivityProbeMethodNone => 0,
+        ConnectivityProbeMethod::ConnectivityProbeMethodICMP => 1,
+        ConnectivityProbeMethod::ConnectivityProbeMethodTCP => 2,
+    }
+}
+
+fn method_from_index(index: usize) -> ConnectivityProbeMethod {
+    match index {
+        1 => ConnectivityProbeMethod::ConnectivityProbeMethodICMP,
+        2 => ConnectivityProbeMethod::ConnectivityProbeMethodTCP,
+        _ => ConnectivityProbeMethod::ConnectivityProbeMethodNone,
+    }
+}
+
+struct CellularDialogState {
+    iface_name: String,
+    selected_tab: String,
+    access_points: Vec<AccessPointState>,
+    selected: usize,
+    probe: ProbeState,
+}
+
+impl CellularDialogState {
+    fn current(&self) -> Option<&AccessPointState> {
+        self.access_points.get(self.selected)
+    }
+
+    fn current_mut(&mut self) -> Option<&mut AccessPointState> {
+        self.access_points.get_mut(self.selected)
+    }
+
+    fn access_point_labels(&self) -> Vec<String> {
+        if self.access_points.is_empty() {
+            return vec!["no access points".to_string()];
+        }
+        let count = self.access_points.len();
+        self.access_points
+            .iter()
+            .enumerate()
+            .map(|(i, ap)| format!("{}/{} {}", i + 1, count, ap.label()))
+            .collect()
+    }
+
+    // visible fields of the current tab. Rows from top to bottom, up to two
+    // fields in a row
+    fn rows(&self) -> Vec<Vec<&'static str>> {
+        let mut rows = vec![];
+        if self.selected_tab == TAB_PROBE {
+            if self.probe.disable {
+                rows.push(vec!["probe_state"]);
+                return rows;
+            }
+            rows.push(vec!["probe_state", "probe_method"]);
+            match self.probe.method {
+                ConnectivityProbeMethod::ConnectivityProbeMethodNone => {}
+                ConnectivityProbeMethod::ConnectivityProbeMethodICMP => {
+                    rows.push(vec!["probe_host"])
+                }
+                ConnectivityProbeMethod::ConnectivityProbeMethodTCP => {
+                    rows.push(vec!["probe_host", "probe_port"])
+                }
+            }
+        } else if let Some(ap) = self.current() {
+            rows.push(vec!["access_point"]);
+            rows.push(vec!["sim_slot", "apn"]);
+            rows.push(vec!["ip_type", "auth"]);
+            if ap.auth_protocol != WwanAuthProtocol::None {
+                rows.push(vec!["username", "password"]);
+            }
+            rows.push(vec!["plmns", "rats"]);
+            rows.push(vec!["roaming"]);
+            rows.push(vec!["attach_apn"]);
+        } else {
+            rows.push(vec!["access_point"]);
+        }
+        rows
+    }
+
+    fn tab_order(&self) -> Vec<&'static str> {
+        let mut order = self.rows().concat();
+        if self.selected_tab == TAB_ACCESS_POINTS {
+            // the selector is not focusable without access points
+            order.retain(|f| *f != "access_point" || self.current().is_some());
+            order.push("add");
+            if self.current().is_some() {
+                order.push("remove");
+            }
+        }
+        order.extend(["ok", "cancel"]);
+        order
+    }
+}
+
+fn is_spin_box(name: &str) -> bool {
+    matches!(
+        name,
+        "access_point" | "ip_type" | "auth" | "roaming" | "probe_state" | "probe_method"
+    )
+}
+
+fn on_init(w: &mut Window<CellularDialogState>) {
+    let tab_hint = theme::tab_switch_hint();
+    w.add_widget(
+        "tabs",
+        TabElement::new(
+            vec![TAB_ACCESS_POINTS, TAB_PROBE],
+            TAB_ACCESS_POINTS,
+            Some(tab_hint.as_str()),
+        ),
+    );
+    w.add_widget("add", ButtonElement::new("add"));
+    w.add_widget("remove", ButtonElement::new("remove"));
+    w.add_widget("ok", ButtonElement::new("ok"));
+    w.add_widget("cancel", ButtonElement::new("cancel"));
+    create_access_point_widgets(w);
+    create_probe_widgets(w);
+    update_tab_order(w);
+}
+
+// (re)create widgets for the selected access point
+fn create_access_point_widgets(w: &mut Window<CellularDialogState>) {
+    w.add_widget(
+        "access_point",
+        SpinBoxElement::new(w.state.access_point_labels()).selected(w.state.selected),
+    );
+    update_credentials_status(w);
+
+    let Some(ap) = w.state.current().cloned() else {
+        return;
+    };
+    let password_hint = if ap.original.encrypted_credentials.is_cipher {
+        "encrypted, leave empty to keep"
+    } else {
+        "password for the APN"
+    };
+    let ip_type = IP_TYPES
+        .iter()
+        .position(|(value, _)| *value == ap.ip_type)
+        .unwrap_or_default();
+
+    w.add_widget(
+        "sim_slot",
+        InputFieldElement::new("SIM slot", Some(ap.sim_slot.as_str()))
+            .with_text_hint("1, 2, ... or 0 for the active SIM"),
+    );
+    w.add_widget(
+        "apn",
+        InputFieldElement::new("APN", Some(ap.apn.as_str())).with_text_hint("e.g. internet"),
+    );
+    w.add_widget(
+        "ip_type",
+        SpinBoxElement::new(IP_TYPES.iter().map(|(_, label)| *label).collect()).selected(ip_type),
+    );
+    w.add_widget(
+        "auth",
+        SpinBoxElement::new(AUTH_PROTOCOLS.to_vec()).selected(auth_index(&ap.auth_protocol)),
+    );
+    w.add_widget(
+        "username",
+        InputFieldElement::new("Username", Some(ap.username.as_str())),
+    );
+    w.add_widget(
+        "password",
+        InputFieldElement::new("Password", Some(ap.password.as_str()))
+            .masked(true)
+            .with_text_hint(password_hint),
+    );
+    w.add_widget(
+        "plmns",
+        InputFieldElement::new("Preferred PLMNs", Some(ap.preferred_plmns.as_str()))
+            .with_text_hint("e.g. 262-01, 310-260"),
+    );
+    w.add_widget(
+        "rats",
+        InputFieldElement::new("Preferred RATs", Some(ap.preferred_rats.as_str()))
+            .with_text_hint("gsm, umts, lte, 5gnr"),
+    );
+    w.add_widget(
+        "roaming",
+        SpinBoxElement::new(ROAMING.to_vec()).selected(ap.forbid_roaming as usize),
+    );
+    w.add_widget(
+        "attach_apn",
+        InputFieldElement::new("Attach APN", Some(ap.attach_apn.as_str()))
+            .with_text_hint("leave empty if not required"),
+    );
+}
+
+fn create_probe_widgets(w: &mut Window<CellularDialogState>) {
+    let probe = w.state.probe.clone();
+    w.add_widget(
+        "probe_state",
+        SpinBoxElement::new(PROBE_STATES.to_vec()).selected(probe.disable as usize),
+    );
+    w.add_widget(
+        "probe_method",
+        SpinBoxElement::new(PROBE_METHODS.to_vec()).selected(method_index(&probe.method)),
+    );
+    w.add_widget(
+        "probe_host",
+        InputFieldElement::new("Host", Some(probe.host.as_str()))
+            .with_text_hint("IP address or host name"),
+    );
+    w.add_widget(
+        "probe_port",
+        InputFieldElement::new("Port", Some(probe.port.as_str())).with_text_hint("e.g. 443"),
+    );
+}
+
+fn update_credentials_status(w: &mut Window<CellularDialogState>) {
+    let text = w
+        .state
+        .current()
+        .map(|ap| ap.credentials_status())
+        .unwrap_or_else(|| "Press 'add' to configure an access point".to_string());
+    w.add_widget("credentials", LabelElement::new(text));
+}
+
+fn update_tab_order(w: &mut Window<CellularDialogState>) {
+    let order = w
+        .state
+        .tab_order()
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+    w.set_focus_tracker_tab_order(order);
+}
+
+fn set_focus(w: &mut Window<CellularDialogState>, name: &str) {
+    if let Some(index) = w.state.tab_order().iter().position(|n| *n == name) {
+        w.set_focused_view(index);
+    }
+}
+
+fn select_tab(w: &mut Window<CellularDialogState>, tab: &str) {
+    if let Some(tabs) = w
+        .get_widget_mut("tabs")
+        .and_then(|widget| widget.as_any_mut().downcast_mut::<TabElement>())
+    {
+        tabs.select(tab);
+    }
+    w.state.selected_tab = tab.to_string();
+    update_tab_order(w);
+}
+
+fn set_field_errors(w: &mut Window<CellularDialogState>, names: &[&str], errors: &[&FieldError]) {
+    for &name in names {
+        let error = errors
+            .iter()
+            .find(|e| field_widget_name(e.field) == Some(name))
+            .map(|e| e.message.clone());
+        if let Some(field) = w
+            .get_widget_mut(name)
+            .and_then(|widget| widget.as_any_mut().downcast_mut::<InputFieldElement>())
+        {
+            field.set_error(error);
+        }
+    }
+}
+
+/// Switch to the tab of the first error, mark invalid fields and focus the first one
+fn show_errors(w: &mut Window<CellularDialogState>, errors: &[CellularFieldError]) {
+    let ap_index = errors.iter().find_map(|e| match e {
+        CellularFieldError::AccessPoint { index, .. } => Some(*index),
+        CellularFieldError::Probe(_) => None,
+    });
+    let probe_errors = errors
+        .iter()
+        .filter_map(|e| match e {
+            CellularFieldError::Probe(error) => Some(error),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    create_probe_widgets(w);
+    set_field_errors(w, &PROBE_INPUT_FIELDS, &probe_errors);
+
+    let first = if let Some(index) = ap_index {
+        w.state.selected = index;
+        create_access_point_widgets(w);
+        let ap_errors = errors
+            .iter()
+            .filter_map(|e| match e {
+                CellularFieldError::AccessPoint { index: i, error } if *i == index => Some(error),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        set_field_errors(w, &AP_INPUT_FIELDS, &ap_errors);
+        select_tab(w, TAB_ACCESS_POINTS);
+        ap_errors.first().and_then(|e| field_widget_name(e.field))
+    } else {
+        select_tab(w, TAB_PROBE);
+        probe_errors
+            .first()
+            .and_then(|e| field_widget_name(e.field))
+    };
+    if let Some(first) = first {
+        set_focus(w, first);
+    }
+}
+
+fn field_widget_name(field: DpcField) -> Option<&'static str> {
+    match field {
+        DpcField::SimSlot => Some("sim_slot"),
+        DpcField::Apn => Some("apn"),
+        DpcField::Username => Some("username"),
+        DpcField::Password => Some("password"),
+        DpcField::Plmns => Some("plmns"),
+        DpcField::Rats => Some("rats"),
+        DpcField::AttachApn => Some("attach_apn"),
+        DpcField::ProbeHost => Some("probe_host"),
+        DpcField::ProbePort => Some("probe_port"),
+        _ => None,
+    }
+}
+
+fn do_layout(w: &mut Window<CellularDialogState>, rect: &Rect, _model: &Rc<Model>) {
+    w.clear_layout();
+
+    let rect = centered_rect_min(40, 80, 60, 32, *rect);
+    let content_with_buttons = rect.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+    w.update_layout("frame", rect);
+
+    let [tabs, content, credentials, buttons] = Layout::vertical(vec![
+        Constraint::Length(3),
+        Constraint::Fill(1),
+        Constraint::Length(2),
+        Constraint::Length(3),
+    ])
+    .areas(content_with_buttons);
+    w.update_layout("tabs", tabs);
+
+    let [add, remove, _, ok, cancel] = Layout::horizontal(vec![
+        Constraint::Length(7),
+        Constraint::Length(10),
+        Constraint::Fill(1),
+        Constraint::Length(6),
+        Constraint::Length(10),
+    ])
+    .flex(Flex::End)
+    .areas(buttons);
+    w.update_layout("ok", ok);
+    w.update_layout("cancel", cancel);
+
+    if w.state.selected_tab == TAB_ACCESS_POINTS {
+        w.update_layout("add", add);
+        w.update_layout("credentials", credentials);
+        if w.state.current().is_some() {
+            w.update_layout("remove", remove);
+        }
+    }
+
+    // rows of spin boxes take one line, rows with input fields take three
+    // lines if there is enough space for all of them
+    let rows = w.state.rows();
+    let is_spin_row = |row: &Vec<&str>| row.iter().all(|f| is_spin_box(f));
+    let spin_rows = rows.iter().filter(|r| is_spin_row(r)).count() as u16;
+    let input_rows = rows.len() as u16 - spin_rows;
+    let height = if content.height >= spin_rows + input_rows * 3 {
+        3
+    } else {
+        1
+    };
+    let constraints = rows
+        .iter()
+        .map(|r| Constraint::Length(if is_spin_row(r) { 1 } else { height }));
+    let areas = Layout::vertical(constraints).split(content);
+    for (row, area) in rows.iter().zip(areas.iter()) {
+        let columns = Layout::horizontal(vec![Constraint::Fill(1); row.len()])
+            .spacing(1)
+            .split(*area);
+        for (name, column) in row.iter().zip(columns.iter()) {
+            w.update_layout(*name, *column);
+        }
+    }
+}
+
+fn do_render(
+    w: &mut Window<CellularDialogState>,
+    _rect: &Rect,
+    frame: &mut Frame<'_>,
+    _model: &Rc<Model>,
+) {
+    let frame_rect = w.get_layout("frame");
+
+    // clear area under the dialog
+    frame.render_widget(Clear {}, frame_rect);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(Color::White))
+        .style(Style::default().bg(Color::Black))
+        .title(format!("{}: cellular", w.state.iface_name));
+
+    frame.render_widget(block, frame_rect);
+}
+
+fn on_key_event(w: &mut Window<CellularDialogState>, key: KeyEvent) -> Option<Action> {
+    if key.code == KeyCode::Esc {
+        return Some(Action::new(&w.name, UiActions::DismissDialog));
+    }
+    Some(Action::new(
+        "tabs",
+        w.get_widget_mut("tabs")?.handle_key_event(key)?,
+    ))
+}
+
+fn on_child_ui_action(
+    w: &mut Window<CellularDialogState>,
+    source: &String,
+    action: &UiActions,
+) -> Option<Action> {
+    debug!("on_child_ui_action: {}:{:?}", source, action);
+    match action {
+        UiActions::TabChanged(_, selected_tab) => {
+            w.state.selected_tab = selected_tab.clone();
+            update_tab_order(w);
+            w.set_focused_view(0);
+            Some(Action::new(source, UiActions::Redraw))
+        }
+        UiActions::SpinBox { selected } => {
+            match source.as_str() {
+                "access_point" => {
+                    w.state.selected = *selected;
+                    create_access_point_widgets(w);
+                }
+                "probe_state" => w.state.probe.disable = *selected == 1,
+                "probe_method" => w.state.probe.method = method_from_index(*selected),
+                _ => {
+                    let ap = w.state.current_mut()?;
+                    match source.as_str() {
+                        "ip_type" => ap.ip_type = IP_TYPES[*selected].0.to_string(),
+                        "auth" => ap.auth_protocol = auth_from_index(*selected),
+                        "roaming" => ap.forbid_roaming = *selected == 1,
+                        _ => return None,
+                    }
+                    update_credentials_status(w);
+                }
+            }
+            update_tab_order(w);
+            set_focus(w, source);
+            Some(Action::new(source, UiActions::Redraw))
+        }
+        UiActions::Input { text } => {
+            match source.as_str() {
+                "probe_host" => w.state.probe.host = text.clone(),
+                "probe_port" => w.state.probe.port = text.clone(),
+                _ => {
+                    let ap = w.state.current_mut()?;
+                    match source.as_str() {
+                        "sim_slot" => ap.sim_slot = text.clone(),
+                        "apn" => ap.apn = text.clone(),
+                        "username" => ap.username = text.clone(),
+                        "password" => ap.password = text.clone(),
+                        "plmns" => ap.preferred_plmns = text.clone(),
+                        "rats" => ap.preferred_rats = text.clone(),
+                        "attach_apn" => ap.attach_apn = text.clone(),
+                        _ => {}
+                    }
+                }
+            }
+            match source.as_str() {
+                // SIM slot and APN are shown in the access point selector
+                "sim_slot" | "apn" => {
+                    let labels = w.state.access_point_labels();
+                    let selected = w.state.selected;
+                    w.add_widget(
+                        "access_point",
+                        SpinBoxElement::new(labels).selected(selected),
+                    );
+                }
+                "username" | "password" => update_credentials_status(w),
+                _ => {}
+            }
+            None
+        }
+        UiActions::ButtonClicked(_) => match source.as_str() {
+            "add" => {
+                // new access points get the next free SIM slot
+                let slot = w
+                    .state
+                    .access_points
+                    .iter()
+                    .filter_map(|ap| ap.sim_slot.trim().parse::<u8>().ok())
+                    .max()
+                    .map_or(1, |s| s.saturating_add(1));
+                w.state.access_points.push(AccessPointState {
+                    sim_slot: slot.to_string(),
+                    ..Default::default()
+                });
+                w.state.selected = w.state.access_points.len() - 1;
+                create_access_point_widgets(w);
+                update_tab_order(w);
+                set_focus(w, "apn");
+                Some(Action::new(source, UiActions::Redraw))
+            }
+            "remove" => {
+                if w.state.selected < w.state.access_points.len() {
+                    w.state.access_points.remove(w.state.selected);
+                }
+                w.state.selected = w
+                    .state
+                    .selected
+                    .min(w.state.access_points.len().saturating_sub(1));
+                create_access_point_widgets(w);
+                update_tab_order(w);
+                let focus = if w.state.access_points.is_empty() {
+                    "add"
+                } else {
+                    "access_point"
+                };
+                set_focus(w, focus);
+                Some(Action::new(source, UiActions::Redraw))
+            }
+            "ok" => match validate_cellular_config(&w.state.access_points, &w.state.probe) {
+                Ok((access_points, probe)) => Some(Action::new(
+                    &w.name,
+                    UiActions::AppAction(MonActions::CellularConfigUpdated(
+                        w.state.iface_name.clone(),
+                        access_points,
+                        probe,
+                    )),
+                )),
+                Err(errors) => {
+                    show_errors(w, &errors);
+                    Some(Action::new(source, UiActions::Redraw))
+                }
+            },
+            "cancel" => Some(Action::new(&w.name, UiActions::DismissDialog)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+pub fn create_cellular_dialog(
+    iface_name: &str,
+    access_points: &[CellularAccessPoint],
+    probe: &WwanProbe,
+) -> impl IWindow {
+    let state = CellularDialogState {
+        iface_name: iface_name.to_string(),
+        selected_tab: TAB_ACCESS_POINTS.to_string(),
+        access_points: access_points.iter().map(AccessPointState::from).collect(),
+        selected: 0,
+        probe: ProbeState::from(probe),
+    };
+    Window::builder("Cellular configuration")
+        .with_on_init(on_init)
+        .with_layout(do_layout)
+        .with_render(do_render)
+        .with_on_key_event(on_key_event)
+        .with_on_child_ui_action(on_child_ui_action)
+        .with_state(state)
+        .build()
+        .unwrap()
+}
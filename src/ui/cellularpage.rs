@@ -0,0 +1,354 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::rc::Rc;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use log::debug;
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Margin, Rect},
+    style::{Color, Style, Stylize},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::{
+    ipc::eve_types::{WwanNetworkMetrics, WwanPacketStats, WwanProvider, WwanSignalInfo},
+    model::{
+        device::network::{CellularStatus, NetworkType},
+        model::Model,
+    },
+    traits::IWindow,
+    ui::action::UiActions,
+};
+
+use super::{action::Action, widgets::button::ButtonElement, window::Window};
+
+const LABEL_LENGTH: u16 = 12;
+
+struct CellularPageState {
+    iface: String,
+}
+
+fn on_init(w: &mut Window<CellularPageState>) {
+    w.add_widget("close", ButtonElement::new("close"));
+    w.set_focus_tracker_tab_order(vec!["close"]);
+}
+
+fn or_na(value: &str) -> String {
+    if value.is_empty() {
+        "N/A".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn row<'a>(label: &'a str, value: String) -> Row<'a> {
+    let height = value.lines().count().max(1) as u16;
+    Row::new(vec![
+        Cell::from(label).style(Style::new().yellow()),
+        Cell::from(value).style(Style::new().white()),
+    ])
+    .height(height)
+}
+
+fn error_row<'a>(label: &'a str, error: &Option<String>) -> Option<Row<'a>> {
+    error.as_ref().map(|e| {
+        Row::new(vec![
+            Cell::from(label).style(Style::new().yellow()),
+            Cell::from(e.clone()).style(Style::new().red()),
+        ])
+    })
+}
+
+fn provider_to_string(provider: &WwanProvider) -> String {
+    let mut flags = vec![];
+    if provider.current_serving {
+        flags.push("serving");
+    }
+    if provider.roaming {
+        flags.push("roaming");
+    }
+    if provider.forbidden {
+        flags.push("forbidden");
+    }
+    let name = format!("{} ({})", or_na(&provider.description), provider.plmn);
+    if flags.is_empty() {
+        name
+    } else {
+        format!("{} [{}]", name, flags.join(", "))
+    }
+}
+
+fn modem_rows(status: &CellularStatus) -> Vec<Row<'static>> {
+    let module = &status.module;
+    let addrs = &status.phys_addrs;
+    let mut rows = vec![
+        row("Name", or_na(&module.name)),
+        row("Manufacturer", or_na(&module.manufacturer)),
+        row("Model", or_na(&module.model)),
+        row("IMEI", or_na(&module.imei)),
+        row("Firmware", or_na(&module.revision)),
+        row("Protocol", or_na(&module.control_protocol)),
+        row("Mode", or_na(&module.op_mode)),
+        row("Device", or_na(&addrs.dev)),
+    ];
+    // the modem is attached either over USB or PCI
+    if !addrs.usb.is_empty() {
+        rows.push(row("USB", addrs.usb.clone()));
+    }
+    if !addrs.pci.is_empty() {
+        rows.push(row("PCI", addrs.pci.clone()));
+    }
+    rows
+}
+
+fn sim_rows(status: &CellularStatus) -> Vec<Row<'static>> {
+    if status.sim_cards.is_empty() {
+        return vec![row("SIM cards", "N/A".to_string())];
+    }
+    status
+        .sim_cards
+        .iter()
+        .flat_map(|sim| {
+            let mut state = vec![or_na(&sim.state), sim.sim_type.to_string()];
+            if sim.slot_activated {
+                state.push("active".to_string());
+            }
+            [
+                Row::new(vec![
+                    Cell::from(format!("Slot {}", sim.slot_number)).style(Style::new().yellow()),
+                    Cell::from(state.join(", ")).style(Style::new().white()),
+                ]),
+                row("  ICCID", or_na(&sim.iccid)),
+                row("  IMSI", or_na(&sim.imsi)),
+            ]
+        })
+        .collect()
+}
+
+fn network_rows(status: &CellularStatus) -> Vec<Row<'static>> {
+    let provider = status
+        .current_provider
+        .as_ref()
+        .map_or("N/A".to_string(), provider_to_string);
+
+    let visible = if status.visible_providers.is_empty() {
+        "N/A".to_string()
+    } else {
+        status
+            .visible_providers
+            .iter()
+            .map(provider_to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let rats = if status.current_rats.is_empty() {
+        "N/A".to_string()
+    } else {
+        status
+            .current_rats
+            .iter()
+            .map(|rat| rat.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let connected = status.connected_at.map_or(
+        Row::new(vec![
+            Cell::from("Connected").style(Style::new().yellow()),
+            Cell::from("No").style(Style::new().red()),
+        ]),
+        |t| {
+            row(
+                "Connected",
+                t.format("since %Y-%m-%d %H:%M:%S UTC").to_string(),
+            )
+        },
+    );
+
+    let ip = &status.ip_settings;
+    let dns = ip.dns_servers.as_ref().map_or("N/A".to_string(), |list| {
+        list.iter()
+            .map(|ip| ip.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    });
+
+    let mut rows = vec![
+        row("Provider", provider),
+        row("Visible", visible),
+        row("RATs", rats),
+        connected,
+        row(
+            "Address",
+            ip.address.map_or("N/A".to_string(), |a| a.to_string()),
+        ),
+        row(
+            "Gateway",
+            ip.gateway.map_or("N/A".to_string(), |g| g.to_string()),
+        ),
+        row("DNS", dns),
+    ];
+    if ip.mtu != 0 {
+        rows.push(row("MTU", ip.mtu.to_string()));
+    }
+    rows.extend(error_row("Config error", &status.config_error));
+    rows.extend(error_row("Probe error", &status.probe_error));
+    rows
+}
+
+fn signal_rows(metrics: Option<&WwanNetworkMetrics>) -> Vec<Row<'static>> {
+    let Some(metrics) = metrics else {
+        return vec![row("Signal", "N/A".to_string())];
+    };
+    let signal = |v: i32, unit: &str| {
+        WwanSignalInfo::value(v).map_or("N/A".to_string(), |v| format!("{} {}", v, unit))
+    };
+    let stats = |bytes: u64, packets: u64, drops: u64| {
+        format!("{} bytes, {} packets, {} dropped", bytes, packets, drops)
+    };
+    let info = &metrics.signal_info;
+    let WwanPacketStats {
+        rx_bytes,
+        rx_packets,
+        rx_drops,
+        tx_bytes,
+        tx_packets,
+        tx_drops,
+    } = metrics.packet_stats;
+    vec![
+        row("RSSI", signal(info.rssi, "dBm")),
+        row("RSRQ", signal(info.rsrq, "dB")),
+        row("RSRP", signal(info.rsrp, "dBm")),
+        row("SNR", signal(info.snr, "dB")),
+        row("RX", stats(rx_bytes, rx_packets, rx_drops)),
+        row("TX", stats(tx_bytes, tx_packets, tx_drops)),
+    ]
+}
+
+fn render_section(frame: &mut Frame<'_>, rect: Rect, title: &str, rows: Vec<Row<'static>>) {
+    let table = Table::new(
+        rows,
+        [Constraint::Length(LABEL_LENGTH), Constraint::Fill(1)],
+    )
+    .block(
+        Block::default()
+            .borders(Borders::TOP)
+            .title(format!(" {} ", title))
+            .title_alignment(Alignment::Center),
+    )
+    .column_spacing(1);
+    frame.render_widget(table, rect);
+}
+
+fn do_render(
+    w: &mut Window<CellularPageState>,
+    _rect: &Rect,
+    frame: &mut Frame<'_>,
+    model: &Rc<Model>,
+) {
+    let frame_rect = w.get_layout("frame");
+    let content_rect = w.get_layout("content");
+
+    frame.render_widget(Clear {}, frame_rect);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(Color::White))
+        .style(Style::default().bg(Color::Black))
+        .title(format!("{} {}", w.state.iface, w.name));
+    frame.render_widget(block, frame_rect);
+
+    let model = model.borrow();
+    // the port may disappear from the status while the page is shown
+    let status = model
+        .network
+        .iter()
+        .find(|iface| iface.name == w.state.iface)
+        .and_then(|iface| match &iface.media {
+            NetworkType::Cellular(status) => Some(status),
+            _ => None,
+        });
+    let Some(status) = status else {
+        let text = format!("No cellular status for {}", w.state.iface);
+        frame.render_widget(Paragraph::new(text).red(), content_rect);
+        return;
+    };
+
+    let [left, right] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .spacing(1)
+            .areas(content_rect);
+
+    let modem = modem_rows(status);
+    let [modem_rect, sim_rect] = Layout::vertical([
+        Constraint::Length(modem.len() as u16 + 1),
+        Constraint::Fill(1),
+    ])
+    .areas(left);
+    render_section(frame, modem_rect, "Modem", modem);
+    render_section(frame, sim_rect, "SIM cards", sim_rows(status));
+
+    let signal = signal_rows(model.get_wwan_metrics(&status.logical_label));
+    let [network_rect, signal_rect] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(signal.len() as u16 + 1),
+    ])
+    .areas(right);
+    render_section(frame, network_rect, "Network", network_rows(status));
+    render_section(frame, signal_rect, "Signal", signal);
+}
+
+fn do_layout(w: &mut Window<CellularPageState>, rect: &Rect, _model: &Rc<Model>) {
+    let content_with_buttons = rect.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+
+    w.update_layout("frame", *rect);
+
+    let [content, buttons] = Layout::vertical(vec![Constraint::Fill(1), Constraint::Length(3)])
+        .areas(content_with_buttons);
+    w.update_layout("content", content);
+
+    let [close] = Layout::horizontal(vec![Constraint::Length(9)])
+        .flex(Flex::End)
+        .areas(buttons);
+    w.update_layout("close", close);
+}
+
+fn on_key_event(w: &mut Window<CellularPageState>, key: KeyEvent) -> Option<Action> {
+    if key.code == KeyCode::Esc {
+        return Some(Action::new(&w.name, UiActions::DismissDialog));
+    }
+    None
+}
+
+fn on_child_ui_action(
+    w: &mut Window<CellularPageState>,
+    source: &String,
+    action: &UiActions,
+) -> Option<Action> {
+    debug!("on_child_ui_action: {}:{:?}", source, action);
+    match action {
+        UiActions::ButtonClicked(_) => Some(Action::new(&w.name, UiActions::DismissDialog)),
+        _ => None,
+    }
+}
+
+pub fn create_cellular_page(iface_name: &str) -> impl IWindow {
+    Window::builder("Cellular status")
+        .with_on_init(on_init)
+        .with_layout(do_layout)
+        .with_render(do_render)
+        .with_on_key_event(on_key_event)
+        .with_on_child_ui_action(on_child_ui_action)
+        .with_state(CellularPageState {
+            iface: iface_name.to_string(),
+        })
+        .build()
+        .unwrap()
+}
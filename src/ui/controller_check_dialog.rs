@@ -0,0 +1,237 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::rc::Rc;
+
+use chrono::Utc;
+use crossterm::event::{KeyCode, KeyEvent};
+use log::debug;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Margin, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    diag::controller::{CheckStep, ControllerCheck},
+    dpc::certificate::ProxyCertificate,
+    model::model::{Model, MonitorModel},
+    traits::IWindow,
+    ui::action::UiActions,
+};
+
+use super::{action::Action, theme, widgets::button::ButtonElement, window::Window};
+
+const LABEL_LENGTH: usize = 9;
+// width of the step status and of the indentation of the details
+const STATUS_LENGTH: usize = 7;
+
+#[derive(Default)]
+struct ControllerCheckDialogState {
+    scroll: u16,
+}
+
+fn on_init(w: &mut Window<ControllerCheckDialogState>) {
+    w.add_widget("close", ButtonElement::new("close"));
+    w.set_focus_tracker_tab_order(vec!["close"]);
+}
+
+fn field(label: &str, value: Span<'static>) -> Line<'static> {
+    Line::from(vec![format!("{:<LABEL_LENGTH$}", label).yellow(), value])
+}
+
+fn step_lines(step: &CheckStep) -> Vec<Line<'static>> {
+    let status = match step.result {
+        Ok(_) => format!("{:<STATUS_LENGTH$}", "[ ok ]").green(),
+        Err(_) if !step.required => format!("{:<STATUS_LENGTH$}", "[warn]").yellow(),
+        Err(_) => format!("{:<STATUS_LENGTH$}", "[fail]").red(),
+    };
+    let mut title = vec![status, step.name.clone().white()];
+    if let Some(elapsed) = step.elapsed {
+        title.push(format!(" {:.1} ms", elapsed.as_secs_f64() * 1000.0).dark_gray());
+    }
+    let details = match &step.result {
+        Ok(details) => details.clone().white(),
+        Err(error) if !step.required => error.clone().yellow(),
+        Err(error) => error.clone().red(),
+    };
+    let mut lines = vec![Line::from(title)];
+    if !details.content.is_empty() {
+        lines.push(Line::from(vec![" ".repeat(STATUS_LENGTH).into(), details]));
+    }
+    lines
+}
+
+/// Proxy CA certificates of the management ports
+fn proxy_cas(model: &MonitorModel) -> Vec<ProxyCertificate> {
+    model
+        .network
+        .iter()
+        .filter(|iface| iface.is_mgmt)
+        .flat_map(|iface| iface.proxy_certificates.iter())
+        .filter_map(|pem| ProxyCertificate::from_pem(pem).ok())
+        .collect()
+}
+
+fn chain_lines(
+    certificates: &[ProxyCertificate],
+    proxy_cas: &[ProxyCertificate],
+) -> Vec<Line<'static>> {
+    let now = Utc::now();
+    let mut lines = vec![Line::from("Certificate chain").bold().white()];
+    for (i, cert) in certificates.iter().enumerate() {
+        let validity = if cert.is_valid_at(now) {
+            "".into()
+        } else if now < cert.not_before {
+            " not yet valid".red()
+        } else {
+            " expired".red()
+        };
+        lines.push(Line::from(vec![
+            format!("{:<3}", i).white(),
+            "Subject  ".yellow(),
+            cert.subject.clone().white(),
+        ]));
+        lines.push(Line::from(vec![
+            "   Issuer   ".yellow(),
+            cert.issuer.clone().white(),
+        ]));
+        lines.push(Line::from(vec![
+            "   Expires  ".yellow(),
+            cert.not_after
+                .format("%Y-%m-%d %H:%M UTC")
+                .to_string()
+                .white(),
+            validity,
+        ]));
+        // an intercepting proxy re-signs the server certificate
+        if let Some(ca) = proxy_cas.iter().find(|ca| ca.subject == cert.issuer) {
+            lines
+                .push(Line::from(format!("   issued by the proxy CA {}", ca.common_name)).yellow());
+        }
+    }
+    lines
+}
+
+fn check_text(check: &ControllerCheck, proxy_cas: &[ProxyCertificate]) -> Text<'static> {
+    let proxy = match &check.proxy {
+        Some(Ok(proxy)) => proxy.to_string().white(),
+        Some(Err(error)) => error.clone().red(),
+        None => "none".white(),
+    };
+    let mut lines = vec![
+        field("Server", check.server.clone().white()),
+        field("Proxy", proxy),
+        Line::default(),
+    ];
+    lines.extend(check.steps.iter().flat_map(step_lines));
+    lines.push(Line::default());
+    lines.push(if check.running {
+        Line::from("Checking...").yellow()
+    } else if check.passed() {
+        Line::from("The controller is reachable").green()
+    } else {
+        Line::from("The controller is not reachable").red()
+    });
+    if !check.certificates.is_empty() {
+        lines.push(Line::default());
+        lines.extend(chain_lines(&check.certificates, proxy_cas));
+    }
+    Text::from(lines)
+}
+
+fn do_render(
+    w: &mut Window<ControllerCheckDialogState>,
+    _rect: &Rect,
+    frame: &mut Frame<'_>,
+    model: &Rc<Model>,
+) {
+    let frame_rect = w.get_layout("frame");
+    let content_rect = w.get_layout("content");
+    let hint_rect = w.get_layout("hint");
+
+    frame.render_widget(Clear {}, frame_rect);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(Color::White))
+        .style(Style::default().bg(Color::Black))
+        .title(w.name.clone());
+    frame.render_widget(block, frame_rect);
+
+    let (up, down) = theme::scroll_arrows();
+    let hint = format!("{up}/{down} - scroll");
+    frame.render_widget(Line::from(hint).dark_gray(), hint_rect);
+
+    let model = model.borrow();
+    let paragraph = Paragraph::new(check_text(&model.controller_check, &proxy_cas(&model)))
+        .wrap(Wrap { trim: false })
+        .scroll((w.state.scroll, 0));
+    frame.render_widget(paragraph, content_rect);
+}
+
+fn do_layout(w: &mut Window<ControllerCheckDialogState>, rect: &Rect, _model: &Rc<Model>) {
+    let content_with_buttons = rect.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+
+    w.update_layout("frame", *rect);
+
+    let [content, hint, buttons] = Layout::vertical(vec![
+        Constraint::Fill(1),
+        Constraint::Length(1),
+        Constraint::Length(3),
+    ])
+    .areas(content_with_buttons);
+    w.update_layout("content", content);
+    w.update_layout("hint", hint);
+
+    let [close] = Layout::horizontal(vec![Constraint::Length(9)])
+        .flex(Flex::End)
+        .areas(buttons);
+    w.update_layout("close", close);
+}
+
+fn on_key_event(w: &mut Window<ControllerCheckDialogState>, key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => Some(Action::new(&w.name, UiActions::DismissDialog)),
+        KeyCode::Up => {
+            w.state.scroll = w.state.scroll.saturating_sub(1);
+            Some(Action::new(&w.name, UiActions::Redraw))
+        }
+        KeyCode::Down => {
+            w.state.scroll = w.state.scroll.saturating_add(1);
+            Some(Action::new(&w.name, UiActions::Redraw))
+        }
+        _ => None,
+    }
+}
+
+fn on_child_ui_action(
+    w: &mut Window<ControllerCheckDialogState>,
+    source: &String,
+    action: &UiActions,
+) -> Option<Action> {
+    debug!("on_child_ui_action: {}:{:?}", source, action);
+    match action {
+        UiActions::ButtonClicked(_) => Some(Action::new(&w.name, UiActions::DismissDialog)),
+        _ => None,
+    }
+}
+
+/// Steps of the controller reachability check and the certificate chain
+pub fn create_controller_check_dialog() -> impl IWindow {
+    Window::builder("Controller reachability")
+        .with_on_init(on_init)
+        .with_layout(do_layout)
+        .with_render(do_render)
+        .with_on_key_event(on_key_event)
+        .with_on_child_ui_action(on_child_ui_action)
+        .with_state(ControllerCheckDialogState::default())
+        .build()
+        .unwrap()
+}
@@ -0,0 +1,141 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::rc::Rc;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use log::debug;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Margin, Rect},
+    style::{Color, Style},
+    widgets::{Block, BorderType, Borders, Clear},
+    Frame,
+};
+
+use crate::{
+    actions::MonActions, crash_report::CrashReport, model::model::Model, traits::IWindow,
+    ui::action::UiActions,
+};
+
+use super::{
+    action::Action,
+    widgets::{button::ButtonElement, label::LabelElement},
+    window::Window,
+};
+
+struct CrashReportDialogState {
+    report: CrashReport,
+}
+
+impl CrashReportDialogState {
+    fn content(&self) -> String {
+        let report = &self.report;
+        format!(
+            "The monitor crashed during the session {} (version {}).\n\nCause: {}\nLocation: {}{}\nReport: {}",
+            report.session,
+            report.crate_version,
+            report.summary(),
+            report.location().unwrap_or_else(|| "unknown".to_string()),
+            report
+                .top_frame()
+                .map(|frame| format!(" in {}", frame))
+                .unwrap_or_default(),
+            report.path.display()
+        )
+    }
+}
+
+fn on_init(w: &mut Window<CrashReportDialogState>) {
+    w.add_widget("label", LabelElement::new(w.state.content()));
+    // buttons
+    w.add_widget("bundle", ButtonElement::new("Add to support bundle"));
+    w.add_widget("ok", ButtonElement::new("ok"));
+
+    w.set_focus_tracker_tab_order(vec!["ok", "bundle"]);
+}
+
+fn do_render(
+    w: &mut Window<CrashReportDialogState>,
+    _rect: &Rect,
+    frame: &mut Frame<'_>,
+    _model: &Rc<Model>,
+) {
+    let frame_rect = w.get_layout("frame");
+
+    // clear area under the dialog
+    frame.render_widget(Clear {}, frame_rect);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(Color::Red))
+        .style(Style::default().bg(Color::Black))
+        .title(w.name.clone());
+
+    frame.render_widget(block, frame_rect);
+}
+
+fn do_layout(w: &mut Window<CrashReportDialogState>, rect: &Rect, _model: &Rc<Model>) {
+    let rect = crate::ui::tools::centered_rect_fixed(64, 14, *rect);
+    let content_with_buttons = rect.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+
+    w.update_layout("frame", rect);
+
+    let [content, buttons] = Layout::vertical(vec![Constraint::Fill(1), Constraint::Length(3)])
+        .areas(content_with_buttons);
+    w.update_layout("label", content);
+
+    let [bundle, ok] = Layout::horizontal(vec![Constraint::Length(25), Constraint::Length(6)])
+        .flex(Flex::End)
+        .areas(buttons);
+    w.update_layout("bundle", bundle);
+    w.update_layout("ok", ok);
+}
+
+fn on_key_event(w: &mut Window<CrashReportDialogState>, key: KeyEvent) -> Option<Action> {
+    // Esc hides the dialog without acknowledging the report. It is shown again on next start
+    if key.code == KeyCode::Esc {
+        return Some(Action::new(&w.name, UiActions::DismissDialog));
+    }
+    None
+}
+
+fn on_child_ui_action(
+    w: &mut Window<CrashReportDialogState>,
+    source: &String,
+    action: &UiActions,
+) -> Option<Action> {
+    debug!("on_child_ui_action: {}:{:?}", source, action);
+    match action {
+        // button labels differ from widget names, so match on the source
+        UiActions::ButtonClicked(_) => match source.as_str() {
+            "ok" => Some(Action::new(
+                &w.name,
+                UiActions::AppAction(MonActions::CrashReportAcknowledged(w.state.report.id())),
+            )),
+            "bundle" => Some(Action::new(
+                &w.name,
+                UiActions::AppAction(MonActions::CrashReportAddToBundle(w.state.report.id())),
+            )),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+pub fn create_crash_report_dialog(report: &CrashReport) -> impl IWindow {
+    Window::builder("Previous crash report")
+        .with_on_init(on_init)
+        .with_layout(do_layout)
+        .with_render(do_render)
+        .with_on_key_event(on_key_event)
+        .with_on_child_ui_action(on_child_ui_action)
+        .with_state(CrashReportDialogState {
+            report: report.clone(),
+        })
+        .build()
+        .unwrap()
+}
@@ -0,0 +1,476 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{net::IpAddr, rc::Rc};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use log::debug;
+use ratatui::{
+    layout::{Constraint, Layout, Margin, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use strum::IntoEnumIterator;
+
+use crate::{
+    actions::MonActions,
+    diag::{DiagBinding, DiagRequest, DiagSession, DiagState, DiagTool},
+    model::{
+        device::network::{IpV6LinikLocal, NetworkInterfaceStatus},
+        model::Model,
+    },
+    traits::{IWindow, TextInput},
+    ui::action::UiActions,
+};
+
+use super::{
+    action::Action,
+    widgets::{button::ButtonElement, input_field::InputFieldElement, spin_box::SpinBoxElement},
+    window::Window,
+};
+
+const LABEL_LENGTH: usize = 12;
+const CUSTOM_DNS: &str = "custom";
+
+struct DiagPageState {
+    tool: DiagTool,
+    // choices of the "bind" spinner, rebuilt when the interfaces change
+    bindings: Vec<DiagBinding>,
+    binding: usize,
+    // configured DNS servers and the interface they are configured on
+    dns_servers: Vec<(IpAddr, String)>,
+    // index into dns_servers, the last choice is a custom server
+    dns_server: usize,
+    target: String,
+    port: String,
+    dns_custom: String,
+    // number of lines scrolled back from the end of the output
+    scroll_back: usize,
+    output_height: usize,
+}
+
+impl Default for DiagPageState {
+    fn default() -> Self {
+        Self {
+            tool: DiagTool::Ping,
+            bindings: vec![DiagBinding::Any],
+            binding: 0,
+            dns_servers: Vec::new(),
+            dns_server: 0,
+            target: String::new(),
+            port: "443".to_string(),
+            dns_custom: String::new(),
+            scroll_back: 0,
+            output_height: 0,
+        }
+    }
+}
+
+impl DiagPageState {
+    fn custom_dns(&self) -> bool {
+        self.dns_server >= self.dns_servers.len()
+    }
+
+    fn tab_order(&self) -> Vec<&'static str> {
+        let mut order = vec!["tool", "bind"];
+        if self.tool == DiagTool::DnsLookup {
+            order.push("dns_spinner");
+            if self.custom_dns() {
+                order.push("dns_custom");
+            }
+        }
+        order.push("target");
+        if self.tool == DiagTool::TcpConnect {
+            order.push("port");
+        }
+        order.extend(["run", "stop"]);
+        order
+    }
+}
+
+/// An interface, or each of its addresses as the source. Link local IPv6
+/// addresses are left out, binding to the interface covers them
+fn interface_bindings(iface: &NetworkInterfaceStatus) -> Vec<DiagBinding> {
+    let ipv4 = iface.ipv4.iter().flatten().map(|addr| IpAddr::from(*addr));
+    let ipv6 = iface
+        .ipv6
+        .iter()
+        .flatten()
+        .filter(|addr| !addr.is_link_local())
+        .map(|addr| IpAddr::from(*addr));
+    std::iter::once(DiagBinding::Interface(iface.name.clone()))
+        .chain(ipv4.chain(ipv6).map(|addr| DiagBinding::Source {
+            iface: iface.name.clone(),
+            addr,
+        }))
+        .collect()
+}
+
+fn dns_servers(network: &[NetworkInterfaceStatus]) -> Vec<(IpAddr, String)> {
+    let mut servers: Vec<(IpAddr, String)> = Vec::new();
+    for iface in network {
+        for server in iface.dns.iter().flatten() {
+            if !servers.iter().any(|(addr, _)| addr == server) {
+                servers.push((*server, iface.name.clone()));
+            }
+        }
+    }
+    servers
+}
+
+fn on_init(w: &mut Window<DiagPageState>) {
+    let tools = DiagTool::iter().map(|t| t.to_string()).collect::<Vec<_>>();
+    w.add_widget("tool", SpinBoxElement::new(tools));
+    w.add_widget(
+        "bind",
+        SpinBoxElement::new(vec![DiagBinding::Any.to_string()]),
+    );
+    w.add_widget("dns_spinner", SpinBoxElement::new(vec![CUSTOM_DNS]));
+    w.add_widget(
+        "dns_custom",
+        InputFieldElement::new("DNS server", Some("")).with_text_hint("IP address"),
+    );
+    w.add_widget(
+        "target",
+        InputFieldElement::new("Target", Some("")).with_text_hint("host name or IP address"),
+    );
+    w.add_widget("port", InputFieldElement::new("Port", Some("443")));
+    w.add_widget("run", ButtonElement::new("run"));
+    w.add_widget("stop", ButtonElement::new("stop"));
+    let order = w.state.tab_order();
+    w.set_focus_tracker_tab_order(order);
+}
+
+/// Recreate the spinners whose choices come from the network status. The
+/// selection is kept if the chosen item is still there
+fn update_spinners(w: &mut Window<DiagPageState>, network: &[NetworkInterfaceStatus]) {
+    let bindings = std::iter::once(DiagBinding::Any)
+        .chain(network.iter().flat_map(interface_bindings))
+        .collect::<Vec<_>>();
+    if bindings != w.state.bindings {
+        let selected = &w.state.bindings[w.state.binding];
+        w.state.binding = bindings.iter().position(|b| b == selected).unwrap_or(0);
+        let labels = bindings.iter().map(|b| b.to_string()).collect::<Vec<_>>();
+        w.add_widget(
+            "bind",
+            SpinBoxElement::new(labels).selected(w.state.binding),
+        );
+        w.state.bindings = bindings;
+    }
+
+    let servers = dns_servers(network);
+    if servers != w.state.dns_servers {
+        // a custom server stays selected unless it was the only choice
+        let custom = w.state.custom_dns() && !w.state.dns_servers.is_empty();
+        let selected = w.state.dns_servers.get(w.state.dns_server).map(|s| s.0);
+        w.state.dns_server = selected
+            .and_then(|addr| servers.iter().position(|(a, _)| *a == addr))
+            .unwrap_or(if custom { servers.len() } else { 0 });
+        let labels = servers
+            .iter()
+            .map(|(addr, iface)| format!("{} ({})", addr, iface))
+            .chain(std::iter::once(CUSTOM_DNS.to_string()))
+            .collect::<Vec<_>>();
+        w.add_widget(
+            "dns_spinner",
+            SpinBoxElement::new(labels).selected(w.state.dns_server),
+        );
+        w.state.dns_servers = servers;
+        update_tab_order(w);
+    }
+}
+
+fn update_tab_order(w: &mut Window<DiagPageState>) {
+    let order = w.state.tab_order();
+    w.set_focus_tracker_tab_order(order);
+}
+
+fn set_focus(w: &mut Window<DiagPageState>, name: &str) {
+    if let Some(index) = w.state.tab_order().iter().position(|n| *n == name) {
+        w.set_focused_view(index);
+    }
+}
+
+fn set_field_error(w: &mut Window<DiagPageState>, name: &str, error: Option<String>) {
+    if let Some(field) = w
+        .get_widget_mut(name)
+        .and_then(|widget| widget.as_any_mut().downcast_mut::<InputFieldElement>())
+    {
+        field.set_error(error);
+    }
+}
+
+/// Build the request from the form. Invalid fields are marked and the first
+/// one is focused
+fn build_request(w: &mut Window<DiagPageState>) -> Option<DiagRequest> {
+    let state = &w.state;
+    let target = state.target.trim().to_string();
+    // the target is passed to ping and traceroute, it must not be an option
+    let target_error = if target.is_empty() {
+        Some("enter a host name or IP address")
+    } else if target.starts_with('-') || target.contains(char::is_whitespace) {
+        Some("invalid host name")
+    } else {
+        None
+    };
+    let port = match state.tool {
+        DiagTool::TcpConnect => state.port.trim().parse::<u16>().ok().filter(|p| *p != 0),
+        _ => Some(0),
+    };
+    let dns_server = match state.tool {
+        DiagTool::DnsLookup if state.custom_dns() => state.dns_custom.trim().parse().ok(),
+        DiagTool::DnsLookup => Some(state.dns_servers[state.dns_server].0),
+        _ => None,
+    };
+    let dns_error = state.tool == DiagTool::DnsLookup && dns_server.is_none();
+
+    let request = DiagRequest {
+        tool: state.tool,
+        target,
+        port: port.unwrap_or_default(),
+        dns_server,
+        binding: state.bindings[state.binding].clone(),
+    };
+
+    set_field_error(w, "target", target_error.map(String::from));
+    set_field_error(
+        w,
+        "port",
+        port.is_none()
+            .then(|| "enter a port from 1 to 65535".to_string()),
+    );
+    set_field_error(
+        w,
+        "dns_custom",
+        dns_error.then(|| "enter the IP address of a DNS server".to_string()),
+    );
+    if dns_error {
+        set_focus(w, "dns_custom");
+    } else if target_error.is_some() {
+        set_focus(w, "target");
+    } else if port.is_none() {
+        set_focus(w, "port");
+    } else {
+        return Some(request);
+    }
+    None
+}
+
+fn label(text: &str) -> Span<'static> {
+    format!("{:<LABEL_LENGTH$}", text).yellow()
+}
+
+fn output_title(session: &DiagSession) -> Line<'static> {
+    let state = match &session.state {
+        DiagState::Idle => return Line::from("Output"),
+        DiagState::Running => "running".yellow(),
+        DiagState::Done => "done".green(),
+        DiagState::Failed(e) => format!("failed: {}", e).red(),
+    };
+    Line::from(vec![format!("{} - ", session.title).into(), state])
+}
+
+fn do_render(
+    w: &mut Window<DiagPageState>,
+    _rect: &Rect,
+    frame: &mut Frame<'_>,
+    model: &Rc<Model>,
+) {
+    let frame_rect = w.get_layout("frame");
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Plain)
+        .border_style(Style::default().fg(Color::White))
+        .title("Network diagnostics");
+    frame.render_widget(block, frame_rect);
+
+    frame.render_widget(label("Tool"), w.get_layout("tool_label"));
+    frame.render_widget(label("Bind to"), w.get_layout("bind_label"));
+    if w.state.tool == DiagTool::DnsLookup {
+        frame.render_widget(label("DNS server"), w.get_layout("dns_label"));
+    }
+
+    let output_rect = w.get_layout("output");
+    let model = model.borrow();
+    let session = &model.diagnostics;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Gray))
+        .title(output_title(session));
+    let inner = block.inner(output_rect);
+    frame.render_widget(block, output_rect);
+
+    // follow the end of the output unless scrolled back
+    w.state.output_height = inner.height as usize;
+    let max_scroll = session.lines.len().saturating_sub(w.state.output_height);
+    w.state.scroll_back = w.state.scroll_back.min(max_scroll);
+    let end = session.lines.len() - w.state.scroll_back;
+    let start = end.saturating_sub(w.state.output_height);
+    let text = if session.state == DiagState::Idle {
+        vec![Line::from("Choose a tool and a target, then press run").dark_gray()]
+    } else {
+        session.lines[start..end]
+            .iter()
+            .map(|l| Line::from(l.clone()))
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(text).white(), inner);
+
+    let hint = "Enter - run | stop - cancel | PgUp/PgDn - scroll output";
+    frame.render_widget(Line::from(hint).dark_gray(), w.get_layout("hint"));
+}
+
+fn do_layout(w: &mut Window<DiagPageState>, rect: &Rect, model: &Rc<Model>) {
+    update_spinners(w, &model.borrow().network);
+
+    // only the widgets of the selected tool are laid out and rendered
+    w.clear_layout();
+    w.update_layout("frame", *rect);
+    let inner = rect.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+
+    let dns = w.state.tool == DiagTool::DnsLookup;
+    let tcp = w.state.tool == DiagTool::TcpConnect;
+    let custom_dns = dns && w.state.custom_dns();
+    let [tool, bind, dns_row, inputs, output, hint] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Length(dns as u16),
+        Constraint::Length(3),
+        Constraint::Fill(1),
+        Constraint::Length(1),
+    ])
+    .areas(inner);
+
+    let mut rows = vec![("tool", tool), ("bind", bind)];
+    if dns {
+        rows.push(("dns", dns_row));
+    }
+    for (name, area) in rows {
+        let [label, spinner] =
+            Layout::horizontal([Constraint::Length(LABEL_LENGTH as u16), Constraint::Fill(1)])
+                .areas(area);
+        w.update_layout(format!("{}_label", name), label);
+        let name = if name == "dns" { "dns_spinner" } else { name };
+        w.update_layout(name, spinner);
+    }
+
+    let [target, port, dns_custom, run, stop] = Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Length(if tcp { 9 } else { 0 }),
+        Constraint::Length(if custom_dns { 20 } else { 0 }),
+        Constraint::Length(7),
+        Constraint::Length(8),
+    ])
+    .areas(inputs);
+    w.update_layout("target", target);
+    if tcp {
+        w.update_layout("port", port);
+    }
+    if custom_dns {
+        w.update_layout("dns_custom", dns_custom);
+    }
+    w.update_layout("run", run);
+    w.update_layout("stop", stop);
+    w.update_layout("output", output);
+    w.update_layout("hint", hint);
+}
+
+fn run_action(w: &mut Window<DiagPageState>) -> Option<Action> {
+    let request = build_request(w)?;
+    w.state.scroll_back = 0;
+    Some(Action::new(
+        &w.name,
+        UiActions::AppAction(MonActions::RunDiagnostic(request)),
+    ))
+}
+
+fn on_key_event(w: &mut Window<DiagPageState>, key: KeyEvent) -> Option<Action> {
+    let page = w.state.output_height.saturating_sub(1).max(1);
+    match key.code {
+        KeyCode::PageUp => {
+            w.state.scroll_back = w.state.scroll_back.saturating_add(page);
+            Some(Action::new(&w.name, UiActions::Redraw))
+        }
+        KeyCode::PageDown => {
+            w.state.scroll_back = w.state.scroll_back.saturating_sub(page);
+            Some(Action::new(&w.name, UiActions::Redraw))
+        }
+        // run from any of the input fields
+        KeyCode::Enter => {
+            let focused = w.state.tab_order()[w.get_focused_view()];
+            match focused {
+                "target" | "port" | "dns_custom" => {
+                    run_action(w).or(Some(Action::new(&w.name, UiActions::Redraw)))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn on_child_ui_action(
+    w: &mut Window<DiagPageState>,
+    source: &String,
+    action: &UiActions,
+) -> Option<Action> {
+    debug!("on_child_ui_action: {}:{:?}", source, action);
+    match action {
+        UiActions::SpinBox { selected } => match source.as_str() {
+            "tool" => {
+                w.state.tool = DiagTool::iter().nth(*selected).unwrap_or(DiagTool::Ping);
+                update_tab_order(w);
+                set_focus(w, "tool");
+                Some(Action::new(source, UiActions::Redraw))
+            }
+            "bind" => {
+                w.state.binding = *selected;
+                Some(Action::new(source, UiActions::Redraw))
+            }
+            "dns_spinner" => {
+                w.state.dns_server = *selected;
+                update_tab_order(w);
+                set_focus(w, "dns_spinner");
+                Some(Action::new(source, UiActions::Redraw))
+            }
+            _ => None,
+        },
+        UiActions::ButtonClicked(_) => match source.as_str() {
+            "run" => run_action(w).or(Some(Action::new(source, UiActions::Redraw))),
+            "stop" => Some(Action::new(
+                &w.name,
+                UiActions::AppAction(MonActions::CancelDiagnostic),
+            )),
+            _ => None,
+        },
+        UiActions::Input { text } => {
+            match source.as_str() {
+                "target" => w.state.target = text.clone(),
+                "port" => w.state.port = text.clone(),
+                "dns_custom" => w.state.dns_custom = text.clone(),
+                _ => return None,
+            }
+            set_field_error(w, source, None);
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Ping, TCP connect, DNS lookup and traceroute bound to a chosen interface
+pub fn create_diagnostics_page() -> impl IWindow {
+    Window::builder("Diagnostics")
+        .with_on_init(on_init)
+        .with_layout(do_layout)
+        .with_render(do_render)
+        .with_on_key_event(on_key_event)
+        .with_on_child_ui_action(on_child_ui_action)
+        .with_state(DiagPageState::default())
+        .build()
+        .unwrap()
+}
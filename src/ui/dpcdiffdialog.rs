@@ -0,0 +1,186 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::rc::Rc;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use log::debug;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Margin, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    actions::MonActions,
+    dpc::diff::{DpcDiff, PortChange, ValueChange},
+    ipc::eve_types::DevicePortConfig,
+    model::model::Model,
+    traits::IWindow,
+    ui::action::UiActions,
+};
+
+use super::{action::Action, theme, widgets::button::ButtonElement, window::Window};
+
+const FIELD_LENGTH: usize = 10;
+
+struct DpcDiffDialogState {
+    diff: DpcDiff,
+    // DPC sent when the change is confirmed
+    dpc: DevicePortConfig,
+    scroll: u16,
+}
+
+fn on_init(w: &mut Window<DpcDiffDialogState>) {
+    w.add_widget("apply", ButtonElement::new("apply"));
+    w.add_widget("cancel", ButtonElement::new("cancel"));
+    w.set_focus_tracker_tab_order(vec!["apply", "cancel"]);
+}
+
+fn change_spans(change: &ValueChange) -> Vec<Span<'static>> {
+    match change {
+        ValueChange::Unchanged(v) => vec![format!("  {}", v).gray()],
+        ValueChange::Added(v) => vec![format!("+ {}", v).green()],
+        ValueChange::Removed(v) => vec![format!("- {}", v).red()],
+        ValueChange::Modified(old, new) => vec![
+            "~ ".yellow(),
+            old.clone().red(),
+            " → ".white(),
+            new.clone().green(),
+        ],
+        ValueChange::Moved(v) => vec![format!("↕ {} (moved)", v).yellow()],
+    }
+}
+
+fn diff_text(diff: &DpcDiff) -> Text<'static> {
+    let mut lines = Vec::new();
+    for port in &diff.ports {
+        let (change, style) = match port.change {
+            PortChange::Added => ("new port", Style::new().green()),
+            PortChange::Removed => ("removed", Style::new().red()),
+            PortChange::Modified => ("modified", Style::new().yellow()),
+        };
+        lines.push(Line::from(vec![
+            port.name.clone().white().bold(),
+            " ".into(),
+            Span::styled(format!("({})", change), style),
+        ]));
+        for field in &port.fields {
+            for (i, change) in field.changes.iter().enumerate() {
+                // the field name is shown on the first line of a list only
+                let label = if i == 0 { field.field } else { "" };
+                let mut spans = vec![format!("  {:<FIELD_LENGTH$} ", label).yellow()];
+                spans.extend(change_spans(change));
+                lines.push(Line::from(spans));
+            }
+        }
+        lines.push(Line::default());
+    }
+    Text::from(lines)
+}
+
+fn do_render(
+    w: &mut Window<DpcDiffDialogState>,
+    _rect: &Rect,
+    frame: &mut Frame<'_>,
+    _model: &Rc<Model>,
+) {
+    let frame_rect = w.get_layout("frame");
+    let content_rect = w.get_layout("content");
+    let hint_rect = w.get_layout("hint");
+
+    frame.render_widget(Clear {}, frame_rect);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(Color::White))
+        .style(Style::default().bg(Color::Black))
+        .title(w.name.clone());
+    frame.render_widget(block, frame_rect);
+
+    let (up, down) = theme::scroll_arrows();
+    let hint = format!("{up}/{down} - scroll | apply - send the new port configuration to EVE");
+    frame.render_widget(Line::from(hint).dark_gray(), hint_rect);
+
+    let paragraph = Paragraph::new(diff_text(&w.state.diff))
+        .wrap(Wrap { trim: false })
+        .scroll((w.state.scroll, 0));
+    frame.render_widget(paragraph, content_rect);
+}
+
+fn do_layout(w: &mut Window<DpcDiffDialogState>, rect: &Rect, _model: &Rc<Model>) {
+    let content_with_buttons = rect.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+
+    w.update_layout("frame", *rect);
+
+    let [content, hint, buttons] = Layout::vertical(vec![
+        Constraint::Fill(1),
+        Constraint::Length(1),
+        Constraint::Length(3),
+    ])
+    .areas(content_with_buttons);
+    w.update_layout("content", content);
+    w.update_layout("hint", hint);
+
+    let [apply, cancel] = Layout::horizontal(vec![Constraint::Length(9), Constraint::Length(10)])
+        .flex(Flex::End)
+        .areas(buttons);
+    w.update_layout("apply", apply);
+    w.update_layout("cancel", cancel);
+}
+
+fn on_key_event(w: &mut Window<DpcDiffDialogState>, key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => Some(Action::new(&w.name, UiActions::DismissDialog)),
+        KeyCode::Up => {
+            w.state.scroll = w.state.scroll.saturating_sub(1);
+            Some(Action::new(&w.name, UiActions::Redraw))
+        }
+        KeyCode::Down => {
+            w.state.scroll = w.state.scroll.saturating_add(1);
+            Some(Action::new(&w.name, UiActions::Redraw))
+        }
+        _ => None,
+    }
+}
+
+fn on_child_ui_action(
+    w: &mut Window<DpcDiffDialogState>,
+    source: &String,
+    action: &UiActions,
+) -> Option<Action> {
+    debug!("on_child_ui_action: {}:{:?}", source, action);
+    match action {
+        UiActions::ButtonClicked(_) => match source.as_str() {
+            "apply" => Some(Action::new(
+                &w.name,
+                UiActions::AppAction(MonActions::DpcConfirmed(Box::new(w.state.dpc.clone()))),
+            )),
+            _ => Some(Action::new(&w.name, UiActions::DismissDialog)),
+        },
+        _ => None,
+    }
+}
+
+/// Show what changes if `dpc` is applied and ask for confirmation
+pub fn create_dpc_diff_dialog(diff: DpcDiff, dpc: DevicePortConfig) -> impl IWindow {
+    Window::builder("Confirm port configuration changes")
+        .with_on_init(on_init)
+        .with_layout(do_layout)
+        .with_render(do_render)
+        .with_on_key_event(on_key_event)
+        .with_on_child_ui_action(on_child_ui_action)
+        .with_state(DpcDiffDialogState {
+            diff,
+            dpc,
+            scroll: 0,
+        })
+        .build()
+        .unwrap()
+}
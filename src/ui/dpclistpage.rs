@@ -0,0 +1,216 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::rc::Rc;
+
+use chrono::{DateTime, Utc};
+use crossterm::event::{KeyCode, KeyEvent};
+use log::debug;
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Margin, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{
+        Block, BorderType, Borders, Cell, Clear, HighlightSpacing, Paragraph, Row, Table,
+        TableState,
+    },
+    Frame,
+};
+
+use crate::{
+    ipc::eve_types::{DPCState, DevicePortConfig},
+    model::model::Model,
+    traits::IWindow,
+    ui::action::UiActions,
+};
+
+use super::{action::Action, theme, widgets::button::ButtonElement, window::Window};
+
+const KEY_LENGTH: u16 = 10;
+const TIME_LENGTH: u16 = 19;
+const STATE_LENGTH: u16 = 22;
+
+#[derive(Default)]
+struct DpcListPageState {
+    table: TableState,
+    // number of DPCs rendered last time
+    size: usize,
+}
+
+fn on_init(w: &mut Window<DpcListPageState>) {
+    w.add_widget("close", ButtonElement::new("close"));
+    w.set_focus_tracker_tab_order(vec!["close"]);
+}
+
+/// Timestamps which were never set are zero
+pub fn time_to_string(time: &DateTime<Utc>) -> String {
+    if time.timestamp() == 0 {
+        "never".to_string()
+    } else {
+        time.format("%Y-%m-%d %H:%M:%S").to_string()
+    }
+}
+
+pub fn state_style(state: &DPCState) -> Style {
+    match state {
+        DPCState::Success => Style::new().green(),
+        DPCState::Fail | DPCState::FailWithIPAndDNS => Style::new().red(),
+        _ => Style::new().yellow(),
+    }
+}
+
+fn dpc_row(dpc: &DevicePortConfig, in_use: bool) -> Row<'static> {
+    let marker = if in_use { "*" } else { "" };
+    let result = if dpc.test_results.is_error() {
+        Cell::from(dpc.test_results.last_error.clone()).style(Style::new().red())
+    } else {
+        Cell::from(format!(
+            "succeeded {}",
+            time_to_string(&dpc.test_results.last_succeeded)
+        ))
+    };
+    Row::new(vec![
+        Cell::from(marker).style(Style::new().green()),
+        Cell::from(dpc.key.clone()),
+        Cell::from(time_to_string(&dpc.time_priority)),
+        Cell::from(dpc.state.to_string()).style(state_style(&dpc.state)),
+        result,
+    ])
+}
+
+fn do_render(
+    w: &mut Window<DpcListPageState>,
+    _rect: &Rect,
+    frame: &mut Frame<'_>,
+    model: &Rc<Model>,
+) {
+    let frame_rect = w.get_layout("frame");
+    let content_rect = w.get_layout("content");
+    let hint_rect = w.get_layout("hint");
+
+    frame.render_widget(Clear {}, frame_rect);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(Color::White))
+        .style(Style::default().bg(Color::Black))
+        .title(w.name.clone());
+    frame.render_widget(block, frame_rect);
+
+    let (up, down) = theme::scroll_arrows();
+    let hint = format!("{up}/{down} - navigate | Enter - view and revert | * - in use");
+    frame.render_widget(Line::from(hint).dark_gray(), hint_rect);
+
+    let model = model.borrow();
+    let Some(list) = model.get_dpc_list() else {
+        frame.render_widget(Paragraph::new("No port configuration").red(), content_rect);
+        w.state.size = 0;
+        return;
+    };
+    let dpcs = list.port_config_list.as_deref().unwrap_or_default();
+    let rows = dpcs
+        .iter()
+        .enumerate()
+        .map(|(i, dpc)| dpc_row(dpc, i == list.current_index as usize))
+        .collect::<Vec<_>>();
+
+    w.state.size = rows.len();
+    if rows.is_empty() {
+        w.state.table.select(None);
+    } else {
+        let selected = w.state.table.selected().unwrap_or_default();
+        w.state.table.select(Some(selected.min(rows.len() - 1)));
+    }
+
+    let header =
+        Row::new(vec!["", "Key", "Priority", "State", "Test result"]).style(Style::new().yellow());
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(1),
+            Constraint::Length(KEY_LENGTH),
+            Constraint::Length(TIME_LENGTH),
+            Constraint::Length(STATE_LENGTH),
+            Constraint::Fill(1),
+        ],
+    )
+    .block(
+        Block::default()
+            .borders(Borders::TOP)
+            .title(" Port configurations ")
+            .title_alignment(Alignment::Center),
+    )
+    .header(header)
+    .row_highlight_style(Style::new().bg(Color::DarkGray))
+    .highlight_symbol(theme::highlight_bar())
+    .highlight_spacing(HighlightSpacing::Always)
+    .column_spacing(1);
+    frame.render_stateful_widget(table, content_rect, &mut w.state.table);
+}
+
+fn do_layout(w: &mut Window<DpcListPageState>, rect: &Rect, _model: &Rc<Model>) {
+    let content_with_buttons = rect.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+
+    w.update_layout("frame", *rect);
+
+    let [content, hint, buttons] = Layout::vertical(vec![
+        Constraint::Fill(1),
+        Constraint::Length(1),
+        Constraint::Length(3),
+    ])
+    .areas(content_with_buttons);
+    w.update_layout("content", content);
+    w.update_layout("hint", hint);
+
+    let [close] = Layout::horizontal(vec![Constraint::Length(9)])
+        .flex(Flex::End)
+        .areas(buttons);
+    w.update_layout("close", close);
+}
+
+fn on_key_event(w: &mut Window<DpcListPageState>, key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => Some(Action::new(&w.name, UiActions::DismissDialog)),
+        KeyCode::Up => {
+            w.state.table.select_previous();
+            Some(Action::new(&w.name, UiActions::Redraw))
+        }
+        KeyCode::Down => {
+            w.state.table.select_next();
+            Some(Action::new(&w.name, UiActions::Redraw))
+        }
+        KeyCode::Enter => {
+            let selected = w.state.table.selected().filter(|i| *i < w.state.size)?;
+            Some(Action::new(&w.name, UiActions::ShowDpc(selected)))
+        }
+        _ => None,
+    }
+}
+
+fn on_child_ui_action(
+    w: &mut Window<DpcListPageState>,
+    source: &String,
+    action: &UiActions,
+) -> Option<Action> {
+    debug!("on_child_ui_action: {}:{:?}", source, action);
+    match action {
+        UiActions::ButtonClicked(_) => Some(Action::new(&w.name, UiActions::DismissDialog)),
+        _ => None,
+    }
+}
+
+pub fn create_dpc_list_page() -> impl IWindow {
+    Window::builder("Port configurations")
+        .with_on_init(on_init)
+        .with_layout(do_layout)
+        .with_render(do_render)
+        .with_on_key_event(on_key_event)
+        .with_on_child_ui_action(on_child_ui_action)
+        .with_state(DpcListPageState::default())
+        .build()
+        .unwrap()
+}
@@ -0,0 +1,293 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::rc::Rc;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use log::debug;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Margin, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    actions::MonActions,
+    ipc::eve_types::{DevicePortConfig, DhcpType, L2LinkType, NetworkPortConfig, WirelessType},
+    model::model::Model,
+    traits::IWindow,
+    ui::action::UiActions,
+};
+
+use super::{
+    action::Action,
+    dpclistpage::{state_style, time_to_string},
+    theme,
+    widgets::button::ButtonElement,
+    window::Window,
+};
+
+struct DpcPageState {
+    dpc: DevicePortConfig,
+    in_use: bool,
+    scroll: u16,
+}
+
+fn on_init(w: &mut Window<DpcPageState>) {
+    w.add_widget("close", ButtonElement::new("close"));
+    // the DPC in use cannot be reverted to
+    if w.state.in_use {
+        w.set_focus_tracker_tab_order(vec!["close"]);
+    } else {
+        w.add_widget("revert", ButtonElement::new("revert"));
+        w.set_focus_tracker_tab_order(vec!["revert", "close"]);
+    }
+}
+
+fn field<'a>(label: &'a str, value: impl Into<Span<'a>>) -> Line<'a> {
+    Line::from(vec![format!("{:<16}", label).yellow(), value.into()])
+}
+
+fn ip_to_string(port: &NetworkPortConfig) -> String {
+    let dhcp = &port.dhcp_config;
+    match dhcp.dhcp {
+        DhcpType::Client => "DHCP".to_string(),
+        DhcpType::None => "none".to_string(),
+        DhcpType::Static => {
+            let mut parts = vec![dhcp
+                .addr_subnet
+                .map_or("N/A".to_string(), |a| a.to_string())];
+            if !dhcp.gateway.is_empty() {
+                parts.push(format!("gateway {}", dhcp.gateway));
+            }
+            if let Some(dns) = dhcp.dns_servers.as_ref().filter(|d| !d.is_empty()) {
+                let dns = dns.iter().map(|ip| ip.to_string()).collect::<Vec<_>>();
+                parts.push(format!("DNS {}", dns.join(", ")));
+            }
+            if !dhcp.domain_name.is_empty() {
+                parts.push(format!("domain {}", dhcp.domain_name));
+            }
+            format!("static {}", parts.join(", "))
+        }
+        _ => format!("{:?}", dhcp.dhcp),
+    }
+}
+
+fn l2_to_string(port: &NetworkPortConfig) -> Option<String> {
+    let l2 = &port.l2_link_config;
+    match l2.l2_type {
+        L2LinkType::L2LinkTypeNone => None,
+        L2LinkType::L2LinkTypeVLAN => {
+            let vlan = l2.vlan.clone().unwrap_or_default();
+            Some(format!("VLAN {} on {}", vlan.id, vlan.parent_port))
+        }
+        L2LinkType::L2LinkTypeBond => {
+            let bond = l2.bond.clone().unwrap_or_default();
+            let ports = bond.aggregated_ports.unwrap_or_default().join(", ");
+            Some(format!("bond {} of {}", bond.mode, ports))
+        }
+    }
+}
+
+fn proxy_to_string(port: &NetworkPortConfig) -> Option<String> {
+    let proxy = &port.proxy_config;
+    if let Some(proxies) = proxy.proxies.as_ref().filter(|p| !p.is_empty()) {
+        let servers = proxies
+            .iter()
+            .map(|p| format!("{}:{}", p.server, p.port))
+            .collect::<Vec<_>>();
+        Some(servers.join(", "))
+    } else if !proxy.pacfile.is_empty() {
+        Some("PAC file".to_string())
+    } else if proxy.network_proxy_enable {
+        Some(format!("WPAD {}", proxy.network_proxy_url))
+    } else {
+        None
+    }
+}
+
+fn port_lines(port: &NetworkPortConfig) -> Vec<Line<'static>> {
+    let mut usage = vec![if port.is_mgmt {
+        "management"
+    } else {
+        "app-shared"
+    }
+    .to_string()];
+    if port.is_l3_port {
+        usage.push("L3".to_string());
+    }
+    usage.push(format!("cost {}", port.cost));
+    if port.mtu != 0 {
+        usage.push(format!("MTU {}", port.mtu));
+    }
+
+    let mut lines = vec![
+        Line::from(vec![
+            port.if_name.clone().white().bold(),
+            format!(" ({})", port.logical_label).white(),
+        ]),
+        field("  Usage", usage.join(", ")),
+        field("  IP", ip_to_string(port)),
+    ];
+    if let Some(l2) = l2_to_string(port) {
+        lines.push(field("  L2", l2));
+    }
+    match port.wireless_cfg.w_type {
+        WirelessType::None => {}
+        WirelessType::Wifi => {
+            let count = port.wireless_cfg.wifi.as_ref().map_or(0, |w| w.len());
+            lines.push(field("  Wireless", format!("WiFi, {} networks", count)));
+        }
+        WirelessType::Cellular => {
+            let count = port
+                .wireless_cfg
+                .cellular_v2
+                .as_ref()
+                .map_or(0, |c| c.access_points.as_ref().map_or(0, |a| a.len()));
+            lines.push(field(
+                "  Wireless",
+                format!("cellular, {} access points", count),
+            ));
+        }
+    }
+    if let Some(proxy) = proxy_to_string(port) {
+        lines.push(field("  Proxy", proxy));
+    }
+    if port.test_results.is_error() {
+        lines.push(field("  Error", port.test_results.last_error.clone().red()));
+    }
+    lines
+}
+
+fn dpc_text(dpc: &DevicePortConfig, in_use: bool) -> Text<'static> {
+    let results = &dpc.test_results;
+    let mut lines = vec![
+        field("Key", dpc.key.clone()),
+        field("Priority", time_to_string(&dpc.time_priority)),
+        field(
+            "State",
+            Span::styled(dpc.state.to_string(), state_style(&dpc.state)),
+        ),
+        field("In use", if in_use { "yes".green() } else { "no".white() }),
+        field("Last succeeded", time_to_string(&results.last_succeeded)),
+        field("Last failed", time_to_string(&results.last_failed)),
+    ];
+    if results.is_error() {
+        lines.push(field("Last error", results.last_error.clone().red()));
+    }
+    for port in &dpc.ports {
+        lines.push(Line::default());
+        lines.extend(port_lines(port));
+    }
+    Text::from(lines)
+}
+
+fn do_render(
+    w: &mut Window<DpcPageState>,
+    _rect: &Rect,
+    frame: &mut Frame<'_>,
+    _model: &Rc<Model>,
+) {
+    let frame_rect = w.get_layout("frame");
+    let content_rect = w.get_layout("content");
+    let hint_rect = w.get_layout("hint");
+
+    frame.render_widget(Clear {}, frame_rect);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(Color::White))
+        .style(Style::default().bg(Color::Black))
+        .title(format!("{}: {}", w.name, w.state.dpc.key));
+    frame.render_widget(block, frame_rect);
+
+    let (up, down) = theme::scroll_arrows();
+    let hint = if w.state.in_use {
+        format!("{up}/{down} - scroll | this configuration is in use")
+    } else {
+        format!("{up}/{down} - scroll | revert - submit as a new manual configuration")
+    };
+    frame.render_widget(Line::from(hint).dark_gray(), hint_rect);
+
+    let paragraph = Paragraph::new(dpc_text(&w.state.dpc, w.state.in_use))
+        .wrap(Wrap { trim: false })
+        .scroll((w.state.scroll, 0));
+    frame.render_widget(paragraph, content_rect);
+}
+
+fn do_layout(w: &mut Window<DpcPageState>, rect: &Rect, _model: &Rc<Model>) {
+    let content_with_buttons = rect.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+
+    w.update_layout("frame", *rect);
+
+    let [content, hint, buttons] = Layout::vertical(vec![
+        Constraint::Fill(1),
+        Constraint::Length(1),
+        Constraint::Length(3),
+    ])
+    .areas(content_with_buttons);
+    w.update_layout("content", content);
+    w.update_layout("hint", hint);
+
+    let [revert, close] = Layout::horizontal(vec![Constraint::Length(10), Constraint::Length(9)])
+        .flex(Flex::End)
+        .areas(buttons);
+    w.update_layout("revert", revert);
+    w.update_layout("close", close);
+}
+
+fn on_key_event(w: &mut Window<DpcPageState>, key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => Some(Action::new(&w.name, UiActions::DismissDialog)),
+        KeyCode::Up => {
+            w.state.scroll = w.state.scroll.saturating_sub(1);
+            Some(Action::new(&w.name, UiActions::Redraw))
+        }
+        KeyCode::Down => {
+            w.state.scroll = w.state.scroll.saturating_add(1);
+            Some(Action::new(&w.name, UiActions::Redraw))
+        }
+        _ => None,
+    }
+}
+
+fn on_child_ui_action(
+    w: &mut Window<DpcPageState>,
+    source: &String,
+    action: &UiActions,
+) -> Option<Action> {
+    debug!("on_child_ui_action: {}:{:?}", source, action);
+    match action {
+        UiActions::ButtonClicked(_) => match source.as_str() {
+            "revert" => Some(Action::new(
+                &w.name,
+                UiActions::AppAction(MonActions::DpcReverted(Box::new(w.state.dpc.clone()))),
+            )),
+            _ => Some(Action::new(&w.name, UiActions::DismissDialog)),
+        },
+        _ => None,
+    }
+}
+
+/// Show a DPC in full. `in_use` is set for the DPC the device currently uses
+pub fn create_dpc_page(dpc: &DevicePortConfig, in_use: bool) -> impl IWindow {
+    Window::builder("Port configuration")
+        .with_on_init(on_init)
+        .with_layout(do_layout)
+        .with_render(do_render)
+        .with_on_key_event(on_key_event)
+        .with_on_child_ui_action(on_child_ui_action)
+        .with_state(DpcPageState {
+            dpc: dpc.clone(),
+            in_use,
+            scroll: 0,
+        })
+        .build()
+        .unwrap()
+}
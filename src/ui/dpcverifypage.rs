@@ -0,0 +1,202 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::rc::Rc;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use log::debug;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Margin, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    actions::MonActions,
+    dpc::verification::{DpcVerification, VerificationStage},
+    model::model::Model,
+    traits::IWindow,
+    ui::action::UiActions,
+};
+
+use super::{
+    action::Action,
+    dpclistpage::{state_style, time_to_string},
+    theme,
+    widgets::button::ButtonElement,
+    window::Window,
+};
+
+const LABEL_LENGTH: usize = 14;
+
+#[derive(Default)]
+struct DpcVerifyPageState {
+    scroll: u16,
+}
+
+fn on_init(w: &mut Window<DpcVerifyPageState>) {
+    w.add_widget("close", ButtonElement::new("close"));
+    w.add_widget("edit", ButtonElement::new("edit"));
+    w.add_widget("revert", ButtonElement::new("revert"));
+    w.set_focus_tracker_tab_order(vec!["close", "edit", "revert"]);
+}
+
+fn field(label: &str, value: Span<'static>) -> Line<'static> {
+    Line::from(vec![format!("{:<LABEL_LENGTH$}", label).yellow(), value])
+}
+
+fn stage_span(stage: &VerificationStage) -> Span<'static> {
+    match stage {
+        VerificationStage::Pending => "waiting for EVE".yellow(),
+        VerificationStage::Testing(state) => Span::styled(state.to_string(), state_style(state)),
+        VerificationStage::Applied => "in use".green(),
+        VerificationStage::FellBack(_) => "failed".red(),
+        VerificationStage::Rejected => "rejected".red(),
+    }
+}
+
+fn verification_text(v: &DpcVerification) -> Text<'static> {
+    let mut lines = vec![
+        field("Configuration", v.dpc.key.clone().white()),
+        field("Priority", time_to_string(&v.dpc.time_priority).white()),
+        field("Replaces", v.previous.key.clone().white()),
+        field("State", stage_span(&v.stage)),
+    ];
+    // once finished, EVE testing other DPCs is unrelated to this one
+    if !v.is_finished() {
+        let testing = if v.testing {
+            "in progress".yellow()
+        } else {
+            "idle".white()
+        };
+        lines.push(field("EVE testing", testing));
+    }
+    lines.push(Line::default());
+    lines.push(Line::from(v.guidance()).white());
+    if !v.last_error.is_empty() {
+        lines.push(Line::default());
+        lines.push(Line::from("Error").red().bold());
+        lines.extend(
+            v.last_error
+                .split(':')
+                .map(|s| Line::from(format!("  {}", s.trim())).red()),
+        );
+    }
+    Text::from(lines)
+}
+
+fn do_render(
+    w: &mut Window<DpcVerifyPageState>,
+    _rect: &Rect,
+    frame: &mut Frame<'_>,
+    model: &Rc<Model>,
+) {
+    let frame_rect = w.get_layout("frame");
+    let content_rect = w.get_layout("content");
+    let hint_rect = w.get_layout("hint");
+
+    frame.render_widget(Clear {}, frame_rect);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(Color::White))
+        .style(Style::default().bg(Color::Black))
+        .title(w.name.clone());
+    frame.render_widget(block, frame_rect);
+
+    let (up, down) = theme::scroll_arrows();
+    let hint = format!("{up}/{down} - scroll | edit - change again | revert - undo the change");
+    frame.render_widget(Line::from(hint).dark_gray(), hint_rect);
+
+    let model = model.borrow();
+    let paragraph = match model.dpc_verification.as_ref() {
+        Some(v) => Paragraph::new(verification_text(v)),
+        None => Paragraph::new("No port configuration was applied").red(),
+    };
+    let paragraph = paragraph
+        .wrap(Wrap { trim: false })
+        .scroll((w.state.scroll, 0));
+    frame.render_widget(paragraph, content_rect);
+}
+
+fn do_layout(w: &mut Window<DpcVerifyPageState>, rect: &Rect, _model: &Rc<Model>) {
+    let content_with_buttons = rect.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+
+    w.update_layout("frame", *rect);
+
+    let [content, hint, buttons] = Layout::vertical(vec![
+        Constraint::Fill(1),
+        Constraint::Length(1),
+        Constraint::Length(3),
+    ])
+    .areas(content_with_buttons);
+    w.update_layout("content", content);
+    w.update_layout("hint", hint);
+
+    let [edit, revert, close] = Layout::horizontal(vec![
+        Constraint::Length(8),
+        Constraint::Length(10),
+        Constraint::Length(9),
+    ])
+    .flex(Flex::End)
+    .areas(buttons);
+    w.update_layout("edit", edit);
+    w.update_layout("revert", revert);
+    w.update_layout("close", close);
+}
+
+fn on_key_event(w: &mut Window<DpcVerifyPageState>, key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => Some(Action::new(&w.name, UiActions::DismissDialog)),
+        KeyCode::Up => {
+            w.state.scroll = w.state.scroll.saturating_sub(1);
+            Some(Action::new(&w.name, UiActions::Redraw))
+        }
+        KeyCode::Down => {
+            w.state.scroll = w.state.scroll.saturating_add(1);
+            Some(Action::new(&w.name, UiActions::Redraw))
+        }
+        _ => None,
+    }
+}
+
+fn on_child_ui_action(
+    w: &mut Window<DpcVerifyPageState>,
+    source: &String,
+    action: &UiActions,
+) -> Option<Action> {
+    debug!("on_child_ui_action: {}:{:?}", source, action);
+    match action {
+        UiActions::ButtonClicked(_) => match source.as_str() {
+            "edit" => Some(Action::new(
+                &w.name,
+                UiActions::AppAction(MonActions::DpcEditAgain),
+            )),
+            "revert" => Some(Action::new(
+                &w.name,
+                UiActions::AppAction(MonActions::DpcRevertApplied),
+            )),
+            _ => Some(Action::new(&w.name, UiActions::DismissDialog)),
+        },
+        _ => None,
+    }
+}
+
+/// Follow the last DPC sent to EVE through the verification
+pub fn create_dpc_verify_page() -> impl IWindow {
+    Window::builder("Port configuration verification")
+        .with_on_init(on_init)
+        .with_layout(do_layout)
+        .with_render(do_render)
+        .with_on_key_event(on_key_event)
+        .with_on_child_ui_action(on_child_ui_action)
+        .with_state(DpcVerifyPageState::default())
+        .build()
+        .unwrap()
+}
@@ -24,6 +24,8 @@ struct InputDialogState {
     caption: String,
     content: String,
     hint: String,
+    // adds a "check" button sending the current content
+    check_action: Option<fn(String) -> MonActions>,
 }
 
 fn on_init(w: &mut Window<InputDialogState>) {
@@ -33,10 +35,16 @@ fn on_init(w: &mut Window<InputDialogState>) {
             .with_text_hint(w.state.hint.clone()),
     );
     // buttons
+    let mut tab_order = vec!["input"];
+    if w.state.check_action.is_some() {
+        w.add_widget("check", ButtonElement::new("check"));
+        tab_order.push("check");
+    }
     w.add_widget("ok", ButtonElement::new("ok"));
     w.add_widget("cancel", ButtonElement::new("cancel"));
+    tab_order.extend(["ok", "cancel"]);
 
-    w.set_focus_tracker_tab_order(vec!["input", "ok", "cancel"]);
+    w.set_focus_tracker_tab_order(tab_order);
 }
 
 fn do_render(
@@ -85,9 +93,16 @@ fn do_layout(w: &mut Window<InputDialogState>, rect: &Rect, _model: &Rc<Model>)
     w.update_layout("input", tabs);
 
     // buttons
-    let [ok, cancel] = Layout::horizontal(vec![Constraint::Length(6), Constraint::Length(10)])
-        .flex(Flex::End)
-        .areas(buttons);
+    let [check, ok, cancel] = Layout::horizontal(vec![
+        Constraint::Length(if w.state.check_action.is_some() { 9 } else { 0 }),
+        Constraint::Length(6),
+        Constraint::Length(10),
+    ])
+    .flex(Flex::End)
+    .areas(buttons);
+    if w.state.check_action.is_some() {
+        w.update_layout("check", check);
+    }
     w.update_layout("ok", ok);
     w.update_layout("cancel", cancel);
 }
@@ -110,6 +125,12 @@ fn on_child_ui_action(
     match action {
         UiActions::ButtonClicked(name) => match name.as_str() {
             "cancel" => Some(Action::new(&w.name, UiActions::DismissDialog)),
+            "check" => w.state.check_action.map(|check| {
+                Action::new(
+                    &w.name,
+                    UiActions::AppAction(check(w.state.content.clone())),
+                )
+            }),
             "ok" => Some(Action::new(
                 &w.name,
                 UiActions::AppAction(MonActions::ServerUpdated(w.state.content.clone())),
@@ -132,6 +153,7 @@ pub fn create_input_dialog(
     caption: &str,
     content: &str,
     hint: &str,
+    check_action: Option<fn(String) -> MonActions>,
 ) -> impl IWindow {
     let w = Window::builder(window_caption)
         .with_on_init(on_init)
@@ -143,6 +165,7 @@ pub fn create_input_dialog(
             caption: caption.to_string(),
             content: content.to_string(),
             hint: hint.to_string(),
+            check_action,
         })
         .build()
         .unwrap();
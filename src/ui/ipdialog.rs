@@ -1,34 +1,43 @@
 // Copyright (c) 2024-2025 Zededa, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::HashMap, net::AddrParseError, net::Ipv4Addr, rc::Rc};
+use std::{collections::HashMap, net::AddrParseError, net::Ipv4Addr, path::Path, rc::Rc};
 use url::Url;
 
+use chrono::Utc;
+
 use crossterm::event::{KeyCode, KeyEvent};
 use log::debug;
 use ratatui::{
     layout::{Constraint, Flex, Layout, Margin, Rect},
-    style::{Color, Style},
-    widgets::{Block, BorderType, Borders, Clear},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
-use std::net::Ipv6Addr;
+use std::net::{IpAddr, Ipv6Addr};
 use std::num::ParseIntError;
 use std::str::FromStr;
 use thiserror::Error;
 
 use crate::{
     actions::MonActions,
+    dpc::{
+        builder::{build_dpc, parse_cost, parse_mtu, validate_interface, DpcField, FieldError},
+        certificate::{load_pem_file, ProxyCertificate},
+    },
+    ipc::eve_types::DevicePortConfig,
     model::{
         device::network::{NetworkInterfaceStatus, ProxyConfig},
         model::Model,
     },
-    traits::IWindow,
+    traits::{IWindow, TextInput},
 };
 
 use super::{
     action::{Action, UiActions},
-    tools::centered_rect,
+    theme,
+    tools::centered_rect_min,
     widgets::{
         button::ButtonElement, input_field::InputFieldElement, spin_box::SpinBoxElement,
         tab::TabElement,
@@ -69,7 +78,8 @@ pub struct InterfaceState {
     pub mask: String,
     pub gw: String,
     pub proxy_url: String,
-    pub proxy_certificate: String,
+    /// PEM encoded CA certificates of the proxy, one per entry
+    pub proxy_certificates: Vec<Vec<u8>>,
     pub pac_file: String,
     pub domain: String,
     pub dns: String,
@@ -79,6 +89,11 @@ pub struct InterfaceState {
     pub proxy_https: Option<Url>,
     pub proxy_ftp: Option<Url>,
     pub proxy_socks: Option<Url>,
+    // port settings
+    pub is_mgmt: bool,
+    pub cost: String,
+    pub mtu: String,
+    pub alias: String,
 }
 
 impl InterfaceState {
@@ -86,6 +101,14 @@ impl InterfaceState {
         self.ip_dhcp
     }
 
+    fn proxy_cert_pem(&self) -> Option<Vec<Vec<u8>>> {
+        if self.proxy_certificates.is_empty() {
+            None
+        } else {
+            Some(self.proxy_certificates.clone())
+        }
+    }
+
     /// Create a ProxyConfig from the current InterfaceState
     pub fn create_proxy_config(&self) -> crate::ipc::eve_types::ProxyConfig {
         use crate::ipc::eve_types::{NetworkProxyType, ProxyConfig, ProxyEntry};
@@ -99,7 +122,8 @@ impl InterfaceState {
                 network_proxy_url: String::new(),
                 wpad_url: String::new(),
                 exceptions: String::new(),
-                proxy_cert_pem: None,
+                // an intercepting proxy may be configured by WPAD or DHCP
+                proxy_cert_pem: self.proxy_cert_pem(),
             },
             ProxyType::Manual => {
                 let mut proxies = Vec::new();
@@ -166,11 +190,7 @@ impl InterfaceState {
                     network_proxy_enable: has_network_proxy_url,
                     pacfile: self.pac_file.clone(),
                     network_proxy_url: self.proxy_url.clone(),
-                    proxy_cert_pem: if !self.proxy_certificate.is_empty() {
-                        Some(vec![self.proxy_certificate.clone().into_bytes()])
-                    } else {
-                        None
-                    },
+                    proxy_cert_pem: self.proxy_cert_pem(),
                     wpad_url: String::new(),
                     exceptions: String::new(),
                 }
@@ -196,7 +216,7 @@ mod tests {
             mask: "255.255.255.0".to_string(),
             gw: "192.168.1.1".to_string(),
             proxy_url: "".to_string(),
-            proxy_certificate: "".to_string(),
+            proxy_certificates: vec![],
             pac_file: "".to_string(),
             domain: "example.com".to_string(),
             dns: "8.8.8.8".to_string(),
@@ -205,6 +225,10 @@ mod tests {
             proxy_https: None,
             proxy_ftp: None,
             proxy_socks: None,
+            is_mgmt: true,
+            cost: "0".to_string(),
+            mtu: "".to_string(),
+            alias: "".to_string(),
         };
 
         let proxy_config = interface_state.create_proxy_config();
@@ -227,7 +251,7 @@ mod tests {
             mask: "255.255.255.0".to_string(),
             gw: "192.168.1.1".to_string(),
             proxy_url: "http://proxy.example.com:8080".to_string(),
-            proxy_certificate: "test-cert".to_string(),
+            proxy_certificates: vec![b"test-cert".to_vec()],
             pac_file: "http://proxy.example.com/proxy.pac".to_string(),
             domain: "example.com".to_string(),
             dns: "8.8.8.8".to_string(),
@@ -236,6 +260,10 @@ mod tests {
             proxy_https: Some(Url::parse("https://proxy.example.com:8443").unwrap()),
             proxy_ftp: Some(Url::parse("ftp://proxy.example.com:21").unwrap()),
             proxy_socks: Some(Url::parse("socks://proxy.example.com:1080").unwrap()),
+            is_mgmt: true,
+            cost: "0".to_string(),
+            mtu: "".to_string(),
+            alias: "".to_string(),
         };
 
         let proxy_config = interface_state.create_proxy_config();
@@ -299,7 +327,7 @@ mod tests {
             mask: "255.255.255.0".to_string(),
             gw: "192.168.1.1".to_string(),
             proxy_url: "".to_string(),
-            proxy_certificate: "".to_string(),
+            proxy_certificates: vec![],
             pac_file: "".to_string(),
             domain: "example.com".to_string(),
             dns: "8.8.8.8".to_string(),
@@ -308,6 +336,10 @@ mod tests {
             proxy_https: None,
             proxy_ftp: None,
             proxy_socks: None,
+            is_mgmt: true,
+            cost: "0".to_string(),
+            mtu: "".to_string(),
+            alias: "".to_string(),
         };
 
         let proxy_config = interface_state.create_proxy_config();
@@ -334,7 +366,7 @@ mod tests {
             mask: "255.255.255.0".to_string(),
             gw: "192.168.1.1".to_string(),
             proxy_url: "".to_string(),
-            proxy_certificate: "".to_string(),
+            proxy_certificates: vec![],
             pac_file: "".to_string(),
             domain: "example.com".to_string(),
             dns: "8.8.8.8".to_string(),
@@ -343,6 +375,10 @@ mod tests {
             proxy_https: None,
             proxy_ftp: None,
             proxy_socks: None,
+            is_mgmt: true,
+            cost: "0".to_string(),
+            mtu: "".to_string(),
+            alias: "".to_string(),
         };
 
         let proxy_config = interface_state.create_proxy_config();
@@ -362,7 +398,7 @@ mod tests {
             mask: "255.255.255.0".to_string(),
             gw: "192.168.1.1".to_string(),
             proxy_url: "http://proxy.example.com:8080".to_string(),
-            proxy_certificate: "test-cert".to_string(),
+            proxy_certificates: vec![b"test-cert".to_vec()],
             pac_file: "".to_string(),
             domain: "example.com".to_string(),
             dns: "8.8.8.8".to_string(),
@@ -371,6 +407,10 @@ mod tests {
             proxy_https: None,
             proxy_ftp: None,
             proxy_socks: None,
+            is_mgmt: true,
+            cost: "0".to_string(),
+            mtu: "".to_string(),
+            alias: "".to_string(),
         };
 
         // Test that create_proxy_config works and returns the expected config
@@ -413,7 +453,7 @@ mod tests {
             mask: "255.255.255.0".to_string(),
             gw: "192.168.1.1".to_string(),
             proxy_url: "http://network-proxy.example.com:8080".to_string(),
-            proxy_certificate: "".to_string(),
+            proxy_certificates: vec![],
             pac_file: "".to_string(),
             domain: "example.com".to_string(),
             dns: "8.8.8.8".to_string(),
@@ -422,6 +462,10 @@ mod tests {
             proxy_https: None,
             proxy_ftp: None,
             proxy_socks: None,
+            is_mgmt: true,
+            cost: "0".to_string(),
+            mtu: "".to_string(),
+            alias: "".to_string(),
         };
 
         let proxy_config = interface_state.create_proxy_config();
@@ -467,6 +511,14 @@ pub struct IpDialogState {
     focus_tarcker_state: HashMap<String, usize>,
     pub new_iface_state: InterfaceState,
     pub old_iface_state: InterfaceState,
+    // index into new_iface_state.proxy_certificates
+    selected_certificate: usize,
+    cert_path: String,
+    import_error: Option<String>,
+    // the DPC the settings are applied to, None if EVE did not send it yet
+    current_dpc: Option<DevicePortConfig>,
+    // error of a setting without an input field, e.g. the usage of the port
+    error: Option<String>,
 }
 
 impl IpDialogState {
@@ -491,23 +543,26 @@ impl IpDialogState {
                     ]
                 }
             }
-            "Proxy" => match self.new_iface_state.proxy_type {
-                ProxyType::None => vec!["proxy_spinner"],
-                ProxyType::Manual => {
-                    vec![
-                        "proxy_spinner",
-                        "http",
-                        "https",
-                        "ftp",
-                        "socks",
-                        // this is not supported yet but let's keep it here for future use
-                        // "certificate",
-                        // "upload",
-                    ]
-                } // this is not supported yet but let's keep it here for future use
-                  // ProxyType::Wad => vec!["proxy_spinner"],
-                  // ProxyType::Pac => vec!["proxy_spinner", "pac_file", "upload"],
-            },
+            "Proxy" => {
+                let mut order = match self.new_iface_state.proxy_type {
+                    ProxyType::None => vec!["proxy_spinner"],
+                    ProxyType::Manual => {
+                        vec!["proxy_spinner", "http", "https", "ftp", "socks"]
+                    } // this is not supported yet but let's keep it here for future use
+                      // ProxyType::Wad => vec!["proxy_spinner"],
+                      // ProxyType::Pac => vec!["proxy_spinner", "pac_file", "upload"],
+                };
+                let has_certificates = !self.new_iface_state.proxy_certificates.is_empty();
+                if has_certificates {
+                    order.push("cert_spinner");
+                }
+                order.extend(["cert_path", "import"]);
+                if has_certificates {
+                    order.push("remove");
+                }
+                order
+            }
+            "Port settings" => vec!["usage_spinner", "cost", "mtu", "alias"],
             _ => vec![],
         };
         order.push("ok");
@@ -524,6 +579,9 @@ fn on_init(w: &mut Window<IpDialogState>) {
 fn init_focus_tracker(w: &mut Window<IpDialogState>) {
     w.state.focus_tarcker_state.insert("IP".to_string(), 0);
     w.state.focus_tarcker_state.insert("Proxy".to_string(), 0);
+    w.state
+        .focus_tarcker_state
+        .insert("Port settings".to_string(), 0);
     let current_tab_order = w
         .state
         .get_current_tab_order()
@@ -604,12 +662,13 @@ pub fn validate_ipv6_cidr(input: &str) -> Result<(Ipv6Addr, u8), CidrError> {
 
 fn create_widgets(w: &mut Window<IpDialogState>) {
     // create all widgets only once. We draw only widgets that present in the layout
+    let tab_hint = theme::tab_switch_hint();
     w.add_widget(
         "tabs",
         TabElement::new(
-            vec!["IP", "Proxy"],
+            vec!["IP", "Proxy", "Port settings"],
             "IP",
-            Some(" Use ctrl + ◄ ► to change tab"),
+            Some(tab_hint.as_str()),
         ),
     );
 
@@ -632,7 +691,8 @@ fn create_widgets(w: &mut Window<IpDialogState>) {
         InputFieldElement::new("IPv4", Some(w.state.new_iface_state.ipv4.as_str()))
             .with_text_hint("e.g. 192.168.0.1")
             .validate(|ip| match validate_ipv4_cidr(ip) {
-                Ok(_) => Ok(()),
+                // empty for IPv6 only
+                Ok(_) | Err(CidrError::EmptyInput) => Ok(()),
                 Err(e) => Err(e.to_string()),
             }),
     );
@@ -640,9 +700,10 @@ fn create_widgets(w: &mut Window<IpDialogState>) {
     w.add_widget(
         "ipv6",
         InputFieldElement::new("IPv6", Some(w.state.new_iface_state.ipv6.as_str()))
-            .with_text_hint("e.g. c820::1")
+            .with_text_hint("e.g. 2001:db8::5/64")
             .validate(|ip| match validate_ipv6_cidr(ip) {
-                Ok(_) => Ok(()),
+                // empty for IPv4 only and dual-stack
+                Ok(_) | Err(CidrError::EmptyInput) => Ok(()),
                 Err(e) => Err(e.to_string()),
             }),
     );
@@ -652,8 +713,9 @@ fn create_widgets(w: &mut Window<IpDialogState>) {
         InputFieldElement::new("Mask", Some(w.state.new_iface_state.mask.as_str()))
             .with_text_hint("e.g. 255.255.255.0")
             .validate(|mask| {
+                // not used for IPv6
                 if mask.is_empty() {
-                    return Err("Mask cannot be empty".to_string());
+                    return Ok(());
                 }
                 // try to parse as IPv4 CIDR
                 match validate_ipv4_cidr(mask) {
@@ -746,21 +808,210 @@ fn create_widgets(w: &mut Window<IpDialogState>) {
             ),
         ),
     );
+
+    // port settings widgets
+    let index = if w.state.new_iface_state.is_mgmt {
+        0
+    } else {
+        1
+    };
+    w.add_widget(
+        "usage_spinner",
+        SpinBoxElement::new(vec!["Management", "App only"]).selected(index),
+    );
+    w.add_widget(
+        "cost",
+        InputFieldElement::new("Cost", Some(w.state.new_iface_state.cost.as_str()))
+            .with_text_hint("0-255, ports with lower cost are preferred")
+            .validate(|cost| parse_cost(cost).map(|_| ())),
+    );
+    w.add_widget(
+        "mtu",
+        InputFieldElement::new("MTU", Some(w.state.new_iface_state.mtu.as_str()))
+            .with_text_hint("e.g. 1500, empty for default")
+            .validate(|mtu| parse_mtu(mtu).map(|_| ())),
+    );
+    w.add_widget(
+        "alias",
+        InputFieldElement::new("Alias", Some(w.state.new_iface_state.alias.as_str()))
+            .with_text_hint("e.g. uplink-1"),
+    );
+
+    // proxy CA certificates
+    w.add_widget(
+        "cert_path",
+        InputFieldElement::new("Certificate file", Some(""))
+            .with_text_hint("e.g. /media/usb/proxy-ca.pem"),
+    );
+    w.add_widget("import", ButtonElement::new("import"));
+    w.add_widget("remove", ButtonElement::new("remove"));
+    update_certificate_widgets(w);
+
     // w.add_widget(
     //     "pac_file",
     //     InputFieldElement::new("PAC file", Some(&w.state.new_iface_state.pac_file.as_str()))
     //         .enabled(false),
     // );
-    // This is not supported yet but let's keep it here for future use
-    // w.add_widget(
-    //     "certificate",
-    //     InputFieldElement::new(
-    //         "Proxy Certificcate",
-    //         Some(&w.state.new_iface_state.proxy_certificate.as_str()),
-    //     )
-    //     .enabled(false),
-    // );
-    // w.add_widget("upload", ButtonElement::new("Upload"));
+}
+
+const CERT_LABEL_LENGTH: usize = 9;
+
+fn cert_field(label: &str, value: Vec<Span<'static>>) -> Line<'static> {
+    let mut spans = vec![format!("{:<CERT_LABEL_LENGTH$}", label).yellow()];
+    spans.extend(value);
+    Line::from(spans)
+}
+
+fn certificate_info(cert: &ProxyCertificate) -> Text<'static> {
+    let now = Utc::now();
+    let validity = if cert.is_valid_at(now) {
+        "".into()
+    } else if now < cert.not_before {
+        " not yet valid".red()
+    } else {
+        " expired".red()
+    };
+    let fingerprint = cert.fingerprint_hex();
+    // 16 bytes per line
+    let (first, second) = fingerprint.split_at(48);
+    Text::from(vec![
+        cert_field("Subject", vec![cert.subject.clone().white()]),
+        cert_field("Issuer", vec![cert.issuer.clone().white()]),
+        cert_field(
+            "Valid",
+            vec![
+                format!(
+                    "{} - {}",
+                    cert.not_before.format("%Y-%m-%d"),
+                    cert.not_after.format("%Y-%m-%d")
+                )
+                .white(),
+                validity,
+            ],
+        ),
+        cert_field(
+            "SHA-256",
+            vec![first.trim_end_matches(':').to_string().white()],
+        ),
+        cert_field("", vec![second.to_string().white()]),
+    ])
+}
+
+fn certificate_text(state: &IpDialogState) -> Text<'static> {
+    if let Some(error) = &state.import_error {
+        return Text::from(format!("Import failed: {}", error)).red();
+    }
+    let certs = &state.new_iface_state.proxy_certificates;
+    match certs.get(state.selected_certificate) {
+        Some(pem) => match ProxyCertificate::from_pem(pem) {
+            Ok(cert) => certificate_info(&cert),
+            Err(e) => Text::from(e.to_string()).red(),
+        },
+        None => Text::from("Import the CA certificate of a TLS intercepting proxy from a PEM file")
+            .white(),
+    }
+}
+
+/// Recreate the certificate selector for the current list
+fn update_certificate_widgets(w: &mut Window<IpDialogState>) {
+    let certs = &w.state.new_iface_state.proxy_certificates;
+    let mut labels = certs
+        .iter()
+        .enumerate()
+        .map(|(i, pem)| {
+            let name = ProxyCertificate::from_pem(pem)
+                .map(|cert| cert.common_name)
+                .unwrap_or_else(|_| "unreadable certificate".to_string());
+            format!("{}/{} {}", i + 1, certs.len(), name)
+        })
+        .collect::<Vec<_>>();
+    if labels.is_empty() {
+        labels.push("no certificates".to_string());
+    }
+    let selected = w.state.selected_certificate.min(labels.len() - 1);
+    w.add_widget(
+        "cert_spinner",
+        SpinBoxElement::new(labels).selected(selected),
+    );
+}
+
+fn set_import_error(w: &mut Window<IpDialogState>, error: Option<String>) {
+    if let Some(field) = w
+        .get_widget_mut("cert_path")
+        .and_then(|widget| widget.as_any_mut().downcast_mut::<InputFieldElement>())
+    {
+        field.set_error(error.clone());
+    }
+    w.state.import_error = error;
+}
+
+/// Add the certificates of the file in `cert_path`. Certificates which are
+/// already in the list are skipped
+fn import_certificates(w: &mut Window<IpDialogState>) {
+    let path = w.state.cert_path.trim().to_string();
+    if path.is_empty() {
+        set_import_error(w, Some("enter the path of a PEM file".to_string()));
+        return;
+    }
+    let certs = match load_pem_file(Path::new(&path)) {
+        Ok(certs) => certs,
+        Err(e) => {
+            set_import_error(w, Some(e.to_string()));
+            return;
+        }
+    };
+    let list = &mut w.state.new_iface_state.proxy_certificates;
+    let mut known = list
+        .iter()
+        .filter_map(|pem| ProxyCertificate::from_pem(pem).ok())
+        .map(|cert| cert.fingerprint)
+        .collect::<Vec<_>>();
+    let first_new = list.len();
+    for cert in certs {
+        if !known.contains(&cert.fingerprint) {
+            known.push(cert.fingerprint);
+            list.push(cert.pem);
+        }
+    }
+    if list.len() == first_new {
+        set_import_error(w, Some("the certificates are already imported".to_string()));
+        return;
+    }
+    w.state.selected_certificate = first_new;
+    set_import_error(w, None);
+    update_certificate_widgets(w);
+    update_tab_order(w);
+    set_focus(w, "import");
+}
+
+fn remove_certificate(w: &mut Window<IpDialogState>) {
+    let list = &mut w.state.new_iface_state.proxy_certificates;
+    if w.state.selected_certificate < list.len() {
+        list.remove(w.state.selected_certificate);
+    }
+    w.state.selected_certificate = w
+        .state
+        .selected_certificate
+        .min(list.len().saturating_sub(1));
+    set_import_error(w, None);
+    update_certificate_widgets(w);
+    update_tab_order(w);
+    // the remove button disappears with the last certificate
+    if w.state.new_iface_state.proxy_certificates.is_empty() {
+        set_focus(w, "cert_path");
+    } else {
+        set_focus(w, "remove");
+    }
+}
+
+// input fields are drawn with a border if there is enough room for all of them.
+// Otherwise they are rendered in a single line each
+pub(crate) fn field_height(rect: &Rect, count: u16) -> u16 {
+    if rect.height >= count * 3 {
+        3
+    } else {
+        1
+    }
 }
 
 fn update_ip_layout(w: &mut Window<IpDialogState>, rect: &Rect) {
@@ -772,16 +1023,9 @@ fn update_ip_layout(w: &mut Window<IpDialogState>, rect: &Rect) {
     w.update_layout("ip_spinner", spinner_rect);
 
     if !w.state.new_iface_state.ip_dhcp {
-        let [ip, mask, gw, ipv6, domain, dns, ntp] = Layout::vertical(vec![
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-        ])
-        .areas(input_rect);
+        let [ip, mask, gw, ipv6, domain, dns, ntp] =
+            Layout::vertical(vec![Constraint::Length(field_height(&input_rect, 7)); 7])
+                .areas(input_rect);
 
         w.update_layout("ipv4", ip);
         w.update_layout("mask", mask);
@@ -794,34 +1038,54 @@ fn update_ip_layout(w: &mut Window<IpDialogState>, rect: &Rect) {
 }
 fn update_proxy_layout(w: &mut Window<IpDialogState>, rect: &Rect) {
     debug!("update_proxy_layout");
-    let [spinner_rect, input_rect] =
-        Layout::vertical(vec![Constraint::Length(1), Constraint::Fill(1)]).areas(*rect);
+    // the certificate selector, at least 7 lines of info and the file input
+    const CERTIFICATES_MIN_HEIGHT: u16 = 11;
+    let proxies_height = match w.state.new_iface_state.proxy_type {
+        ProxyType::None => 0,
+        ProxyType::Manual => {
+            let available = Rect {
+                height: rect.height.saturating_sub(1 + CERTIFICATES_MIN_HEIGHT),
+                ..*rect
+            };
+            4 * field_height(&available, 4)
+        }
+    };
+    let [spinner_rect, input_rect, cert_spinner, cert_info, cert_file] = Layout::vertical(vec![
+        Constraint::Length(1),
+        Constraint::Length(proxies_height),
+        Constraint::Length(1),
+        Constraint::Fill(1),
+        Constraint::Length(3),
+    ])
+    .areas(*rect);
 
     w.update_layout("proxy_spinner", spinner_rect);
 
+    let [cert_path, import, remove] = Layout::horizontal(vec![
+        Constraint::Fill(1),
+        Constraint::Length(10),
+        Constraint::Length(10),
+    ])
+    .areas(cert_file);
+    w.update_layout("cert_spinner", cert_spinner);
+    w.update_layout("cert_info", cert_info);
+    w.update_layout("cert_path", cert_path);
+    w.update_layout("import", import);
+    if !w.state.new_iface_state.proxy_certificates.is_empty() {
+        w.update_layout("remove", remove);
+    }
+
     match w.state.new_iface_state.proxy_type {
         ProxyType::None => {}
         ProxyType::Manual => {
-            let [http, https, ftp, socks, certificate] = Layout::vertical(vec![
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
-            ])
-            .areas(input_rect);
-
-            let [cert_str, upload_button] =
-                Layout::horizontal(vec![Constraint::Fill(1), Constraint::Length(10)])
-                    .flex(Flex::End)
-                    .areas(certificate);
+            let [http, https, ftp, socks] =
+                Layout::vertical(vec![Constraint::Length(field_height(&input_rect, 4)); 4])
+                    .areas(input_rect);
 
             w.update_layout("http", http);
             w.update_layout("https", https);
             w.update_layout("ftp", ftp);
             w.update_layout("socks", socks);
-            w.update_layout("certificate", cert_str);
-            w.update_layout("upload", upload_button);
         } // This is not supported yet but let's keep it here for future use
           // ProxyType::Pac => {
           //     let [pac_file_area] = Layout::vertical(vec![Constraint::Length(3)]).areas(input_rect);
@@ -836,6 +1100,21 @@ fn update_proxy_layout(w: &mut Window<IpDialogState>, rect: &Rect) {
     }
 }
 
+fn update_port_layout(w: &mut Window<IpDialogState>, rect: &Rect) {
+    debug!("update_port_layout");
+    let [spinner_rect, input_rect] =
+        Layout::vertical(vec![Constraint::Length(1), Constraint::Fill(1)]).areas(*rect);
+
+    w.update_layout("usage_spinner", spinner_rect);
+
+    let [cost, mtu, alias] =
+        Layout::vertical(vec![Constraint::Length(field_height(&input_rect, 3)); 3])
+            .areas(input_rect);
+    w.update_layout("cost", cost);
+    w.update_layout("mtu", mtu);
+    w.update_layout("alias", alias);
+}
+
 fn update_current_layout(w: &mut Window<IpDialogState>, rect: &Rect) {
     match w.state.selected_tab.as_str() {
         "IP" => {
@@ -844,6 +1123,9 @@ fn update_current_layout(w: &mut Window<IpDialogState>, rect: &Rect) {
         "Proxy" => {
             update_proxy_layout(w, rect);
         }
+        "Port settings" => {
+            update_port_layout(w, rect);
+        }
         _ => {}
     }
 }
@@ -852,7 +1134,8 @@ fn ip_dialog_layout(w: &mut Window<IpDialogState>, rect: &Rect, _model: &Rc<Mode
     debug!("ip_dialog_layout. selected tab: {}", w.state.selected_tab);
     w.clear_layout();
 
-    let rect = centered_rect(40, 80, *rect);
+    // on small screens take as much space as possible
+    let rect = centered_rect_min(40, 80, 60, 32, *rect);
     let content_with_buttons = rect.inner(Margin {
         horizontal: 1,
         vertical: 1,
@@ -873,10 +1156,14 @@ fn ip_dialog_layout(w: &mut Window<IpDialogState>, rect: &Rect, _model: &Rc<Mode
 
     update_current_layout(w, &dialog_content_rect);
 
-    // buttons
-    let [ok, cancel] = Layout::horizontal(vec![Constraint::Length(6), Constraint::Length(10)])
-        .flex(Flex::End)
-        .areas(buttons);
+    // buttons, errors without an input field are shown next to them
+    let [error, ok, cancel] = Layout::horizontal(vec![
+        Constraint::Fill(1),
+        Constraint::Length(6),
+        Constraint::Length(10),
+    ])
+    .areas(buttons);
+    w.update_layout("error", error);
     w.update_layout("ok", ok);
     w.update_layout("cancel", cancel);
 }
@@ -902,6 +1189,16 @@ fn ip_dialog_render(
         .title(w.state.new_iface_state.iface_name.as_str());
 
     frame.render_widget(block, frame_rect);
+
+    if w.state.selected_tab == "Proxy" {
+        let info = Paragraph::new(certificate_text(&w.state)).wrap(Wrap { trim: false });
+        frame.render_widget(info, w.get_layout("cert_info"));
+    }
+
+    if let Some(error) = w.state.error.clone() {
+        let error = Paragraph::new(error.red()).wrap(Wrap { trim: false });
+        frame.render_widget(error, w.get_layout("error"));
+    }
 }
 
 fn on_key_event(w: &mut Window<IpDialogState>, key: KeyEvent) -> Option<Action> {
@@ -944,17 +1241,40 @@ fn on_child_ui_action(
                 update_tab_order(w);
                 Some(Action::new(source, UiActions::Redraw))
             }
+            "usage_spinner" => {
+                w.state.new_iface_state.is_mgmt = *selected == 0;
+                Some(Action::new(source, UiActions::Redraw))
+            }
+            "cert_spinner" => {
+                w.state.selected_certificate = *selected;
+                set_import_error(w, None);
+                Some(Action::new(source, UiActions::Redraw))
+            }
             _ => None,
         },
         UiActions::ButtonClicked(name) => match name.as_str() {
             "cancel" => Some(Action::new(&w.name, UiActions::DismissDialog)),
-            "ok" => Some(Action::new(
-                &w.name,
-                UiActions::AppAction(MonActions::NetworkInterfaceUpdated(
-                    w.state.old_iface_state.clone(),
-                    w.state.new_iface_state.clone(),
+            "import" => {
+                import_certificates(w);
+                Some(Action::new(source, UiActions::Redraw))
+            }
+            "remove" => {
+                remove_certificate(w);
+                Some(Action::new(source, UiActions::Redraw))
+            }
+            "ok" => match check_settings(&w.state) {
+                Ok(()) => Some(Action::new(
+                    &w.name,
+                    UiActions::AppAction(MonActions::NetworkInterfaceUpdated(
+                        w.state.old_iface_state.clone(),
+                        w.state.new_iface_state.clone(),
+                    )),
                 )),
-            )),
+                Err(errors) => {
+                    show_field_errors(w, &errors);
+                    Some(Action::new(source, UiActions::Redraw))
+                }
+            },
             _ => None,
         },
         UiActions::Input { text } => {
@@ -970,6 +1290,10 @@ fn on_child_ui_action(
                 "ftp" => w.state.new_iface_state.proxy_ftp = parse_proxy_url(&text, "ftp"),
                 "socks" => w.state.new_iface_state.proxy_socks = parse_proxy_url(&text, "socks"),
                 "ntp" => w.state.new_iface_state.ntp = text.clone(),
+                "cost" => w.state.new_iface_state.cost = text.clone(),
+                "mtu" => w.state.new_iface_state.mtu = text.clone(),
+                "alias" => w.state.new_iface_state.alias = text.clone(),
+                "cert_path" => w.state.cert_path = text.clone(),
                 _ => {}
             }
             None
@@ -978,6 +1302,96 @@ fn on_child_ui_action(
     }
 }
 
+/// Validate the settings and build the DPC with them the same way the
+/// application does, so the dialog stays open with the errors shown
+fn check_settings(state: &IpDialogState) -> Result<(), Vec<FieldError>> {
+    validate_interface(&state.new_iface_state)?;
+    match &state.current_dpc {
+        Some(dpc) => build_dpc(dpc, &state.old_iface_state, &state.new_iface_state).map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+fn field_widget_name(field: DpcField) -> Option<&'static str> {
+    match field {
+        DpcField::Interface => Some("ip_spinner"),
+        DpcField::Usage => Some("usage_spinner"),
+        DpcField::Ipv4 => Some("ipv4"),
+        DpcField::Mask => Some("mask"),
+        DpcField::Gateway => Some("gw"),
+        DpcField::Ipv6 => Some("ipv6"),
+        DpcField::Domain => Some("domain"),
+        DpcField::Dns => Some("dns"),
+        DpcField::Ntp => Some("ntp"),
+        DpcField::Cost => Some("cost"),
+        DpcField::Mtu => Some("mtu"),
+        // WiFi and cellular settings are edited in their own dialogs
+        _ => None,
+    }
+}
+
+/// Mark invalid input fields and move focus to the first one
+fn show_field_errors(w: &mut Window<IpDialogState>, errors: &[FieldError]) {
+    const FIELDS: [&str; 9] = [
+        "ipv4", "mask", "gw", "ipv6", "domain", "dns", "ntp", "cost", "mtu",
+    ];
+    for name in FIELDS {
+        let error = errors
+            .iter()
+            .find(|e| field_widget_name(e.field) == Some(name))
+            .map(|e| e.message.clone());
+        if let Some(field) = w
+            .get_widget_mut(name)
+            .and_then(|widget| widget.as_any_mut().downcast_mut::<InputFieldElement>())
+        {
+            field.set_error(error);
+        }
+    }
+    // settings without an input field are reported below the tabs
+    w.state.error = errors
+        .iter()
+        .find(|e| matches!(e.field, DpcField::Interface | DpcField::Usage))
+        .map(|e| e.to_string());
+
+    let Some(first) = errors.iter().find_map(|e| field_widget_name(e.field)) else {
+        return;
+    };
+    // switch to the tab of the first invalid field
+    let tab = match first {
+        "usage_spinner" | "cost" | "mtu" => "Port settings",
+        _ => "IP",
+    };
+    if w.state.selected_tab != tab {
+        if let Some(tabs) = w
+            .get_widget_mut("tabs")
+            .and_then(|widget| widget.as_any_mut().downcast_mut::<TabElement>())
+        {
+            tabs.select(tab);
+        }
+        let old_tab = w.state.selected_tab.clone();
+        save_restore_ft_state(w, &old_tab, &tab.to_string());
+    }
+    if let Some(index) = w
+        .state
+        .get_current_tab_order()
+        .iter()
+        .position(|name| *name == first)
+    {
+        w.set_focused_view(index);
+    }
+}
+
+fn set_focus(w: &mut Window<IpDialogState>, name: &str) {
+    if let Some(index) = w
+        .state
+        .get_current_tab_order()
+        .iter()
+        .position(|n| *n == name)
+    {
+        w.set_focused_view(index);
+    }
+}
+
 fn save_restore_ft_state(w: &mut Window<IpDialogState>, old_tab: &String, selected_tab: &String) {
     // save FocusTracker state for the old tab
     w.state
@@ -1021,13 +1435,25 @@ impl From<&NetworkInterfaceStatus> for IpDialogState {
             .map(|addr| addr.to_string())
             .unwrap_or_default();
 
-        let ipv6 = iface
-            .ipv6
-            .as_ref()
-            .map(|ipv6: &Vec<std::net::Ipv6Addr>| ipv6.first().cloned())
-            .flatten()
-            .map(|addr| addr.to_string())
-            .unwrap_or_default();
+        // EVE supports only one static address per port. IPv6 is filled only
+        // for IPv6-only interfaces, with the prefix length of its subnet
+        let ipv6 = if ipv4.is_empty() {
+            iface
+                .ipv6
+                .iter()
+                .flatten()
+                .find_map(|addr| {
+                    iface
+                        .ipv6_subnets
+                        .iter()
+                        .flatten()
+                        .find(|subnet| subnet.contains(&IpAddr::V6(*addr)))
+                        .map(|subnet| format!("{}/{}", addr, subnet.prefix_len()))
+                })
+                .unwrap_or_default()
+        } else {
+            String::new()
+        };
 
         let proxy_type = match iface.proxy_config {
             ProxyConfig::None => ProxyType::None,
@@ -1105,18 +1531,17 @@ impl From<&NetworkInterfaceStatus> for IpDialogState {
                 .subnet
                 .map(|ip| ip.netmask().to_string())
                 .unwrap_or_default(),
+            // a port has a single gateway. Take the one of the address family
+            // shown in the dialog
             gw: iface
                 .routes
-                .as_ref()
-                .map(|ip| {
-                    ip.iter()
-                        .map(|ip| ip.to_string())
-                        .collect::<Vec<_>>()
-                        .join(",")
-                })
+                .iter()
+                .flatten()
+                .find(|gw| gw.is_ipv6() == (ipv4.is_empty() && !ipv6.is_empty()))
+                .map(|gw| gw.to_string())
                 .unwrap_or_default(),
             proxy_url,
-            proxy_certificate: "".to_string(),
+            proxy_certificates: iface.proxy_certificates.clone(),
             pac_file,
             domain,
             dns,
@@ -1125,6 +1550,15 @@ impl From<&NetworkInterfaceStatus> for IpDialogState {
             proxy_http,
             proxy_https,
             proxy_socks,
+            is_mgmt: iface.is_mgmt,
+            cost: iface.cost.to_string(),
+            // 0 is the default MTU
+            mtu: if iface.mtu == 0 {
+                String::new()
+            } else {
+                iface.mtu.to_string()
+            },
+            alias: iface.alias.clone(),
         };
 
         let old_iface_state = new_iface_state.clone();
@@ -1134,12 +1568,23 @@ impl From<&NetworkInterfaceStatus> for IpDialogState {
             focus_tarcker_state: HashMap::new(),
             new_iface_state,
             old_iface_state,
+            selected_certificate: 0,
+            cert_path: String::new(),
+            import_error: None,
+            current_dpc: None,
+            error: None,
         }
     }
 }
 
-pub fn create_ip_dialog(iface: &NetworkInterfaceStatus) -> impl IWindow {
-    let state = IpDialogState::from(iface);
+pub fn create_ip_dialog(
+    iface: &NetworkInterfaceStatus,
+    current_dpc: Option<DevicePortConfig>,
+) -> impl IWindow {
+    let state = IpDialogState {
+        current_dpc,
+        ..IpDialogState::from(iface)
+    };
     let w = Window::builder("IP configuration")
         .with_layout(ip_dialog_layout)
         .with_render(ip_dialog_render)
@@ -0,0 +1,118 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::rc::Rc;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use log::debug;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Margin, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::{model::model::Model, traits::IWindow, ui::action::UiActions};
+
+use super::{action::Action, widgets::button::ButtonElement, window::Window};
+
+const KEY_LENGTH: usize = 15;
+const DIALOG_WIDTH: u16 = 56;
+
+/// Key bindings of a page which do not fit into the status bar
+struct KeyHelpDialogState {
+    keys: Vec<(String, String)>,
+}
+
+fn on_init(w: &mut Window<KeyHelpDialogState>) {
+    w.add_widget("ok", ButtonElement::new("ok"));
+    w.set_focus_tracker_tab_order(vec!["ok"]);
+}
+
+fn do_render(
+    w: &mut Window<KeyHelpDialogState>,
+    _rect: &Rect,
+    frame: &mut Frame<'_>,
+    _model: &Rc<Model>,
+) {
+    let frame_rect = w.get_layout("frame");
+    let content_rect = w.get_layout("content");
+
+    frame.render_widget(Clear {}, frame_rect);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(Color::White))
+        .style(Style::default().bg(Color::Black))
+        .title(w.name.clone());
+    frame.render_widget(block, frame_rect);
+
+    let lines = w
+        .state
+        .keys
+        .iter()
+        .map(|(key, description)| {
+            Line::from(vec![
+                format!("{:<KEY_LENGTH$}", key).yellow(),
+                description.clone().white(),
+            ])
+        })
+        .collect::<Vec<_>>();
+    frame.render_widget(Paragraph::new(Text::from(lines)), content_rect);
+}
+
+fn do_layout(w: &mut Window<KeyHelpDialogState>, rect: &Rect, _model: &Rc<Model>) {
+    // borders and the button below the keys
+    let height = w.state.keys.len() as u16 + 5;
+    let rect = crate::ui::tools::centered_rect_fixed(DIALOG_WIDTH, height, *rect);
+    let content_with_buttons = rect.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+
+    w.update_layout("frame", rect);
+
+    let [content, buttons] = Layout::vertical(vec![Constraint::Fill(1), Constraint::Length(3)])
+        .areas(content_with_buttons);
+    w.update_layout("content", content);
+
+    let [ok] = Layout::horizontal(vec![Constraint::Length(6)])
+        .flex(Flex::End)
+        .areas(buttons);
+    w.update_layout("ok", ok);
+}
+
+fn on_key_event(w: &mut Window<KeyHelpDialogState>, key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('?') => Some(Action::new(&w.name, UiActions::DismissDialog)),
+        _ => None,
+    }
+}
+
+fn on_child_ui_action(
+    w: &mut Window<KeyHelpDialogState>,
+    source: &String,
+    action: &UiActions,
+) -> Option<Action> {
+    debug!("on_child_ui_action: {}:{:?}", source, action);
+    match action {
+        UiActions::ButtonClicked(_) => Some(Action::new(&w.name, UiActions::DismissDialog)),
+        _ => None,
+    }
+}
+
+pub fn create_key_help_dialog(title: &str, keys: &[(String, String)]) -> impl IWindow {
+    Window::builder(title)
+        .with_on_init(on_init)
+        .with_layout(do_layout)
+        .with_render(do_render)
+        .with_on_key_event(on_key_event)
+        .with_on_child_ui_action(on_child_ui_action)
+        .with_state(KeyHelpDialogState {
+            keys: keys.to_vec(),
+        })
+        .build()
+        .unwrap()
+}
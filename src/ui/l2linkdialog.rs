@@ -0,0 +1,577 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::rc::Rc;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use log::debug;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Margin, Rect},
+    style::{Color, Style},
+    widgets::{Block, BorderType, Borders, Clear},
+    Frame,
+};
+
+use crate::{
+    actions::MonActions,
+    dpc::{
+        builder::{DpcField, FieldError},
+        l2link::{
+            bond_member_candidates, port_label, validate_bond, validate_vlan,
+            vlan_parent_candidates, L2LinkEdit,
+        },
+    },
+    ipc::eve_types::{BondMode, LacpRate, NetworkPortConfig},
+    model::model::Model,
+    traits::{IWindow, TextInput},
+};
+
+use super::{
+    action::{Action, UiActions},
+    tools::centered_rect_min,
+    widgets::{
+        button::ButtonElement, input_field::InputFieldElement, label::LabelElement,
+        spin_box::SpinBoxElement,
+    },
+    window::Window,
+};
+
+const BOND_MODES: [BondMode; 8] = [
+    BondMode::BondModeUnspecified,
+    BondMode::BondModeBalanceRR,
+    BondMode::BondModeActiveBackup,
+    BondMode::BondModeBalanceXOR,
+    BondMode::BondModeBroadcast,
+    BondMode::BondMode802Dot3AD,
+    BondMode::BondModeBalanceTLB,
+    BondMode::BondModeBalanceALB,
+];
+const LACP_RATES: [LacpRate; 3] = [
+    LacpRate::LacpRateUnspecified,
+    LacpRate::LacpRateSlow,
+    LacpRate::LacpRateFast,
+];
+const MONITORS: [&str; 3] = ["Monitor: none", "Monitor: MII", "Monitor: ARP"];
+
+const INPUT_FIELDS: [&str; 7] = [
+    "name",
+    "vlan_id",
+    "ports",
+    "interval",
+    "up_delay",
+    "down_delay",
+    "arp_targets",
+];
+
+/// Link monitoring of a bond. MII and ARP monitors are mutually exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum BondMonitor {
+    #[default]
+    None,
+    Mii,
+    Arp,
+}
+
+/// VLAN as it is edited in the dialog
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VlanState {
+    pub name: String,
+    // logical label of the parent port
+    pub parent: String,
+    pub id: String,
+}
+
+/// Bond as it is edited in the dialog
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BondState {
+    pub name: String,
+    // comma separated logical labels of aggregated ports
+    pub ports: String,
+    pub mode: BondMode,
+    pub lacp_rate: LacpRate,
+    pub monitor: BondMonitor,
+    // monitor interval in milliseconds
+    pub interval: String,
+    pub up_delay: String,
+    pub down_delay: String,
+    pub arp_targets: String,
+}
+
+impl From<&NetworkPortConfig> for VlanState {
+    fn from(port: &NetworkPortConfig) -> Self {
+        let vlan = port.l2_link_config.vlan.clone().unwrap_or_default();
+        Self {
+            name: port_label(port).to_string(),
+            parent: vlan.parent_port,
+            id: vlan.id.to_string(),
+        }
+    }
+}
+
+impl From<&NetworkPortConfig> for BondState {
+    fn from(port: &NetworkPortConfig) -> Self {
+        let bond = port.l2_link_config.bond.clone().unwrap_or_default();
+        let (mii, arp) = (&bond.mii_monitor, &bond.arp_monitor);
+        let (monitor, interval) = if mii.enabled {
+            (BondMonitor::Mii, mii.interval)
+        } else if arp.enabled {
+            (BondMonitor::Arp, arp.interval)
+        } else {
+            (BondMonitor::None, 0)
+        };
+        let to_string = |v: u32| if v == 0 { String::new() } else { v.to_string() };
+        Self {
+            name: port_label(port).to_string(),
+            ports: bond.aggregated_ports.clone().unwrap_or_default().join(", "),
+            mode: bond.mode.clone(),
+            lacp_rate: bond.lacp_rate.clone(),
+            monitor,
+            interval: to_string(interval),
+            up_delay: to_string(mii.up_delay),
+            down_delay: to_string(mii.down_delay),
+            arp_targets: arp
+                .ip_targets
+                .iter()
+                .flatten()
+                .map(|ip| ip.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        }
+    }
+}
+
+/// VLAN or bond being edited
+#[derive(Debug, Clone, PartialEq)]
+pub enum L2LinkForm {
+    Vlan(VlanState),
+    Bond(BondState),
+}
+
+struct L2LinkDialogState {
+    // name of the edited link, None for a new one
+    original: Option<String>,
+    form: L2LinkForm,
+    // ports of the DPC the link is validated against
+    ports: Vec<NetworkPortConfig>,
+    parents: Vec<String>,
+    members: Vec<String>,
+    // errors which cannot be shown next to an input field
+    error: Option<String>,
+}
+
+impl L2LinkDialogState {
+    // visible fields. Rows from top to bottom, up to three fields in a row
+    fn rows(&self) -> Vec<Vec<&'static str>> {
+        match &self.form {
+            L2LinkForm::Vlan(_) => vec![vec!["name", "vlan_id"], vec!["parent"]],
+            L2LinkForm::Bond(bond) => {
+                let mut rows = vec![vec!["name", "ports"]];
+                if bond.mode == BondMode::BondMode802Dot3AD {
+                    rows.push(vec!["mode", "lacp_rate"]);
+                } else {
+                    rows.push(vec!["mode"]);
+                }
+                rows.push(vec!["monitor"]);
+                match bond.monitor {
+                    BondMonitor::None => {}
+                    BondMonitor::Mii => rows.push(vec!["interval", "up_delay", "down_delay"]),
+                    BondMonitor::Arp => rows.push(vec!["interval", "arp_targets"]),
+                }
+                rows
+            }
+        }
+    }
+
+    fn tab_order(&self) -> Vec<&'static str> {
+        let mut order = self.rows().concat();
+        order.extend(["ok", "cancel"]);
+        order
+    }
+
+    fn info(&self) -> String {
+        if let Some(error) = &self.error {
+            return format!("Error: {}", error);
+        }
+        match &self.form {
+            L2LinkForm::Vlan(_) if self.parents.is_empty() => {
+                "No port can carry VLANs. Create a bond first".to_string()
+            }
+            L2LinkForm::Vlan(_) => "VLAN ID is 1 to 4094".to_string(),
+            L2LinkForm::Bond(_) if self.members.is_empty() => {
+                "No free Ethernet ports to aggregate".to_string()
+            }
+            L2LinkForm::Bond(_) => format!("Available ports: {}", self.members.join(", ")),
+        }
+    }
+}
+
+fn is_spin_box(name: &str) -> bool {
+    matches!(name, "parent" | "mode" | "lacp_rate" | "monitor")
+}
+
+fn monitor_index(monitor: BondMonitor) -> usize {
+    match monitor {
+        BondMonitor::None => 0,
+        BondMonitor::Mii => 1,
+        BondMonitor::Arp => 2,
+    }
+}
+
+fn monitor_from_index(index: usize) -> BondMonitor {
+    match index {
+        1 => BondMonitor::Mii,
+        2 => BondMonitor::Arp,
+        _ => BondMonitor::None,
+    }
+}
+
+fn on_init(w: &mut Window<L2LinkDialogState>) {
+    w.add_widget("ok", ButtonElement::new("ok"));
+    w.add_widget("cancel", ButtonElement::new("cancel"));
+    create_form_widgets(w);
+    update_tab_order(w);
+}
+
+fn create_form_widgets(w: &mut Window<L2LinkDialogState>) {
+    update_info(w);
+    match w.state.form.clone() {
+        L2LinkForm::Vlan(vlan) => {
+            w.add_widget(
+                "name",
+                InputFieldElement::new("Name", Some(vlan.name.as_str()))
+                    .with_text_hint("e.g. eth0.100"),
+            );
+            w.add_widget(
+                "vlan_id",
+                InputFieldElement::new("VLAN ID", Some(vlan.id.as_str())).with_text_hint("1-4094"),
+            );
+            let parents = if w.state.parents.is_empty() {
+                vec!["Parent: none".to_string()]
+            } else {
+                w.state
+                    .parents
+                    .iter()
+                    .map(|p| format!("Parent: {}", p))
+                    .collect()
+            };
+            let selected = w
+                .state
+                .parents
+                .iter()
+                .position(|p| *p == vlan.parent)
+                .unwrap_or_default();
+            w.add_widget("parent", SpinBoxElement::new(parents).selected(selected));
+        }
+        L2LinkForm::Bond(bond) => {
+            w.add_widget(
+                "name",
+                InputFieldElement::new("Name", Some(bond.name.as_str()))
+                    .with_text_hint("e.g. bond0"),
+            );
+            w.add_widget(
+                "ports",
+                InputFieldElement::new("Ports", Some(bond.ports.as_str()))
+                    .with_text_hint("e.g. eth0, eth1"),
+            );
+            let modes = BOND_MODES
+                .iter()
+                .map(|m| format!("Mode: {}", m))
+                .collect::<Vec<_>>();
+            let mode = BOND_MODES.iter().position(|m| *m == bond.mode);
+            w.add_widget(
+                "mode",
+                SpinBoxElement::new(modes).selected(mode.unwrap_or_default()),
+            );
+            let rates = LACP_RATES
+                .iter()
+                .map(|r| format!("LACP rate: {}", r))
+                .collect::<Vec<_>>();
+            let rate = LACP_RATES.iter().position(|r| *r == bond.lacp_rate);
+            w.add_widget(
+                "lacp_rate",
+                SpinBoxElement::new(rates).selected(rate.unwrap_or_default()),
+            );
+            w.add_widget(
+                "monitor",
+                SpinBoxElement::new(MONITORS.to_vec()).selected(monitor_index(bond.monitor)),
+            );
+            w.add_widget(
+                "interval",
+                InputFieldElement::new("Interval", Some(bond.interval.as_str()))
+                    .with_text_hint("milliseconds"),
+            );
+            w.add_widget(
+                "up_delay",
+                InputFieldElement::new("Up delay", Some(bond.up_delay.as_str()))
+                    .with_text_hint("milliseconds"),
+            );
+            w.add_widget(
+                "down_delay",
+                InputFieldElement::new("Down delay", Some(bond.down_delay.as_str()))
+                    .with_text_hint("milliseconds"),
+            );
+            w.add_widget(
+                "arp_targets",
+                InputFieldElement::new("ARP targets", Some(bond.arp_targets.as_str()))
+                    .with_text_hint("e.g. 192.168.1.1"),
+            );
+        }
+    }
+}
+
+fn update_info(w: &mut Window<L2LinkDialogState>) {
+    let text = w.state.info();
+    w.add_widget("info", LabelElement::new(text));
+}
+
+fn update_tab_order(w: &mut Window<L2LinkDialogState>) {
+    let order = w
+        .state
+        .tab_order()
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+    w.set_focus_tracker_tab_order(order);
+}
+
+fn set_focus(w: &mut Window<L2LinkDialogState>, name: &str) {
+    if let Some(index) = w.state.tab_order().iter().position(|n| *n == name) {
+        w.set_focused_view(index);
+    }
+}
+
+fn field_widget_name(field: DpcField) -> Option<&'static str> {
+    match field {
+        DpcField::Name => Some("name"),
+        DpcField::VlanId => Some("vlan_id"),
+        DpcField::BondPorts => Some("ports"),
+        DpcField::MonitorInterval => Some("interval"),
+        DpcField::UpDelay => Some("up_delay"),
+        DpcField::DownDelay => Some("down_delay"),
+        DpcField::ArpTargets => Some("arp_targets"),
+        _ => None,
+    }
+}
+
+/// Mark invalid fields and focus the first one. Other errors are shown in
+/// the info line
+fn show_errors(w: &mut Window<L2LinkDialogState>, errors: &[FieldError]) {
+    create_form_widgets(w);
+    for name in INPUT_FIELDS {
+        let error = errors
+            .iter()
+            .find(|e| field_widget_name(e.field) == Some(name))
+            .map(|e| e.message.clone());
+        if let Some(field) = w
+            .get_widget_mut(name)
+            .and_then(|widget| widget.as_any_mut().downcast_mut::<InputFieldElement>())
+        {
+            field.set_error(error);
+        }
+    }
+    w.state.error = errors
+        .iter()
+        .find(|e| field_widget_name(e.field).is_none())
+        .map(|e| e.to_string());
+    update_info(w);
+
+    let visible = w.state.tab_order();
+    if let Some(first) = errors
+        .iter()
+        .filter_map(|e| field_widget_name(e.field))
+        .find(|name| visible.contains(name))
+    {
+        set_focus(w, first);
+    }
+}
+
+fn do_layout(w: &mut Window<L2LinkDialogState>, rect: &Rect, _model: &Rc<Model>) {
+    w.clear_layout();
+
+    let rect = centered_rect_min(40, 60, 60, 20, *rect);
+    let content_with_buttons = rect.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+    w.update_layout("frame", rect);
+
+    let [content, info, buttons] = Layout::vertical(vec![
+        Constraint::Fill(1),
+        Constraint::Length(2),
+        Constraint::Length(3),
+    ])
+    .areas(content_with_buttons);
+    w.update_layout("info", info);
+
+    let [ok, cancel] = Layout::horizontal(vec![Constraint::Length(6), Constraint::Length(10)])
+        .flex(Flex::End)
+        .areas(buttons);
+    w.update_layout("ok", ok);
+    w.update_layout("cancel", cancel);
+
+    // rows of spin boxes take one line, rows with input fields take three
+    // lines if there is enough space for all of them
+    let rows = w.state.rows();
+    let is_spin_row = |row: &Vec<&str>| row.iter().all(|f| is_spin_box(f));
+    let spin_rows = rows.iter().filter(|r| is_spin_row(r)).count() as u16;
+    let input_rows = rows.len() as u16 - spin_rows;
+    let height = if content.height >= spin_rows + input_rows * 3 {
+        3
+    } else {
+        1
+    };
+    let constraints = rows
+        .iter()
+        .map(|r| Constraint::Length(if is_spin_row(r) { 1 } else { height }));
+    let areas = Layout::vertical(constraints).split(content);
+    for (row, area) in rows.iter().zip(areas.iter()) {
+        let columns = Layout::horizontal(vec![Constraint::Fill(1); row.len()])
+            .spacing(1)
+            .split(*area);
+        for (name, column) in row.iter().zip(columns.iter()) {
+            w.update_layout(*name, *column);
+        }
+    }
+}
+
+fn do_render(
+    w: &mut Window<L2LinkDialogState>,
+    _rect: &Rect,
+    frame: &mut Frame<'_>,
+    _model: &Rc<Model>,
+) {
+    let frame_rect = w.get_layout("frame");
+
+    // clear area under the dialog
+    frame.render_widget(Clear {}, frame_rect);
+
+    let kind = match w.state.form {
+        L2LinkForm::Vlan(_) => "VLAN",
+        L2LinkForm::Bond(_) => "bond",
+    };
+    let title = match &w.state.original {
+        Some(name) => format!("{}: {}", name, kind),
+        None => format!("New {}", kind),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(Color::White))
+        .style(Style::default().bg(Color::Black))
+        .title(title);
+
+    frame.render_widget(block, frame_rect);
+}
+
+fn on_key_event(w: &mut Window<L2LinkDialogState>, key: KeyEvent) -> Option<Action> {
+    if key.code == KeyCode::Esc {
+        return Some(Action::new(&w.name, UiActions::DismissDialog));
+    }
+    None
+}
+
+fn on_child_ui_action(
+    w: &mut Window<L2LinkDialogState>,
+    source: &String,
+    action: &UiActions,
+) -> Option<Action> {
+    debug!("on_child_ui_action: {}:{:?}", source, action);
+    match action {
+        UiActions::SpinBox { selected } => {
+            let parents = w.state.parents.clone();
+            match &mut w.state.form {
+                L2LinkForm::Vlan(vlan) if source == "parent" => {
+                    vlan.parent = parents.get(*selected).cloned().unwrap_or_default();
+                }
+                L2LinkForm::Bond(bond) => match source.as_str() {
+                    "mode" => bond.mode = BOND_MODES[*selected].clone(),
+                    "lacp_rate" => bond.lacp_rate = LACP_RATES[*selected].clone(),
+                    "monitor" => bond.monitor = monitor_from_index(*selected),
+                    _ => return None,
+                },
+                _ => return None,
+            }
+            update_tab_order(w);
+            set_focus(w, source);
+            Some(Action::new(source, UiActions::Redraw))
+        }
+        UiActions::Input { text } => {
+            match &mut w.state.form {
+                L2LinkForm::Vlan(vlan) => match source.as_str() {
+                    "name" => vlan.name = text.clone(),
+                    "vlan_id" => vlan.id = text.clone(),
+                    _ => {}
+                },
+                L2LinkForm::Bond(bond) => match source.as_str() {
+                    "name" => bond.name = text.clone(),
+                    "ports" => bond.ports = text.clone(),
+                    "interval" => bond.interval = text.clone(),
+                    "up_delay" => bond.up_delay = text.clone(),
+                    "down_delay" => bond.down_delay = text.clone(),
+                    "arp_targets" => bond.arp_targets = text.clone(),
+                    _ => {}
+                },
+            }
+            None
+        }
+        UiActions::ButtonClicked(_) => match source.as_str() {
+            "ok" => {
+                let original = w.state.original.as_deref();
+                let result = match &w.state.form {
+                    L2LinkForm::Vlan(vlan) => validate_vlan(vlan, original, &w.state.ports),
+                    L2LinkForm::Bond(bond) => validate_bond(bond, original, &w.state.ports),
+                };
+                match result {
+                    Ok(link) => Some(Action::new(
+                        &w.name,
+                        UiActions::AppAction(MonActions::L2LinkUpdated(L2LinkEdit::Set {
+                            original: w.state.original.clone(),
+                            link,
+                        })),
+                    )),
+                    Err(errors) => {
+                        show_errors(w, &errors);
+                        Some(Action::new(source, UiActions::Redraw))
+                    }
+                }
+            }
+            "cancel" => Some(Action::new(&w.name, UiActions::DismissDialog)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Create or edit a VLAN or bond of the DPC with `ports`. `original` is the
+/// name of the edited link, None for a new one
+pub fn create_l2link_dialog(
+    original: Option<&str>,
+    form: L2LinkForm,
+    ports: &[NetworkPortConfig],
+) -> impl IWindow {
+    let mut form = form;
+    let parents = vlan_parent_candidates(ports);
+    // a new VLAN is created on the first port which can carry it
+    if let L2LinkForm::Vlan(vlan) = &mut form {
+        if !parents.contains(&vlan.parent) {
+            vlan.parent = parents.first().cloned().unwrap_or_default();
+        }
+    }
+    let state = L2LinkDialogState {
+        original: original.map(str::to_string),
+        form,
+        ports: ports.to_vec(),
+        parents,
+        members: bond_member_candidates(ports, original),
+        error: None,
+    };
+    Window::builder("L2 link")
+        .with_on_init(on_init)
+        .with_layout(do_layout)
+        .with_render(do_render)
+        .with_on_key_event(on_key_event)
+        .with_on_child_ui_action(on_child_ui_action)
+        .with_state(state)
+        .build()
+        .unwrap()
+}
@@ -0,0 +1,213 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::rc::Rc;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use log::debug;
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Margin, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{
+        Block, BorderType, Borders, Cell, Clear, HighlightSpacing, Paragraph, Row, Table,
+        TableState,
+    },
+    Frame,
+};
+
+use crate::{
+    actions::MonActions,
+    dpc::l2link::{l2_topology, L2LinkEdit, L2Node, L2NodeKind},
+    model::model::Model,
+    traits::IWindow,
+    ui::action::UiActions,
+};
+
+use super::{action::Action, theme, widgets::button::ButtonElement, window::Window};
+
+const NAME_LENGTH: u16 = 24;
+
+#[derive(Default)]
+struct L2PageState {
+    // tree as it was rendered last time
+    nodes: Vec<L2Node>,
+    table: TableState,
+}
+
+impl L2PageState {
+    fn selected_link(&self) -> Option<&L2Node> {
+        self.table
+            .selected()
+            .and_then(|i| self.nodes.get(i))
+            .filter(|node| node.is_l2_link())
+    }
+}
+
+fn on_init(w: &mut Window<L2PageState>) {
+    w.add_widget("close", ButtonElement::new("close"));
+    w.set_focus_tracker_tab_order(vec!["close"]);
+}
+
+fn tree_prefix(node: &L2Node) -> &'static str {
+    match (node.depth, node.last) {
+        (0, _) => "",
+        (_, false) => "├─ ",
+        (_, true) => "└─ ",
+    }
+}
+
+fn kind_to_string(kind: &L2NodeKind) -> String {
+    match kind {
+        L2NodeKind::Port => "Ethernet".to_string(),
+        L2NodeKind::BondMember => "bond member".to_string(),
+        L2NodeKind::Bond(mode) => format!("bond, mode {}", mode),
+        L2NodeKind::Vlan(id) => format!("VLAN {}", id),
+    }
+}
+
+fn do_render(w: &mut Window<L2PageState>, _rect: &Rect, frame: &mut Frame<'_>, model: &Rc<Model>) {
+    let frame_rect = w.get_layout("frame");
+    let content_rect = w.get_layout("content");
+    let hint_rect = w.get_layout("hint");
+
+    frame.render_widget(Clear {}, frame_rect);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(Color::White))
+        .style(Style::default().bg(Color::Black))
+        .title(w.name.clone());
+    frame.render_widget(block, frame_rect);
+
+    let (up, down) = theme::scroll_arrows();
+    let hint =
+        format!("{up}/{down} - navigate | v - new VLAN | b - new bond | Enter - edit | d - delete");
+    frame.render_widget(Line::from(hint).dark_gray(), hint_rect);
+
+    // the tree is rebuilt on every frame, the DPC may change at any time
+    let nodes = model
+        .borrow()
+        .get_current_dpc()
+        .map(|dpc| l2_topology(&dpc.ports))
+        .unwrap_or_default();
+    if nodes.is_empty() {
+        frame.render_widget(Paragraph::new("No port configuration").red(), content_rect);
+        w.state.nodes = nodes;
+        return;
+    }
+
+    let selected = w.state.table.selected().unwrap_or_default();
+    w.state.table.select(Some(selected.min(nodes.len() - 1)));
+
+    let rows = nodes
+        .iter()
+        .map(|node| {
+            let name = format!("{}{}", tree_prefix(node), node.name);
+            let style = if node.is_l2_link() {
+                Style::new().white()
+            } else {
+                Style::new().gray()
+            };
+            Row::new(vec![
+                Cell::from(name).style(style),
+                Cell::from(kind_to_string(&node.kind)).style(Style::new().yellow()),
+            ])
+        })
+        .collect::<Vec<_>>();
+
+    let table = Table::new(rows, [Constraint::Length(NAME_LENGTH), Constraint::Fill(1)])
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .title(" Ports, bonds and VLANs ")
+                .title_alignment(Alignment::Center),
+        )
+        .row_highlight_style(Style::new().bg(Color::DarkGray))
+        .highlight_symbol(theme::highlight_bar())
+        .highlight_spacing(HighlightSpacing::Always)
+        .column_spacing(1);
+    frame.render_stateful_widget(table, content_rect, &mut w.state.table);
+    w.state.nodes = nodes;
+}
+
+fn do_layout(w: &mut Window<L2PageState>, rect: &Rect, _model: &Rc<Model>) {
+    let content_with_buttons = rect.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+
+    w.update_layout("frame", *rect);
+
+    let [content, hint, buttons] = Layout::vertical(vec![
+        Constraint::Fill(1),
+        Constraint::Length(1),
+        Constraint::Length(3),
+    ])
+    .areas(content_with_buttons);
+    w.update_layout("content", content);
+    w.update_layout("hint", hint);
+
+    let [close] = Layout::horizontal(vec![Constraint::Length(9)])
+        .flex(Flex::End)
+        .areas(buttons);
+    w.update_layout("close", close);
+}
+
+fn on_key_event(w: &mut Window<L2PageState>, key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => Some(Action::new(&w.name, UiActions::DismissDialog)),
+        KeyCode::Up => {
+            w.state.table.select_previous();
+            Some(Action::new(&w.name, UiActions::Redraw))
+        }
+        KeyCode::Down => {
+            w.state.table.select_next();
+            Some(Action::new(&w.name, UiActions::Redraw))
+        }
+        KeyCode::Char('v') => Some(Action::new(&w.name, UiActions::CreateVlan)),
+        KeyCode::Char('b') => Some(Action::new(&w.name, UiActions::CreateBond)),
+        KeyCode::Enter => {
+            let node = w.state.selected_link()?;
+            Some(Action::new(
+                &w.name,
+                UiActions::EditL2Link(node.name.clone()),
+            ))
+        }
+        KeyCode::Char('d') | KeyCode::Delete => {
+            let node = w.state.selected_link()?;
+            Some(Action::new(
+                &w.name,
+                UiActions::AppAction(MonActions::L2LinkUpdated(L2LinkEdit::Delete(
+                    node.name.clone(),
+                ))),
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn on_child_ui_action(
+    w: &mut Window<L2PageState>,
+    source: &String,
+    action: &UiActions,
+) -> Option<Action> {
+    debug!("on_child_ui_action: {}:{:?}", source, action);
+    match action {
+        UiActions::ButtonClicked(_) => Some(Action::new(&w.name, UiActions::DismissDialog)),
+        _ => None,
+    }
+}
+
+pub fn create_l2_page() -> impl IWindow {
+    Window::builder("L2 topology")
+        .with_on_init(on_init)
+        .with_layout(do_layout)
+        .with_render(do_render)
+        .with_on_key_event(on_key_event)
+        .with_on_child_ui_action(on_child_ui_action)
+        .with_state(L2PageState::default())
+        .build()
+        .unwrap()
+}
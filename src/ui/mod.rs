@@ -3,21 +3,39 @@
 
 pub mod action;
 pub mod activity;
+pub mod app_command_dialog;
 pub mod app_page;
+pub mod cellulardialog;
+pub mod cellularpage;
+pub mod controller_check_dialog;
+pub mod crash_report_dialog;
+pub mod diagpage;
 pub mod dialog;
+pub mod dpcdiffdialog;
+pub mod dpclistpage;
+pub mod dpcpage;
+pub mod dpcverifypage;
 pub mod focus_tracker;
 #[cfg(debug_assertions)]
 pub mod homepage;
 pub mod input_dialog;
 pub mod ipdialog;
+pub mod key_help_dialog;
+pub mod l2linkdialog;
+pub mod l2page;
 pub mod layer_stack;
 pub mod message_box;
 pub mod networkpage;
+// goldens are rendered with the debug-only Home tab
+#[cfg(all(test, debug_assertions))]
+mod snapshot_tests;
 pub mod statusbar;
 pub mod summary_page;
+pub mod theme;
 pub mod tools;
 pub mod traits;
 pub mod ui;
 pub mod vaultpage;
 pub mod widgets;
+pub mod wifidialog;
 pub mod window;
@@ -3,13 +3,14 @@
 
 use std::{cell::RefCell, rc::Rc};
 
+use chrono::{DateTime, Utc};
 use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::{Color, Style, Styled, Stylize},
     text::{Line, Span, Text},
     widgets::{
-        Block, BorderType, Borders, Cell, HighlightSpacing, Padding, Paragraph, Row,
+        Block, BorderType, Borders, Cell, HighlightSpacing, Padding, Paragraph, Row, Sparkline,
         StatefulWidget, Table, TableState,
     },
     Frame,
@@ -18,12 +19,14 @@ use ratatui::{
 use crate::{
     events::Event,
     model::device::network::{NetworkInterfaceStatus, NetworkType},
+    model::device::traffic::{Counters, InterfaceTraffic, LinkStatistics},
     model::model::{Model, MonitorModel},
     traits::{IEventHandler, IPresenter, IWindow},
 };
 
 use super::{
     action::{Action, UiActions},
+    theme,
     traits::{ISelectable, ISelector},
 };
 
@@ -34,6 +37,20 @@ pub const IPV6_MAX_LENGTH: u16 = 39;
 pub const IFACE_LABEL_LENGTH: u16 = 10;
 pub const CTRL_STATUS_LENGTH: u16 = 12;
 
+// all keys of the page, the status bar has room for a few of them only
+const KEY_HELP: [(&str, &str); 10] = [
+    ("Up/Down", "select an interface"),
+    ("Ctrl+Home/End", "select the first/last interface"),
+    ("Enter", "edit the interface"),
+    ("w", "WiFi networks of the interface"),
+    ("c", "cellular access points"),
+    ("m", "modem status"),
+    ("l", "L2 links, VLANs and bonds"),
+    ("p", "port configurations"),
+    ("v", "verification of the last change"),
+    ("?", "this help"),
+];
+
 #[derive(Default)]
 struct NetworkPage {
     list: InterfaceList,
@@ -78,14 +95,33 @@ impl Default for InterfaceList {
 
 impl IWindow for NetworkPage {
     fn status_bar_tips(&self) -> Option<String> {
-        Some(format!("↑/↓ - navigate | Enter - edit interface"))
+        let (up, down) = theme::scroll_arrows();
+        Some(format!(
+            "{up}/{down} - navigate | Enter - edit interface | ? - more keys"
+        ))
     }
 }
 
 pub fn info_row_from_iface<'a, 'b>(iface: &'a NetworkInterfaceStatus) -> Row<'b> {
-    // cells #1,2 IFace name and Link status
+    let (cells, height) = info_cells_from_iface(iface);
+    Row::new(cells).height(height)
+}
+
+/// Cells of the interface row and the height of the row
+pub fn info_cells_from_iface(iface: &NetworkInterfaceStatus) -> (Vec<Cell<'static>>, u16) {
+    // cells #1,2 IFace name with the media below and Link status
+    let mut name = Text::from(iface.name.clone());
+    let media = match &iface.media {
+        NetworkType::Ethernet => None,
+        NetworkType::Vlan(vlan) => Some(format!("VLAN {}", vlan.id)),
+        media => Some(media.to_string()),
+    };
+    if let Some(media) = media {
+        name.push_line(Line::from(media).dark_gray());
+    }
+    let name_height = name.height();
     let mut cells = vec![
-        Cell::from(iface.name.clone()),
+        Cell::from(name),
         if iface.up {
             Cell::from("UP").style(Style::new().green())
         } else {
@@ -97,7 +133,7 @@ pub fn info_row_from_iface<'a, 'b>(iface: &'a NetworkInterfaceStatus) -> Row<'b>
     let ipv4_len = iface.ipv4.as_ref().map_or(0, |v| v.len());
     let ipv6_len = iface.ipv6.as_ref().map_or(0, |v| v.len());
 
-    let height = (ipv4_len + ipv6_len).max(1);
+    let height = (ipv4_len + ipv6_len).max(name_height);
 
     // join Ipv4 and Ipv6 addresses and separate by newline
     let combined_ip_list_iter = iface
@@ -135,12 +171,158 @@ pub fn info_row_from_iface<'a, 'b>(iface: &'a NetworkInterfaceStatus) -> Row<'b>
         cells.push(Cell::from("Disconnected").style(Style::new().red()));
     }
 
-    Row::new(cells).height(height as u16)
+    (cells, height as u16)
+}
+
+/// Size with SI prefixes, e.g. 1.8 GB
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["kB", "MB", "GB", "TB", "PB"];
+    if bytes < 1000 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1000.0;
+    let mut unit = 0;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Throughput in bits per second, e.g. 12.5 Mbit/s
+fn format_rate(bytes_per_second: u64) -> String {
+    const UNITS: [&str; 4] = ["kbit/s", "Mbit/s", "Gbit/s", "Tbit/s"];
+    let bits = bytes_per_second.saturating_mul(8);
+    if bits < 1000 {
+        return format!("{} bit/s", bits);
+    }
+    let mut value = bits as f64 / 1000.0;
+    let mut unit = 0;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Age of a timestamp, e.g. 5 min ago
+fn format_age(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let age = now.signed_duration_since(time);
+    if age.num_days() > 0 {
+        format!("{} days ago", age.num_days())
+    } else if age.num_hours() > 0 {
+        format!("{} h ago", age.num_hours())
+    } else if age.num_minutes() > 0 {
+        format!("{} min ago", age.num_minutes())
+    } else {
+        "just now".to_string()
+    }
+}
+
+/// Two lines per address: the public IP with the age of the lookup, then the
+/// ISP and the location
+fn public_ip_row<'a>(iface: &NetworkInterfaceStatus) -> Row<'a> {
+    if iface.geo.is_empty() {
+        return Row::new(vec![
+            Cell::from("Public IP").style(Style::new().yellow()),
+            Cell::from("N/A").style(Style::new().white()),
+        ]);
+    }
+    let now = Utc::now();
+    let mut lines = vec![];
+    for geo in &iface.geo {
+        let mut public_ip = vec![geo.public_ip.clone().white()];
+        if !geo.hostname.is_empty() {
+            public_ip.push(format!(" ({})", geo.hostname).white());
+        }
+        public_ip.push(format!(" via {}", geo.addr).dark_gray());
+        let age = geo
+            .updated_at
+            .map_or("age unknown".to_string(), |time| format_age(time, now));
+        public_ip.push(format!(", {}", age).dark_gray());
+        lines.push(Line::from(public_ip));
+
+        let isp = [geo.org.clone(), geo.location()]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(Line::from(isp).white());
+    }
+    let height = lines.len() as u16;
+    Row::new(vec![
+        Cell::from("Public IP").style(Style::new().yellow()),
+        Cell::from(Text::from(lines)),
+    ])
+    .height(height)
+}
+
+fn link_state(stats: &LinkStatistics) -> String {
+    let mut state = vec![match stats.carrier {
+        Some(true) => "carrier up".to_string(),
+        Some(false) => "carrier down".to_string(),
+        None => "carrier N/A".to_string(),
+    }];
+    if let Some(speed) = stats.speed {
+        state.push(format!("{} Mbit/s", speed));
+    }
+    if let Some(duplex) = &stats.duplex {
+        state.push(format!("{} duplex", duplex));
+    }
+    if let Some(changes) = stats.carrier_changes {
+        state.push(format!("{} carrier changes", changes));
+    }
+    state.join(", ")
+}
+
+fn counters_row<'a>(label: &'a str, counters: &Counters) -> Row<'a> {
+    let style = if counters.errors > 0 || counters.dropped > 0 {
+        Style::new().red()
+    } else {
+        Style::new().white()
+    };
+    Row::new(vec![
+        Cell::from(label).style(Style::new().yellow()),
+        Cell::from(Line::from(vec![
+            format!(
+                "{} in {} packets, ",
+                format_bytes(counters.bytes),
+                counters.packets
+            )
+            .white(),
+            format!("{} errors, {} dropped", counters.errors, counters.dropped).set_style(style),
+        ])),
+    ])
+}
+
+fn traffic_rows<'a>(traffic: Option<&InterfaceTraffic>) -> Vec<Row<'a>> {
+    let Some(traffic) = traffic else {
+        return vec![Row::new(vec![
+            Cell::from("Traffic").style(Style::new().yellow()),
+            Cell::from("N/A").style(Style::new().white()),
+        ])];
+    };
+    let stats = &traffic.stats;
+    let link_style = if stats.carrier == Some(false) {
+        Style::new().red()
+    } else {
+        Style::new().white()
+    };
+    vec![
+        Row::new(vec![
+            Cell::from("Link").style(Style::new().yellow()),
+            Cell::from(link_state(stats)).style(link_style),
+        ]),
+        counters_row("RX", &stats.rx),
+        counters_row("TX", &stats.tx),
+    ]
 }
 
-fn details_table_from_iface<'a, 'b>(iface: &'a NetworkInterfaceStatus) -> Vec<Row<'b>> {
+fn details_table_from_iface<'a, 'b>(
+    iface: &'a NetworkInterfaceStatus,
+    traffic: Option<&InterfaceTraffic>,
+) -> Vec<Row<'b>> {
     // Row 0: Interface type
-    // //FIXME: doesn't work reliably
     let iface_type = iface.media.to_string();
     let iface_type_row = Row::new(vec![
         Cell::from("Type").style(Style::new().yellow()),
@@ -215,9 +397,60 @@ fn details_table_from_iface<'a, 'b>(iface: &'a NetworkInterfaceStatus) -> Vec<Ro
             ]);
             table.push(ssid_row);
         }
-        NetworkType::Cellular(_) => {}
+        NetworkType::Cellular(cellular_status) => {
+            // Row 4: serving provider, the rest is on the modem status page
+            let provider = cellular_status
+                .current_provider
+                .as_ref()
+                .map_or("N/A".to_string(), |p| {
+                    format!("{} ({})", p.description, p.plmn)
+                });
+            let provider_row = Row::new(vec![
+                Cell::from("Provider").style(Style::new().yellow()),
+                Cell::from(provider).style(Style::new().white()),
+            ]);
+            table.push(provider_row);
+            let connected_row = Row::new(vec![
+                Cell::from("Modem").style(Style::new().yellow()),
+                if cellular_status.is_connected() {
+                    Cell::from("Connected").style(Style::new().green())
+                } else {
+                    Cell::from("Disconnected").style(Style::new().red())
+                },
+            ]);
+            table.push(connected_row);
+        }
+        NetworkType::Vlan(vlan) => {
+            table.push(Row::new(vec![
+                Cell::from("VLAN ID").style(Style::new().yellow()),
+                Cell::from(vlan.id.to_string()).style(Style::new().white()),
+            ]));
+            table.push(Row::new(vec![
+                Cell::from("Parent").style(Style::new().yellow()),
+                Cell::from(vlan.parent_name()).style(Style::new().white()),
+            ]));
+        }
+        NetworkType::Bond(bond) => {
+            let members = if bond.members.is_empty() {
+                "N/A".to_string()
+            } else {
+                bond.members.join(", ")
+            };
+            table.push(Row::new(vec![
+                Cell::from("Bond mode").style(Style::new().yellow()),
+                Cell::from(format!("{}, LACP rate {}", bond.mode, bond.lacp_rate))
+                    .style(Style::new().white()),
+            ]));
+            table.push(Row::new(vec![
+                Cell::from("Members").style(Style::new().yellow()),
+                Cell::from(members).style(Style::new().white()),
+            ]));
+        }
     }
 
+    table.push(public_ip_row(iface));
+    table.extend(traffic_rows(traffic));
+
     // Row 5: Errors if any. One line per error. The heights of the rows are set to the number of errors
     let errors = iface.errors.as_ref().map_or_else(
         || "N/A".to_string(),
@@ -281,9 +514,15 @@ impl NetworkPage {
             return;
         }
         let iface = iface.unwrap();
+        let model = model.borrow();
+        let traffic = model.traffic.get(&iface.name);
+
+        let [details_rect, traffic_rect] =
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Fill(1)]).areas(rect);
+
         // create a table with the interface details. First column is the label, second column is the value
         // create header for the table
-        let rows = details_table_from_iface(&iface);
+        let rows = details_table_from_iface(&iface, traffic);
         let table = Table::new(rows, [Constraint::Length(10), Constraint::Percentage(90)])
             .block(
                 Block::default()
@@ -293,7 +532,63 @@ impl NetworkPage {
             .style(Style::default().fg(Color::White))
             .column_spacing(1);
 
-        frame.render_widget(table, rect);
+        frame.render_widget(table, details_rect);
+        self.render_throughput(traffic, traffic_rect, frame);
+    }
+
+    fn render_throughput(
+        &mut self,
+        traffic: Option<&InterfaceTraffic>,
+        rect: Rect,
+        frame: &mut Frame,
+    ) {
+        let block = Block::default().borders(Borders::ALL).title("Throughput");
+        let inner = block.inner(rect);
+        frame.render_widget(block, rect);
+
+        let Some(traffic) = traffic else {
+            frame.render_widget(Paragraph::new("N/A").white(), inner);
+            return;
+        };
+
+        let [rx_label, rx_graph, tx_label, tx_graph] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .areas(inner);
+
+        let graphs = [
+            (
+                "RX ",
+                traffic.rx_throughput(),
+                &traffic.rx_rate,
+                Color::Green,
+                rx_label,
+                rx_graph,
+            ),
+            (
+                "TX ",
+                traffic.tx_throughput(),
+                &traffic.tx_rate,
+                Color::Cyan,
+                tx_label,
+                tx_graph,
+            ),
+        ];
+        for (label, rate, history, color, label_rect, graph_rect) in graphs {
+            let line = Line::from(vec![label.yellow(), format_rate(rate).white()]);
+            frame.render_widget(line, label_rect);
+            // the newest samples are at the right edge
+            let skip = history.len().saturating_sub(graph_rect.width as usize);
+            let samples = history.iter().skip(skip).copied().collect::<Vec<_>>();
+            let sparkline = Sparkline::default()
+                .data(&samples)
+                .bar_set(theme::sparkline_bars())
+                .style(Style::default().fg(color));
+            frame.render_widget(sparkline, graph_rect);
+        }
     }
     fn render_interface_list(&mut self, model: &Rc<Model>, list_rect: Rect, frame: &mut Frame) {
         // create header for the table
@@ -331,7 +626,7 @@ impl NetworkPage {
             // .style(Style::default().bg(Color::Black));
             .padding(Padding::new(1, 1, 1, 1));
 
-        let bar = " █ ";
+        let bar = theme::highlight_bar();
 
         // Create a List from all list items and highlight the currently selected one
         let list = Table::new(
@@ -404,6 +699,36 @@ impl IEventHandler for NetworkPage {
                         return Some(Action::new("net", UiActions::EditIfaceConfig(selected)));
                     }
                 }
+                KeyCode::Char('w') => {
+                    if let Some(selected) = self.list.selected() {
+                        return Some(Action::new("net", UiActions::EditWifiConfig(selected)));
+                    }
+                }
+                KeyCode::Char('c') => {
+                    if let Some(selected) = self.list.selected() {
+                        return Some(Action::new("net", UiActions::EditCellularConfig(selected)));
+                    }
+                }
+                KeyCode::Char('m') => {
+                    if let Some(selected) = self.list.selected() {
+                        return Some(Action::new("net", UiActions::ShowCellularStatus(selected)));
+                    }
+                }
+                KeyCode::Char('l') => return Some(Action::new("net", UiActions::ShowL2Topology)),
+                KeyCode::Char('p') => return Some(Action::new("net", UiActions::ShowDpcList)),
+                KeyCode::Char('v') => {
+                    return Some(Action::new("net", UiActions::ShowDpcVerification))
+                }
+                KeyCode::Char('?') => {
+                    let keys = KEY_HELP
+                        .iter()
+                        .map(|(key, help)| (key.to_string(), help.to_string()))
+                        .collect();
+                    return Some(Action::new(
+                        "net",
+                        UiActions::ShowKeyHelp("Network keys".to_string(), keys),
+                    ));
+                }
                 _ => {}
             },
             _ => {}
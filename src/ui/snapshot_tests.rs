@@ -0,0 +1,736 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Golden snapshot tests for pages and dialogs.
+//!
+//! Every page is rendered by `Ui` into a test terminal of several sizes using
+//! a model built from `test_data/ipc-tests` and `test_data/kmsg`. The rendered
+//! text is compared against `test_data/snapshots/<name>-<width>x<height>.txt`.
+//!
+//! Run `UPDATE_SNAPSHOTS=1 cargo test snapshot` to regenerate golden files
+//! after an intended layout change and review the diff before committing.
+//! Snapshots are taken from debug builds which have the Home tab, so the
+//! module is not compiled for release builds.
+
+use std::{cell::RefCell, path::PathBuf, rc::Rc};
+
+use chrono::Utc;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use strum::IntoEnumIterator;
+use tokio::sync::mpsc;
+
+use crate::{
+    crash_report::find_crash_reports,
+    diag::{
+        controller::{CheckEvent, CheckMessage, CheckStep, ProxyServer},
+        DiagEvent, DiagMessage,
+    },
+    dpc::{
+        certificate::parse_pem_bundle,
+        diff::diff_dpc,
+        verification::{DpcVerification, VerificationStage},
+    },
+    events::Event,
+    ipc::{
+        eve_types::{
+            BondMode, CellularAccessPoint, CipherBlockStatus, DhcpType, ErrorSeverity, Inprogress,
+            LacpRate, SwState, WifiConfig, WifiKeySchemeType, WwanAuthProtocol, WwanProbe,
+        },
+        message::IpcMessage,
+    },
+    kmsg::read_kmsg_file,
+    model::{
+        device::{
+            network::{AddressGeo, BondStatus, NetworkType, VlanStatus, WiFiStatus},
+            traffic::{Counters, InterfaceTraffic, LinkStatistics},
+        },
+        model::{AppCommand, AppError, AppInstanceState, AppOperation, Model, MonitorModel},
+    },
+    terminal::TerminalWrapper,
+    ui::{
+        action::{Action, UiActions},
+        l2linkdialog::{BondMonitor, BondState, L2LinkForm},
+        theme::{self, RenderProfile},
+        ui::{Ui, UiTabs},
+    },
+};
+
+const SIZES: [(u16, u16); 3] = [(80, 24), (120, 40), (200, 60)];
+
+fn test_data_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data")
+}
+
+/// Replay recorded IPC messages in the order they were received
+fn load_model() -> Rc<Model> {
+    let mut model = MonitorModel::default();
+
+    let mut files = std::fs::read_dir(test_data_path().join("ipc-tests"))
+        .unwrap()
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter_map(|path| {
+            let index = path
+                .file_name()?
+                .to_str()?
+                .strip_prefix("eve_ipc_message-")?
+                .strip_suffix(".json")?
+                .parse::<u32>()
+                .ok()?;
+            Some((index, path))
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+
+    for (_, path) in files {
+        let content = std::fs::read_to_string(&path).unwrap();
+        let Ok(msg) = serde_json::from_str::<IpcMessage>(&content) else {
+            continue;
+        };
+        match msg {
+            IpcMessage::DPCList(cfg) => model.set_dpc_list(cfg),
+            IpcMessage::NetworkStatus(cfg) => model.update_network_status(cfg),
+            IpcMessage::AppStatus(app) => model.update_app_status(app),
+            IpcMessage::DownloaderStatus(dnl) => model.update_downloader_status(dnl),
+            IpcMessage::AppSummary(summary) => model.update_app_summary(summary),
+            IpcMessage::NodeStatus(status) => model.update_node_status(status),
+            IpcMessage::OnboardingStatus(status) => model.update_onboarding_status(status),
+            IpcMessage::VaultStatus(status) => model.update_vault_status(status),
+            IpcMessage::ZedAgentStatus(status) => model.update_zed_agent_status(status),
+            IpcMessage::TpmLogs(logs) => model.update_tpm_logs(logs),
+            IpcMessage::WwanMetrics(metrics) => model.update_wwan_metrics(metrics),
+            _ => {}
+        }
+    }
+
+    // the version is part of the top bar. Keep it stable
+    model.app_version = "0.0.0-test".to_string();
+    model.dmesg =
+        read_kmsg_file(test_data_path().join("kmsg").join("kmsg.txt")).unwrap_or_default();
+
+    Rc::new(RefCell::new(model))
+}
+
+/// Apply a fixture which is not part of the recorded message sequence
+fn apply_fixture(model: &Rc<Model>, name: &str) {
+    let content = std::fs::read_to_string(test_data_path().join("ipc-tests").join(name)).unwrap();
+    let mut model = model.borrow_mut();
+    match serde_json::from_str::<IpcMessage>(&content).unwrap() {
+        IpcMessage::NetworkStatus(cfg) => model.update_network_status(cfg),
+        IpcMessage::WwanMetrics(metrics) => model.update_wwan_metrics(metrics),
+        IpcMessage::DPCList(list) => model.set_dpc_list(list),
+        msg => panic!("Unexpected fixture {}: {:?}", name, msg),
+    }
+}
+
+fn create_ui(width: u16, height: u16) -> Ui {
+    // the receiver is dropped, actions emitted while rendering are ignored
+    let (action_tx, _) = mpsc::unbounded_channel::<Action>();
+    let terminal = TerminalWrapper::open_test_terminal(width, height).unwrap();
+    let mut ui = Ui::new(action_tx, terminal).unwrap();
+    ui.init();
+    ui
+}
+
+fn render(ui: &mut Ui, model: &Rc<Model>) -> String {
+    // widgets are laid out on the first frame, so render twice
+    ui.draw(model.clone());
+    ui.draw(model.clone());
+
+    let buffer = ui.terminal.test_backend().unwrap().buffer();
+    let area = buffer.area;
+    let mut text = String::new();
+    for y in 0..area.height {
+        let line = (0..area.width)
+            .map(|x| buffer[(x, y)].symbol())
+            .collect::<String>();
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = test_data_path()
+        .join("snapshots")
+        .join(format!("{}.txt", name));
+
+    if std::env::var("UPDATE_SNAPSHOTS").is_ok() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "Missing snapshot {:?}. Run with UPDATE_SNAPSHOTS=1 to create it",
+            path
+        )
+    });
+    assert!(
+        expected == actual,
+        "Snapshot {:?} does not match.\n--- expected\n{}\n--- actual\n{}",
+        path,
+        expected,
+        actual
+    );
+}
+
+/// Render `tab` with an optional dialog on top at every size in `SIZES`
+fn check_snapshots(name: &str, tab: UiTabs, show_dialog: impl Fn(&mut Ui, &Rc<Model>)) {
+    theme::set_profile(RenderProfile::unicode());
+    let model = load_model();
+    for (width, height) in SIZES {
+        let mut ui = create_ui(width, height);
+        ui.selected_tab = tab;
+        show_dialog(&mut ui, &model);
+        let actual = render(&mut ui, &model);
+        assert_snapshot(&format!("{}-{}x{}", name, width, height), &actual);
+    }
+}
+
+#[test]
+fn snapshot_summary_page() {
+    check_snapshots("summary", UiTabs::Summary, |_, _| {});
+}
+
+#[test]
+fn snapshot_network_page() {
+    check_snapshots("network", UiTabs::Network, |_, _| {});
+}
+
+#[test]
+fn snapshot_network_traffic() {
+    check_snapshots("network_traffic", UiTabs::Network, |ui, model| {
+        let stats = LinkStatistics {
+            rx: Counters {
+                bytes: 1843526711,
+                packets: 1523402,
+                errors: 0,
+                dropped: 12,
+            },
+            tx: Counters {
+                bytes: 95712344,
+                packets: 612877,
+                errors: 0,
+                dropped: 0,
+            },
+            speed: Some(1000),
+            duplex: Some("full".to_string()),
+            carrier: Some(true),
+            carrier_changes: Some(2),
+        };
+        let rx_rate = (0..300u64).map(|i| (i % 40) * 25_000).collect::<Vec<_>>();
+        let tx_rate = (0..300u64).map(|i| (i % 20) * 4_000).collect::<Vec<_>>();
+        let traffic = InterfaceTraffic::with_history(stats, &rx_rate, &tx_rate);
+        model.borrow_mut().traffic.insert("eth0", traffic);
+        ui.handle_event(Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)));
+    });
+}
+
+#[test]
+fn snapshot_network_public_ip() {
+    check_snapshots("network_public_ip", UiTabs::Network, |ui, model| {
+        let updated_at = Some(Utc::now() - chrono::Duration::seconds(330));
+        model.borrow_mut().network[0].geo = vec![
+            AddressGeo {
+                addr: "192.168.1.10".parse().unwrap(),
+                public_ip: "203.0.113.7".to_string(),
+                hostname: "gw.example.net".to_string(),
+                org: "AS64500 Example ISP".to_string(),
+                city: "Berlin".to_string(),
+                region: "Berlin".to_string(),
+                country: "DE".to_string(),
+                updated_at,
+            },
+            AddressGeo {
+                addr: "fec0::c05f:1599:5c5f:1ff4".parse().unwrap(),
+                public_ip: "2001:db8::7".to_string(),
+                hostname: String::new(),
+                org: "AS64500 Example ISP".to_string(),
+                city: String::new(),
+                region: String::new(),
+                country: "DE".to_string(),
+                updated_at: None,
+            },
+        ];
+        ui.handle_event(Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)));
+    });
+}
+
+#[test]
+fn snapshot_network_l2_links() {
+    check_snapshots("network_l2_links", UiTabs::Network, |ui, model| {
+        let network = &mut model.borrow_mut().network;
+        network[0].media = NetworkType::Vlan(VlanStatus {
+            parent: "ethernet2".to_string(),
+            parent_ifname: Some("eth2".to_string()),
+            id: 100,
+        });
+        network[1].media = NetworkType::Bond(BondStatus {
+            members: vec!["ethernet2".to_string(), "ethernet3".to_string()],
+            mode: BondMode::BondModeActiveBackup,
+            lacp_rate: LacpRate::LacpRateFast,
+        });
+        ui.handle_event(Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)));
+    });
+}
+
+#[test]
+fn snapshot_summary_media() {
+    check_snapshots("summary_media", UiTabs::Summary, |_, model| {
+        let network = &mut model.borrow_mut().network;
+        network[0].media = NetworkType::WiFi(WiFiStatus {
+            ssid: Some("Example Corp".to_string()),
+        });
+        network[1].media = NetworkType::Vlan(VlanStatus {
+            parent: "ethernet2".to_string(),
+            parent_ifname: Some("eth2".to_string()),
+            id: 100,
+        });
+    });
+}
+
+#[test]
+fn snapshot_applications_page() {
+    check_snapshots("applications", UiTabs::Applications, |_, _| {});
+}
+
+#[test]
+fn snapshot_app_details() {
+    check_snapshots("app_details", UiTabs::Applications, |ui, model| {
+        let time = "2025-07-28T13:20:00Z".parse().unwrap();
+        let mut model = model.borrow_mut();
+        let app = model
+            .apps
+            .values_mut()
+            .find(|app| app.name == "cs_nginx-qemu-1")
+            .unwrap();
+        app.state = AppInstanceState::Error(
+            SwState::Halted,
+            AppError {
+                error: "qemu exited: could not allocate guest RAM".to_string(),
+                source: "types.DomainStatus".to_string(),
+                time,
+                severity: ErrorSeverity::Error,
+                retry_condition: "Retrying in 60 seconds".to_string(),
+            },
+        );
+        app.restart = Some(AppOperation {
+            stage: Inprogress::BringUp,
+            started_at: Some(time),
+        });
+        app.missing_memory = true;
+        drop(model);
+        ui.handle_event(Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)));
+    });
+}
+
+#[test]
+fn snapshot_applications_filtered() {
+    check_snapshots(
+        "applications_filtered",
+        UiTabs::Applications,
+        |ui, model| {
+            for app in model.borrow_mut().apps.values_mut() {
+                app.state = match app.name.as_str() {
+                    "cs_nginx-qemu-1" => AppInstanceState::Normal(SwState::Running),
+                    _ => AppInstanceState::Normal(SwState::Broken),
+                };
+            }
+            // sort by state, show failed apps and search for "nginx"
+            for c in "sff/NGINX".chars() {
+                ui.handle_event(Event::Key(KeyEvent::new(
+                    KeyCode::Char(c),
+                    KeyModifiers::NONE,
+                )));
+            }
+        },
+    );
+}
+
+#[test]
+fn snapshot_app_command_dialog() {
+    check_snapshots("app_command_dialog", UiTabs::Applications, |ui, model| {
+        let uuid = *model
+            .borrow()
+            .apps
+            .iter()
+            .find(|(_, app)| app.name == "cs_nginx-qemu-2")
+            .unwrap()
+            .0;
+        ui.show_app_command_dialog("cs_nginx-qemu-2", uuid, AppCommand::Purge);
+    });
+}
+
+#[test]
+fn snapshot_vault_page() {
+    check_snapshots("vault", UiTabs::Vault, |_, _| {});
+}
+
+#[test]
+fn snapshot_dmesg_page() {
+    check_snapshots("dmesg", UiTabs::Dmesg, |_, _| {});
+}
+
+#[test]
+fn snapshot_ip_dialog() {
+    check_snapshots("ip_dialog", UiTabs::Network, |ui, model| {
+        let iface = model.borrow().network[0].clone();
+        ui.show_ip_dialog(iface, None);
+    });
+}
+
+#[test]
+fn snapshot_ip_dialog_static() {
+    check_snapshots("ip_dialog_static", UiTabs::Network, |ui, model| {
+        let iface = model.borrow().network[0].clone();
+        ui.show_ip_dialog(iface, None);
+        // widgets are created on the first frame
+        render(ui, model);
+        // switch the mode spin box from DHCP to Static to show all fields
+        let right = KeyEvent::new(KeyCode::Right, KeyModifiers::NONE);
+        ui.handle_event(Event::Key(right));
+    });
+}
+
+#[test]
+fn snapshot_ip_dialog_port_settings() {
+    check_snapshots("ip_dialog_port_settings", UiTabs::Network, |ui, model| {
+        let iface = model.borrow().network[0].clone();
+        ui.show_ip_dialog(iface, None);
+        render(ui, model);
+        // IP -> Proxy -> Port settings
+        let next_tab = KeyEvent::new(KeyCode::Right, KeyModifiers::CONTROL);
+        ui.handle_event(Event::Key(next_tab));
+        ui.handle_event(Event::Key(next_tab));
+    });
+}
+
+#[test]
+fn snapshot_ip_dialog_proxy_certificates() {
+    check_snapshots(
+        "ip_dialog_proxy_certificates",
+        UiTabs::Network,
+        |ui, model| {
+            let mut iface = model.borrow().network[0].clone();
+            let path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_data/certs/bundle.pem");
+            iface.proxy_certificates = parse_pem_bundle(&std::fs::read_to_string(path).unwrap())
+                .unwrap()
+                .into_iter()
+                .map(|cert| cert.pem)
+                .collect();
+            ui.show_ip_dialog(iface, None);
+            render(ui, model);
+            let next_tab = KeyEvent::new(KeyCode::Right, KeyModifiers::CONTROL);
+            ui.handle_event(Event::Key(next_tab));
+        },
+    );
+}
+
+#[test]
+fn snapshot_wifi_dialog() {
+    check_snapshots("wifi_dialog", UiTabs::Network, |ui, _| {
+        let networks = vec![
+            WifiConfig {
+                ssid: "office".to_string(),
+                key_scheme: WifiKeySchemeType::KeySchemeWpaPsk,
+                identity: String::new(),
+                password: String::new(),
+                priority: 10,
+                cipher_block_status: CipherBlockStatus {
+                    cipher_block_id: "cb-1".to_string(),
+                    is_cipher: true,
+                    ..Default::default()
+                },
+            },
+            WifiConfig {
+                ssid: "guest".to_string(),
+                key_scheme: WifiKeySchemeType::KeySchemeNone,
+                identity: String::new(),
+                password: String::new(),
+                priority: 0,
+                cipher_block_status: CipherBlockStatus::default(),
+            },
+        ];
+        ui.show_wifi_dialog("wlan0", &networks);
+    });
+}
+
+#[test]
+fn snapshot_cellular_dialog() {
+    check_snapshots("cellular_dialog", UiTabs::Network, |ui, _| {
+        let access_points = vec![CellularAccessPoint {
+            sim_slot: 1,
+            apn: "internet".to_string(),
+            ip_type: "ipv4v6".to_string(),
+            auth_protocol: WwanAuthProtocol::Chap,
+            encrypted_credentials: CipherBlockStatus {
+                is_cipher: true,
+                ..Default::default()
+            },
+            preferred_plmns: Some(vec!["262-01".to_string()]),
+            forbid_roaming: true,
+            ..Default::default()
+        }];
+        ui.show_cellular_dialog("wwan0", &access_points, &WwanProbe::default());
+    });
+}
+
+#[test]
+fn snapshot_cellular_page() {
+    check_snapshots("cellular_page", UiTabs::Network, |ui, model| {
+        apply_fixture(model, "wwan_network_status.json");
+        apply_fixture(model, "wwan_metrics.json");
+        ui.show_cellular_page("wwan0");
+    });
+}
+
+#[test]
+fn snapshot_l2_page() {
+    check_snapshots("l2_page", UiTabs::Network, |ui, _| {
+        ui.show_l2_page();
+    });
+}
+
+#[test]
+fn snapshot_bond_dialog() {
+    check_snapshots("bond_dialog", UiTabs::Network, |ui, model| {
+        let ports = model.borrow().get_current_dpc().unwrap().ports.clone();
+        let bond = BondState {
+            name: "bond0".to_string(),
+            ports: "eth0, eth1".to_string(),
+            mode: BondMode::BondMode802Dot3AD,
+            lacp_rate: LacpRate::LacpRateFast,
+            monitor: BondMonitor::Mii,
+            interval: "100".to_string(),
+            ..Default::default()
+        };
+        ui.show_l2link_dialog(None, L2LinkForm::Bond(bond), &ports);
+    });
+}
+
+#[test]
+fn snapshot_dpc_list_page() {
+    check_snapshots("dpc_list_page", UiTabs::Network, |ui, model| {
+        apply_fixture(model, "eve_ipc_message-err-1.json");
+        ui.show_dpc_list_page();
+    });
+}
+
+#[test]
+fn snapshot_dpc_page() {
+    check_snapshots("dpc_page", UiTabs::Network, |ui, model| {
+        apply_fixture(model, "eve_ipc_message-err-1.json");
+        let dpc = model
+            .borrow()
+            .get_dpc_list()
+            .unwrap()
+            .port_config_list
+            .as_ref()
+            .unwrap()[3]
+            .clone();
+        ui.show_dpc_page(&dpc, false);
+    });
+}
+
+#[test]
+fn snapshot_dpc_diff_dialog() {
+    check_snapshots("dpc_diff_dialog", UiTabs::Network, |ui, model| {
+        let current = model.borrow().get_current_dpc().unwrap().clone();
+        let mut dpc = current.clone();
+        let eth0 = &mut dpc.ports[0].dhcp_config;
+        eth0.dhcp = DhcpType::Static;
+        eth0.addr_subnet = Some("192.168.1.10/24".parse().unwrap());
+        eth0.gateway = "192.168.1.1".to_string();
+        eth0.dns_servers = Some(vec!["1.1.1.1".parse().unwrap()]);
+        dpc.ports[1].mtu = 9000;
+        ui.show_dpc_diff_dialog(diff_dpc(&current, &dpc), dpc);
+    });
+}
+
+#[test]
+fn snapshot_dpc_verify_page() {
+    check_snapshots("dpc_verify_page", UiTabs::Network, |ui, model| {
+        let current = model.borrow().get_current_dpc().unwrap().clone();
+        let mut dpc = current.to_new_dpc_with_key("manual");
+        dpc.time_priority = "2025-03-01T10:20:30Z".parse().unwrap();
+        dpc.ports[0].dhcp_config.dhcp = DhcpType::Static;
+        let mut verification = DpcVerification::new(dpc, current);
+        verification.stage = VerificationStage::FellBack("zedagent".to_string());
+        verification.last_error =
+            "not enough working ports (0); failed with: eth0: no IP address".to_string();
+        model.borrow_mut().dpc_verification = Some(verification);
+        ui.show_dpc_verify_page();
+    });
+}
+
+#[test]
+fn snapshot_diagnostics_page() {
+    check_snapshots("diagnostics", UiTabs::Diagnostics, |_, model| {
+        let session = &mut model.borrow_mut().diagnostics;
+        let run = session.start("Ping 192.168.1.1 via eth0".to_string());
+        let lines = [
+            "$ ping -c 4 -W 2 -I eth0 192.168.1.1",
+            "PING 192.168.1.1 (192.168.1.1): 56 data bytes",
+            "64 bytes from 192.168.1.1: seq=0 ttl=64 time=0.512 ms",
+            "64 bytes from 192.168.1.1: seq=1 ttl=64 time=0.431 ms",
+        ];
+        for line in lines {
+            session.handle(DiagMessage {
+                run,
+                event: DiagEvent::Output(line.to_string()),
+            });
+        }
+        session.handle(DiagMessage {
+            run,
+            event: DiagEvent::Finished(Err("cancelled".to_string())),
+        });
+    });
+}
+
+#[test]
+fn snapshot_controller_check_dialog() {
+    check_snapshots("controller_check", UiTabs::Summary, |ui, model| {
+        let pem = std::fs::read_to_string(test_data_path().join("certs/proxy-ca.pem")).unwrap();
+        let certificates = parse_pem_bundle(&pem).unwrap();
+        let mut model = model.borrow_mut();
+        // the controller certificate is re-signed by the intercepting proxy
+        if let Some(iface) = model.network.iter_mut().find(|iface| iface.is_mgmt) {
+            iface.proxy_certificates = vec![pem.into_bytes()];
+        }
+        let proxy = ProxyServer {
+            host: "proxy.example.com".to_string(),
+            port: 3128,
+        };
+        let check = &mut model.controller_check;
+        let run = check.start(
+            "https://zedcloud.local.zededa.net".to_string(),
+            Some(Ok(proxy)),
+        );
+        let steps = [
+            ("URL", Ok("zedcloud.local.zededa.net:443"), None, true),
+            (
+                "DNS zedcloud.local.zededa.net",
+                Err("failed to lookup address information"),
+                Some(2100),
+                false,
+            ),
+            (
+                "DNS proxy.example.com",
+                Ok("192.168.1.10"),
+                Some(1200),
+                true,
+            ),
+            ("TCP connect", Ok("192.168.1.10:3128"), Some(850), true),
+            (
+                "Proxy CONNECT via proxy.example.com:3128",
+                Ok("HTTP/1.1 200 Connection established"),
+                Some(15300),
+                true,
+            ),
+            (
+                "TLS handshake",
+                Ok("TLS 1.2, ECDHE-RSA-AES128-GCM-SHA256"),
+                Some(42100),
+                true,
+            ),
+        ];
+        for (name, result, elapsed, required) in steps {
+            check.handle(CheckMessage {
+                run,
+                event: CheckEvent::Step(CheckStep {
+                    name: name.to_string(),
+                    result: result.map(str::to_string).map_err(str::to_string),
+                    elapsed: elapsed.map(std::time::Duration::from_micros),
+                    required,
+                }),
+            });
+        }
+        check.handle(CheckMessage {
+            run,
+            event: CheckEvent::Certificates(certificates),
+        });
+        check.handle(CheckMessage {
+            run,
+            event: CheckEvent::Finished,
+        });
+        drop(model);
+        ui.show_controller_check_dialog();
+    });
+}
+
+#[test]
+fn snapshot_crash_report_dialog() {
+    let mut report =
+        find_crash_reports(test_data_path().join("crash-reports"), None, &[]).remove(0);
+    // the goldens must not depend on the checkout directory
+    report.path = report
+        .path
+        .strip_prefix(env!("CARGO_MANIFEST_DIR"))
+        .unwrap()
+        .to_path_buf();
+    check_snapshots("crash_report_dialog", UiTabs::Summary, |ui, _| {
+        ui.show_crash_report_dialog(&report);
+    });
+}
+
+#[test]
+fn snapshot_input_dialog() {
+    check_snapshots("input_dialog", UiTabs::Summary, |ui, _| {
+        ui.show_server_url_dialog("https://zedcloud.local.zededa.net");
+    });
+}
+
+#[test]
+fn snapshot_network_key_help() {
+    check_snapshots("network_key_help", UiTabs::Network, |ui, _| {
+        let action = ui.handle_event(Event::Key(KeyEvent::new(
+            KeyCode::Char('?'),
+            KeyModifiers::NONE,
+        )));
+        match action.map(|a| a.action) {
+            Some(UiActions::ShowKeyHelp(title, keys)) => ui.show_key_help_dialog(&title, &keys),
+            action => panic!("Unexpected action {:?}", action),
+        }
+    });
+}
+
+#[test]
+fn status_bar_tips_fit_80_columns() {
+    theme::set_profile(RenderProfile::unicode());
+    let model = load_model();
+    let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let mut states = UiTabs::iter().map(|tab| (tab, None)).collect::<Vec<_>>();
+    // the applications page has its own tips while expanded and searching
+    states.push((UiTabs::Applications, Some(key(KeyCode::Enter))));
+    states.push((UiTabs::Applications, Some(key(KeyCode::Char('/')))));
+
+    for (tab, event) in states {
+        let mut ui = create_ui(80, 24);
+        ui.selected_tab = tab;
+        render(&mut ui, &model);
+        if let Some(event) = event {
+            ui.handle_event(event);
+        }
+        let screen = render(&mut ui, &model);
+        if let Some(tips) = model.borrow().status_bar_tips.clone() {
+            assert!(
+                screen.contains(&tips),
+                "Tips of {:?} do not fit into 80 columns: {}",
+                tab,
+                tips
+            );
+        }
+    }
+}
+
+#[test]
+fn snapshot_ascii_profile() {
+    theme::set_profile(RenderProfile::ascii());
+    let model = load_model();
+    let mut ui = create_ui(80, 24);
+    ui.selected_tab = UiTabs::Network;
+    let actual = render(&mut ui, &model);
+    theme::set_profile(RenderProfile::unicode());
+    assert_snapshot("network-ascii-80x24", &actual);
+}
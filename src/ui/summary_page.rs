@@ -17,13 +17,20 @@ use ratatui::{
 use crate::{
     events::Event,
     ipc::eve_types::{AttestState, ZedAgentStatus},
-    model::model::{Model, OnboardingStatus, VaultStatus},
+    model::{
+        device::network::NetworkType,
+        model::{Model, OnboardingStatus, VaultStatus},
+    },
     traits::{IEventHandler, IPresenter, IWindow},
-    ui::action::{Action, UiActions},
+    ui::{
+        action::{Action, UiActions},
+        theme,
+    },
 };
 
 use super::networkpage::{
-    CTRL_STATUS_LENGTH, IFACE_LABEL_LENGTH, IPV6_MAX_LENGTH, LINK_STATE_LENGTH, MAC_LENGTH,
+    info_cells_from_iface, CTRL_STATUS_LENGTH, IFACE_LABEL_LENGTH, IPV6_MAX_LENGTH,
+    LINK_STATE_LENGTH, MAC_LENGTH,
 };
 
 #[derive(Default)]
@@ -86,15 +93,18 @@ impl SummaryPage {
 
 impl IWindow for SummaryPage {
     fn status_bar_tips(&self) -> Option<String> {
+        let arrows = theme::arrows();
         Some(format!(
-            "Alt + ◄ ► linux terminal | Ctrl + s change server | Ctrl + ◄ ► switch tabs"
+            "Alt+{left} {right} terminal | Ctrl+s server | Ctrl+r check | Ctrl+{left} {right} tabs",
+            left = arrows.left,
+            right = arrows.right
         ))
     }
 }
 
 impl IEventHandler for SummaryPage {
     fn handle_event(&mut self, event: crate::events::Event) -> Option<super::action::Action> {
-        // handle Ctrl+s to change the server
+        // handle Ctrl+s to change the server and Ctrl+r to check it
         match event {
             Event::Key(key)
                 if (key.code == KeyCode::Char('s')) && (key.modifiers == KeyModifiers::CONTROL) =>
@@ -102,6 +112,12 @@ impl IEventHandler for SummaryPage {
                 debug!("CTRL+s: server change requested");
                 return Some(Action::new("net", UiActions::ChangeServer));
             }
+            Event::Key(key)
+                if (key.code == KeyCode::Char('r')) && (key.modifiers == KeyModifiers::CONTROL) =>
+            {
+                debug!("CTRL+r: server check requested");
+                return Some(Action::new("net", UiActions::CheckServer));
+            }
             _ => {}
         }
         None
@@ -140,7 +156,7 @@ impl IPresenter for SummaryPage {
         .block(
             ratatui::widgets::Block::default()
                 .borders(ratatui::widgets::Borders::ALL)
-                .title("Server (CTRL+s to change)"),
+                .title("Server (CTRL+s to change, CTRL+r to check)"),
         )
         .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
         frame.render_widget(server_url, server);
@@ -491,6 +507,7 @@ impl SummaryPage {
             Cell::from("IPv4/IPv6").style(Style::default()),
             Cell::from("MAC").style(Style::default()),
             Cell::from("Controller").style(Style::default()),
+            Cell::from("Details").style(Style::default()),
         ]);
 
         // create list items from the interface
@@ -498,7 +515,11 @@ impl SummaryPage {
             .borrow()
             .network
             .iter()
-            .map(|iface| super::networkpage::info_row_from_iface(iface))
+            .map(|iface| {
+                let (mut cells, height) = info_cells_from_iface(iface);
+                cells.push(Cell::from(media_details(&iface.media)));
+                Row::new(cells).height(height)
+            })
             .collect::<Vec<_>>();
 
         // create a surrounding block for the list
@@ -520,6 +541,7 @@ impl SummaryPage {
                 Constraint::Length(IPV6_MAX_LENGTH),
                 Constraint::Max(MAC_LENGTH),
                 Constraint::Length(CTRL_STATUS_LENGTH),
+                Constraint::Fill(1),
             ],
         )
         .block(block)
@@ -608,3 +630,35 @@ impl SummaryPage {
         self.render_interface_list(model, iface_list_rect, frame);
     }
 }
+
+/// The media specific part of the interface in one line, e.g. the SSID
+fn media_details(media: &NetworkType) -> Line<'static> {
+    match media {
+        NetworkType::Ethernet => Line::default(),
+        NetworkType::WiFi(wifi) => match &wifi.ssid {
+            Some(ssid) => Line::from(vec!["SSID ".yellow(), ssid.clone().white()]),
+            None => Line::from("WiFi not connected".red()),
+        },
+        NetworkType::Cellular(cellular) => {
+            let mut spans = match &cellular.current_provider {
+                Some(provider) => vec![provider.description.clone().white(), " ".into()],
+                None => vec![],
+            };
+            if let Some(rat) = cellular.current_rats.first() {
+                spans.push(format!("{} ", rat).white());
+            }
+            if cellular.is_connected() {
+                spans.push("modem connected".green());
+            } else {
+                spans.push("modem disconnected".red());
+            }
+            Line::from(spans)
+        }
+        // the VLAN ID is below the interface name
+        NetworkType::Vlan(vlan) => Line::from(vec!["Parent ".yellow(), vlan.parent_name().white()]),
+        NetworkType::Bond(bond) => Line::from(vec![
+            format!("{} ", bond.mode).yellow(),
+            bond.members.join(", ").white(),
+        ]),
+    }
+}
@@ -0,0 +1,369 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rendering profile of the terminal the monitor is running on.
+//!
+//! EVE devices are often managed over a serial console (ttyS0, hvc0, ...) that
+//! can display neither Unicode box drawing characters nor more than 8 or 16
+//! colors. Widgets ask the profile for the symbols they draw, and `Ui::draw`
+//! runs [`apply_profile`] over the final frame so nothing unsupported reaches
+//! the terminal even if a widget does not know about the profile.
+
+use std::cell::Cell;
+
+use log::info;
+use ratatui::{buffer::Buffer, style::Color, symbols};
+use serde::{Deserialize, Serialize};
+
+/// Number of colors supported by the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    /// 8 basic ANSI colors. No bright variants
+    Basic8,
+    /// 16 ANSI colors. Bright black (DarkGray) is unreadable on many
+    /// serial terminals so it is not used either
+    Basic16,
+    /// Full palette. No color mapping is done
+    Full,
+}
+
+/// Rendering profile selected in the application config
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderProfileMode {
+    /// detect terminal capabilities
+    #[default]
+    Auto,
+    /// force Unicode symbols and full color palette
+    Unicode,
+    /// force serial console profile: ASCII only and 8 colors
+    Ascii,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderProfile {
+    pub ascii: bool,
+    pub colors: ColorDepth,
+}
+
+/// Symbols for arrows used in spin boxes, scrollbars and hints
+pub struct Arrows {
+    pub left: &'static str,
+    pub right: &'static str,
+    pub up: &'static str,
+    pub down: &'static str,
+}
+
+const UNICODE_ARROWS: Arrows = Arrows {
+    left: "◄",
+    right: "►",
+    up: "▲",
+    down: "▼",
+};
+
+const ASCII_ARROWS: Arrows = Arrows {
+    left: "<",
+    right: ">",
+    up: "^",
+    down: "v",
+};
+
+impl Default for RenderProfile {
+    fn default() -> Self {
+        Self::unicode()
+    }
+}
+
+impl RenderProfile {
+    pub fn unicode() -> Self {
+        Self {
+            ascii: false,
+            colors: ColorDepth::Full,
+        }
+    }
+
+    pub fn ascii() -> Self {
+        Self {
+            ascii: true,
+            colors: ColorDepth::Basic8,
+        }
+    }
+
+    pub fn from_mode(mode: RenderProfileMode) -> Self {
+        match mode {
+            RenderProfileMode::Auto => Self::detect(),
+            RenderProfileMode::Unicode => Self::unicode(),
+            RenderProfileMode::Ascii => Self::ascii(),
+        }
+    }
+
+    /// Detect terminal capabilities from the environment and the name of the
+    /// terminal device the monitor is attached to
+    pub fn detect() -> Self {
+        let env = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let locale = env("LC_ALL")
+            .or_else(|| env("LC_CTYPE"))
+            .or_else(|| env("LANG"));
+        let tty = std::fs::read_link("/proc/self/fd/0")
+            .ok()
+            .map(|p| p.to_string_lossy().to_string());
+
+        let profile = Self::detect_from(
+            env("TERM").as_deref(),
+            locale.as_deref(),
+            env("COLORTERM").as_deref(),
+            tty.as_deref(),
+        );
+        info!(
+            "Detected render profile {:?} (TERM={:?}, locale={:?}, tty={:?})",
+            profile,
+            env("TERM"),
+            locale,
+            tty
+        );
+        profile
+    }
+
+    fn detect_from(
+        term: Option<&str>,
+        locale: Option<&str>,
+        colorterm: Option<&str>,
+        tty: Option<&str>,
+    ) -> Self {
+        let term = term.unwrap_or_default();
+        let is_serial = tty.is_some_and(is_serial_tty);
+        let is_legacy_term = matches!(term, "dumb" | "ansi" | "vt52" | "vt100" | "vt102" | "vt220");
+        // an explicitly configured non-UTF-8 locale means no Unicode
+        let is_utf8_locale = locale.is_none_or(|l| {
+            let l = l.to_lowercase();
+            l.contains("utf-8") || l.contains("utf8")
+        });
+
+        let ascii = is_serial || is_legacy_term || !is_utf8_locale;
+
+        let colors = if matches!(colorterm, Some("truecolor") | Some("24bit"))
+            || term.contains("256color")
+        {
+            ColorDepth::Full
+        } else if is_serial || is_legacy_term {
+            ColorDepth::Basic8
+        } else if term == "linux" {
+            // Linux VT console renders all 16 ANSI colors including DarkGray
+            ColorDepth::Full
+        } else {
+            ColorDepth::Basic16
+        };
+
+        Self { ascii, colors }
+    }
+}
+
+fn is_serial_tty(tty: &str) -> bool {
+    let name = tty.trim_start_matches("/dev/");
+    ["ttyS", "ttyAMA", "ttyUSB", "ttymxc", "ttyPS", "hvc"]
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+thread_local! {
+    static PROFILE: Cell<RenderProfile> = Cell::new(RenderProfile::default());
+}
+
+pub fn set_profile(profile: RenderProfile) {
+    info!("Using render profile: {:?}", profile);
+    PROFILE.with(|p| p.set(profile));
+}
+
+pub fn profile() -> RenderProfile {
+    PROFILE.with(|p| p.get())
+}
+
+pub fn arrows() -> &'static Arrows {
+    if profile().ascii {
+        &ASCII_ARROWS
+    } else {
+        &UNICODE_ARROWS
+    }
+}
+
+/// Up and down arrows used for scrolling hints and scrollbars
+pub fn scroll_arrows() -> (&'static str, &'static str) {
+    if profile().ascii {
+        ("^", "v")
+    } else {
+        ("↑", "↓")
+    }
+}
+
+/// Marker drawn in front of the selected row of tables
+pub fn highlight_bar() -> &'static str {
+    if profile().ascii {
+        " > "
+    } else {
+        " █ "
+    }
+}
+
+/// Bars of sparklines, e.g. traffic graphs
+pub fn sparkline_bars() -> symbols::bar::Set {
+    if profile().ascii {
+        symbols::bar::Set {
+            full: "#",
+            seven_eighths: "#",
+            three_quarters: "=",
+            five_eighths: "=",
+            half: "-",
+            three_eighths: "-",
+            one_quarter: "_",
+            one_eighth: "_",
+            empty: " ",
+        }
+    } else {
+        symbols::bar::NINE_LEVELS
+    }
+}
+
+/// Hint for switching tabs with Ctrl + arrows
+pub fn tab_switch_hint() -> String {
+    let arrows = arrows();
+    format!(" Use ctrl + {} {} to change tab", arrows.left, arrows.right)
+}
+
+/// Replace a non-ASCII symbol with the closest ASCII one
+fn ascii_symbol(symbol: &str) -> &'static str {
+    match symbol {
+        "─" | "━" | "┄" | "┈" | "╌" => "-",
+        "═" => "=",
+        "│" | "┃" | "║" | "┆" | "┊" | "╎" => "|",
+        "┌" | "┐" | "└" | "┘" | "╭" | "╮" | "╰" | "╯" | "┏" | "┓" | "┗" | "┛" | "╔" | "╗" | "╚"
+        | "╝" | "├" | "┤" | "┬" | "┴" | "┼" | "╠" | "╣" | "╦" | "╩" | "╬" | "┣" | "┫" | "┳"
+        | "┻" | "╋" => "+",
+        "█" | "▉" | "▊" | "▋" | "▌" | "▍" | "▎" | "▏" | "▐" | "▀" | "▄" | "▓" | "▒" => {
+            "#"
+        }
+        "░" => ".",
+        "◄" | "←" | "◀" => "<",
+        "►" | "→" | "▶" => ">",
+        "▲" | "↑" => "^",
+        "▼" | "↓" => "v",
+        "•" | "●" => "*",
+        "…" => ".",
+        "–" | "—" => "-",
+        _ => "?",
+    }
+}
+
+fn map_color(color: Color, depth: ColorDepth) -> Color {
+    match (depth, color) {
+        (ColorDepth::Full, c) => c,
+        // DarkGray is replaced by blue. It is still readable on black and
+        // differs from the normal text color
+        (_, Color::DarkGray) => Color::Blue,
+        (_, Color::Rgb(..)) | (_, Color::Indexed(_)) => Color::Reset,
+        (ColorDepth::Basic16, c) => c,
+        (ColorDepth::Basic8, Color::White) => Color::Gray,
+        (ColorDepth::Basic8, Color::LightRed) => Color::Red,
+        (ColorDepth::Basic8, Color::LightGreen) => Color::Green,
+        (ColorDepth::Basic8, Color::LightYellow) => Color::Yellow,
+        (ColorDepth::Basic8, Color::LightBlue) => Color::Blue,
+        (ColorDepth::Basic8, Color::LightMagenta) => Color::Magenta,
+        (ColorDepth::Basic8, Color::LightCyan) => Color::Cyan,
+        (ColorDepth::Basic8, c) => c,
+    }
+}
+
+/// Downgrade the rendered frame to the capabilities of the current profile
+pub fn apply_profile(buf: &mut Buffer) {
+    let profile = profile();
+    if !profile.ascii && profile.colors == ColorDepth::Full {
+        return;
+    }
+
+    for cell in buf.content.iter_mut() {
+        if profile.ascii && !cell.symbol().is_ascii() {
+            let symbol = ascii_symbol(cell.symbol());
+            cell.set_symbol(symbol);
+        }
+        if profile.colors != ColorDepth::Full {
+            cell.fg = map_color(cell.fg, profile.colors);
+            cell.bg = map_color(cell.bg, profile.colors);
+            // mapping may make text invisible e.g. DarkGray on Blue
+            if cell.fg == cell.bg && cell.fg != Color::Reset {
+                cell.fg = if cell.bg == Color::Black {
+                    Color::Gray
+                } else {
+                    Color::Black
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{
+        layout::Rect,
+        style::Style,
+        widgets::{Block, BorderType, Borders, Widget},
+    };
+
+    #[test]
+    fn test_detect_serial_console() {
+        let p = RenderProfile::detect_from(Some("vt220"), None, None, Some("/dev/ttyS0"));
+        assert_eq!(p, RenderProfile::ascii());
+        let p = RenderProfile::detect_from(Some("linux"), None, None, Some("/dev/hvc0"));
+        assert_eq!(p, RenderProfile::ascii());
+    }
+
+    #[test]
+    fn test_detect_vt_console() {
+        let p = RenderProfile::detect_from(Some("linux"), None, None, Some("/dev/tty1"));
+        assert_eq!(p, RenderProfile::unicode());
+    }
+
+    #[test]
+    fn test_detect_desktop() {
+        let p = RenderProfile::detect_from(
+            Some("xterm-256color"),
+            Some("en_US.UTF-8"),
+            Some("truecolor"),
+            Some("/dev/pts/3"),
+        );
+        assert_eq!(p, RenderProfile::unicode());
+
+        let p = RenderProfile::detect_from(Some("xterm"), Some("C"), None, Some("/dev/pts/3"));
+        assert_eq!(
+            p,
+            RenderProfile {
+                ascii: true,
+                colors: ColorDepth::Basic16
+            }
+        );
+    }
+
+    #[test]
+    fn test_apply_ascii_profile() {
+        set_profile(RenderProfile::ascii());
+        let area = Rect::new(0, 0, 4, 3);
+        let mut buf = Buffer::empty(area);
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .style(Style::default().fg(Color::White).bg(Color::DarkGray))
+            .render(area, &mut buf);
+        apply_profile(&mut buf);
+        set_profile(RenderProfile::unicode());
+
+        let symbols = buf
+            .content
+            .iter()
+            .map(|c| c.symbol().to_string())
+            .collect::<String>();
+        assert_eq!(symbols, "+==+|  |+==+");
+        assert!(buf
+            .content
+            .iter()
+            .all(|c| c.fg == Color::Gray && c.bg == Color::Blue));
+    }
+}
@@ -39,6 +39,27 @@ pub fn centered_rect_fixed(width: u16, height: u16, r: Rect) -> Rect {
     ])
     .split(popup_layout[1])[1]
 }
+
+// same as centered_rect but never smaller than min_width x min_height unless `r` is smaller.
+// keeps dialogs usable on small terminals e.g. 80x24 serial console
+pub fn centered_rect_min(
+    percent_x: u16,
+    percent_y: u16,
+    min_width: u16,
+    min_height: u16,
+    r: Rect,
+) -> Rect {
+    let rect = centered_rect(percent_x, percent_y, r);
+    if rect.width >= min_width && rect.height >= min_height {
+        return rect;
+    }
+    centered_rect_fixed(
+        rect.width.max(min_width).min(r.width),
+        rect.height.max(min_height).min(r.height),
+        r,
+    )
+}
+
 #[derive(Default)]
 pub struct ElementHashMap<T> {
     layout: HashMap<String, T>,
@@ -2,6 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    actions::MonActions,
+    crash_report::CrashReport,
+    dpc::diff::DpcDiff,
+    ipc::eve_types::{
+        CellularAccessPoint, DevicePortConfig, NetworkPortConfig, WifiConfig, WwanProbe,
+    },
     model::device::network::NetworkInterfaceStatus,
     traits::{IPresenter, IWindow},
     ui::{input_dialog::create_input_dialog, ipdialog::create_ip_dialog},
@@ -21,10 +27,14 @@ use ratatui::{
 use std::rc::Rc;
 use strum::{Display, EnumCount, EnumIter, FromRepr, IntoEnumIterator};
 use tokio::sync::mpsc::UnboundedSender;
+use uuid::Uuid;
 
 use crate::{
     events::Event,
-    model::{device::dmesg::DmesgViewer, model::Model},
+    model::{
+        device::dmesg::DmesgViewer,
+        model::{AppCommand, Model},
+    },
     terminal::TerminalWrapper,
     traits::IEventHandler,
     ui::action::UiActions,
@@ -32,13 +42,28 @@ use crate::{
 
 use super::{
     action::Action,
+    app_command_dialog::create_app_command_dialog,
     app_page::ApplicationsPage,
+    cellulardialog::create_cellular_dialog,
+    cellularpage::create_cellular_page,
+    controller_check_dialog::create_controller_check_dialog,
+    crash_report_dialog::create_crash_report_dialog,
+    diagpage::create_diagnostics_page,
+    dpcdiffdialog::create_dpc_diff_dialog,
+    dpclistpage::create_dpc_list_page,
+    dpcpage::create_dpc_page,
+    dpcverifypage::create_dpc_verify_page,
+    key_help_dialog::create_key_help_dialog,
+    l2linkdialog::{create_l2link_dialog, L2LinkForm},
+    l2page::create_l2_page,
     layer_stack::LayerStack,
     message_box::create_system_message_box,
     networkpage::create_network_page,
     statusbar::{create_status_bar, StatusBarState},
     summary_page::SummaryPage,
+    theme,
     vaultpage::VaultPage,
+    wifidialog::create_wifi_dialog,
     window::Window,
 };
 
@@ -66,6 +91,7 @@ pub enum UiTabs {
     #[cfg(debug_assertions)]
     Home,
     Network,
+    Diagnostics,
     Applications,
     Vault,
     Dmesg,
@@ -92,7 +118,7 @@ impl Ui {
 
     fn tabs() -> Tabs<'static> {
         let tab_titles = UiTabs::iter().map(UiTabs::to_tab_title);
-        let block = Block::new().title(" Use ctrl + ◄ ► to change tab");
+        let block = Block::new().title(theme::tab_switch_hint());
         Tabs::new(tab_titles)
             .block(block)
             .highlight_style(Modifier::REVERSED)
@@ -108,6 +134,7 @@ impl Ui {
         }
 
         self.views[UiTabs::Network as usize].push(Box::new(create_network_page()));
+        self.views[UiTabs::Diagnostics as usize].push(Box::new(create_diagnostics_page()));
 
         self.views[UiTabs::Applications as usize].push(Box::new(ApplicationsPage::new()));
         self.views[UiTabs::Dmesg as usize].push(Box::new(DmesgViewer::new()));
@@ -160,6 +187,9 @@ impl Ui {
             // render status bar
             self.status_bar
                 .render(&statusbar_rect, frame, &model, false);
+
+            // downgrade symbols and colors for serial consoles
+            theme::apply_profile(frame.buffer_mut());
         });
     }
 
@@ -306,8 +336,77 @@ impl Ui {
         self.views[self.selected_tab as usize].pop()
     }
 
-    pub fn show_ip_dialog(&mut self, iface: NetworkInterfaceStatus) {
-        let d = create_ip_dialog(&iface);
+    pub fn show_ip_dialog(
+        &mut self,
+        iface: NetworkInterfaceStatus,
+        current_dpc: Option<DevicePortConfig>,
+    ) {
+        let d = create_ip_dialog(&iface, current_dpc);
+        self.push_layer(d);
+    }
+
+    pub fn show_wifi_dialog(&mut self, iface_name: &str, networks: &[WifiConfig]) {
+        let d = create_wifi_dialog(iface_name, networks);
+        self.push_layer(d);
+    }
+
+    pub fn show_cellular_dialog(
+        &mut self,
+        iface_name: &str,
+        access_points: &[CellularAccessPoint],
+        probe: &WwanProbe,
+    ) {
+        let d = create_cellular_dialog(iface_name, access_points, probe);
+        self.push_layer(d);
+    }
+
+    pub fn show_cellular_page(&mut self, iface_name: &str) {
+        let d = create_cellular_page(iface_name);
+        self.push_layer(d);
+    }
+
+    pub fn show_l2_page(&mut self) {
+        let d = create_l2_page();
+        self.push_layer(d);
+    }
+
+    pub fn show_l2link_dialog(
+        &mut self,
+        original: Option<&str>,
+        form: L2LinkForm,
+        ports: &[NetworkPortConfig],
+    ) {
+        let d = create_l2link_dialog(original, form, ports);
+        self.push_layer(d);
+    }
+
+    pub fn show_dpc_list_page(&mut self) {
+        let d = create_dpc_list_page();
+        self.push_layer(d);
+    }
+
+    pub fn show_dpc_page(&mut self, dpc: &DevicePortConfig, in_use: bool) {
+        let d = create_dpc_page(dpc, in_use);
+        self.push_layer(d);
+    }
+
+    pub fn show_dpc_verify_page(&mut self) {
+        let d = create_dpc_verify_page();
+        self.push_layer(d);
+    }
+
+    pub fn show_controller_check_dialog(&mut self) {
+        let d = create_controller_check_dialog();
+        self.push_layer(d);
+    }
+
+    pub fn show_app_command_dialog(&mut self, app_name: &str, uuid: Uuid, command: AppCommand) {
+        let d = create_app_command_dialog(app_name, uuid, command);
+        self.push_layer(d);
+    }
+
+    pub fn show_dpc_diff_dialog(&mut self, diff: DpcDiff, dpc: DevicePortConfig) {
+        let d = create_dpc_diff_dialog(diff, dpc);
         self.push_layer(d);
     }
 
@@ -317,10 +416,21 @@ impl Ui {
             "Server URL",
             url,
             "https://prod.zedcontrol.zededa.net",
+            Some(MonActions::CheckController),
         );
         self.push_layer(d);
     }
 
+    pub fn show_key_help_dialog(&mut self, title: &str, keys: &[(String, String)]) {
+        let d = create_key_help_dialog(title, keys);
+        self.push_layer(d);
+    }
+
+    pub fn show_crash_report_dialog(&mut self, report: &CrashReport) {
+        let d = create_crash_report_dialog(report);
+        self.push_layer(d);
+    }
+
     pub fn message_box(&mut self, title: &str, message: &str) {
         let d = super::message_box::create_message_box(title, message);
         self.push_layer(d);
@@ -32,7 +32,10 @@ use crate::{
     traits::{IEventHandler, IPresenter, IWindow},
 };
 
-use super::traits::{ISelectable, ISelector};
+use super::{
+    theme,
+    traits::{ISelectable, ISelector},
+};
 
 trait TpmEventDecode {
     fn short_description(&self) -> Line;
@@ -673,7 +676,7 @@ impl VaultPage {
         frame: &mut Frame<'_>,
         parsing_result: &ParsingResults,
     ) {
-        let bar = " █ ";
+        let bar = theme::highlight_bar();
         trace!("Rendering VaultPage");
         let tpm_events = &parsing_result.tpm_log_parse_result;
 
@@ -783,7 +786,7 @@ impl VaultPage {
         diff_only: bool,
         tcg_event_names: bool,
     ) {
-        let bar = " █ ";
+        let bar = theme::highlight_bar();
         let tpm_events = &evelt_log;
         let rows = tpm_events
             .iter()
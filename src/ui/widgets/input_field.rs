@@ -5,7 +5,7 @@ use crossterm::event::{KeyCode, KeyEvent};
 use log::trace;
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Rect, Size},
+    layout::{Alignment, Constraint, Layout, Rect, Size},
     style::{Color, Style},
     widgets::{Block, BorderType, Borders, Paragraph, Widget},
 };
@@ -56,6 +56,8 @@ pub struct InputFieldElement {
     modifiers: Vec<InputModifiers>,
     size_hint: Option<Size>,
     text_hint: Option<String>,
+    // show '*' instead of characters e.g. for passwords
+    masked: bool,
 }
 
 impl TextInput for InputFieldElement {
@@ -117,6 +119,7 @@ impl InputFieldElement {
             ],
             size_hint: None,
             text_hint: None,
+            masked: false,
         }
     }
 
@@ -135,6 +138,11 @@ impl InputFieldElement {
         self
     }
 
+    pub fn masked(mut self, masked: bool) -> Self {
+        self.masked = masked;
+        self
+    }
+
     pub fn with_text_hint<S: Into<String>>(mut self, text_hint: S) -> Self {
         self.text_hint = Some(text_hint.into());
         self
@@ -163,6 +171,25 @@ impl InputFieldElement {
             (true, true) => Style::default().fg(Color::Yellow),
         };
 
+        // take size hist into account
+        let area = self.size_hint.map_or_else(
+            || *area,
+            |s| {
+                area.clamp(Rect {
+                    x: area.x,
+                    y: area.y,
+                    width: s.width,
+                    height: s.height,
+                })
+            },
+        );
+
+        // not enough room for the border, e.g. a dialog on 80x24 serial console
+        if area.height < 3 {
+            self.render_compact(area, buf, style);
+            return;
+        }
+
         // render INS/OVR indicator next to the caption
         let mode = match self.input_mode {
             InputMode::Insert => "INS",
@@ -203,25 +230,39 @@ impl InputFieldElement {
             blk = blk.title_bottom(pos);
         }
 
-        // take size hist into account
-        let area = self.size_hint.map_or_else(
-            || *area,
-            |s| {
-                area.clamp(Rect {
-                    x: area.x,
-                    y: area.y,
-                    width: s.width,
-                    height: s.height,
-                })
-            },
-        );
-
         // get inner area
         let inner_area = blk.inner(area);
         self.text_area = inner_area;
         // render the border and caption
         blk.render(area, buf);
 
+        self.render_value(inner_area, buf);
+    }
+
+    // render the field in a single line as "Caption: value"
+    fn render_compact(&mut self, area: Rect, buf: &mut Buffer, style: Style) {
+        let caption = format!("{}: ", self.caption);
+        let caption_style = if self.validation_error.is_some() {
+            Style::default().fg(Color::Red)
+        } else {
+            style
+        };
+
+        let [caption_area, inner_area] = Layout::horizontal([
+            Constraint::Length(caption.len() as u16),
+            Constraint::Fill(1),
+        ])
+        .areas(area.clamp(Rect { height: 1, ..area }));
+
+        Paragraph::new(caption)
+            .style(caption_style.bg(Color::Black))
+            .render(caption_area, buf);
+
+        self.text_area = inner_area;
+        self.render_value(inner_area, buf);
+    }
+
+    fn render_value(&mut self, inner_area: Rect, buf: &mut Buffer) {
         // if value is empty, render the text hint
         if self.value.as_ref().map(|v| v.is_empty()).unwrap_or(true) {
             if let Some(text_hint) = self.text_hint.as_deref() {
@@ -233,8 +274,15 @@ impl InputFieldElement {
             return;
         }
 
+        let value = self.value.as_deref().unwrap_or_default();
+        let value = if self.masked {
+            "*".repeat(value.chars().count())
+        } else {
+            value.to_string()
+        };
+
         // render the input field
-        let input = Paragraph::new(self.value.as_deref().unwrap_or_default())
+        let input = Paragraph::new(value)
             .style(Style::default().fg(Color::White))
             .alignment(Alignment::Left)
             .scroll((0, self.scroll_left)); // note reversed order (y,x)
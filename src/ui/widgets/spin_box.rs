@@ -12,7 +12,7 @@ use ratatui::{
 
 use crate::{
     traits::{IElementEventHandler, IWidget, IWidgetPresenter},
-    ui::action::UiActions,
+    ui::{action::UiActions, theme},
 };
 #[derive(PartialEq)]
 pub enum SpinBoxLayout {
@@ -93,16 +93,16 @@ impl SpinBoxElement {
         up_arrow_rect: Rect,
         down_arrow_rect: Rect,
         text_rect: Rect,
-        up_arrow: char,
-        down_arrow: char,
+        up_arrow: &str,
+        down_arrow: &str,
         style: Style,
         buf: &mut Buffer,
     ) {
         let text = self.items[self.selected].clone();
-        let up = Paragraph::new(up_arrow.to_string())
+        let up = Paragraph::new(up_arrow)
             .alignment(Alignment::Center)
             .style(style.bg(Color::Gray).fg(Color::DarkGray));
-        let down = Paragraph::new(down_arrow.to_string())
+        let down = Paragraph::new(down_arrow)
             .alignment(Alignment::Center)
             .style(style.bg(Color::Gray).fg(Color::DarkGray));
         let text = Paragraph::new(text)
@@ -176,6 +176,8 @@ impl IWidgetPresenter for SpinBoxElement {
         // shrink the area to the size of the spinner
         let area = Rect::new(area.x, area.y, max_size as u16, 1);
 
+        let arrows = theme::arrows();
+
         // create 3 regions |◄| TEXT |►|
         if self.layout == SpinBoxLayout::Horizontal {
             let [left, text, right] = Layout::horizontal([
@@ -184,7 +186,15 @@ impl IWidgetPresenter for SpinBoxElement {
                 Constraint::Length(1),
             ])
             .areas(area);
-            self.draw_spinner(left, right, text, '◄', '►', style, frame.buffer_mut());
+            self.draw_spinner(
+                left,
+                right,
+                text,
+                arrows.left,
+                arrows.right,
+                style,
+                frame.buffer_mut(),
+            );
         } else {
             let [text, left, right] = Layout::horizontal([
                 Constraint::Min(0),
@@ -192,7 +202,15 @@ impl IWidgetPresenter for SpinBoxElement {
                 Constraint::Length(1),
             ])
             .areas(area);
-            self.draw_spinner(left, right, text, '▲', '▼', style, frame.buffer_mut());
+            self.draw_spinner(
+                left,
+                right,
+                text,
+                arrows.up,
+                arrows.down,
+                style,
+                frame.buffer_mut(),
+            );
         };
     }
 }
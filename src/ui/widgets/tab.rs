@@ -31,6 +31,13 @@ impl TabElement {
             caption: caption.map(|s| s.into()),
         }
     }
+
+    /// Select the tab by name without emitting TabChanged
+    pub fn select(&mut self, tab: &str) {
+        if let Some(index) = self.tabs.iter().position(|t| t == tab) {
+            self.ft.set_focused_index(index);
+        }
+    }
 }
 
 impl IWidget for TabElement {
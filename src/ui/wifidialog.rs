@@ -0,0 +1,500 @@
+// Copyright (c) 2025 Zededa, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::rc::Rc;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use log::debug;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Margin, Rect},
+    style::{Color, Style},
+    widgets::{Block, BorderType, Borders, Clear},
+    Frame,
+};
+
+use crate::{
+    actions::MonActions,
+    dpc::{
+        builder::DpcField,
+        wifi::{validate_wifi_networks, WifiFieldError},
+    },
+    ipc::eve_types::{CipherBlockStatus, WifiConfig, WifiKeySchemeType},
+    model::model::Model,
+    traits::{IWindow, TextInput},
+};
+
+use super::{
+    action::{Action, UiActions},
+    ipdialog::field_height,
+    tools::centered_rect_min,
+    widgets::{
+        button::ButtonElement, input_field::InputFieldElement, label::LabelElement,
+        spin_box::SpinBoxElement,
+    },
+    window::Window,
+};
+
+const KEY_SCHEMES: [&str; 3] = ["Open", "WPA-PSK", "WPA-EAP"];
+const INPUT_FIELDS: [&str; 4] = ["ssid", "identity", "password", "priority"];
+
+/// WiFi network as it is edited in the dialog
+#[derive(Debug, Clone, PartialEq)]
+pub struct WifiNetworkState {
+    pub ssid: String,
+    pub key_scheme: WifiKeySchemeType,
+    pub identity: String,
+    pub password: String,
+    pub priority: String,
+    // credentials encrypted by the controller. Identity and password are
+    // empty in this case
+    pub cipher: CipherBlockStatus,
+}
+
+impl Default for WifiNetworkState {
+    fn default() -> Self {
+        Self {
+            ssid: String::new(),
+            key_scheme: WifiKeySchemeType::KeySchemeWpaPsk,
+            identity: String::new(),
+            password: String::new(),
+            priority: "0".to_string(),
+            cipher: CipherBlockStatus::default(),
+        }
+    }
+}
+
+impl From<&WifiConfig> for WifiNetworkState {
+    fn from(cfg: &WifiConfig) -> Self {
+        Self {
+            ssid: cfg.ssid.clone(),
+            key_scheme: cfg.key_scheme.clone(),
+            identity: cfg.identity.clone(),
+            password: cfg.password.clone(),
+            priority: cfg.priority.to_string(),
+            cipher: cfg.cipher_block_status.clone(),
+        }
+    }
+}
+
+impl WifiNetworkState {
+    fn credentials_status(&self) -> String {
+        let cipher = &self.cipher;
+        match self.key_scheme {
+            WifiKeySchemeType::KeySchemeNone => "Credentials: not required".to_string(),
+            _ if cipher.is_cipher && self.keeps_encrypted_credentials() => {
+                if cipher.error_and_time.is_error() {
+                    format!(
+                        "Credentials: encrypted by the controller, error: {}",
+                        cipher.error_and_time.error_description.error
+                    )
+                } else if cipher.cipher_block_id.is_empty() {
+                    "Credentials: encrypted by the controller".to_string()
+                } else {
+                    format!(
+                        "Credentials: encrypted by the controller (cipher block {})",
+                        cipher.cipher_block_id
+                    )
+                }
+            }
+            _ if cipher.is_cipher => {
+                "Credentials: encrypted by the controller, will be replaced".to_string()
+            }
+            _ => "Credentials: not encrypted".to_string(),
+        }
+    }
+}
+
+fn key_scheme_index(key_scheme: &WifiKeySchemeType) -> usize {
+    match key_scheme {
+        WifiKeySchemeType::KeySchemeWpaPsk => 1,
+        WifiKeySchemeType::KeySchemeWpaEap => 2,
+        _ => 0,
+    }
+}
+
+fn key_scheme_from_index(index: usize) -> WifiKeySchemeType {
+    match index {
+        1 => WifiKeySchemeType::KeySchemeWpaPsk,
+        2 => WifiKeySchemeType::KeySchemeWpaEap,
+        _ => WifiKeySchemeType::KeySchemeNone,
+    }
+}
+
+struct WifiDialogState {
+    iface_name: String,
+    networks: Vec<WifiNetworkState>,
+    selected: usize,
+}
+
+impl WifiDialogState {
+    fn current(&self) -> Option<&WifiNetworkState> {
+        self.networks.get(self.selected)
+    }
+
+    fn current_mut(&mut self) -> Option<&mut WifiNetworkState> {
+        self.networks.get_mut(self.selected)
+    }
+
+    fn network_labels(&self) -> Vec<String> {
+        if self.networks.is_empty() {
+            return vec!["no networks".to_string()];
+        }
+        let count = self.networks.len();
+        self.networks
+            .iter()
+            .enumerate()
+            .map(|(i, n)| {
+                let ssid = if n.ssid.is_empty() { "<new>" } else { &n.ssid };
+                format!("{}/{} {}", i + 1, count, ssid)
+            })
+            .collect()
+    }
+
+    fn tab_order(&self) -> Vec<&str> {
+        let mut order = vec![];
+        if let Some(network) = self.current() {
+            order.extend(["network", "ssid", "key_scheme"]);
+            match network.key_scheme {
+                WifiKeySchemeType::KeySchemeNone => {}
+                WifiKeySchemeType::KeySchemeWpaEap => order.extend(["identity", "password"]),
+                _ => order.push("password"),
+            }
+            order.push("priority");
+        }
+        order.push("add");
+        if self.current().is_some() {
+            order.push("remove");
+        }
+        order.extend(["ok", "cancel"]);
+        order
+    }
+}
+
+fn on_init(w: &mut Window<WifiDialogState>) {
+    w.add_widget("add", ButtonElement::new("add"));
+    w.add_widget("remove", ButtonElement::new("remove"));
+    w.add_widget("ok", ButtonElement::new("ok"));
+    w.add_widget("cancel", ButtonElement::new("cancel"));
+    create_network_widgets(w);
+    update_tab_order(w);
+}
+
+// (re)create widgets for the selected network
+fn create_network_widgets(w: &mut Window<WifiDialogState>) {
+    w.add_widget(
+        "network",
+        SpinBoxElement::new(w.state.network_labels()).selected(w.state.selected),
+    );
+    update_credentials_status(w);
+
+    let Some(network) = w.state.current().cloned() else {
+        return;
+    };
+    let password_hint = if network.cipher.is_cipher {
+        "encrypted, leave empty to keep"
+    } else {
+        "8 to 63 characters"
+    };
+
+    w.add_widget(
+        "ssid",
+        InputFieldElement::new("SSID", Some(network.ssid.as_str())).with_text_hint("e.g. office"),
+    );
+    w.add_widget(
+        "key_scheme",
+        SpinBoxElement::new(KEY_SCHEMES.to_vec()).selected(key_scheme_index(&network.key_scheme)),
+    );
+    w.add_widget(
+        "identity",
+        InputFieldElement::new("Identity", Some(network.identity.as_str()))
+            .with_text_hint("e.g. user@example.com"),
+    );
+    w.add_widget(
+        "password",
+        InputFieldElement::new("Password", Some(network.password.as_str()))
+            .masked(true)
+            .with_text_hint(password_hint),
+    );
+    w.add_widget(
+        "priority",
+        InputFieldElement::new("Priority", Some(network.priority.as_str()))
+            .with_text_hint("higher is preferred"),
+    );
+}
+
+fn update_credentials_status(w: &mut Window<WifiDialogState>) {
+    let text = w
+        .state
+        .current()
+        .map(|n| n.credentials_status())
+        .unwrap_or_else(|| "Press 'add' to configure a network".to_string());
+    w.add_widget("credentials", LabelElement::new(text));
+}
+
+fn update_tab_order(w: &mut Window<WifiDialogState>) {
+    let order = w
+        .state
+        .tab_order()
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+    w.set_focus_tracker_tab_order(order);
+}
+
+fn set_focus(w: &mut Window<WifiDialogState>, name: &str) {
+    if let Some(index) = w.state.tab_order().iter().position(|n| *n == name) {
+        w.set_focused_view(index);
+    }
+}
+
+/// Select the network of the first error, mark invalid fields and focus the first one
+fn show_errors(w: &mut Window<WifiDialogState>, errors: &[WifiFieldError]) {
+    let Some(index) = errors.first().map(|e| e.index) else {
+        return;
+    };
+    w.state.selected = index;
+    create_network_widgets(w);
+    update_tab_order(w);
+
+    let errors = errors
+        .iter()
+        .filter(|e| e.index == index)
+        .filter_map(|e| Some((field_widget_name(e.error.field)?, e.error.message.clone())))
+        .collect::<Vec<_>>();
+    for name in INPUT_FIELDS {
+        let error = errors
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, message)| message.clone());
+        if let Some(field) = w
+            .get_widget_mut(name)
+            .and_then(|widget| widget.as_any_mut().downcast_mut::<InputFieldElement>())
+        {
+            field.set_error(error);
+        }
+    }
+    if let Some((first, _)) = errors.first() {
+        set_focus(w, first);
+    }
+}
+
+fn field_widget_name(field: DpcField) -> Option<&'static str> {
+    match field {
+        DpcField::Ssid => Some("ssid"),
+        DpcField::Identity => Some("identity"),
+        DpcField::Password => Some("password"),
+        DpcField::Priority => Some("priority"),
+        _ => None,
+    }
+}
+
+fn do_layout(w: &mut Window<WifiDialogState>, rect: &Rect, _model: &Rc<Model>) {
+    w.clear_layout();
+
+    let rect = centered_rect_min(40, 80, 60, 24, *rect);
+    let content_with_buttons = rect.inner(Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+    w.update_layout("frame", rect);
+
+    let [content, credentials, buttons] = Layout::vertical(vec![
+        Constraint::Fill(1),
+        Constraint::Length(2),
+        Constraint::Length(3),
+    ])
+    .areas(content_with_buttons);
+    w.update_layout("credentials", credentials);
+
+    let [add, remove, _, ok, cancel] = Layout::horizontal(vec![
+        Constraint::Length(7),
+        Constraint::Length(10),
+        Constraint::Fill(1),
+        Constraint::Length(6),
+        Constraint::Length(10),
+    ])
+    .flex(Flex::End)
+    .areas(buttons);
+    w.update_layout("add", add);
+    w.update_layout("ok", ok);
+    w.update_layout("cancel", cancel);
+
+    let Some(key_scheme) = w.state.current().map(|n| n.key_scheme.clone()) else {
+        let [network, _] =
+            Layout::vertical(vec![Constraint::Length(1), Constraint::Fill(1)]).areas(content);
+        w.update_layout("network", network);
+        return;
+    };
+    w.update_layout("remove", remove);
+
+    let inputs = match key_scheme {
+        WifiKeySchemeType::KeySchemeNone => vec!["ssid", "priority"],
+        WifiKeySchemeType::KeySchemeWpaEap => vec!["ssid", "identity", "password", "priority"],
+        _ => vec!["ssid", "password", "priority"],
+    };
+
+    let [network, rest] =
+        Layout::vertical(vec![Constraint::Length(2), Constraint::Fill(1)]).areas(content);
+    w.update_layout(
+        "network",
+        network.clamp(Rect {
+            height: 1,
+            ..network
+        }),
+    );
+
+    // SSID, key scheme, then the rest of input fields
+    let height = field_height(&rest, inputs.len() as u16 + 1);
+    let mut constraints = vec![Constraint::Length(height), Constraint::Length(1)];
+    constraints.extend(vec![Constraint::Length(height); inputs.len() - 1]);
+    let areas = Layout::vertical(constraints).split(rest);
+
+    w.update_layout("ssid", areas[0]);
+    w.update_layout("key_scheme", areas[1]);
+    for (name, area) in inputs.iter().skip(1).zip(areas.iter().skip(2)) {
+        w.update_layout(*name, *area);
+    }
+}
+
+fn do_render(
+    w: &mut Window<WifiDialogState>,
+    _rect: &Rect,
+    frame: &mut Frame<'_>,
+    _model: &Rc<Model>,
+) {
+    let frame_rect = w.get_layout("frame");
+
+    // clear area under the dialog
+    frame.render_widget(Clear {}, frame_rect);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(Color::White))
+        .style(Style::default().bg(Color::Black))
+        .title(format!("{}: WiFi networks", w.state.iface_name));
+
+    frame.render_widget(block, frame_rect);
+}
+
+fn on_key_event(w: &mut Window<WifiDialogState>, key: KeyEvent) -> Option<Action> {
+    if key.code == KeyCode::Esc {
+        return Some(Action::new(&w.name, UiActions::DismissDialog));
+    }
+    None
+}
+
+fn on_child_ui_action(
+    w: &mut Window<WifiDialogState>,
+    source: &String,
+    action: &UiActions,
+) -> Option<Action> {
+    debug!("on_child_ui_action: {}:{:?}", source, action);
+    match action {
+        UiActions::SpinBox { selected } => match source.as_str() {
+            "network" => {
+                w.state.selected = *selected;
+                create_network_widgets(w);
+                update_tab_order(w);
+                set_focus(w, "network");
+                Some(Action::new(source, UiActions::Redraw))
+            }
+            "key_scheme" => {
+                if let Some(network) = w.state.current_mut() {
+                    network.key_scheme = key_scheme_from_index(*selected);
+                }
+                update_credentials_status(w);
+                update_tab_order(w);
+                set_focus(w, "key_scheme");
+                Some(Action::new(source, UiActions::Redraw))
+            }
+            _ => None,
+        },
+        UiActions::Input { text } => {
+            let network = w.state.current_mut()?;
+            match source.as_str() {
+                "ssid" => network.ssid = text.clone(),
+                "identity" => network.identity = text.clone(),
+                "password" => network.password = text.clone(),
+                "priority" => network.priority = text.clone(),
+                _ => {}
+            }
+            match source.as_str() {
+                // SSID is shown in the network selector
+                "ssid" => {
+                    let labels = w.state.network_labels();
+                    w.add_widget(
+                        "network",
+                        SpinBoxElement::new(labels).selected(w.state.selected),
+                    );
+                }
+                "identity" | "password" => update_credentials_status(w),
+                _ => {}
+            }
+            None
+        }
+        UiActions::ButtonClicked(_) => match source.as_str() {
+            "add" => {
+                w.state.networks.push(WifiNetworkState::default());
+                w.state.selected = w.state.networks.len() - 1;
+                create_network_widgets(w);
+                update_tab_order(w);
+                set_focus(w, "ssid");
+                Some(Action::new(source, UiActions::Redraw))
+            }
+            "remove" => {
+                if w.state.selected < w.state.networks.len() {
+                    w.state.networks.remove(w.state.selected);
+                }
+                w.state.selected = w
+                    .state
+                    .selected
+                    .min(w.state.networks.len().saturating_sub(1));
+                create_network_widgets(w);
+                update_tab_order(w);
+                set_focus(
+                    w,
+                    if w.state.networks.is_empty() {
+                        "add"
+                    } else {
+                        "network"
+                    },
+                );
+                Some(Action::new(source, UiActions::Redraw))
+            }
+            "ok" => match validate_wifi_networks(&w.state.networks) {
+                Ok(networks) => Some(Action::new(
+                    &w.name,
+                    UiActions::AppAction(MonActions::WifiConfigUpdated(
+                        w.state.iface_name.clone(),
+                        networks,
+                    )),
+                )),
+                Err(errors) => {
+                    show_errors(w, &errors);
+                    Some(Action::new(source, UiActions::Redraw))
+                }
+            },
+            "cancel" => Some(Action::new(&w.name, UiActions::DismissDialog)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+pub fn create_wifi_dialog(iface_name: &str, networks: &[WifiConfig]) -> impl IWindow {
+    let state = WifiDialogState {
+        iface_name: iface_name.to_string(),
+        networks: networks.iter().map(WifiNetworkState::from).collect(),
+        selected: 0,
+    };
+    Window::builder("WiFi configuration")
+        .with_on_init(on_init)
+        .with_layout(do_layout)
+        .with_render(do_render)
+        .with_on_key_event(on_key_event)
+        .with_on_child_ui_action(on_child_ui_action)
+        .with_state(state)
+        .build()
+        .unwrap()
+}